@@ -1,16 +1,38 @@
 //! Configuration loading from file, environment, and CLI arguments.
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use crate::session::manager::EPHEMERAL_MEMORY_PATH;
 use crate::types::McpResult;
 
 /// Server configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerConfig {
-    /// Path to the .amem memory file.
+    /// Path to the .amem memory file (empty means resolve from CLI/env defaults).
+    #[serde(default)]
     pub memory_path: String,
+    /// Directory whose `*.amem` files are served as named namespaces
+    /// (empty means single-file mode).
+    #[serde(default)]
+    pub memory_dir: String,
+    /// Namespace used when a request does not name one.
+    #[serde(default = "default_namespace")]
+    pub default_namespace: String,
+    /// Resume the session that was active at the last shutdown instead of
+    /// starting a new one.
+    #[serde(default)]
+    pub continue_session: bool,
+    /// Build the similarity search index while opening the brain instead of
+    /// on the first query.
+    #[serde(default)]
+    pub build_index_on_open: bool,
+    /// Create the memory file when it does not exist; when false, a missing
+    /// file is a startup error.
+    #[serde(default = "default_create_if_missing")]
+    pub create_if_missing: bool,
     /// Transport type ("stdio" or "sse").
     #[serde(default = "default_transport")]
     pub transport: String,
@@ -23,6 +45,117 @@ pub struct ServerConfig {
     /// Log level.
     #[serde(default = "default_log_level")]
     pub log_level: String,
+    /// Write stderr logs as JSON records instead of human-readable lines.
+    #[serde(default)]
+    pub json_logs: bool,
+    /// Emit compact (single-line) JSON in tool output instead of pretty JSON.
+    #[serde(default)]
+    pub compact_output: bool,
+    /// Also return JSON tool results as `structuredContent` objects.
+    #[serde(default)]
+    pub structured_output: bool,
+    /// Seconds between keepalive comments on idle HTTP event streams.
+    #[serde(default = "default_sse_keepalive_secs")]
+    pub sse_keepalive_secs: u64,
+    /// Largest JSON-RPC message accepted on stdio or HTTP, in bytes.
+    #[serde(default = "default_max_request_bytes")]
+    pub max_request_bytes: usize,
+    /// CORS policy for the HTTP transport.
+    #[serde(default)]
+    pub cors: CorsConfig,
+    /// Identity advertised to clients as `serverInfo`.
+    #[serde(default)]
+    pub server_info: ServerInfoConfig,
+    /// Regex patterns masked as `[REDACTED]` in all tool, resource, and prompt output.
+    #[serde(default)]
+    pub redact_patterns: Vec<String>,
+    /// Event type used when a `memory_add` call omits `event_type`; unset
+    /// keeps `event_type` required.
+    #[serde(default)]
+    pub default_event_type: Option<String>,
+    /// Confidence used when a `memory_add` call omits `confidence`.
+    #[serde(default)]
+    pub default_confidence: Option<f32>,
+    /// Metric `memory_similar` scores vectors with when a call omits
+    /// `metric`: `cosine` (default), `dot`, or `euclidean`.
+    #[serde(default)]
+    pub similarity_metric: Option<String>,
+    /// Edge types `memory_traverse` follows when a call omits `edge_types`;
+    /// empty means all types.
+    #[serde(default)]
+    pub default_traversal_edge_types: Vec<String>,
+    /// Per-class and per-tool call timeouts.
+    #[serde(default)]
+    pub tool_timeouts: ToolTimeoutConfig,
+    /// Fail `initialize` when the client asks for an unsupported protocol
+    /// version, instead of warning and proceeding.
+    #[serde(default)]
+    pub strict_protocol_version: bool,
+}
+
+/// Cross-origin policy for the HTTP transport.
+///
+/// The default (no allowed origins) sends no CORS headers, so only same-origin
+/// browser clients can call the server.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CorsConfig {
+    /// Allowed origins (e.g. `https://app.example.com`), or `["*"]` for any.
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    /// Allowed methods. Empty means `GET` and `POST`.
+    #[serde(default)]
+    pub allowed_methods: Vec<String>,
+    /// Allowed request headers. Empty means `authorization`, `content-type`, `x-user-id`.
+    #[serde(default)]
+    pub allowed_headers: Vec<String>,
+}
+
+/// Overrides for the `serverInfo` sent in the `initialize` response, so a
+/// wrapping product can present its own name. Unset fields keep the defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServerInfoConfig {
+    /// Advertised name (default: `agentic-memory-mcp`).
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Advertised version (default: the crate version).
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Optional display title.
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+/// Tool call timeouts in seconds. Unset fields keep the built-in defaults
+/// (read 30, write 60, long-running 600); `0` disables the limit.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolTimeoutConfig {
+    /// Limit for read-only tools such as `memory_query` and `memory_similar`.
+    #[serde(default)]
+    pub read_secs: Option<u64>,
+    /// Limit for tools that change the graph, and for unlisted tools.
+    #[serde(default)]
+    pub write_secs: Option<u64>,
+    /// Limit for long-running tools such as `memory_reembed` and `memory_save`.
+    #[serde(default)]
+    pub long_secs: Option<u64>,
+    /// Limits for single tools by name, overriding their class.
+    #[serde(default)]
+    pub tools: HashMap<String, u64>,
+}
+
+impl CorsConfig {
+    /// Whether any cross-origin access is enabled.
+    pub fn is_enabled(&self) -> bool {
+        !self.allowed_origins.is_empty()
+    }
+}
+
+fn default_namespace() -> String {
+    crate::session::manager::DEFAULT_NAMESPACE.to_string()
+}
+
+fn default_create_if_missing() -> bool {
+    true
 }
 
 fn default_transport() -> String {
@@ -41,14 +174,41 @@ fn default_log_level() -> String {
     "info".to_string()
 }
 
+fn default_sse_keepalive_secs() -> u64 {
+    15
+}
+
+fn default_max_request_bytes() -> usize {
+    crate::transport::framing::DEFAULT_MAX_REQUEST_BYTES
+}
+
 impl Default for ServerConfig {
     fn default() -> Self {
         Self {
             memory_path: resolve_default_memory_path(),
+            memory_dir: String::new(),
+            default_namespace: default_namespace(),
+            continue_session: false,
+            build_index_on_open: false,
+            create_if_missing: default_create_if_missing(),
             transport: default_transport(),
             sse_addr: default_sse_addr(),
             auto_save_interval: default_auto_save_interval(),
             log_level: default_log_level(),
+            json_logs: false,
+            compact_output: false,
+            structured_output: false,
+            sse_keepalive_secs: default_sse_keepalive_secs(),
+            max_request_bytes: default_max_request_bytes(),
+            cors: CorsConfig::default(),
+            server_info: ServerInfoConfig::default(),
+            redact_patterns: Vec::new(),
+            default_event_type: None,
+            default_confidence: None,
+            similarity_metric: None,
+            default_traversal_edge_types: Vec::new(),
+            tool_timeouts: ToolTimeoutConfig::default(),
+            strict_protocol_version: false,
         }
     }
 }
@@ -69,6 +229,10 @@ pub fn load_config(path: &str) -> McpResult<ServerConfig> {
 /// 1. Explicit path (CLI arg)
 /// 2. AMEM_BRAIN environment variable
 /// 3. ~/.brain.amem (global default)
+///
+/// The chosen path is expanded with [`expand_path_template`], so
+/// `~/memories/{date}.amem` opens a new file each day. The ephemeral path
+/// `:memory:` is returned unchanged.
 pub fn resolve_memory_path(explicit: Option<&str>) -> String {
     if let Some(path) = explicit {
         return absolutize_path(path);
@@ -82,15 +246,69 @@ pub fn resolve_memory_path(explicit: Option<&str>) -> String {
 }
 
 fn resolve_default_memory_path() -> String {
-    let home = std::env::var("HOME")
-        .or_else(|_| std::env::var("USERPROFILE"))
-        .unwrap_or_else(|_| ".".to_string());
+    format!("{}/.brain.amem", home_dir())
+}
+
+/// Expand placeholders in a configured memory path.
+///
+/// - `{home}`: the user's home directory
+/// - `{user}`: the login name (`USER` / `USERNAME`)
+/// - `{date}`: today's local date as `YYYY-MM-DD`
+/// - `${VAR}`: the environment variable `VAR`
+///
+/// Unknown `{...}` placeholders and unset variables are left as written.
+/// Paths without placeholders are returned unchanged.
+pub fn expand_path_template(raw: &str) -> String {
+    if !raw.contains('{') {
+        return raw.to_string();
+    }
 
-    format!("{home}/.brain.amem")
+    let mut out = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(open) = rest.find('{') {
+        let is_env = rest[..open].ends_with('$');
+        let Some(close) = rest[open..].find('}').map(|i| open + i) else {
+            break;
+        };
+        let name = &rest[open + 1..close];
+        let prefix_end = if is_env { open - 1 } else { open };
+        let value = if is_env {
+            std::env::var(name).ok().or_else(|| {
+                tracing::warn!("Memory path references unset variable ${{{name}}}");
+                None
+            })
+        } else {
+            match name {
+                "home" => Some(home_dir()),
+                "user" => std::env::var("USER")
+                    .or_else(|_| std::env::var("USERNAME"))
+                    .ok(),
+                "date" => Some(chrono::Local::now().format("%Y-%m-%d").to_string()),
+                _ => None,
+            }
+        };
+        out.push_str(&rest[..prefix_end]);
+        match value {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(&rest[prefix_end..=close]),
+        }
+        rest = &rest[close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn home_dir() -> String {
+    std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .unwrap_or_else(|_| ".".to_string())
 }
 
 fn absolutize_path(raw: &str) -> String {
-    let expanded = expand_home(raw);
+    if raw == EPHEMERAL_MEMORY_PATH {
+        return raw.to_string();
+    }
+    let expanded = expand_home(&expand_path_template(raw));
     let path = PathBuf::from(expanded);
     if path.is_absolute() {
         return path.display().to_string();
@@ -104,15 +322,10 @@ fn absolutize_path(raw: &str) -> String {
 
 fn expand_home(raw: &str) -> String {
     if raw == "~" {
-        return std::env::var("HOME")
-            .or_else(|_| std::env::var("USERPROFILE"))
-            .unwrap_or_else(|_| ".".to_string());
+        return home_dir();
     }
     if let Some(rest) = raw.strip_prefix("~/") {
-        let home = std::env::var("HOME")
-            .or_else(|_| std::env::var("USERPROFILE"))
-            .unwrap_or_else(|_| ".".to_string());
-        return format!("{home}/{rest}");
+        return format!("{}/{rest}", home_dir());
     }
     raw.to_string()
 }
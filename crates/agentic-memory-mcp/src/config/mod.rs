@@ -2,4 +2,7 @@
 
 pub mod loader;
 
-pub use loader::{load_config, resolve_memory_path, ServerConfig};
+pub use loader::{
+    expand_path_template, load_config, resolve_memory_path, CorsConfig, ServerConfig,
+    ServerInfoConfig, ToolTimeoutConfig,
+};
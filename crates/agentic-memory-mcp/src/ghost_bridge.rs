@@ -7,7 +7,7 @@
 //! Gated behind `#[cfg(feature = "v3")]`.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use agentic_memory::v3::edge_cases;
 use agentic_memory::v3::engine::SessionResumeResult;
@@ -21,7 +21,7 @@ use crate::session::SessionManager;
 ///
 /// Returns `None` if no AI clients are detected (memory still works via MCP tools).
 pub fn spawn_ghost_writer(
-    session: Arc<Mutex<SessionManager>>,
+    session: Arc<RwLock<SessionManager>>,
 ) -> Option<tokio::task::JoinHandle<()>> {
     let clients = GhostWriter::detect_all_memory_dirs();
     if clients.is_empty() {
@@ -64,7 +64,7 @@ pub fn spawn_ghost_writer(
 }
 
 /// Perform one sync cycle — build context from V2 graph, write to all clients.
-async fn sync_once(session: &Arc<Mutex<SessionManager>>, clients: &[DetectedClient]) {
+async fn sync_once(session: &Arc<RwLock<SessionManager>>, clients: &[DetectedClient]) {
     let context = match build_context_from_v2(session).await {
         Some(ctx) => ctx,
         None => return,
@@ -86,9 +86,9 @@ async fn sync_once(session: &Arc<Mutex<SessionManager>>, clients: &[DetectedClie
 /// Extracts recent decisions, facts, episodes, skills, and corrections
 /// to populate the context that GhostWriter formats for each AI client.
 async fn build_context_from_v2(
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> Option<SessionResumeResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let query = session.query_engine();
 
@@ -104,6 +104,7 @@ async fn build_context_from_v2(
                 created_after: None,
                 created_before: None,
                 min_decay_score: None,
+                max_decay_score: None,
                 max_results: 10,
                 sort_by: PatternSort::MostRecent,
             },
@@ -122,6 +123,7 @@ async fn build_context_from_v2(
                 created_after: None,
                 created_before: None,
                 min_decay_score: None,
+                max_decay_score: None,
                 max_results: 10,
                 sort_by: PatternSort::MostRecent,
             },
@@ -140,6 +142,7 @@ async fn build_context_from_v2(
                 created_after: None,
                 created_before: None,
                 min_decay_score: None,
+                max_decay_score: None,
                 max_results: 1,
                 sort_by: PatternSort::MostRecent,
             },
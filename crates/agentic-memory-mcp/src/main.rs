@@ -6,22 +6,31 @@ use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use clap::{Args, Parser, Subcommand};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer as _;
 
-use agentic_memory_mcp::config::resolve_memory_path;
-use agentic_memory_mcp::protocol::ProtocolHandler;
+use agentic_memory_mcp::config::{load_config, resolve_memory_path, ServerConfig};
+use agentic_memory_mcp::protocol::{HandlerSettings, ProtocolHandler};
 use agentic_memory_mcp::session::autosave::spawn_maintenance;
-use agentic_memory_mcp::session::SessionManager;
+use agentic_memory_mcp::session::manager::{DEFAULT_NAMESPACE, EPHEMERAL_MEMORY_PATH};
+use agentic_memory_mcp::session::tool_defaults::DEFAULT_CONFIDENCE;
+use agentic_memory_mcp::session::{SessionManager, ToolDefaults};
+use agentic_memory_mcp::streaming::{LogSinks, McpLogLayer};
+use agentic_memory_mcp::tools::timeout::ToolTimeouts;
 use agentic_memory_mcp::tools::ToolRegistry;
 use agentic_memory_mcp::transport::capture::{
     self, CaptureDirection, CaptureWalStatus, CapturedTransportEntry,
 };
 use agentic_memory_mcp::transport::StdioTransport;
-use agentic_memory_mcp::types::MemoryMode;
+use agentic_memory_mcp::types::{
+    edge_json, parse_edge_types, server_info, McpError, MemoryMode, OutputFormat, Redactor,
+};
 
 mod daemon;
 
@@ -680,6 +689,10 @@ struct Cli {
     #[arg(long, default_value = "info")]
     log_level: String,
 
+    /// Write logs to stderr as JSON records, one per line.
+    #[arg(long)]
+    json_logs: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -692,6 +705,28 @@ enum Commands {
         #[arg(short, long)]
         memory: Option<String>,
 
+        /// Serve every .amem file in this directory as a named namespace.
+        #[arg(long, conflicts_with = "memory")]
+        memory_dir: Option<String>,
+
+        /// Keep the graph in memory only; nothing is read from or saved to disk.
+        #[arg(long, conflicts_with_all = ["memory", "memory_dir"])]
+        ephemeral: bool,
+
+        /// Namespace used when a request does not name one (with --memory-dir).
+        #[arg(long)]
+        namespace: Option<String>,
+
+        /// Resume the session that was active at the last shutdown instead of
+        /// starting a new one.
+        #[arg(long)]
+        continue_session: bool,
+
+        /// Fail instead of creating a new memory file when --memory points at
+        /// a file that does not exist.
+        #[arg(long)]
+        no_create: bool,
+
         /// Configuration file path.
         #[arg(short, long)]
         config: Option<String>,
@@ -717,6 +752,29 @@ enum Commands {
         #[arg(short, long)]
         memory: Option<String>,
 
+        /// Serve every .amem file in this directory as a named namespace
+        /// (single-user mode).
+        #[arg(long, conflicts_with = "memory")]
+        memory_dir: Option<String>,
+
+        /// Keep the graph in memory only; nothing is read from or saved to disk.
+        #[arg(long, conflicts_with_all = ["memory", "memory_dir"])]
+        ephemeral: bool,
+
+        /// Namespace used when a request does not name one (with --memory-dir).
+        #[arg(long)]
+        namespace: Option<String>,
+
+        /// Resume the session that was active at the last shutdown instead of
+        /// starting a new one.
+        #[arg(long)]
+        continue_session: bool,
+
+        /// Fail instead of creating a new memory file when --memory points at
+        /// a file that does not exist.
+        #[arg(long)]
+        no_create: bool,
+
         /// Configuration file path.
         #[arg(short, long)]
         config: Option<String>,
@@ -745,7 +803,12 @@ enum Commands {
     },
 
     /// Validate a memory file.
-    Validate,
+    Validate {
+        /// Also walk the graph for dangling edges, supersedes cycles, empty
+        /// episodes, and wrong-dimension vectors; exit 1 if any are found.
+        #[arg(long)]
+        deep: bool,
+    },
 
     /// Print server capabilities as JSON.
     Info,
@@ -844,6 +907,303 @@ fn enforce_mode_runtime_policy(mode: MemoryMode) {
     );
 }
 
+/// `--ephemeral` stands in for the `:memory:` path.
+fn serving_memory_path(memory: Option<String>, ephemeral: bool) -> Option<String> {
+    if ephemeral {
+        return Some(EPHEMERAL_MEMORY_PATH.to_string());
+    }
+    memory
+}
+
+fn load_server_config(path: Option<&str>) -> anyhow::Result<Option<ServerConfig>> {
+    Ok(path.map(load_config).transpose()?)
+}
+
+/// Open the brain a server fronts: a single file, or every namespace in a
+/// memory directory (CLI flags override the config file). Each graph gets its
+/// own maintenance task and the configured tool defaults, resumes its last
+/// session with `continue_session`, and builds its similarity index up front
+/// with `build_index_on_open`. A missing single file is an error unless both
+/// `create_if_missing` and the config file allow creating it.
+async fn open_serving_session(
+    memory: Option<String>,
+    memory_dir: Option<String>,
+    namespace: Option<String>,
+    continue_session: bool,
+    create_if_missing: bool,
+    config: Option<&ServerConfig>,
+    memory_mode: MemoryMode,
+) -> anyhow::Result<Arc<RwLock<SessionManager>>> {
+    let tool_defaults = tool_defaults_config(config)?;
+    let memory_dir = memory_dir.or_else(|| {
+        config
+            .filter(|c| memory.is_none() && !c.memory_dir.is_empty())
+            .map(|c| c.memory_dir.clone())
+    });
+
+    let mut session = match memory_dir {
+        Some(dir) => {
+            let dir = resolve_memory_path(Some(&dir));
+            let default_namespace = namespace
+                .or_else(|| config.map(|c| c.default_namespace.clone()))
+                .unwrap_or_else(|| DEFAULT_NAMESPACE.to_string());
+            tracing::info!("Memory dir: {dir} (default namespace '{default_namespace}')");
+            SessionManager::open_dir(&dir, &default_namespace)?
+        }
+        None => {
+            let memory_path = resolve_memory_path(memory.as_deref());
+            tracing::info!("Brain: {memory_path}");
+            if create_if_missing && config.is_none_or(|c| c.create_if_missing) {
+                SessionManager::open(&memory_path)?
+            } else {
+                SessionManager::open_existing(&memory_path)?
+            }
+        }
+    };
+
+    let continue_session = continue_session || config.is_some_and(|c| c.continue_session);
+    let build_index = config.is_some_and(|c| c.build_index_on_open);
+    session.apply_memory_mode(memory_mode);
+    session.set_tool_defaults(tool_defaults.clone());
+    if continue_session {
+        session.resume_last_session();
+    }
+    if build_index {
+        session.prebuild_text_index();
+    }
+    for sibling in session.namespaces().values() {
+        let mut guard = sibling.write().await;
+        guard.apply_memory_mode(memory_mode);
+        guard.set_tool_defaults(tool_defaults.clone());
+        if continue_session {
+            guard.resume_last_session();
+        }
+        if build_index {
+            guard.prebuild_text_index();
+        }
+        let _maintenance_task = spawn_maintenance(sibling.clone(), guard.maintenance_interval());
+    }
+    let maintenance_interval = session.maintenance_interval();
+    let session = Arc::new(RwLock::new(session));
+    let _maintenance_task = spawn_maintenance(session.clone(), maintenance_interval);
+    Ok(session)
+}
+
+/// Request size cap for either transport: `AMEM_MAX_REQUEST_BYTES`, then the
+/// config file, then the built-in default.
+fn max_request_bytes(config: Option<&ServerConfig>) -> usize {
+    std::env::var("AMEM_MAX_REQUEST_BYTES")
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .or_else(|| config.map(|c| c.max_request_bytes))
+        .unwrap_or(agentic_memory_mcp::transport::framing::DEFAULT_MAX_REQUEST_BYTES)
+}
+
+/// Read a boolean environment flag; `None` when unset.
+fn env_flag(name: &str) -> Option<bool> {
+    std::env::var(name).ok().map(|v| {
+        matches!(
+            v.trim().to_ascii_lowercase().as_str(),
+            "1" | "true" | "yes" | "on"
+        )
+    })
+}
+
+/// Compile the output redaction patterns. `AMEM_REDACT_PATTERNS`
+/// (`;;`-separated) overrides the config file.
+fn redaction_config(config: Option<&ServerConfig>) -> anyhow::Result<Redactor> {
+    let patterns: Vec<String> = match std::env::var("AMEM_REDACT_PATTERNS") {
+        Ok(raw) => raw
+            .split(";;")
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Err(_) => config
+            .map(|c| c.redact_patterns.clone())
+            .unwrap_or_default(),
+    };
+    let redactor = Redactor::new(&patterns)?;
+    if redactor.is_enabled() {
+        tracing::info!("Output redaction: {} pattern(s)", patterns.len());
+    }
+    Ok(redactor)
+}
+
+/// Fallbacks for omitted tool arguments. `AMEM_DEFAULT_EVENT_TYPE`,
+/// `AMEM_DEFAULT_CONFIDENCE`, `AMEM_SIMILARITY_METRIC` and
+/// `AMEM_TRAVERSAL_EDGE_TYPES` override the config file.
+fn tool_defaults_config(config: Option<&ServerConfig>) -> anyhow::Result<ToolDefaults> {
+    let event_type = std::env::var("AMEM_DEFAULT_EVENT_TYPE")
+        .ok()
+        .or_else(|| config.and_then(|c| c.default_event_type.clone()))
+        .filter(|name| !name.trim().is_empty())
+        .map(|name| {
+            agentic_memory::EventType::from_name(name.trim())
+                .ok_or_else(|| anyhow::anyhow!("Unknown default event type '{name}'"))
+        })
+        .transpose()?;
+    let confidence = match std::env::var("AMEM_DEFAULT_CONFIDENCE") {
+        Ok(raw) => Some(
+            raw.trim()
+                .parse::<f32>()
+                .map_err(|e| anyhow::anyhow!("Invalid AMEM_DEFAULT_CONFIDENCE '{raw}': {e}"))?,
+        ),
+        Err(_) => config.and_then(|c| c.default_confidence),
+    };
+    if let Some(confidence) = confidence {
+        if !(0.0..=1.0).contains(&confidence) {
+            anyhow::bail!("Default confidence must be between 0.0 and 1.0, got {confidence}");
+        }
+    }
+    if let Some(event_type) = event_type {
+        tracing::info!("memory_add default event type: {}", event_type.name());
+    }
+    Ok(ToolDefaults {
+        event_type,
+        confidence: confidence.unwrap_or(DEFAULT_CONFIDENCE),
+        similarity_metric: similarity_metric_config(config)?,
+        traversal_edge_types: traversal_config(config)?,
+    })
+}
+
+/// Settings for the protocol handlers serving clients: the configured
+/// `serverInfo`, protocol version strictness, tool output format, output
+/// redaction, the log records forwarded to clients, and tool call timeouts.
+/// `AMEM_STRICT_PROTOCOL_VERSION`, `AMEM_COMPACT_OUTPUT` and
+/// `AMEM_STRUCTURED_OUTPUT` override the config file.
+fn handler_settings(
+    config: Option<&ServerConfig>,
+    log_sinks: LogSinks,
+) -> anyhow::Result<HandlerSettings> {
+    let strict_protocol_version = env_flag("AMEM_STRICT_PROTOCOL_VERSION")
+        .unwrap_or_else(|| config.is_some_and(|c| c.strict_protocol_version));
+    if strict_protocol_version {
+        tracing::info!("Protocol version: strict");
+    }
+    let info = config.map(|c| c.server_info.clone()).unwrap_or_default();
+    let server_info = server_info(info.name, info.version, info.title);
+    if config.is_some() {
+        tracing::info!("Server info: {} {}", server_info.name, server_info.version);
+    }
+    let compact =
+        env_flag("AMEM_COMPACT_OUTPUT").unwrap_or_else(|| config.is_some_and(|c| c.compact_output));
+    if compact {
+        tracing::info!("Tool output: compact JSON");
+    }
+    let structured = env_flag("AMEM_STRUCTURED_OUTPUT")
+        .unwrap_or_else(|| config.is_some_and(|c| c.structured_output));
+    if structured {
+        tracing::info!("Tool output: structuredContent enabled");
+    }
+    Ok(HandlerSettings {
+        strict_protocol_version,
+        server_info,
+        output: OutputFormat {
+            compact,
+            structured,
+        },
+        redactor: redaction_config(config)?,
+        log_sinks,
+        tool_timeouts: tool_timeout_config(config)?,
+    })
+}
+
+/// The default `memory_similar` metric. `AMEM_SIMILARITY_METRIC` overrides
+/// the config file.
+fn similarity_metric_config(
+    config: Option<&ServerConfig>,
+) -> anyhow::Result<agentic_memory::SimilarityMetric> {
+    let Some(name) = std::env::var("AMEM_SIMILARITY_METRIC")
+        .ok()
+        .or_else(|| config.and_then(|c| c.similarity_metric.clone()))
+        .filter(|name| !name.trim().is_empty())
+    else {
+        return Ok(agentic_memory::SimilarityMetric::default());
+    };
+    let metric = agentic_memory::SimilarityMetric::from_name(name.trim())
+        .ok_or_else(|| anyhow::anyhow!("Unknown similarity metric '{name}'"))?;
+    tracing::info!("memory_similar default metric: {}", metric.name());
+    Ok(metric)
+}
+
+/// The edge types `memory_traverse` follows by default; empty means all.
+/// `AMEM_TRAVERSAL_EDGE_TYPES` (comma-separated) overrides the config file.
+fn traversal_config(
+    config: Option<&ServerConfig>,
+) -> anyhow::Result<Vec<agentic_memory::EdgeType>> {
+    let names: Vec<String> = match std::env::var("AMEM_TRAVERSAL_EDGE_TYPES") {
+        Ok(raw) => raw
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Err(_) => config
+            .map(|c| c.default_traversal_edge_types.clone())
+            .unwrap_or_default(),
+    };
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+    let edge_types = parse_edge_types(&names)
+        .map_err(|e| anyhow::anyhow!("default_traversal_edge_types: {e}"))?;
+    tracing::info!("memory_traverse default edge types: {}", names.join(", "));
+    Ok(edge_types)
+}
+
+/// Tool call timeouts. `AMEM_TOOL_TIMEOUT_SECS` sets one limit for every
+/// tool and overrides the config file; `0` disables timeouts.
+fn tool_timeout_config(config: Option<&ServerConfig>) -> anyhow::Result<ToolTimeouts> {
+    let limit = |secs: u64| (secs > 0).then(|| Duration::from_secs(secs));
+    if let Some(raw) = std::env::var("AMEM_TOOL_TIMEOUT_SECS")
+        .ok()
+        .filter(|raw| !raw.trim().is_empty())
+    {
+        let secs: u64 = raw
+            .trim()
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid AMEM_TOOL_TIMEOUT_SECS '{raw}'"))?;
+        tracing::info!("Tool call timeout: {secs}s");
+        return Ok(ToolTimeouts::uniform(limit(secs)));
+    }
+    let Some(config) = config.map(|c| &c.tool_timeouts) else {
+        return Ok(ToolTimeouts::default());
+    };
+    let mut timeouts = ToolTimeouts::default();
+    if let Some(secs) = config.read_secs {
+        timeouts.read = limit(secs);
+    }
+    if let Some(secs) = config.write_secs {
+        timeouts.write = limit(secs);
+    }
+    if let Some(secs) = config.long_secs {
+        timeouts.long = limit(secs);
+    }
+    for (tool, secs) in &config.tools {
+        timeouts.tools.insert(tool.clone(), limit(*secs));
+    }
+    Ok(timeouts)
+}
+
+/// Whether stderr logs are JSON: `--json-logs`, or `json_logs` in the
+/// config file of the serve command. Runs before logging is set up, so an
+/// unreadable config file is left for the command itself to report.
+fn json_logs_enabled(cli: &Cli) -> bool {
+    if cli.json_logs {
+        return true;
+    }
+    let config = match &cli.command {
+        Some(Commands::Serve { config, .. }) => config.as_deref(),
+        #[cfg(feature = "sse")]
+        Some(Commands::ServeHttp { config, .. }) => config.as_deref(),
+        _ => None,
+    };
+    config
+        .and_then(|path| load_config(path).ok())
+        .is_some_and(|c| c.json_logs)
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
@@ -852,38 +1212,66 @@ async fn main() -> anyhow::Result<()> {
     let filter = tracing_subscriber::EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(&cli.log_level));
 
-    tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .with_writer(std::io::stderr)
+    // Stderr honours --log-level; the MCP layer filters on the level the
+    // client sets with logging/setLevel.
+    let stderr_layer = if json_logs_enabled(&cli) {
+        tracing_subscriber::fmt::layer()
+            .json()
+            .with_writer(std::io::stderr)
+            .boxed()
+    } else {
+        tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .boxed()
+    };
+    let mcp_log_layer = McpLogLayer::new();
+    let log_sinks = mcp_log_layer.sinks();
+    tracing_subscriber::registry()
+        .with(stderr_layer.with_filter(filter))
+        .with(mcp_log_layer)
         .init();
 
     match cli.command.unwrap_or(Commands::Serve {
         memory: None,
+        memory_dir: None,
+        ephemeral: false,
+        namespace: None,
+        continue_session: false,
+        no_create: false,
         config: None,
         log_level: None,
         mode: "smart".to_string(),
     }) {
         Commands::Serve {
             memory,
-            config: _,
+            memory_dir,
+            ephemeral,
+            namespace,
+            continue_session,
+            no_create,
+            config,
             log_level: _,
             mode,
         } => {
-            let effective_memory = memory.or(cli.memory);
-            let memory_path = resolve_memory_path(effective_memory.as_deref());
+            let server_config = load_server_config(config.as_deref())?;
+            let settings = handler_settings(server_config.as_ref(), log_sinks)?;
             let memory_mode = MemoryMode::parse(&mode).unwrap_or_else(|| {
                 tracing::warn!("Unknown mode '{mode}', falling back to 'smart'");
                 MemoryMode::Smart
             });
             enforce_mode_runtime_policy(memory_mode);
             tracing::info!("AgenticMemory MCP server");
-            tracing::info!("Brain: {memory_path}");
+            let session = open_serving_session(
+                serving_memory_path(memory.or(cli.memory), ephemeral),
+                memory_dir,
+                namespace,
+                continue_session,
+                !no_create,
+                server_config.as_ref(),
+                memory_mode,
+            )
+            .await?;
             tracing::info!("Mode: {mode}");
-            let mut session = SessionManager::open(&memory_path)?;
-            session.apply_memory_mode(memory_mode);
-            let maintenance_interval = session.maintenance_interval();
-            let session = Arc::new(Mutex::new(session));
-            let _maintenance_task = spawn_maintenance(session.clone(), maintenance_interval);
 
             // V3 Ghost Writer: background sync to Claude, Cursor, Windsurf, Cody
             #[cfg(feature = "v3")]
@@ -892,8 +1280,9 @@ async fn main() -> anyhow::Result<()> {
                 agentic_memory_mcp::ghost_bridge::spawn_ghost_writer(session.clone())
             };
 
-            let handler = ProtocolHandler::with_mode(session, memory_mode);
-            let transport = StdioTransport::new(handler);
+            let handler = ProtocolHandler::with_mode(session, memory_mode).with_settings(settings);
+            let transport = StdioTransport::new(handler)
+                .with_max_request_bytes(max_request_bytes(server_config.as_ref()));
             transport.run().await?;
         }
 
@@ -901,7 +1290,12 @@ async fn main() -> anyhow::Result<()> {
         Commands::ServeHttp {
             addr,
             memory,
-            config: _,
+            memory_dir,
+            ephemeral,
+            namespace,
+            continue_session,
+            no_create,
+            config,
             log_level: _,
             mode,
             token,
@@ -909,7 +1303,7 @@ async fn main() -> anyhow::Result<()> {
             data_dir,
         } => {
             use agentic_memory_mcp::session::tenant::TenantRegistry;
-            use agentic_memory_mcp::transport::sse::{ServerMode, SseTransport};
+            use agentic_memory_mcp::transport::sse::{ServerMode, SseTransport, DEFAULT_KEEPALIVE};
 
             let memory_mode = MemoryMode::parse(&mode).unwrap_or_else(|| {
                 tracing::warn!("Unknown mode '{mode}', falling back to 'smart'");
@@ -920,6 +1314,18 @@ async fn main() -> anyhow::Result<()> {
             // Resolve token: CLI flag > env var
             let effective_token = token.or_else(|| std::env::var("AGENTIC_TOKEN").ok());
 
+            let server_config = load_server_config(config.as_deref())?;
+            let settings = handler_settings(server_config.as_ref(), log_sinks)?;
+            let keepalive_secs = server_config
+                .as_ref()
+                .map(|c| c.sse_keepalive_secs)
+                .unwrap_or(DEFAULT_KEEPALIVE.as_secs())
+                .max(1);
+            let cors = server_config
+                .as_ref()
+                .map(|c| c.cors.clone())
+                .unwrap_or_default();
+
             let server_mode = if multi_tenant {
                 let dir = data_dir.unwrap_or_else(|| {
                     eprintln!("Error: --data-dir is required when using --multi-tenant");
@@ -931,21 +1337,28 @@ async fn main() -> anyhow::Result<()> {
                 tracing::info!("Mode: {mode}");
                 ServerMode::MultiTenant {
                     data_dir: dir.clone(),
-                    registry: Arc::new(Mutex::new(TenantRegistry::new(&dir))),
+                    registry: Arc::new(tokio::sync::Mutex::new(
+                        TenantRegistry::new(&dir)
+                            .with_tool_defaults(tool_defaults_config(server_config.as_ref())?),
+                    )),
                     memory_mode,
+                    settings,
                 }
             } else {
-                let effective_memory = memory.or(cli.memory);
-                let memory_path = resolve_memory_path(effective_memory.as_deref());
                 tracing::info!("AgenticMemory MCP server");
-                tracing::info!("Brain: {memory_path}");
+                let session = open_serving_session(
+                    serving_memory_path(memory.or(cli.memory), ephemeral),
+                    memory_dir,
+                    namespace,
+                    continue_session,
+                    !no_create,
+                    server_config.as_ref(),
+                    memory_mode,
+                )
+                .await?;
                 tracing::info!("Mode: {mode}");
-                let mut session = SessionManager::open(&memory_path)?;
-                session.apply_memory_mode(memory_mode);
-                let maintenance_interval = session.maintenance_interval();
-                let session = Arc::new(Mutex::new(session));
-                let _maintenance_task = spawn_maintenance(session.clone(), maintenance_interval);
-                let handler = ProtocolHandler::with_mode(session, memory_mode);
+                let handler =
+                    ProtocolHandler::with_mode(session, memory_mode).with_settings(settings);
                 ServerMode::Single(Arc::new(handler))
             };
 
@@ -953,20 +1366,45 @@ async fn main() -> anyhow::Result<()> {
                 tracing::info!("Auth: bearer token required");
             }
 
-            let transport = SseTransport::with_config(effective_token, server_mode);
+            let transport = SseTransport::with_config(effective_token, server_mode)
+                .with_cors(cors)
+                .with_keepalive(Duration::from_secs(keepalive_secs))
+                .with_max_request_bytes(max_request_bytes(server_config.as_ref()));
             transport.run(&addr).await?;
         }
 
-        Commands::Validate => {
+        Commands::Validate { deep } => {
             let memory_path = resolve_memory_path(cli.memory.as_deref());
-            match SessionManager::open(&memory_path) {
+            match SessionManager::open_existing(&memory_path) {
                 Ok(session) => {
                     let graph = session.graph();
-                    println!("Valid memory file: {memory_path}");
+                    let report = deep.then(|| agentic_memory::check_integrity(graph));
+                    if report.as_ref().is_some_and(|r| !r.is_ok()) {
+                        println!("Integrity problems in memory file: {memory_path}");
+                    } else {
+                        println!("Valid memory file: {memory_path}");
+                    }
                     println!("  Nodes: {}", graph.node_count());
                     println!("  Edges: {}", graph.edge_count());
                     println!("  Dimension: {}", graph.dimension());
                     println!("  Sessions: {}", graph.session_index().session_count());
+                    if let Some(report) = report {
+                        println!("  Orphan nodes: {}", report.orphan_count);
+                        println!("  Problems: {}", report.issues.len());
+                        for issue in &report.issues {
+                            println!("    - {issue}");
+                        }
+                        if !report.is_ok() {
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                Err(McpError::IncompatibleFormat { found, expected }) => {
+                    eprintln!("Incompatible memory file: {memory_path}");
+                    eprintln!("  File format version: {found}");
+                    eprintln!("  Supported up to:     {expected}");
+                    eprintln!("  Upgrade agentic-memory-mcp to a release that reads v{found}.");
+                    std::process::exit(2);
                 }
                 Err(e) => {
                     eprintln!("Invalid memory file: {e}");
@@ -1029,7 +1467,7 @@ async fn main() -> anyhow::Result<()> {
                 }
             }
 
-            match session.graph_mut().remove_node(node_id) {
+            match session.delete_node(node_id) {
                 Ok(_removed) => {
                     if let Err(e) = session.save() {
                         eprintln!("Error saving: {e}");
@@ -1076,19 +1514,8 @@ async fn main() -> anyhow::Result<()> {
                         })
                         .collect();
 
-                    let edges_json: Vec<serde_json::Value> = graph
-                        .edges()
-                        .iter()
-                        .map(|e| {
-                            serde_json::json!({
-                                "source_id": e.source_id,
-                                "target_id": e.target_id,
-                                "edge_type": e.edge_type.name(),
-                                "weight": e.weight,
-                                "created_at": e.created_at,
-                            })
-                        })
-                        .collect();
+                    let edges_json: Vec<serde_json::Value> =
+                        graph.edges().iter().map(edge_json).collect();
 
                     let output = serde_json::json!({
                         "nodes": nodes_json,
@@ -1143,12 +1570,12 @@ async fn main() -> anyhow::Result<()> {
                 std::process::exit(1);
             }
 
-            // Find nodes below threshold
+            // Find nodes below threshold; pinned nodes are never forgotten
             let to_remove: Vec<(u64, f32, String)> = session
                 .graph()
                 .nodes()
                 .iter()
-                .filter(|n| n.decay_score < keep_above)
+                .filter(|n| n.decay_score < keep_above && !session.is_pinned(n.id))
                 .map(|n| {
                     let preview = if n.content.len() > 60 {
                         format!("{}...", &n.content[..60])
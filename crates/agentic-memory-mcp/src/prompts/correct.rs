@@ -2,10 +2,11 @@
 
 use serde_json::Value;
 
+use super::PromptLocale;
 use crate::types::{McpError, McpResult, PromptGetResult, PromptMessage, ToolContent};
 
 /// Expand the `correct` prompt with the given arguments.
-pub fn expand(args: Value) -> McpResult<PromptGetResult> {
+pub fn expand(args: Value, locale: PromptLocale) -> McpResult<PromptGetResult> {
     let old_belief = args
         .get("old_belief")
         .and_then(|v| v.as_str())
@@ -18,26 +19,64 @@ pub fn expand(args: Value) -> McpResult<PromptGetResult> {
             McpError::InvalidParams("'new_information' argument is required".to_string())
         })?;
 
+    let reason_label = match locale {
+        PromptLocale::En => "Reason",
+        PromptLocale::Es => "Motivo",
+        PromptLocale::De => "Grund",
+    };
     let reason = args
         .get("reason")
         .and_then(|v| v.as_str())
-        .map(|r| format!("\nReason: {r}\n"))
+        .map(|r| format!("\n{reason_label}: {r}\n"))
         .unwrap_or_default();
 
-    let text = format!(
-        "I need to correct my understanding:\n\n\
-         Previous belief: {old_belief}\n\
-         Correct information: {new_information}\n\
-         {reason}\n\
-         Please:\n\
-         1. Use memory_query to find the node containing the old belief\n\
-         2. Use memory_causal to see what depends on this belief\n\
-         3. Use memory_correct to create the correction\n\
-         4. Consider if dependent decisions should also be corrected"
-    );
+    let (text, description) = match locale {
+        PromptLocale::En => (
+            format!(
+                "I need to correct my understanding:\n\n\
+                 Previous belief: {old_belief}\n\
+                 Correct information: {new_information}\n\
+                 {reason}\n\
+                 Please:\n\
+                 1. Use memory_query to find the node containing the old belief\n\
+                 2. Use memory_causal to see what depends on this belief\n\
+                 3. Use memory_correct to create the correction\n\
+                 4. Consider if dependent decisions should also be corrected"
+            ),
+            "Guide for updating beliefs",
+        ),
+        PromptLocale::Es => (
+            format!(
+                "Necesito corregir lo que entendía:\n\n\
+                 Creencia anterior: {old_belief}\n\
+                 Información correcta: {new_information}\n\
+                 {reason}\n\
+                 Por favor:\n\
+                 1. Usa memory_query para encontrar el nodo con la creencia anterior\n\
+                 2. Usa memory_causal para ver qué depende de esta creencia\n\
+                 3. Usa memory_correct para crear la corrección\n\
+                 4. Valora si las decisiones dependientes también deben corregirse"
+            ),
+            "Guía para actualizar creencias",
+        ),
+        PromptLocale::De => (
+            format!(
+                "Ich muss mein Verständnis korrigieren:\n\n\
+                 Bisherige Annahme: {old_belief}\n\
+                 Richtige Information: {new_information}\n\
+                 {reason}\n\
+                 Bitte:\n\
+                 1. Finde mit memory_query den Knoten mit der alten Annahme\n\
+                 2. Prüfe mit memory_causal, was von dieser Annahme abhängt\n\
+                 3. Lege mit memory_correct die Korrektur an\n\
+                 4. Prüfe, ob abhängige Entscheidungen ebenfalls korrigiert werden müssen"
+            ),
+            "Anleitung zum Aktualisieren von Annahmen",
+        ),
+    };
 
     Ok(PromptGetResult {
-        description: Some("Guide for updating beliefs".to_string()),
+        description: Some(description.to_string()),
         messages: vec![PromptMessage {
             role: "user".to_string(),
             content: ToolContent::Text { text },
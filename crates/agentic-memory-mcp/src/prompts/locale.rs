@@ -0,0 +1,61 @@
+//! Language of the guidance prose emitted by the prompt templates.
+//!
+//! Tool names, argument names, and user-supplied values are never
+//! translated; only the surrounding instructions change.
+
+/// A language the prompt templates are bundled in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PromptLocale {
+    /// English (the default).
+    #[default]
+    En,
+    /// Spanish.
+    Es,
+    /// German.
+    De,
+}
+
+impl PromptLocale {
+    /// Every bundled locale.
+    pub fn all() -> &'static [PromptLocale] {
+        &[Self::En, Self::Es, Self::De]
+    }
+
+    /// Parse a locale tag such as `es`, `de-AT`, or `en_US.UTF-8`; only the
+    /// language part is used.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag
+            .trim()
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Self::En),
+            "es" => Some(Self::Es),
+            "de" => Some(Self::De),
+            _ => None,
+        }
+    }
+
+    /// Read `AMEM_PROMPT_LOCALE`, falling back to English when it is unset
+    /// or names a language that is not bundled.
+    pub fn from_env() -> Self {
+        match std::env::var("AMEM_PROMPT_LOCALE") {
+            Ok(tag) if !tag.trim().is_empty() => Self::from_tag(&tag).unwrap_or_else(|| {
+                tracing::warn!("Unsupported prompt locale '{}', using English", tag.trim());
+                Self::En
+            }),
+            _ => Self::En,
+        }
+    }
+
+    /// The locale's language tag.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Es => "es",
+            Self::De => "de",
+        }
+    }
+}
@@ -1,9 +1,11 @@
 //! MCP prompt templates for common memory operations.
 
 pub mod correct;
+pub mod locale;
 pub mod reflect;
 pub mod registry;
 pub mod remember;
 pub mod summarize;
 
+pub use locale::PromptLocale;
 pub use registry::PromptRegistry;
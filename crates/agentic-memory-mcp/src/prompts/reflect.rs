@@ -2,10 +2,11 @@
 
 use serde_json::Value;
 
+use super::PromptLocale;
 use crate::types::{McpError, McpResult, PromptGetResult, PromptMessage, ToolContent};
 
 /// Expand the `reflect` prompt with the given arguments.
-pub fn expand(args: Value) -> McpResult<PromptGetResult> {
+pub fn expand(args: Value, locale: PromptLocale) -> McpResult<PromptGetResult> {
     let topic = args
         .get("topic")
         .and_then(|v| v.as_str())
@@ -14,21 +15,54 @@ pub fn expand(args: Value) -> McpResult<PromptGetResult> {
     let node_hint = args
         .get("node_id")
         .and_then(|v| v.as_u64())
-        .map(|id| format!("\nStart from node #{id}.\n"))
+        .map(|id| match locale {
+            PromptLocale::En => format!("\nStart from node #{id}.\n"),
+            PromptLocale::Es => format!("\nEmpieza por el nodo #{id}.\n"),
+            PromptLocale::De => format!("\nBeginne bei Knoten #{id}.\n"),
+        })
         .unwrap_or_default();
 
-    let text = format!(
-        "I want to understand my reasoning about: {topic}\n\
-         {node_hint}\n\
-         Please help me reflect by:\n\
-         1. Use memory_query to find relevant decisions or beliefs\n\
-         2. Use memory_traverse with direction=\"backward\" to find the reasoning chain\n\
-         3. Use memory_causal to understand dependencies\n\
-         4. Summarize the reasoning chain clearly"
-    );
+    let (text, description) = match locale {
+        PromptLocale::En => (
+            format!(
+                "I want to understand my reasoning about: {topic}\n\
+                 {node_hint}\n\
+                 Please help me reflect by:\n\
+                 1. Use memory_query to find relevant decisions or beliefs\n\
+                 2. Use memory_traverse with direction=\"backward\" to find the reasoning chain\n\
+                 3. Use memory_causal to understand dependencies\n\
+                 4. Summarize the reasoning chain clearly"
+            ),
+            "Guide for understanding past decisions",
+        ),
+        PromptLocale::Es => (
+            format!(
+                "Quiero entender mi razonamiento sobre: {topic}\n\
+                 {node_hint}\n\
+                 Ayúdame a reflexionar así:\n\
+                 1. Usa memory_query para encontrar las decisiones o creencias relevantes\n\
+                 2. Usa memory_traverse con direction=\"backward\" para encontrar la cadena de razonamiento\n\
+                 3. Usa memory_causal para entender las dependencias\n\
+                 4. Resume la cadena de razonamiento con claridad"
+            ),
+            "Guía para entender decisiones pasadas",
+        ),
+        PromptLocale::De => (
+            format!(
+                "Ich möchte meine Überlegungen verstehen zu: {topic}\n\
+                 {node_hint}\n\
+                 Hilf mir beim Nachdenken:\n\
+                 1. Finde mit memory_query die relevanten Entscheidungen oder Annahmen\n\
+                 2. Finde mit memory_traverse und direction=\"backward\" die Begründungskette\n\
+                 3. Verstehe mit memory_causal die Abhängigkeiten\n\
+                 4. Fasse die Begründungskette klar zusammen"
+            ),
+            "Anleitung zum Verstehen früherer Entscheidungen",
+        ),
+    };
 
     Ok(PromptGetResult {
-        description: Some("Guide for understanding past decisions".to_string()),
+        description: Some(description.to_string()),
         messages: vec![PromptMessage {
             role: "user".to_string(),
             content: ToolContent::Text { text },
@@ -1,7 +1,7 @@
 //! Prompt registration and dispatch for MCP prompt templates.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde_json::Value;
 
@@ -87,18 +87,22 @@ impl PromptRegistry {
     }
 
     /// Expand a prompt with the given arguments, dispatching to the appropriate handler.
+    ///
+    /// The guidance text is written in the session's prompt locale
+    /// (`AMEM_PROMPT_LOCALE`).
     pub async fn get(
         name: &str,
         arguments: Option<Value>,
-        session: &Arc<Mutex<SessionManager>>,
+        session: &Arc<RwLock<SessionManager>>,
     ) -> McpResult<PromptGetResult> {
         let args = arguments.unwrap_or(Value::Object(serde_json::Map::new()));
+        let locale = session.read().await.prompt_locale();
 
         match name {
-            "remember" => remember::expand(args),
-            "reflect" => reflect::expand(args),
-            "correct" => correct::expand(args),
-            "summarize" => summarize::expand(args, session).await,
+            "remember" => remember::expand(args, locale),
+            "reflect" => reflect::expand(args, locale),
+            "correct" => correct::expand(args, locale),
+            "summarize" => summarize::expand(args, locale, session).await,
             _ => Err(McpError::PromptNotFound(name.to_string())),
         }
     }
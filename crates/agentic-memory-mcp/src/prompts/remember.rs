@@ -2,10 +2,11 @@
 
 use serde_json::Value;
 
+use super::PromptLocale;
 use crate::types::{McpError, McpResult, PromptGetResult, PromptMessage, ToolContent};
 
 /// Expand the `remember` prompt with the given arguments.
-pub fn expand(args: Value) -> McpResult<PromptGetResult> {
+pub fn expand(args: Value, locale: PromptLocale) -> McpResult<PromptGetResult> {
     let information = args
         .get("information")
         .and_then(|v| v.as_str())
@@ -13,24 +14,58 @@ pub fn expand(args: Value) -> McpResult<PromptGetResult> {
 
     let context = args.get("context").and_then(|v| v.as_str()).unwrap_or("");
 
+    let context_label = match locale {
+        PromptLocale::En => "Context",
+        PromptLocale::Es => "Contexto",
+        PromptLocale::De => "Kontext",
+    };
     let context_line = if context.is_empty() {
         String::new()
     } else {
-        format!("\nContext: {context}\n")
+        format!("\n{context_label}: {context}\n")
     };
 
-    let text = format!(
-        "I need to remember the following information:\n\n\
-         {information}\n\
-         {context_line}\n\
-         Please analyze this information and:\n\
-         1. Determine the appropriate event type (fact, decision, inference, skill)\n\
-         2. Identify any existing memories this might relate to or contradict\n\
-         3. Use the memory_add tool to store this information with appropriate edges"
-    );
+    let (text, description) = match locale {
+        PromptLocale::En => (
+            format!(
+                "I need to remember the following information:\n\n\
+                 {information}\n\
+                 {context_line}\n\
+                 Please analyze this information and:\n\
+                 1. Determine the appropriate event type (fact, decision, inference, skill)\n\
+                 2. Identify any existing memories this might relate to or contradict\n\
+                 3. Use the memory_add tool to store this information with appropriate edges"
+            ),
+            "Guide for storing new information",
+        ),
+        PromptLocale::Es => (
+            format!(
+                "Necesito recordar la siguiente información:\n\n\
+                 {information}\n\
+                 {context_line}\n\
+                 Analiza esta información y:\n\
+                 1. Determina el tipo de evento adecuado (fact, decision, inference, skill)\n\
+                 2. Identifica los recuerdos existentes con los que se relaciona o a los que contradice\n\
+                 3. Usa la herramienta memory_add para guardarla con las aristas adecuadas"
+            ),
+            "Guía para guardar información nueva",
+        ),
+        PromptLocale::De => (
+            format!(
+                "Ich muss mir folgende Information merken:\n\n\
+                 {information}\n\
+                 {context_line}\n\
+                 Bitte analysiere diese Information und:\n\
+                 1. Bestimme den passenden Ereignistyp (fact, decision, inference, skill)\n\
+                 2. Finde bestehende Erinnerungen, mit denen sie zusammenhängt oder denen sie widerspricht\n\
+                 3. Speichere sie mit dem Werkzeug memory_add und passenden Kanten"
+            ),
+            "Anleitung zum Speichern neuer Informationen",
+        ),
+    };
 
     Ok(PromptGetResult {
-        description: Some("Guide for storing new information".to_string()),
+        description: Some(description.to_string()),
         messages: vec![PromptMessage {
             role: "user".to_string(),
             content: ToolContent::Text { text },
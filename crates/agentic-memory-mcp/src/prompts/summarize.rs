@@ -1,19 +1,21 @@
 //! Prompt template: "Summarize session N."
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde_json::Value;
 
+use super::PromptLocale;
 use crate::session::SessionManager;
 use crate::types::{McpResult, PromptGetResult, PromptMessage, ToolContent};
 
 /// Expand the `summarize` prompt with the given arguments.
 pub async fn expand(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    locale: PromptLocale,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<PromptGetResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let session_id = args
         .get("session_id")
         .and_then(|v| v.as_u64())
@@ -41,21 +43,54 @@ pub async fn expand(
         })
         .collect();
 
-    let text = format!(
-        "Please summarize session {session_id} which contains {node_count} memories:\n\n\
-         {}\n\n\
-         Create a concise episode summary capturing:\n\
-         1. The main topic or goal\n\
-         2. Key facts learned\n\
-         3. Important decisions made\n\
-         4. Any corrections\n\
-         5. The outcome\n\n\
-         Then use session_end with create_episode=true and your summary.",
-        node_list.join("\n")
-    );
+    let node_list = node_list.join("\n");
+    let (text, description) = match locale {
+        PromptLocale::En => (
+            format!(
+                "Please summarize session {session_id} which contains {node_count} memories:\n\n\
+                 {node_list}\n\n\
+                 Create a concise episode summary capturing:\n\
+                 1. The main topic or goal\n\
+                 2. Key facts learned\n\
+                 3. Important decisions made\n\
+                 4. Any corrections\n\
+                 5. The outcome\n\n\
+                 Then use session_end with create_episode=true and your summary."
+            ),
+            format!("Guide for summarizing session {session_id}"),
+        ),
+        PromptLocale::Es => (
+            format!(
+                "Resume la sesión {session_id}, que contiene {node_count} recuerdos:\n\n\
+                 {node_list}\n\n\
+                 Crea un resumen breve del episodio que recoja:\n\
+                 1. El tema u objetivo principal\n\
+                 2. Los hechos clave aprendidos\n\
+                 3. Las decisiones importantes\n\
+                 4. Las correcciones, si las hay\n\
+                 5. El resultado\n\n\
+                 Después usa session_end con create_episode=true y tu resumen."
+            ),
+            format!("Guía para resumir la sesión {session_id}"),
+        ),
+        PromptLocale::De => (
+            format!(
+                "Bitte fasse Sitzung {session_id} mit {node_count} Erinnerungen zusammen:\n\n\
+                 {node_list}\n\n\
+                 Erstelle eine knappe Episodenzusammenfassung mit:\n\
+                 1. Dem Hauptthema oder Ziel\n\
+                 2. Den wichtigsten gelernten Fakten\n\
+                 3. Den wichtigen Entscheidungen\n\
+                 4. Etwaigen Korrekturen\n\
+                 5. Dem Ergebnis\n\n\
+                 Verwende danach session_end mit create_episode=true und deiner Zusammenfassung."
+            ),
+            format!("Anleitung zum Zusammenfassen von Sitzung {session_id}"),
+        ),
+    };
 
     Ok(PromptGetResult {
-        description: Some(format!("Guide for summarizing session {session_id}")),
+        description: Some(description),
         messages: vec![PromptMessage {
             role: "user".to_string(),
             content: ToolContent::Text { text },
@@ -0,0 +1,165 @@
+//! Server-to-client requests (e.g. `sampling/createMessage`) and response correlation.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::types::{
+    JsonRpcErrorObject, JsonRpcRequest, McpError, McpResult, Redactor, RequestId, JSONRPC_VERSION,
+};
+
+/// How long to wait for the client to answer a sampling request.
+pub const SAMPLING_TIMEOUT: Duration = Duration::from_secs(60);
+
+type Pending = oneshot::Sender<Result<Value, JsonRpcErrorObject>>;
+
+/// Sends requests to the client over the transport and matches up the replies.
+///
+/// Request IDs are strings prefixed with `amem-` so they never collide with
+/// the numeric IDs clients typically use for their own requests.
+pub struct ClientRequester {
+    outbound: mpsc::Sender<JsonRpcRequest>,
+    pending: std::sync::Mutex<HashMap<String, Pending>>,
+    next_id: AtomicU64,
+    redactor: Redactor,
+}
+
+impl ClientRequester {
+    /// Create a requester that writes outgoing requests into `outbound`.
+    pub fn new(outbound: mpsc::Sender<JsonRpcRequest>) -> Self {
+        Self {
+            outbound,
+            pending: std::sync::Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            redactor: Redactor::default(),
+        }
+    }
+
+    /// Mask sampling prompts with `redactor` before they reach the client.
+    pub fn with_redactor(mut self, redactor: Redactor) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
+    /// Send `method` to the client and wait up to `timeout` for its result.
+    pub async fn request(
+        &self,
+        method: &str,
+        params: Value,
+        timeout: Duration,
+    ) -> McpResult<Value> {
+        let id = format!("amem-{}", self.next_id.fetch_add(1, Ordering::Relaxed));
+        let (tx, rx) = oneshot::channel();
+        self.lock_pending().insert(id.clone(), tx);
+        // Unregisters the request however this future ends, including when
+        // the caller drops it on a tool timeout or cancellation.
+        let _pending = PendingGuard {
+            requester: self,
+            id: id.clone(),
+        };
+
+        let request = JsonRpcRequest {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id: RequestId::String(id.clone()),
+            method: method.to_string(),
+            params: Some(params),
+        };
+        if self.outbound.send(request).await.is_err() {
+            return Err(McpError::Transport(
+                "Client request channel closed".to_string(),
+            ));
+        }
+
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(Ok(result))) => Ok(result),
+            Ok(Ok(Err(error))) => Err(McpError::InternalError(format!(
+                "Client rejected {method} ({}): {}",
+                error.code, error.message
+            ))),
+            Ok(Err(_)) => Err(McpError::Transport(format!(
+                "Connection closed before the client answered {method}"
+            ))),
+            Err(_) => Err(McpError::InternalError(format!(
+                "Client did not answer {method} within {}s",
+                timeout.as_secs()
+            ))),
+        }
+    }
+
+    /// Deliver a client response. Returns false if no request is waiting on `id`.
+    pub fn complete(&self, id: &RequestId, outcome: Result<Value, JsonRpcErrorObject>) -> bool {
+        let RequestId::String(key) = id else {
+            return false;
+        };
+        match self.lock_pending().remove(key) {
+            Some(tx) => {
+                let _ = tx.send(outcome);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Ask the client's model to complete a single user prompt via
+    /// `sampling/createMessage` and return the generated text. The prompt is
+    /// masked with the requester's redactor first.
+    pub async fn create_message(
+        &self,
+        system_prompt: &str,
+        prompt: &str,
+        max_tokens: u32,
+    ) -> McpResult<String> {
+        let prompt = self.redactor.text(prompt);
+        let result = self
+            .request(
+                "sampling/createMessage",
+                json!({
+                    "messages": [{
+                        "role": "user",
+                        "content": { "type": "text", "text": prompt.as_ref() }
+                    }],
+                    "systemPrompt": system_prompt,
+                    "includeContext": "none",
+                    "maxTokens": max_tokens,
+                }),
+                SAMPLING_TIMEOUT,
+            )
+            .await?;
+        result
+            .get("content")
+            .and_then(|c| c.get("text"))
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                McpError::InternalError("Sampling result contained no text content".to_string())
+            })
+    }
+
+    fn lock_pending(&self) -> std::sync::MutexGuard<'_, HashMap<String, Pending>> {
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Number of requests still waiting for a client reply.
+    pub fn pending_count(&self) -> usize {
+        self.lock_pending().len()
+    }
+}
+
+/// Removes a request's pending entry when the waiting future ends.
+struct PendingGuard<'a> {
+    requester: &'a ClientRequester,
+    id: String,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        self.requester.lock_pending().remove(&self.id);
+    }
+}
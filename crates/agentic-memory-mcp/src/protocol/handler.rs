@@ -1,30 +1,67 @@
 //! Main request dispatcher — receives JSON-RPC messages, routes to handlers.
 
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{mpsc, Mutex, RwLock};
 
 use serde_json::{json, Value};
 
 use crate::prompts::PromptRegistry;
 use crate::resources::ResourceRegistry;
 use crate::session::SessionManager;
+use crate::streaming::{progress, CancelFlag, CancellationRegistry, LogSinks, LogSubscription};
+use crate::tools::timeout::ToolTimeouts;
 #[cfg(feature = "v3")]
 use crate::tools::v3_tools::{self, SharedEngine};
-use crate::tools::ToolRegistry;
+use crate::tools::{CustomTools, ToolRegistry};
 use crate::types::*;
 #[cfg(feature = "v3")]
 use crate::v3_auto_capture::AutoCaptureMiddleware;
 #[cfg(feature = "v3")]
 use agentic_memory::v3::{EngineConfig, MemoryEngineV3};
 
+use super::client_request::ClientRequester;
 use super::negotiation::NegotiatedCapabilities;
 use super::validator::validate_request;
 
+/// Server configuration a handler applies to the client it serves.
+#[derive(Debug, Clone)]
+pub struct HandlerSettings {
+    /// Fail `initialize` for an unsupported protocol version.
+    pub strict_protocol_version: bool,
+    /// Identity advertised as `serverInfo`.
+    pub server_info: Implementation,
+    /// Rendering of JSON tool results.
+    pub output: OutputFormat,
+    /// Masking applied to everything sent to the client.
+    pub redactor: Redactor,
+    /// Log records the client can subscribe to with `logging/setLevel`.
+    pub log_sinks: LogSinks,
+    /// Limits on how long each tool call may run.
+    pub tool_timeouts: ToolTimeouts,
+    /// Tools registered by the embedding crate, served next to the built-ins.
+    pub custom_tools: CustomTools,
+}
+
+impl Default for HandlerSettings {
+    fn default() -> Self {
+        Self {
+            strict_protocol_version: false,
+            server_info: server_info(None, None, None),
+            output: OutputFormat::default(),
+            redactor: Redactor::default(),
+            log_sinks: LogSinks::default(),
+            tool_timeouts: ToolTimeouts::default(),
+            custom_tools: CustomTools::default(),
+        }
+    }
+}
+
 /// The main protocol handler that dispatches incoming JSON-RPC messages.
 pub struct ProtocolHandler {
-    session: Arc<Mutex<SessionManager>>,
+    session: Arc<RwLock<SessionManager>>,
     capabilities: Arc<Mutex<NegotiatedCapabilities>>,
+    settings: HandlerSettings,
     shutdown_requested: Arc<AtomicBool>,
     memory_mode: MemoryMode,
     /// Tracks whether an auto-session was started so we can auto-end it.
@@ -32,6 +69,12 @@ pub struct ProtocolHandler {
     /// Tracks which session has already had its deterministic resume hook executed.
     last_resumed_session: Arc<Mutex<Option<u32>>>,
     tool_surface: ToolSurface,
+    /// Channel for server-to-client requests, once a transport attaches one.
+    client_requester: OnceLock<Arc<ClientRequester>>,
+    /// Cancellation flags of in-flight requests.
+    cancellations: CancellationRegistry,
+    /// The client's log record subscription, once notifications are attached.
+    log_subscription: std::sync::Mutex<Option<LogSubscription>>,
     /// V3 engine for immortal capture/retrieval tools.
     #[cfg(feature = "v3")]
     v3_engine: SharedEngine,
@@ -61,7 +104,7 @@ impl ToolSurface {
 
 impl ProtocolHandler {
     /// Create a new protocol handler with the given session manager.
-    pub fn new(session: Arc<Mutex<SessionManager>>) -> Self {
+    pub fn new(session: Arc<RwLock<SessionManager>>) -> Self {
         #[cfg(feature = "v3")]
         let v3_engine = init_v3_engine_from_env();
         #[cfg(feature = "v3")]
@@ -69,11 +112,15 @@ impl ProtocolHandler {
         Self {
             session,
             capabilities: Arc::new(Mutex::new(NegotiatedCapabilities::default())),
+            settings: HandlerSettings::default(),
             shutdown_requested: Arc::new(AtomicBool::new(false)),
             memory_mode: MemoryMode::Smart,
             auto_session_started: AtomicBool::new(false),
             last_resumed_session: Arc::new(Mutex::new(None)),
             tool_surface: ToolSurface::from_env(),
+            client_requester: OnceLock::new(),
+            cancellations: CancellationRegistry::new(),
+            log_subscription: std::sync::Mutex::new(None),
             #[cfg(feature = "v3")]
             v3_engine,
             #[cfg(feature = "v3")]
@@ -82,7 +129,7 @@ impl ProtocolHandler {
     }
 
     /// Create a new protocol handler with a specific memory mode.
-    pub fn with_mode(session: Arc<Mutex<SessionManager>>, mode: MemoryMode) -> Self {
+    pub fn with_mode(session: Arc<RwLock<SessionManager>>, mode: MemoryMode) -> Self {
         #[cfg(feature = "v3")]
         let v3_engine = init_v3_engine_from_env();
         #[cfg(feature = "v3")]
@@ -90,11 +137,15 @@ impl ProtocolHandler {
         Self {
             session,
             capabilities: Arc::new(Mutex::new(NegotiatedCapabilities::with_mode(mode))),
+            settings: HandlerSettings::default(),
             shutdown_requested: Arc::new(AtomicBool::new(false)),
             memory_mode: mode,
             auto_session_started: AtomicBool::new(false),
             last_resumed_session: Arc::new(Mutex::new(None)),
             tool_surface: ToolSurface::from_env(),
+            client_requester: OnceLock::new(),
+            cancellations: CancellationRegistry::new(),
+            log_subscription: std::sync::Mutex::new(None),
             #[cfg(feature = "v3")]
             v3_engine,
             #[cfg(feature = "v3")]
@@ -102,11 +153,58 @@ impl ProtocolHandler {
         }
     }
 
+    /// Apply server configuration. Call before the handler serves anything.
+    pub fn with_settings(mut self, settings: HandlerSettings) -> Self {
+        self.capabilities = Arc::new(Mutex::new(
+            NegotiatedCapabilities::with_mode(self.memory_mode)
+                .with_strict_protocol_version(settings.strict_protocol_version)
+                .with_server_info(settings.server_info.clone()),
+        ));
+        self.settings = settings;
+        self
+    }
+
+    /// The session this handler serves.
+    pub fn session(&self) -> &Arc<RwLock<SessionManager>> {
+        &self.session
+    }
+
     /// Returns true once a shutdown request has been handled.
     pub fn shutdown_requested(&self) -> bool {
         self.shutdown_requested.load(Ordering::Relaxed)
     }
 
+    /// Route server-initiated notifications (e.g. save failures, and log
+    /// records once the client calls `logging/setLevel`) into `tx`.
+    pub async fn attach_notifications(&self, tx: mpsc::Sender<JsonRpcNotification>) {
+        let subscription = self
+            .settings
+            .log_sinks
+            .subscribe(tx.clone(), self.settings.redactor.clone());
+        *self
+            .log_subscription
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(subscription);
+        self.session.write().await.set_notification_sender(tx);
+    }
+
+    /// Route server-to-client requests (e.g. `sampling/createMessage`) into `tx`.
+    ///
+    /// The transport must pass the client's replies back through
+    /// [`handle_message`](Self::handle_message) while the originating request
+    /// is still in flight.
+    pub fn attach_client_requests(&self, tx: mpsc::Sender<JsonRpcRequest>) {
+        if self
+            .client_requester
+            .set(Arc::new(
+                ClientRequester::new(tx).with_redactor(self.settings.redactor.clone()),
+            ))
+            .is_err()
+        {
+            tracing::warn!("Client request channel already attached");
+        }
+    }
+
     /// Handle an incoming JSON-RPC message and optionally return a response.
     pub async fn handle_message(&self, msg: JsonRpcMessage) -> Option<Value> {
         match msg {
@@ -115,11 +213,24 @@ impl ProtocolHandler {
                 self.handle_notification(notif).await;
                 None
             }
-            _ => {
-                // Responses and errors from the client are unexpected
-                tracing::warn!("Received unexpected message type from client");
+            JsonRpcMessage::Response(resp) => {
+                self.complete_client_request(&resp.id, Ok(resp.result));
                 None
             }
+            JsonRpcMessage::Error(err) => {
+                self.complete_client_request(&err.id, Err(err.error));
+                None
+            }
+        }
+    }
+
+    fn complete_client_request(&self, id: &RequestId, outcome: Result<Value, JsonRpcErrorObject>) {
+        let delivered = self
+            .client_requester
+            .get()
+            .is_some_and(|requester| requester.complete(id, outcome));
+        if !delivered {
+            tracing::warn!("Received response for unknown request {id:?}");
         }
     }
 
@@ -129,7 +240,7 @@ impl ProtocolHandler {
             return;
         }
 
-        let mut session = self.session.lock().await;
+        let mut session = self.session.write().await;
         let sid = session.current_session_id();
         match session.end_session_with_episode(sid, "Session ended: MCP connection closed") {
             Ok(episode_id) => {
@@ -145,6 +256,15 @@ impl ProtocolHandler {
         self.auto_session_started.store(false, Ordering::Relaxed);
     }
 
+    /// Write unsaved changes to disk; used when a transport shuts down.
+    pub async fn save_pending(&self) -> McpResult<()> {
+        let mut session = self.session.write().await;
+        if session.is_dirty() {
+            session.save()?;
+        }
+        Ok(())
+    }
+
     async fn handle_request(&self, request: JsonRpcRequest) -> Value {
         // Validate JSON-RPC structure
         if let Err(e) = validate_request(&request) {
@@ -152,11 +272,18 @@ impl ProtocolHandler {
         }
 
         let id = request.id.clone();
-        let result = self.dispatch_request(&request).await;
+        let result = self
+            .cancellations
+            .run(&id, self.dispatch_request(&request))
+            .await;
 
         match result {
             Ok(value) => serde_json::to_value(JsonRpcResponse::new(id, value)).unwrap_or_default(),
-            Err(e) => serde_json::to_value(e.to_json_rpc_error(id)).unwrap_or_default(),
+            Err(e) => {
+                let mut error = serde_json::to_value(e.to_json_rpc_error(id)).unwrap_or_default();
+                self.settings.redactor.value(&mut error);
+                error
+            }
         }
     }
 
@@ -181,6 +308,15 @@ impl ProtocolHandler {
             "prompts/list" => self.handle_prompts_list().await,
             "prompts/get" => self.handle_prompts_get(request.params.clone()).await,
 
+            // Completion
+            "completion/complete" => {
+                self.handle_completion_complete(request.params.clone())
+                    .await
+            }
+
+            // Logging
+            "logging/setLevel" => self.handle_logging_set_level(request.params.clone()),
+
             // Ping
             "ping" => Ok(Value::Object(serde_json::Map::new())),
 
@@ -198,7 +334,7 @@ impl ProtocolHandler {
 
                 // Auto-start session when client confirms connection (smart/full mode).
                 if self.memory_mode != MemoryMode::Minimal {
-                    let mut session = self.session.lock().await;
+                    let mut session = self.session.write().await;
                     match session.start_session(None) {
                         Ok(sid) => {
                             self.auto_session_started.store(true, Ordering::Relaxed);
@@ -216,7 +352,18 @@ impl ProtocolHandler {
                 }
             }
             "notifications/cancelled" | "$/cancelRequest" => {
-                tracing::info!("Received cancellation notification");
+                let id = notification
+                    .params
+                    .as_ref()
+                    .and_then(|p| p.get("requestId").or_else(|| p.get("id")))
+                    .and_then(|id| serde_json::from_value::<RequestId>(id.clone()).ok());
+                match id {
+                    Some(id) if self.cancellations.cancel(&id) => {
+                        tracing::info!("Cancelling request {id}");
+                    }
+                    Some(id) => tracing::debug!("Cancellation for finished request {id}"),
+                    None => tracing::warn!("Cancellation notification without a request ID"),
+                }
             }
             _ => {
                 tracing::debug!("Unknown notification: {}", notification.method);
@@ -231,8 +378,17 @@ impl ProtocolHandler {
             .map_err(|e| McpError::InvalidParams(e.to_string()))?
             .ok_or_else(|| McpError::InvalidParams("Initialize params required".to_string()))?;
 
+        let sampling = init_params.capabilities.sampling.is_some();
         let mut caps = self.capabilities.lock().await;
         let result = caps.negotiate(init_params)?;
+        drop(caps);
+
+        if let Some(requester) = self.client_requester.get() {
+            self.session
+                .write()
+                .await
+                .set_sampling_client(sampling.then(|| requester.clone()));
+        }
 
         serde_json::to_value(result).map_err(|e| McpError::InternalError(e.to_string()))
     }
@@ -240,7 +396,7 @@ impl ProtocolHandler {
     async fn handle_shutdown(&self) -> McpResult<Value> {
         tracing::info!("Shutdown requested");
 
-        let mut session = self.session.lock().await;
+        let mut session = self.session.write().await;
 
         // Auto-end session with episode summary if one was auto-started.
         if self.auto_session_started.swap(false, Ordering::Relaxed) {
@@ -257,17 +413,43 @@ impl ProtocolHandler {
         } else {
             session.save()?;
         }
+        for sibling in session.namespaces().values() {
+            sibling.write().await.save()?;
+        }
 
         self.shutdown_requested.store(true, Ordering::Relaxed);
         Ok(Value::Object(serde_json::Map::new()))
     }
 
+    fn handle_logging_set_level(&self, params: Option<Value>) -> McpResult<Value> {
+        let params: SetLevelParams = params
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| McpError::InvalidParams(e.to_string()))?
+            .ok_or_else(|| {
+                McpError::InvalidParams("logging/setLevel params required".to_string())
+            })?;
+        if let Some(subscription) = self
+            .log_subscription
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+        {
+            subscription.set_level(params.level);
+        }
+        tracing::debug!("Client log level set to {:?}", params.level);
+        Ok(Value::Object(serde_json::Map::new()))
+    }
+
     async fn handle_tools_list(&self) -> McpResult<Value> {
+        let defaults = self.session.read().await.tool_defaults().clone();
+        let mut tools = match self.tool_surface {
+            ToolSurface::Full => ToolRegistry::list_tools_with(&defaults),
+            ToolSurface::Compact => ToolRegistry::list_tools_compact(),
+        };
+        tools.extend(self.settings.custom_tools.definitions());
         let result = ToolListResult {
-            tools: match self.tool_surface {
-                ToolSurface::Full => ToolRegistry::list_tools(),
-                ToolSurface::Compact => ToolRegistry::list_tools_compact(),
-            },
+            tools,
             next_cursor: None,
         };
         serde_json::to_value(result).map_err(|e| McpError::InternalError(e.to_string()))
@@ -283,20 +465,40 @@ impl ProtocolHandler {
             .arguments
             .clone()
             .unwrap_or(Value::Object(serde_json::Map::new()));
+        let progress_token = call_params
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.get("progressToken"))
+            .and_then(|token| serde_json::from_value::<ProgressToken>(token.clone()).ok());
 
+        let call_started = std::time::Instant::now();
         self.ensure_resume_hook_for_active_session().await;
 
-        {
-            let mut session = self.session.lock().await;
-            if let Err(e) =
-                session.capture_tool_call(&call_params.name, call_params.arguments.as_ref())
-            {
-                tracing::warn!(
-                    "Auto-capture skipped for tool {} due to error: {}",
-                    call_params.name,
-                    e
-                );
-            }
+        // Decide under a read lock so concurrent reads only queue behind a
+        // write when there is something to capture.
+        let (capture, version_before) = {
+            let session = self.session.read().await;
+            (
+                session.tool_capture_text(&call_params.name, call_params.arguments.as_ref()),
+                session.graph_version(),
+            )
+        };
+        let captured = match capture {
+            Ok(Some(text)) => self
+                .session
+                .write()
+                .await
+                .persist_tool_capture(&text)
+                .map(|_| ()),
+            Ok(None) => Ok(()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = captured {
+            tracing::warn!(
+                "Auto-capture skipped for tool {} due to error: {}",
+                call_params.name,
+                e
+            );
         }
 
         #[cfg(feature = "v3")]
@@ -333,12 +535,20 @@ impl ProtocolHandler {
                     return Err(e);
                 }
                 Some(Err(e)) => ToolCallResult::error(e.to_string()),
-                None => match ToolRegistry::call(
-                    &call_params.name,
-                    call_params.arguments,
-                    &self.session,
-                )
-                .await
+                None => match self
+                    .call_with_timeout(
+                        &call_params.name,
+                        progress::with_progress_token(
+                            progress_token,
+                            ToolRegistry::call_with(
+                                &self.settings.custom_tools,
+                                &call_params.name,
+                                call_params.arguments,
+                                &self.session,
+                            ),
+                        ),
+                    )
+                    .await
                 {
                     Ok(r) => r,
                     Err(e) if e.is_protocol_error() => {
@@ -376,10 +586,60 @@ impl ProtocolHandler {
         if call_params.name == "session_start" {
             self.ensure_resume_hook_for_active_session().await;
         }
-
+        // Resources only come and go when the graph changed during the call.
+        let (session_id, list_changed) = {
+            let session = self.session.read().await;
+            (
+                session.current_session_id(),
+                session.graph_version() != version_before && session.resource_list_changed(),
+            )
+        };
+        if list_changed {
+            self.session.write().await.notify_resource_list_changes();
+        }
+        tracing::debug!(
+            tool = %call_params.name,
+            session_id,
+            is_error = result.is_error.unwrap_or(false),
+            elapsed_ms = call_started.elapsed().as_millis() as u64,
+            "Handled tool call"
+        );
+
+        let result = result
+            .redact(&self.settings.redactor)
+            .render(self.settings.output);
         serde_json::to_value(result).map_err(|e| McpError::InternalError(e.to_string()))
     }
 
+    /// Run a tool call under its timeout. On expiry the call is dropped,
+    /// which releases the session lock if it holds it across an await, and
+    /// the request's cancellation flag is raised so scans running on
+    /// blocking threads stop at their next check.
+    async fn call_with_timeout(
+        &self,
+        tool: &str,
+        call: impl std::future::Future<Output = McpResult<ToolCallResult>>,
+    ) -> McpResult<ToolCallResult> {
+        let Some(limit) = self.settings.tool_timeouts.limit_for(tool) else {
+            return call.await;
+        };
+        match tokio::time::timeout(limit, call).await {
+            Ok(result) => result,
+            Err(_) => {
+                CancelFlag::current().cancel();
+                tracing::warn!(
+                    tool,
+                    timeout_ms = limit.as_millis() as u64,
+                    "Tool call timed out"
+                );
+                Err(McpError::ToolTimeout {
+                    tool: tool.to_string(),
+                    elapsed_ms: limit.as_millis() as u64,
+                })
+            }
+        }
+    }
+
     async fn handle_resources_list(&self) -> McpResult<Value> {
         let result = ResourceListResult {
             resources: ResourceRegistry::list_resources(),
@@ -396,19 +656,72 @@ impl ProtocolHandler {
         serde_json::to_value(result).map_err(|e| McpError::InternalError(e.to_string()))
     }
 
+    async fn handle_completion_complete(&self, params: Option<Value>) -> McpResult<Value> {
+        let params: CompleteParams = params
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| McpError::InvalidParams(e.to_string()))?
+            .ok_or_else(|| {
+                McpError::InvalidParams("completion/complete params required".to_string())
+            })?;
+        let completion = match params.reference {
+            CompletionReference::Resource { uri } => {
+                ResourceRegistry::complete(
+                    &uri,
+                    &params.argument.name,
+                    &params.argument.value,
+                    &self.session,
+                )
+                .await?
+            }
+            // Prompt arguments are free text; there is nothing to enumerate.
+            CompletionReference::Prompt { .. } => Completion::default(),
+        };
+        serde_json::to_value(CompleteResult { completion })
+            .map_err(|e| McpError::InternalError(e.to_string()))
+    }
+
     async fn handle_resources_read(&self, params: Option<Value>) -> McpResult<Value> {
         let read_params: ResourceReadParams = params
             .map(serde_json::from_value)
             .transpose()
             .map_err(|e| McpError::InvalidParams(e.to_string()))?
             .ok_or_else(|| McpError::InvalidParams("Resource read params required".to_string()))?;
+        let session =
+            &SessionManager::resolve_namespace(&self.session, read_params.namespace.as_deref())
+                .await?;
+
+        if !read_params.uris.is_empty() {
+            let uris: Vec<String> = std::iter::once(read_params.uri)
+                .filter(|u| !u.is_empty())
+                .chain(read_params.uris)
+                .collect();
+            #[cfg(feature = "v3")]
+            let mut result =
+                ResourceRegistry::read_batch(&uris, session, Some(&self.v3_engine)).await?;
+            #[cfg(not(feature = "v3"))]
+            let mut result = ResourceRegistry::read_batch(&uris, session).await?;
+            self.settings
+                .redactor
+                .resource_contents(&mut result.contents);
+            return serde_json::to_value(result)
+                .map_err(|e| McpError::InternalError(e.to_string()));
+        }
+        if read_params.uri.is_empty() {
+            return Err(McpError::InvalidParams(
+                "Resource read requires 'uri' or 'uris'".to_string(),
+            ));
+        }
 
         #[cfg(feature = "v3")]
-        let result =
-            ResourceRegistry::read_with_v3(&read_params.uri, &self.session, Some(&self.v3_engine))
+        let mut result =
+            ResourceRegistry::read_with_v3(&read_params.uri, session, Some(&self.v3_engine))
                 .await?;
         #[cfg(not(feature = "v3"))]
-        let result = ResourceRegistry::read(&read_params.uri, &self.session).await?;
+        let mut result = ResourceRegistry::read(&read_params.uri, session).await?;
+        self.settings
+            .redactor
+            .resource_contents(&mut result.contents);
 
         serde_json::to_value(result).map_err(|e| McpError::InternalError(e.to_string()))
     }
@@ -435,7 +748,7 @@ impl ProtocolHandler {
         self.ensure_resume_hook_for_active_session().await;
 
         {
-            let mut session = self.session.lock().await;
+            let mut session = self.session.write().await;
             if let Err(e) =
                 session.capture_prompt_request(&get_params.name, get_params.arguments.as_ref())
             {
@@ -452,7 +765,11 @@ impl ProtocolHandler {
         self.auto_log_prompt_turn(&get_params.name, Some(&prompt_args))
             .await;
 
-        serde_json::to_value(result).map_err(|e| McpError::InternalError(e.to_string()))
+        // Prompt text embeds node content as plain text.
+        let mut value =
+            serde_json::to_value(result).map_err(|e| McpError::InternalError(e.to_string()))?;
+        self.settings.redactor.value(&mut value);
+        Ok(value)
     }
 
     async fn ensure_resume_hook_for_active_session(&self) {
@@ -461,7 +778,7 @@ impl ProtocolHandler {
         }
 
         let current_session = {
-            let session = self.session.lock().await;
+            let session = self.session.read().await;
             session.current_session_id()
         };
 
@@ -1,7 +1,9 @@
 //! MCP protocol layer — message handling, validation, and capability negotiation.
 
+pub mod client_request;
 pub mod handler;
 pub mod negotiation;
 pub mod validator;
 
-pub use handler::ProtocolHandler;
+pub use client_request::ClientRequester;
+pub use handler::{HandlerSettings, ProtocolHandler};
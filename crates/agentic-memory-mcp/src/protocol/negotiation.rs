@@ -1,10 +1,13 @@
 //! MCP capability negotiation during initialization.
 
 use crate::types::{
-    ClientCapabilities, InitializeParams, InitializeResult, McpError, McpResult, MemoryMode,
-    MCP_VERSION,
+    server_info, ClientCapabilities, Implementation, InitializeParams, InitializeResult, McpError,
+    McpResult, MemoryMode, MCP_VERSION,
 };
 
+/// Protocol versions a client may request.
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &[MCP_VERSION];
+
 /// Stored client capabilities after negotiation.
 #[derive(Debug, Clone)]
 pub struct NegotiatedCapabilities {
@@ -14,6 +17,11 @@ pub struct NegotiatedCapabilities {
     pub initialized: bool,
     /// Memory saving mode.
     pub mode: MemoryMode,
+    /// Fail `initialize` for an unsupported protocol version instead of
+    /// warning and proceeding.
+    pub strict_protocol_version: bool,
+    /// `serverInfo` returned from `initialize`.
+    pub server_info: Implementation,
 }
 
 impl Default for NegotiatedCapabilities {
@@ -22,6 +30,8 @@ impl Default for NegotiatedCapabilities {
             client: ClientCapabilities::default(),
             initialized: false,
             mode: MemoryMode::Smart,
+            strict_protocol_version: false,
+            server_info: server_info(None, None, None),
         }
     }
 }
@@ -35,10 +45,29 @@ impl NegotiatedCapabilities {
         }
     }
 
+    /// Set whether an unsupported protocol version fails `initialize`.
+    pub fn with_strict_protocol_version(mut self, strict: bool) -> Self {
+        self.strict_protocol_version = strict;
+        self
+    }
+
+    /// Set the identity advertised as `serverInfo`.
+    pub fn with_server_info(mut self, info: Implementation) -> Self {
+        self.server_info = info;
+        self
+    }
+
     /// Process an initialize request and return the result.
     pub fn negotiate(&mut self, params: InitializeParams) -> McpResult<InitializeResult> {
         // Verify protocol version compatibility
-        if params.protocol_version != MCP_VERSION {
+        if !SUPPORTED_PROTOCOL_VERSIONS.contains(&params.protocol_version.as_str()) {
+            if self.strict_protocol_version {
+                return Err(McpError::InvalidParams(format!(
+                    "Unsupported protocol version {}; this server supports {}",
+                    params.protocol_version,
+                    SUPPORTED_PROTOCOL_VERSIONS.join(", ")
+                )));
+            }
             tracing::warn!(
                 "Client requested protocol version {}, server supports {}. Proceeding with server version.",
                 params.protocol_version,
@@ -54,7 +83,10 @@ impl NegotiatedCapabilities {
             params.client_info.version
         );
 
-        Ok(InitializeResult::with_mode(self.mode))
+        Ok(InitializeResult {
+            server_info: self.server_info.clone(),
+            ..InitializeResult::with_mode(self.mode)
+        })
     }
 
     /// Mark the handshake as complete (after receiving `initialized` notification).
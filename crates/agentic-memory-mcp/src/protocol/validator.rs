@@ -1,6 +1,6 @@
 //! JSON-RPC message validation per MCP spec.
 
-use crate::types::{JsonRpcRequest, McpError, McpResult, JSONRPC_VERSION};
+use crate::types::{JsonRpcRequest, McpError, McpResult, RequestId, JSONRPC_VERSION};
 
 /// Validate that a JSON-RPC request is well-formed.
 pub fn validate_request(request: &JsonRpcRequest) -> McpResult<()> {
@@ -11,6 +11,15 @@ pub fn validate_request(request: &JsonRpcRequest) -> McpResult<()> {
         )));
     }
 
+    // A request must carry an id to be answered by; only notifications go
+    // without one. A null id is rejected rather than treated as a
+    // notification, so the client learns the message was dropped.
+    if request.id == RequestId::Null {
+        return Err(McpError::InvalidRequest(
+            "Request id must not be null; omit the id to send a notification".to_string(),
+        ));
+    }
+
     if request.method.is_empty() {
         return Err(McpError::InvalidRequest(
             "Method name must not be empty".to_string(),
@@ -0,0 +1,89 @@
+//! Argument completion for resource templates — the concrete values that
+//! exist for each template parameter.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use agentic_memory::EventType;
+
+use crate::session::SessionManager;
+use crate::types::{Completion, McpError, McpResult};
+
+/// Complete `argument` of the resource template `uri_template`.
+///
+/// Returns the values starting with `prefix`: node IDs (newest first) for
+/// `amem://node/{id}`, episode node IDs for `amem://episode/{id}`, session
+/// IDs (newest first) for `amem://session/{id}` and event type names for
+/// `amem://types/{type}`. Templates with only paging parameters complete to
+/// nothing.
+pub async fn complete(
+    uri_template: &str,
+    argument: &str,
+    prefix: &str,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<Completion> {
+    let candidates: Vec<String> = match (uri_template, argument) {
+        ("amem://node/{id}" | "amem://node/{id}/vector", "id") => {
+            let session = session.read().await;
+            session
+                .graph()
+                .nodes()
+                .iter()
+                .rev()
+                .map(|node| node.id.to_string())
+                .collect()
+        }
+        ("amem://episode/{id}", "id") => {
+            let session = session.read().await;
+            session
+                .graph()
+                .type_index()
+                .get(EventType::Episode)
+                .iter()
+                .rev()
+                .map(|id| id.to_string())
+                .collect()
+        }
+        ("amem://session/{id}", "id") => {
+            let session = session.read().await;
+            session
+                .graph()
+                .session_index()
+                .session_ids()
+                .iter()
+                .rev()
+                .map(|id| id.to_string())
+                .collect()
+        }
+        ("amem://types/{type}", "type") => EventType::all()
+            .iter()
+            .map(|t| t.name().to_string())
+            .collect(),
+        (
+            "amem://graph/export/nodes{?cursor,limit}" | "amem://graph/export/edges{?cursor,limit}",
+            "cursor" | "limit",
+        ) => Vec::new(),
+        (
+            "amem://node/{id}"
+            | "amem://node/{id}/vector"
+            | "amem://episode/{id}"
+            | "amem://session/{id}"
+            | "amem://types/{type}"
+            | "amem://graph/export/nodes{?cursor,limit}"
+            | "amem://graph/export/edges{?cursor,limit}",
+            _,
+        ) => {
+            return Err(McpError::InvalidParams(format!(
+                "Template {uri_template} has no argument '{argument}'"
+            )))
+        }
+        _ => return Err(McpError::ResourceNotFound(uri_template.to_string())),
+    };
+
+    Ok(Completion::from_matches(
+        candidates
+            .into_iter()
+            .filter(|value| value.starts_with(prefix))
+            .collect(),
+    ))
+}
@@ -0,0 +1,71 @@
+//! Resource handler for `amem://episode/{id}` — an episode and its members.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde_json::{json, Value};
+
+use agentic_memory::{EdgeType, EventType};
+
+use crate::session::episode::{member_ids_in, parse_episode};
+use crate::session::SessionManager;
+use crate::types::{McpError, McpResult, ReadResourceResult, ResourceContent};
+
+/// Read an episode node with its member node IDs.
+///
+/// Members are the sources of the episode's `part_of` edges, or the IDs
+/// listed in its content when it was written without edges.
+///
+/// Structured episodes also return their parsed record under `record`; for
+/// free-text episodes `record` is `null` and `summary` is the node content.
+pub async fn read_episode(
+    id: u64,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ReadResourceResult> {
+    let session = session.read().await;
+    let graph = session.graph();
+
+    let node = graph.get_node(id).ok_or(McpError::NodeNotFound(id))?;
+    if node.event_type != EventType::Episode {
+        return Err(McpError::InvalidParams(format!(
+            "Node {id} is a {}, not an episode",
+            node.event_type.name()
+        )));
+    }
+
+    let mut member_ids: Vec<u64> = graph
+        .edges_to(id)
+        .iter()
+        .filter(|e| e.edge_type == EdgeType::PartOf)
+        .map(|e| e.source_id)
+        .collect();
+    if member_ids.is_empty() {
+        member_ids = member_ids_in(&node.content).unwrap_or_default();
+    }
+    member_ids.sort_unstable();
+
+    let record = parse_episode(&node.content);
+    let summary = match &record {
+        Some(record) => record.get("summary").cloned().unwrap_or(Value::Null),
+        None => json!(node.content),
+    };
+    let content = json!({
+        "id": node.id,
+        "session_id": node.session_id,
+        "created_at": node.created_at,
+        "format": if record.is_some() { "structured" } else { "text" },
+        "summary": summary,
+        "record": record,
+        "member_count": member_ids.len(),
+        "member_ids": member_ids,
+    });
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContent {
+            uri: format!("amem://episode/{id}"),
+            mime_type: Some("application/json".to_string()),
+            text: Some(serde_json::to_string_pretty(&content).unwrap_or_else(|_| "{}".to_string())),
+            blob: None,
+        }],
+    })
+}
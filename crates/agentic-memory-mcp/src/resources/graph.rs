@@ -1,35 +1,26 @@
 //! Resource handlers for `amem://graph/*` — graph-level statistics and views.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde_json::json;
 
-use agentic_memory::EventType;
+use agentic_memory::{CognitiveEvent, EdgeType, EventType, MemoryGraph};
 
+use crate::session::episode::parse_episode;
 use crate::session::SessionManager;
-use crate::types::{McpResult, ReadResourceResult, ResourceContent};
+use crate::types::{edge_json, McpResult, ReadResourceResult, ResourceContent};
 
 /// Read overall graph statistics.
-pub async fn read_stats(session: &Arc<Mutex<SessionManager>>) -> McpResult<ReadResourceResult> {
-    let session = session.lock().await;
-    let graph = session.graph();
-    let type_index = graph.type_index();
-    let session_index = graph.session_index();
+pub async fn read_stats(session: &Arc<RwLock<SessionManager>>) -> McpResult<ReadResourceResult> {
+    let stats = session.read().await.graph_stats();
 
     let content = json!({
-        "node_count": graph.node_count(),
-        "edge_count": graph.edge_count(),
-        "dimension": graph.dimension(),
-        "session_count": session_index.session_count(),
-        "type_counts": {
-            "fact": type_index.count(EventType::Fact),
-            "decision": type_index.count(EventType::Decision),
-            "inference": type_index.count(EventType::Inference),
-            "correction": type_index.count(EventType::Correction),
-            "skill": type_index.count(EventType::Skill),
-            "episode": type_index.count(EventType::Episode),
-        }
+        "node_count": stats.node_count,
+        "edge_count": stats.edge_count,
+        "dimension": stats.dimension,
+        "session_count": stats.session_count,
+        "type_counts": stats.type_counts_json(),
     });
 
     Ok(ReadResourceResult {
@@ -42,36 +33,56 @@ pub async fn read_stats(session: &Arc<Mutex<SessionManager>>) -> McpResult<ReadR
     })
 }
 
-/// Read the most recently created nodes (top 20).
-pub async fn read_recent(session: &Arc<Mutex<SessionManager>>) -> McpResult<ReadResourceResult> {
-    let session = session.lock().await;
+/// Read one page of nodes, most recently created first
+/// (`amem://graph/recent?cursor=&limit=`, default: the top 20).
+pub async fn read_recent(
+    cursor: usize,
+    limit: usize,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ReadResourceResult> {
+    let session = session.read().await;
     let graph = session.graph();
 
-    let recent_ids = graph.temporal_index().most_recent(20);
-    let nodes: Vec<serde_json::Value> = recent_ids
+    let scope = session.effective_session_ids(Vec::new());
+
+    // When scoped, scan the full temporal index so filtering cannot starve the page.
+    let scan_limit = if scope.is_empty() {
+        cursor.saturating_add(limit).saturating_add(1)
+    } else {
+        graph.node_count()
+    };
+    let recent_ids = graph.temporal_index().most_recent(scan_limit);
+    let mut matching = recent_ids
         .iter()
-        .filter_map(|id| {
-            graph.get_node(*id).map(|node| {
-                json!({
-                    "id": node.id,
-                    "event_type": node.event_type.name(),
-                    "content": node.content,
-                    "confidence": node.confidence,
-                    "session_id": node.session_id,
-                    "created_at": node.created_at,
-                })
+        .filter_map(|id| graph.get_node(*id))
+        .filter(|node| scope.is_empty() || scope.contains(&node.session_id))
+        .skip(cursor);
+    let nodes: Vec<serde_json::Value> = matching
+        .by_ref()
+        .take(limit)
+        .map(|node| {
+            json!({
+                "id": node.id,
+                "event_type": node.event_type.name(),
+                "content": node.content,
+                "confidence": node.confidence,
+                "session_id": node.session_id,
+                "created_at": node.created_at,
             })
         })
         .collect();
+    let has_more = matching.next().is_some();
 
     let content = json!({
         "count": nodes.len(),
+        "cursor": cursor,
+        "next_cursor": has_more.then_some(cursor + nodes.len()),
         "nodes": nodes,
     });
 
     Ok(ReadResourceResult {
         contents: vec![ResourceContent {
-            uri: "amem://graph/recent".to_string(),
+            uri: format!("amem://graph/recent?cursor={cursor}&limit={limit}"),
             mime_type: Some("application/json".to_string()),
             text: Some(serde_json::to_string_pretty(&content).unwrap_or_else(|_| "{}".to_string())),
             blob: None,
@@ -79,24 +90,34 @@ pub async fn read_recent(session: &Arc<Mutex<SessionManager>>) -> McpResult<Read
     })
 }
 
-/// Read the most important nodes by decay score (top 20).
-pub async fn read_important(session: &Arc<Mutex<SessionManager>>) -> McpResult<ReadResourceResult> {
-    let session = session.lock().await;
+/// Read the most important nodes: every pinned node, then the top 20 others
+/// by decay score.
+pub async fn read_important(
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ReadResourceResult> {
+    let session = session.read().await;
     let graph = session.graph();
 
-    // Get all nodes sorted by decay_score descending
+    let scope = session.effective_session_ids(Vec::new());
+
+    // Get all nodes sorted by decay_score descending, pinned nodes first
     let mut nodes_with_scores: Vec<_> = graph
         .nodes()
         .iter()
-        .map(|n| (n.id, n.decay_score))
+        .filter(|n| scope.is_empty() || scope.contains(&n.session_id))
+        .map(|n| (n.id, session.is_pinned(n.id), n.decay_score))
         .collect();
-    nodes_with_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    nodes_with_scores.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then(b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal))
+    });
+    let pinned = nodes_with_scores.iter().filter(|n| n.1).count();
 
     let top_nodes: Vec<serde_json::Value> = nodes_with_scores
         .iter()
-        .take(20)
-        .filter_map(|(id, _)| {
-            graph.get_node(*id).map(|node| {
+        .take(pinned + 20)
+        .filter_map(|&(id, pinned, _)| {
+            graph.get_node(id).map(|node| {
                 json!({
                     "id": node.id,
                     "event_type": node.event_type.name(),
@@ -104,6 +125,7 @@ pub async fn read_important(session: &Arc<Mutex<SessionManager>>) -> McpResult<R
                     "confidence": node.confidence,
                     "decay_score": node.decay_score,
                     "session_id": node.session_id,
+                    "pinned": pinned,
                 })
             })
         })
@@ -111,6 +133,7 @@ pub async fn read_important(session: &Arc<Mutex<SessionManager>>) -> McpResult<R
 
     let content = json!({
         "count": top_nodes.len(),
+        "pinned": pinned,
         "nodes": top_nodes,
     });
 
@@ -123,3 +146,229 @@ pub async fn read_important(session: &Arc<Mutex<SessionManager>>) -> McpResult<R
         }],
     })
 }
+
+/// Number of most important nodes included in `amem://graph/digest`.
+pub const DIGEST_TOP_NODES: usize = 5;
+/// Maximum number of contradictions listed in `amem://graph/digest`.
+pub const DIGEST_MAX_CONTRADICTIONS: usize = 10;
+/// Maximum characters of each node's content kept in `amem://graph/digest`.
+const DIGEST_CONTENT_CHARS: usize = 280;
+
+/// Read a compact orientation summary for priming a prompt: counts, the most
+/// important nodes, the latest episode summary, and active contradictions
+/// (`contradicts` edges between two nodes that nothing supersedes).
+pub async fn read_digest(session: &Arc<RwLock<SessionManager>>) -> McpResult<ReadResourceResult> {
+    let session = session.read().await;
+    let graph = session.graph();
+
+    let scope = session.effective_session_ids(Vec::new());
+    let in_scope = |node: &CognitiveEvent| scope.is_empty() || scope.contains(&node.session_id);
+
+    let mut important: Vec<&CognitiveEvent> =
+        graph.nodes().iter().filter(|n| in_scope(n)).collect();
+    important.sort_by(|a, b| {
+        b.decay_score
+            .partial_cmp(&a.decay_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let top_nodes: Vec<serde_json::Value> = important
+        .into_iter()
+        .take(DIGEST_TOP_NODES)
+        .map(|node| {
+            json!({
+                "id": node.id,
+                "event_type": node.event_type.name(),
+                "content": digest_content(&node.content),
+                "confidence": node.confidence,
+            })
+        })
+        .collect();
+
+    let latest_episode = graph
+        .type_index()
+        .get(EventType::Episode)
+        .iter()
+        .filter_map(|id| graph.get_node(*id))
+        .filter(|node| in_scope(node))
+        .max_by_key(|node| (node.session_id, node.created_at))
+        .map(|node| {
+            let summary = match parse_episode(&node.content) {
+                Some(record) => record
+                    .get("summary")
+                    .and_then(|v| v.as_str())
+                    .map(digest_content),
+                None => Some(digest_content(&node.content)),
+            };
+            json!({
+                "id": node.id,
+                "session_id": node.session_id,
+                "created_at": node.created_at,
+                "summary": summary,
+            })
+        });
+
+    let contradictions: Vec<(&CognitiveEvent, &CognitiveEvent)> = graph
+        .edges()
+        .iter()
+        .filter(|e| e.edge_type == EdgeType::Contradicts)
+        .filter(|e| is_live(graph, e.source_id) && is_live(graph, e.target_id))
+        .filter_map(|e| Some((graph.get_node(e.source_id)?, graph.get_node(e.target_id)?)))
+        .filter(|(source, target)| in_scope(source) || in_scope(target))
+        .collect();
+    let listed: Vec<serde_json::Value> = contradictions
+        .iter()
+        .take(DIGEST_MAX_CONTRADICTIONS)
+        .map(|(source, target)| {
+            json!({
+                "source_id": source.id,
+                "source_content": digest_content(&source.content),
+                "target_id": target.id,
+                "target_content": digest_content(&target.content),
+            })
+        })
+        .collect();
+
+    let content = json!({
+        "node_count": graph.node_count(),
+        "edge_count": graph.edge_count(),
+        "session_count": graph.session_index().session_count(),
+        "important_nodes": top_nodes,
+        "latest_episode": latest_episode,
+        "contradiction_count": contradictions.len(),
+        "contradictions": listed,
+    });
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContent {
+            uri: "amem://graph/digest".to_string(),
+            mime_type: Some("application/json".to_string()),
+            text: Some(serde_json::to_string_pretty(&content).unwrap_or_else(|_| "{}".to_string())),
+            blob: None,
+        }],
+    })
+}
+
+fn digest_content(content: &str) -> String {
+    content.chars().take(DIGEST_CONTENT_CHARS).collect()
+}
+
+fn is_live(graph: &MemoryGraph, id: u64) -> bool {
+    !graph
+        .edges_to(id)
+        .iter()
+        .any(|e| e.edge_type == EdgeType::Supersedes)
+}
+
+/// Default page size for `amem://graph/export/{nodes,edges}`.
+pub const DEFAULT_EXPORT_PAGE_SIZE: usize = 500;
+/// Default page size for `amem://graph/recent`.
+pub const DEFAULT_RECENT_PAGE_SIZE: usize = 20;
+
+fn export_node(node: &agentic_memory::CognitiveEvent) -> serde_json::Value {
+    json!({
+        "id": node.id,
+        "event_type": node.event_type.name(),
+        "content": node.content,
+        "confidence": node.confidence,
+        "session_id": node.session_id,
+        "created_at": node.created_at,
+        "access_count": node.access_count,
+        "last_accessed": node.last_accessed,
+        "decay_score": node.decay_score,
+    })
+}
+
+/// Read the entire graph as `{dimension, nodes, edges}`.
+pub async fn read_export(session: &Arc<RwLock<SessionManager>>) -> McpResult<ReadResourceResult> {
+    let session = session.read().await;
+    let graph = session.graph();
+
+    let content = json!({
+        "dimension": graph.dimension(),
+        "node_count": graph.node_count(),
+        "edge_count": graph.edge_count(),
+        "nodes": graph.nodes().iter().map(export_node).collect::<Vec<_>>(),
+        "edges": graph.edges().iter().map(edge_json).collect::<Vec<_>>(),
+    });
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContent {
+            uri: "amem://graph/export".to_string(),
+            mime_type: Some("application/json".to_string()),
+            text: Some(serde_json::to_string(&content).unwrap_or_else(|_| "{}".to_string())),
+            blob: None,
+        }],
+    })
+}
+
+/// Read one page of the node export (`amem://graph/export/nodes?cursor=&limit=`).
+pub async fn read_export_nodes(
+    cursor: usize,
+    limit: usize,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ReadResourceResult> {
+    let session = session.read().await;
+    let graph = session.graph();
+    let total = graph.node_count();
+
+    let nodes: Vec<serde_json::Value> = graph
+        .nodes()
+        .iter()
+        .skip(cursor)
+        .take(limit)
+        .map(export_node)
+        .collect();
+    let end = cursor.saturating_add(nodes.len());
+
+    let content = json!({
+        "total": total,
+        "cursor": cursor,
+        "next_cursor": (end < total).then_some(end),
+        "nodes": nodes,
+    });
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContent {
+            uri: format!("amem://graph/export/nodes?cursor={cursor}&limit={limit}"),
+            mime_type: Some("application/json".to_string()),
+            text: Some(serde_json::to_string(&content).unwrap_or_else(|_| "{}".to_string())),
+            blob: None,
+        }],
+    })
+}
+
+/// Read one page of the edge export (`amem://graph/export/edges?cursor=&limit=`).
+pub async fn read_export_edges(
+    cursor: usize,
+    limit: usize,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ReadResourceResult> {
+    let session = session.read().await;
+    let graph = session.graph();
+    let total = graph.edge_count();
+
+    let edges: Vec<serde_json::Value> = graph
+        .edges()
+        .iter()
+        .skip(cursor)
+        .take(limit)
+        .map(edge_json)
+        .collect();
+    let end = cursor.saturating_add(edges.len());
+
+    let content = json!({
+        "total": total,
+        "cursor": cursor,
+        "next_cursor": (end < total).then_some(end),
+        "edges": edges,
+    });
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContent {
+            uri: format!("amem://graph/export/edges?cursor={cursor}&limit={limit}"),
+            mime_type: Some("application/json".to_string()),
+            text: Some(serde_json::to_string(&content).unwrap_or_else(|_| "{}".to_string())),
+            blob: None,
+        }],
+    })
+}
@@ -1,10 +1,15 @@
 //! MCP resource implementations — read-only views of the memory graph.
 
+pub mod completion;
+pub mod episode;
 pub mod graph;
 pub mod node;
+pub mod page;
 pub mod registry;
+pub mod schema;
 pub mod session;
 pub mod templates;
+pub mod tools;
 pub mod type_index;
 
 pub use registry::ResourceRegistry;
@@ -1,66 +1,88 @@
-//! Resource handler for `amem://node/{id}` — single node with edges.
+//! Resource handlers for `amem://node/{id}` — single node with edges and any
+//! binary attachment — and `amem://node/{id}/vector` — that node's embedding
+//! vector.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde_json::json;
 
 use crate::session::SessionManager;
-use crate::types::{McpError, McpResult, ReadResourceResult, ResourceContent};
+use crate::types::{
+    edge_json, node_json, McpError, McpResult, ReadResourceResult, ResourceContent,
+};
 
 /// Read a single node resource by ID.
 pub async fn read_node(
     id: u64,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ReadResourceResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     let node = graph.get_node(id).ok_or(McpError::NodeNotFound(id))?;
 
-    let outgoing: Vec<serde_json::Value> = graph
-        .edges_from(id)
-        .iter()
-        .map(|e| {
-            json!({
-                "target_id": e.target_id,
-                "edge_type": e.edge_type.name(),
-                "weight": e.weight,
-            })
-        })
-        .collect();
+    let outgoing: Vec<serde_json::Value> = graph.edges_from(id).iter().map(edge_json).collect();
 
-    let incoming: Vec<serde_json::Value> = graph
-        .edges_to(id)
-        .iter()
-        .map(|e| {
-            json!({
-                "source_id": e.source_id,
-                "edge_type": e.edge_type.name(),
-                "weight": e.weight,
-            })
-        })
-        .collect();
+    let incoming: Vec<serde_json::Value> =
+        graph.edges_to(id).iter().map(|e| edge_json(e)).collect();
+
+    let mut content = node_json(node);
+    content["outgoing_edges"] = json!(outgoing);
+    content["incoming_edges"] = json!(incoming);
+    content["graph_version"] = json!(session.graph_version());
+
+    let attachment = session.node_blob(id);
+    if let Some(blob) = attachment {
+        content["blob"] = json!({ "mime_type": blob.mime_type, "size": blob.size });
+    }
+
+    let mut contents = vec![ResourceContent {
+        uri: format!("amem://node/{id}"),
+        mime_type: Some("application/json".to_string()),
+        text: Some(serde_json::to_string_pretty(&content).unwrap_or_else(|_| "{}".to_string())),
+        blob: None,
+    }];
+    // The attachment goes out verbatim as a second content item; redaction
+    // only applies to text.
+    if let Some(blob) = attachment {
+        contents.push(ResourceContent {
+            uri: format!("amem://node/{id}"),
+            mime_type: Some(blob.mime_type.clone()),
+            text: None,
+            blob: Some(blob.data.clone()),
+        });
+    }
+
+    Ok(ReadResourceResult { contents })
+}
+
+/// Read the embedding vector of a single node.
+///
+/// Vectors are only exposed one node at a time so large graphs are never
+/// dumped wholesale.
+pub async fn read_node_vector(
+    id: u64,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ReadResourceResult> {
+    let session = session.read().await;
+    let graph = session.graph();
+
+    let node = graph.get_node(id).ok_or(McpError::NodeNotFound(id))?;
+    let is_zero = node.feature_vec.iter().all(|&x| x == 0.0);
 
     let content = json!({
         "id": node.id,
-        "event_type": node.event_type.name(),
-        "content": node.content,
-        "confidence": node.confidence,
-        "session_id": node.session_id,
-        "created_at": node.created_at,
-        "access_count": node.access_count,
-        "last_accessed": node.last_accessed,
-        "decay_score": node.decay_score,
-        "outgoing_edges": outgoing,
-        "incoming_edges": incoming,
+        "dimension": node.feature_vec.len(),
+        "is_zero_vector": is_zero,
+        "vector": node.feature_vec,
     });
 
     Ok(ReadResourceResult {
         contents: vec![ResourceContent {
-            uri: format!("amem://node/{id}"),
+            uri: format!("amem://node/{id}/vector"),
             mime_type: Some("application/json".to_string()),
-            text: Some(serde_json::to_string_pretty(&content).unwrap_or_else(|_| "{}".to_string())),
+            text: Some(serde_json::to_string(&content).unwrap_or_else(|_| "{}".to_string())),
             blob: None,
         }],
     })
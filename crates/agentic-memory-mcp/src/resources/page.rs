@@ -0,0 +1,38 @@
+//! `?cursor=N&limit=M` paging shared by the listing resources.
+
+use crate::types::{McpError, McpResult};
+
+/// Upper bound on a single page.
+pub const MAX_PAGE_SIZE: usize = 5000;
+
+/// Split a resource URI suffix into its path and query (`"fact?limit=5"` →
+/// `("fact", "?limit=5")`).
+pub fn split_query(rest: &str) -> (&str, &str) {
+    match rest.find('?') {
+        Some(i) => rest.split_at(i),
+        None => (rest, ""),
+    }
+}
+
+/// Parse `?cursor=N&limit=M`, using `default_limit` when no limit is given.
+pub fn parse_page(query: &str, default_limit: usize) -> McpResult<(usize, usize)> {
+    let mut cursor = 0usize;
+    let mut limit = default_limit;
+    let query = query.strip_prefix('?').unwrap_or(query);
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let parsed: usize = value
+            .parse()
+            .map_err(|_| McpError::InvalidParams(format!("Invalid page {key}: {value}")))?;
+        match key {
+            "cursor" => cursor = parsed,
+            "limit" => limit = parsed.clamp(1, MAX_PAGE_SIZE),
+            _ => {
+                return Err(McpError::InvalidParams(format!(
+                    "Unknown page parameter: {key}"
+                )))
+            }
+        }
+    }
+    Ok((cursor, limit))
+}
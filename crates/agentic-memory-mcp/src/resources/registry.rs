@@ -1,14 +1,23 @@
 //! Resource registration and dispatch for MCP resources.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
+
+use serde_json::json;
 
 use crate::session::SessionManager;
 use crate::types::{
-    McpError, McpResult, ReadResourceResult, ResourceDefinition, ResourceTemplateDefinition,
+    Completion, McpError, McpResult, ReadResourceResult, ResourceContent, ResourceDefinition,
+    ResourceTemplateDefinition,
 };
 
-use super::{graph, node, session, templates, type_index};
+/// Maximum number of URIs accepted in one batch read.
+pub const MAX_BATCH_READ: usize = 256;
+/// MIME type of the per-URI error entries in a batch read.
+pub const BATCH_ERROR_MIME: &str = "application/vnd.amem.error+json";
+
+use super::page::{parse_page, split_query};
+use super::{completion, episode, graph, node, schema, session, templates, tools, type_index};
 
 /// Registry of all available MCP resources.
 pub struct ResourceRegistry;
@@ -32,10 +41,20 @@ impl ResourceRegistry {
         resources
     }
 
+    /// Complete a resource template argument from the values that exist.
+    pub async fn complete(
+        uri_template: &str,
+        argument: &str,
+        prefix: &str,
+        session: &Arc<RwLock<SessionManager>>,
+    ) -> McpResult<Completion> {
+        completion::complete(uri_template, argument, prefix, session).await
+    }
+
     /// Read a resource by URI, dispatching to the appropriate handler.
     pub async fn read(
         uri: &str,
-        session: &Arc<Mutex<SessionManager>>,
+        session: &Arc<RwLock<SessionManager>>,
     ) -> McpResult<ReadResourceResult> {
         #[cfg(feature = "v3")]
         {
@@ -47,11 +66,52 @@ impl ResourceRegistry {
         }
     }
 
+    /// Read several resources in one call.
+    ///
+    /// Contents are returned in request order. A URI that fails to read yields a
+    /// single entry with MIME type [`BATCH_ERROR_MIME`] and an `{"error": ...}`
+    /// body instead of failing the whole batch.
+    #[allow(clippy::ptr_arg)]
+    pub async fn read_batch(
+        uris: &[String],
+        session: &Arc<RwLock<SessionManager>>,
+        #[cfg(feature = "v3")] v3_engine: Option<&crate::tools::v3_tools::SharedEngine>,
+    ) -> McpResult<ReadResourceResult> {
+        if uris.len() > MAX_BATCH_READ {
+            return Err(McpError::InvalidParams(format!(
+                "Batch read of {} URIs exceeds limit of {MAX_BATCH_READ}",
+                uris.len()
+            )));
+        }
+
+        let mut contents = Vec::with_capacity(uris.len());
+        for uri in uris {
+            #[cfg(feature = "v3")]
+            let result = Self::read_with_v3(uri, session, v3_engine).await;
+            #[cfg(not(feature = "v3"))]
+            let result = Self::read_with_v3(uri, session).await;
+
+            match result {
+                Ok(read) => contents.extend(read.contents),
+                Err(e) => contents.push(ResourceContent {
+                    uri: uri.clone(),
+                    mime_type: Some(BATCH_ERROR_MIME.to_string()),
+                    text: Some(
+                        json!({ "error": { "code": e.code(), "message": e.to_string() } })
+                            .to_string(),
+                    ),
+                    blob: None,
+                }),
+            }
+        }
+        Ok(ReadResourceResult { contents })
+    }
+
     /// Read a resource by URI with optional V3 engine dispatch.
     #[allow(clippy::ptr_arg)]
     pub async fn read_with_v3(
         uri: &str,
-        session: &Arc<Mutex<SessionManager>>,
+        session: &Arc<RwLock<SessionManager>>,
         #[cfg(feature = "v3")] v3_engine: Option<&crate::tools::v3_tools::SharedEngine>,
     ) -> McpResult<ReadResourceResult> {
         #[cfg(feature = "v3")]
@@ -61,24 +121,66 @@ impl ResourceRegistry {
             }
         }
 
-        if let Some(id_str) = uri.strip_prefix("amem://node/") {
+        if let Some(id_str) = uri
+            .strip_prefix("amem://node/")
+            .and_then(|rest| rest.strip_suffix("/vector"))
+        {
+            let id: u64 = id_str
+                .parse()
+                .map_err(|_| McpError::InvalidParams(format!("Invalid node ID: {id_str}")))?;
+            node::read_node_vector(id, session).await
+        } else if let Some(id_str) = uri.strip_prefix("amem://node/") {
             let id: u64 = id_str
                 .parse()
                 .map_err(|_| McpError::InvalidParams(format!("Invalid node ID: {id_str}")))?;
             node::read_node(id, session).await
-        } else if let Some(id_str) = uri.strip_prefix("amem://session/") {
+        } else if let Some(id_str) = uri.strip_prefix("amem://episode/") {
+            let id: u64 = id_str
+                .parse()
+                .map_err(|_| McpError::InvalidParams(format!("Invalid node ID: {id_str}")))?;
+            episode::read_episode(id, session).await
+        } else if let Some(rest) = uri.strip_prefix("amem://session/") {
+            let (id_str, query) = split_query(rest);
             let id: u32 = id_str
                 .parse()
                 .map_err(|_| McpError::InvalidParams(format!("Invalid session ID: {id_str}")))?;
-            session::read_session(id, session).await
-        } else if let Some(type_name) = uri.strip_prefix("amem://types/") {
-            type_index::read_type(type_name, session).await
+            let (cursor, limit) = parse_page(query, session::DEFAULT_SESSION_PAGE_SIZE)?;
+            session::read_session(id, cursor, limit, session).await
+        } else if let Some(rest) = uri.strip_prefix("amem://types/") {
+            let (type_name, query) = split_query(rest);
+            let (cursor, limit) = parse_page(query, type_index::DEFAULT_TYPE_PAGE_SIZE)?;
+            type_index::read_type(type_name, cursor, limit, session).await
         } else if uri == "amem://graph/stats" {
             graph::read_stats(session).await
-        } else if uri == "amem://graph/recent" {
-            graph::read_recent(session).await
+        } else if let Some(query) = uri
+            .strip_prefix("amem://graph/recent")
+            .filter(|q| q.is_empty() || q.starts_with('?'))
+        {
+            let (cursor, limit) = parse_page(query, graph::DEFAULT_RECENT_PAGE_SIZE)?;
+            graph::read_recent(cursor, limit, session).await
         } else if uri == "amem://graph/important" {
             graph::read_important(session).await
+        } else if uri == "amem://graph/digest" {
+            graph::read_digest(session).await
+        } else if uri == "amem://graph/export" {
+            graph::read_export(session).await
+        } else if let Some(query) = uri
+            .strip_prefix("amem://graph/export/nodes")
+            .filter(|q| q.is_empty() || q.starts_with('?'))
+        {
+            let (cursor, limit) = parse_page(query, graph::DEFAULT_EXPORT_PAGE_SIZE)?;
+            graph::read_export_nodes(cursor, limit, session).await
+        } else if let Some(query) = uri
+            .strip_prefix("amem://graph/export/edges")
+            .filter(|q| q.is_empty() || q.starts_with('?'))
+        {
+            let (cursor, limit) = parse_page(query, graph::DEFAULT_EXPORT_PAGE_SIZE)?;
+            graph::read_export_edges(cursor, limit, session).await
+        } else if uri == "amem://tools/schema" {
+            let defaults = session.read().await.tool_defaults().clone();
+            tools::read_schema(&defaults)
+        } else if uri == "amem://schema/types" {
+            schema::read_types()
         } else {
             Err(McpError::ResourceNotFound(uri.to_string()))
         }
@@ -0,0 +1,33 @@
+//! Resource handler for `amem://schema/types` — every valid event and edge type.
+
+use agentic_memory::{EdgeType, EventType};
+use serde_json::json;
+
+use crate::types::{McpResult, ReadResourceResult, ResourceContent};
+
+/// Read the event and edge type names accepted by the tools, each with a
+/// short description, in discriminant order.
+pub fn read_types() -> McpResult<ReadResourceResult> {
+    let event_types: Vec<serde_json::Value> = EventType::all()
+        .iter()
+        .map(|t| json!({ "name": t.name(), "description": t.description() }))
+        .collect();
+    let edge_types: Vec<serde_json::Value> = EdgeType::all()
+        .iter()
+        .map(|t| json!({ "name": t.name(), "description": t.description() }))
+        .collect();
+
+    let content = json!({
+        "event_types": event_types,
+        "edge_types": edge_types,
+    });
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContent {
+            uri: "amem://schema/types".to_string(),
+            mime_type: Some("application/json".to_string()),
+            text: Some(serde_json::to_string_pretty(&content).unwrap_or_else(|_| "{}".to_string())),
+            blob: None,
+        }],
+    })
+}
@@ -1,19 +1,25 @@
-//! Resource handler for `amem://session/{id}` — all nodes from a session.
+//! Resource handler for `amem://session/{id}` — nodes from a session, paged.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde_json::json;
 
 use crate::session::SessionManager;
 use crate::types::{McpError, McpResult, ReadResourceResult, ResourceContent};
 
-/// Read all nodes belonging to a session.
+/// Default page size for `amem://session/{id}`.
+pub const DEFAULT_SESSION_PAGE_SIZE: usize = 500;
+
+/// Read one page of nodes belonging to a session
+/// (`amem://session/{id}?cursor=&limit=`).
 pub async fn read_session(
     id: u32,
-    session: &Arc<Mutex<SessionManager>>,
+    cursor: usize,
+    limit: usize,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ReadResourceResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     let node_ids = graph.session_index().get_session(id);
@@ -21,8 +27,12 @@ pub async fn read_session(
         return Err(McpError::SessionNotFound(id));
     }
 
+    let total = node_ids.len();
+
     let nodes: Vec<serde_json::Value> = node_ids
         .iter()
+        .skip(cursor)
+        .take(limit)
         .filter_map(|nid| {
             graph.get_node(*nid).map(|node| {
                 json!({
@@ -35,16 +45,20 @@ pub async fn read_session(
             })
         })
         .collect();
+    let end = cursor.saturating_add(limit).min(total);
 
     let content = json!({
         "session_id": id,
         "node_count": nodes.len(),
+        "total": total,
+        "cursor": cursor,
+        "next_cursor": (end < total).then_some(end),
         "nodes": nodes,
     });
 
     Ok(ReadResourceResult {
         contents: vec![ResourceContent {
-            uri: format!("amem://session/{id}"),
+            uri: format!("amem://session/{id}?cursor={cursor}&limit={limit}"),
             mime_type: Some("application/json".to_string()),
             text: Some(serde_json::to_string_pretty(&content).unwrap_or_else(|_| "{}".to_string())),
             blob: None,
@@ -11,16 +11,49 @@ pub fn list_templates() -> Vec<ResourceTemplateDefinition> {
             description: Some("A single cognitive event node with its edges".to_string()),
             mime_type: Some("application/json".to_string()),
         },
+        ResourceTemplateDefinition {
+            uri_template: "amem://node/{id}/vector".to_string(),
+            name: "Memory Node Vector".to_string(),
+            description: Some("The embedding vector of a single node as a JSON array".to_string()),
+            mime_type: Some("application/json".to_string()),
+        },
+        ResourceTemplateDefinition {
+            uri_template: "amem://episode/{id}".to_string(),
+            name: "Episode".to_string(),
+            description: Some(
+                "An episode node with its member node IDs and, when structured, its parsed record"
+                    .to_string(),
+            ),
+            mime_type: Some("application/json".to_string()),
+        },
         ResourceTemplateDefinition {
             uri_template: "amem://session/{id}".to_string(),
             name: "Session Nodes".to_string(),
-            description: Some("All nodes from a specific session".to_string()),
+            description: Some(
+                "Nodes from a specific session, paged with ?cursor=&limit= (default 500)"
+                    .to_string(),
+            ),
             mime_type: Some("application/json".to_string()),
         },
         ResourceTemplateDefinition {
             uri_template: "amem://types/{type}".to_string(),
             name: "Nodes by Type".to_string(),
-            description: Some("All nodes of a specific event type".to_string()),
+            description: Some(
+                "Nodes of a specific event type, paged with ?cursor=&limit= (default 500)"
+                    .to_string(),
+            ),
+            mime_type: Some("application/json".to_string()),
+        },
+        ResourceTemplateDefinition {
+            uri_template: "amem://graph/export/nodes{?cursor,limit}".to_string(),
+            name: "Graph Export (nodes page)".to_string(),
+            description: Some("One page of exported nodes with a next_cursor".to_string()),
+            mime_type: Some("application/json".to_string()),
+        },
+        ResourceTemplateDefinition {
+            uri_template: "amem://graph/export/edges{?cursor,limit}".to_string(),
+            name: "Graph Export (edges page)".to_string(),
+            description: Some("One page of exported edges with a next_cursor".to_string()),
             mime_type: Some("application/json".to_string()),
         },
     ]
@@ -38,7 +71,9 @@ pub fn list_resources() -> Vec<ResourceDefinition> {
         ResourceDefinition {
             uri: "amem://graph/recent".to_string(),
             name: "Recent Nodes".to_string(),
-            description: Some("Most recently created nodes (top 20)".to_string()),
+            description: Some(
+                "Most recently created nodes (top 20; page with ?cursor=&limit=)".to_string(),
+            ),
             mime_type: Some("application/json".to_string()),
         },
         ResourceDefinition {
@@ -47,5 +82,39 @@ pub fn list_resources() -> Vec<ResourceDefinition> {
             description: Some("Nodes with highest decay scores (top 20)".to_string()),
             mime_type: Some("application/json".to_string()),
         },
+        ResourceDefinition {
+            uri: "amem://graph/digest".to_string(),
+            name: "Graph Digest".to_string(),
+            description: Some(
+                "Compact orientation summary: counts, top 5 important nodes, latest episode, \
+                 active contradictions"
+                    .to_string(),
+            ),
+            mime_type: Some("application/json".to_string()),
+        },
+        ResourceDefinition {
+            uri: "amem://graph/export".to_string(),
+            name: "Graph Export".to_string(),
+            description: Some(
+                "Full graph dump as JSON {dimension, nodes, edges} (vectors omitted)".to_string(),
+            ),
+            mime_type: Some("application/json".to_string()),
+        },
+        ResourceDefinition {
+            uri: "amem://tools/schema".to_string(),
+            name: "Tool Schemas".to_string(),
+            description: Some(
+                "JSON object mapping every tool name to its input schema".to_string(),
+            ),
+            mime_type: Some("application/json".to_string()),
+        },
+        ResourceDefinition {
+            uri: "amem://schema/types".to_string(),
+            name: "Type Names".to_string(),
+            description: Some(
+                "Every valid event type and edge type name, with a short description".to_string(),
+            ),
+            mime_type: Some("application/json".to_string()),
+        },
     ]
 }
@@ -0,0 +1,27 @@
+//! Resource handler for `amem://tools/schema` — every built-in tool's input schema.
+
+use std::collections::BTreeMap;
+
+use crate::session::ToolDefaults;
+use crate::tools::ToolRegistry;
+use crate::types::{McpResult, ReadResourceResult, ResourceContent};
+
+/// Read a JSON object mapping each built-in tool name to its `inputSchema`,
+/// as seen by a session with `defaults`.
+///
+/// Keys are sorted so the document diffs cleanly when a schema changes.
+pub fn read_schema(defaults: &ToolDefaults) -> McpResult<ReadResourceResult> {
+    let schemas: BTreeMap<String, serde_json::Value> = ToolRegistry::list_tools_with(defaults)
+        .into_iter()
+        .map(|tool| (tool.name, tool.input_schema))
+        .collect();
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContent {
+            uri: "amem://tools/schema".to_string(),
+            mime_type: Some("application/json".to_string()),
+            text: Some(serde_json::to_string_pretty(&schemas).unwrap_or_else(|_| "{}".to_string())),
+            blob: None,
+        }],
+    })
+}
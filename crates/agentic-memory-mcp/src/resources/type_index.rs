@@ -1,7 +1,7 @@
-//! Resource handler for `amem://types/{type}` — all nodes of a given event type.
+//! Resource handler for `amem://types/{type}` — nodes of a given event type, paged.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde_json::json;
 
@@ -10,42 +10,61 @@ use agentic_memory::EventType;
 use crate::session::SessionManager;
 use crate::types::{McpError, McpResult, ReadResourceResult, ResourceContent};
 
-/// Read all nodes of a specific event type.
+/// Default page size for `amem://types/{type}`.
+pub const DEFAULT_TYPE_PAGE_SIZE: usize = 500;
+
+/// Read one page of nodes of a specific event type
+/// (`amem://types/{type}?cursor=&limit=`).
 pub async fn read_type(
     type_name: &str,
-    session: &Arc<Mutex<SessionManager>>,
+    cursor: usize,
+    limit: usize,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ReadResourceResult> {
     let event_type = EventType::from_name(type_name)
         .ok_or_else(|| McpError::InvalidParams(format!("Unknown event type: {type_name}")))?;
 
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
+    let scope = session.effective_session_ids(Vec::new());
+
     let node_ids = graph.type_index().get(event_type);
-    let nodes: Vec<serde_json::Value> = node_ids
+    let matching: Vec<_> = node_ids
+        .iter()
+        .filter_map(|nid| graph.get_node(*nid))
+        .filter(|node| scope.is_empty() || scope.contains(&node.session_id))
+        .collect();
+    let total = matching.len();
+
+    let nodes: Vec<serde_json::Value> = matching
         .iter()
-        .filter_map(|nid| {
-            graph.get_node(*nid).map(|node| {
-                json!({
-                    "id": node.id,
-                    "content": node.content,
-                    "confidence": node.confidence,
-                    "session_id": node.session_id,
-                    "created_at": node.created_at,
-                })
+        .skip(cursor)
+        .take(limit)
+        .map(|node| {
+            json!({
+                "id": node.id,
+                "content": node.content,
+                "confidence": node.confidence,
+                "session_id": node.session_id,
+                "created_at": node.created_at,
             })
         })
         .collect();
+    let end = cursor.saturating_add(nodes.len());
 
     let content = json!({
         "event_type": type_name,
         "count": nodes.len(),
+        "total": total,
+        "cursor": cursor,
+        "next_cursor": (end < total).then_some(end),
         "nodes": nodes,
     });
 
     Ok(ReadResourceResult {
         contents: vec![ResourceContent {
-            uri: format!("amem://types/{type_name}"),
+            uri: format!("amem://types/{type_name}?cursor={cursor}&limit={limit}"),
             mime_type: Some("application/json".to_string()),
             text: Some(serde_json::to_string_pretty(&content).unwrap_or_else(|_| "{}".to_string())),
             blob: None,
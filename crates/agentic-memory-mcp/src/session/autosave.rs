@@ -2,20 +2,20 @@
 
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use super::manager::SessionManager;
 
 /// Spawn a background task that periodically runs maintenance.
 pub fn spawn_maintenance(
-    session: Arc<Mutex<SessionManager>>,
+    session: Arc<RwLock<SessionManager>>,
     interval: Duration,
 ) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
         let mut ticker = tokio::time::interval(interval);
         loop {
             ticker.tick().await;
-            let mut session = session.lock().await;
+            let mut session = session.write().await;
             if let Err(e) = session.run_maintenance_tick() {
                 tracing::error!("Maintenance tick failed: {e}");
             }
@@ -0,0 +1,126 @@
+//! Node attachments: small binary payloads kept beside the `.amem` file.
+//!
+//! Like tags, attachments have no slot in the binary node record, so they
+//! live in a JSON sidecar (`<brain>.amem.blobs.json`) written alongside every
+//! save. Payloads are stored base64-encoded.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{McpError, McpResult};
+
+/// Default cap on the decoded size of one attachment (`AMEM_MAX_BLOB_BYTES`).
+pub const DEFAULT_MAX_BLOB_BYTES: usize = 64 * 1024;
+
+/// MIME type assumed when the client does not name one.
+pub const DEFAULT_BLOB_MIME_TYPE: &str = "application/octet-stream";
+
+/// A binary attachment on a node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Blob {
+    /// MIME type of the payload.
+    pub mime_type: String,
+    /// Decoded size in bytes.
+    pub size: usize,
+    /// The payload, base64-encoded.
+    pub data: String,
+}
+
+impl Blob {
+    /// Wrap raw bytes.
+    pub fn new(bytes: &[u8], mime_type: Option<String>) -> Self {
+        Self {
+            mime_type: mime_type.unwrap_or_else(|| DEFAULT_BLOB_MIME_TYPE.to_string()),
+            size: bytes.len(),
+            data: STANDARD.encode(bytes),
+        }
+    }
+
+    /// Decode client-supplied `data` in `encoding` (`base64` or `hex`).
+    pub fn decode(data: &str, encoding: &str) -> McpResult<Vec<u8>> {
+        match encoding {
+            "base64" => STANDARD
+                .decode(data.trim())
+                .map_err(|e| McpError::InvalidParams(format!("Invalid base64 blob: {e}"))),
+            "hex" => hex::decode(data.trim())
+                .map_err(|e| McpError::InvalidParams(format!("Invalid hex blob: {e}"))),
+            other => Err(McpError::InvalidParams(format!(
+                "blob_encoding must be 'base64' or 'hex', got '{other}'"
+            ))),
+        }
+    }
+}
+
+/// Attachments for all nodes, keyed by node ID.
+#[derive(Debug, Default)]
+pub struct BlobStore {
+    blobs: BTreeMap<u64, Blob>,
+}
+
+impl BlobStore {
+    /// Sidecar path for a memory file.
+    pub fn sidecar_path(memory_path: &Path) -> PathBuf {
+        let mut name = memory_path.as_os_str().to_os_string();
+        name.push(".blobs.json");
+        PathBuf::from(name)
+    }
+
+    /// Load attachments from the sidecar. A missing sidecar yields an empty store.
+    pub fn load(memory_path: &Path) -> McpResult<Self> {
+        let path = Self::sidecar_path(memory_path);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(&path)?;
+        let blobs: BTreeMap<u64, Blob> = serde_json::from_str(&raw).map_err(|e| {
+            McpError::AgenticMemory(format!("Invalid blob sidecar {}: {e}", path.display()))
+        })?;
+        Ok(Self { blobs })
+    }
+
+    /// Write the sidecar atomically. An empty store removes it.
+    pub fn save(&self, memory_path: &Path) -> McpResult<()> {
+        let path = Self::sidecar_path(memory_path);
+        if self.blobs.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+            return Ok(());
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec(&self.blobs)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    /// Attachment of a node, if any.
+    pub fn get(&self, node_id: u64) -> Option<&Blob> {
+        self.blobs.get(&node_id)
+    }
+
+    /// Attach `blob` to a node, replacing any previous attachment.
+    pub fn insert(&mut self, node_id: u64, blob: Blob) {
+        self.blobs.insert(node_id, blob);
+    }
+
+    /// Overlay our attachments onto `base` (the on-disk store) after a merge
+    /// that renumbered re-added nodes via `id_map` (old ID -> new ID).
+    pub fn merged_onto(&self, mut base: BlobStore, id_map: &HashMap<u64, u64>) -> BlobStore {
+        for (id, blob) in &self.blobs {
+            let id = id_map.get(id).copied().unwrap_or(*id);
+            base.blobs.insert(id, blob.clone());
+        }
+        base
+    }
+
+    /// Drop attachments for nodes not accepted by `exists`. Returns how many were dropped.
+    pub fn retain_nodes(&mut self, exists: impl Fn(u64) -> bool) -> usize {
+        let before = self.blobs.len();
+        self.blobs.retain(|id, _| exists(*id));
+        before - self.blobs.len()
+    }
+}
@@ -0,0 +1,319 @@
+//! Episode node content: the summary as free text, or a structured JSON record.
+//!
+//! A structured episode records which nodes the session held, how many of
+//! each type, and its most important nodes, so an agent can expand the
+//! episode back into its components without parsing prose.
+
+use std::collections::BTreeMap;
+
+use agentic_memory::{CognitiveEvent, EventType, MemoryGraph, MAX_CONTENT_SIZE};
+use serde_json::{json, Map, Value};
+
+/// Value of the `schema` field that marks structured episode content.
+pub const EPISODE_SCHEMA: &str = "amem-episode/1";
+/// Number of key nodes recorded when none is configured.
+pub const DEFAULT_EPISODE_KEY_NODES: usize = 5;
+/// Maximum characters of each key node's content kept in the record.
+const KEY_NODE_MAX_CHARS: usize = 280;
+/// Start of the line listing member IDs in a text episode that is not
+/// linked to its members.
+const MEMBER_LINE_PREFIX: &str = "Members: ";
+
+/// A section a structured episode can contain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpisodeSection {
+    /// The summary text.
+    Summary,
+    /// IDs of every node in the session.
+    NodeIds,
+    /// Node count per event type.
+    TypeCounts,
+    /// The session's most important nodes with their content.
+    KeyNodes,
+}
+
+impl EpisodeSection {
+    /// Every section, in the order they are written by default.
+    pub fn all() -> &'static [EpisodeSection] {
+        &[
+            Self::Summary,
+            Self::NodeIds,
+            Self::TypeCounts,
+            Self::KeyNodes,
+        ]
+    }
+
+    /// Parse a section from its name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "summary" => Some(Self::Summary),
+            "node_ids" => Some(Self::NodeIds),
+            "type_counts" => Some(Self::TypeCounts),
+            "key_nodes" => Some(Self::KeyNodes),
+            _ => None,
+        }
+    }
+
+    /// The section's field name in the record.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Summary => "summary",
+            Self::NodeIds => "node_ids",
+            Self::TypeCounts => "type_counts",
+            Self::KeyNodes => "key_nodes",
+        }
+    }
+}
+
+/// Which sections a structured episode contains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpisodeTemplate {
+    /// Sections to write, in order.
+    pub sections: Vec<EpisodeSection>,
+    /// Maximum number of key nodes recorded.
+    pub key_nodes: usize,
+}
+
+impl Default for EpisodeTemplate {
+    fn default() -> Self {
+        Self {
+            sections: EpisodeSection::all().to_vec(),
+            key_nodes: DEFAULT_EPISODE_KEY_NODES,
+        }
+    }
+}
+
+impl EpisodeTemplate {
+    /// Build the structured content for an episode of `session_id`.
+    ///
+    /// Must be called before the episode node is added, so the record lists
+    /// only the session's own nodes. `node_ids` is left out (and
+    /// `node_ids_omitted` set) when it would push the record over the node
+    /// content limit; members remain reachable through `part_of` edges.
+    pub fn render(&self, graph: &MemoryGraph, session_id: u32, summary: &str) -> String {
+        let node_ids = graph.session_index().get_session(session_id);
+        let nodes: Vec<&CognitiveEvent> = node_ids
+            .iter()
+            .filter_map(|id| graph.get_node(*id))
+            .collect();
+
+        let mut record = Map::new();
+        record.insert("schema".to_string(), json!(EPISODE_SCHEMA));
+        record.insert("session_id".to_string(), json!(session_id));
+        for section in &self.sections {
+            let value = match section {
+                EpisodeSection::Summary => json!(summary),
+                EpisodeSection::NodeIds => json!(node_ids),
+                EpisodeSection::TypeCounts => {
+                    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+                    for node in &nodes {
+                        *counts.entry(node.event_type.name()).or_default() += 1;
+                    }
+                    json!(counts)
+                }
+                EpisodeSection::KeyNodes => json!(key_nodes(&nodes, self.key_nodes)),
+            };
+            record.insert(section.name().to_string(), value);
+        }
+
+        let content = Value::Object(record.clone()).to_string();
+        if content.len() <= MAX_CONTENT_SIZE {
+            return content;
+        }
+        record.remove(EpisodeSection::NodeIds.name());
+        record.insert("node_ids_omitted".to_string(), json!(true));
+        Value::Object(record).to_string()
+    }
+}
+
+/// How an episode is tied to the nodes of its session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EpisodeLinks {
+    /// A `part_of` edge from every member to the episode node.
+    #[default]
+    PartOf,
+    /// No edges; the member IDs are listed in the episode content.
+    Content,
+}
+
+impl EpisodeLinks {
+    /// Read `AMEM_EPISODE_LINKS` (`part_of` or `content`).
+    pub fn from_env() -> Self {
+        let raw = std::env::var("AMEM_EPISODE_LINKS").unwrap_or_default();
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "" | "part_of" => Self::PartOf,
+            "content" => Self::Content,
+            other => {
+                tracing::warn!("Unknown AMEM_EPISODE_LINKS '{other}', using part_of");
+                Self::PartOf
+            }
+        }
+    }
+
+    /// The mode's name: `part_of` or `content`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::PartOf => "part_of",
+            Self::Content => "content",
+        }
+    }
+
+    /// Whether members get `part_of` edges to the episode.
+    pub fn links_members(&self) -> bool {
+        *self == Self::PartOf
+    }
+}
+
+/// How an episode node's content is written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EpisodeFormat {
+    /// The summary text as given.
+    Text,
+    /// A JSON record with the template's sections.
+    Structured(EpisodeTemplate),
+}
+
+impl EpisodeFormat {
+    /// Read `AMEM_EPISODE_FORMAT` (`text` or `structured`), with
+    /// `AMEM_EPISODE_SECTIONS` (comma-separated section names) and
+    /// `AMEM_EPISODE_KEY_NODES` shaping the structured template.
+    pub fn from_env() -> Self {
+        let format = std::env::var("AMEM_EPISODE_FORMAT").unwrap_or_default();
+        if !format.trim().eq_ignore_ascii_case("structured") {
+            return Self::Text;
+        }
+
+        let mut template = EpisodeTemplate::default();
+        if let Ok(raw) = std::env::var("AMEM_EPISODE_SECTIONS") {
+            let mut sections = Vec::new();
+            for name in raw.split(',').filter(|s| !s.trim().is_empty()) {
+                match EpisodeSection::from_name(name) {
+                    Some(section) if !sections.contains(&section) => sections.push(section),
+                    Some(_) => {}
+                    None => tracing::warn!("Ignoring unknown episode section '{}'", name.trim()),
+                }
+            }
+            if !sections.is_empty() {
+                template.sections = sections;
+            }
+        }
+        if let Some(key_nodes) = std::env::var("AMEM_EPISODE_KEY_NODES")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+        {
+            template.key_nodes = key_nodes;
+        }
+        Self::Structured(template)
+    }
+
+    /// The format's name: `text` or `structured`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Structured(_) => "structured",
+        }
+    }
+
+    /// Episode node content for `summary` in this format.
+    ///
+    /// With [`EpisodeLinks::Content`] the member IDs are always written: a
+    /// structured record gains `node_ids` if its template leaves it out, and
+    /// text gets a trailing `Members: #1, #2` line.
+    pub fn render(
+        &self,
+        graph: &MemoryGraph,
+        session_id: u32,
+        summary: &str,
+        links: EpisodeLinks,
+    ) -> String {
+        match (self, links) {
+            (Self::Text, EpisodeLinks::PartOf) => summary.to_string(),
+            (Self::Text, EpisodeLinks::Content) => {
+                let refs: Vec<String> = graph
+                    .session_index()
+                    .get_session(session_id)
+                    .iter()
+                    .map(|id| format!("#{id}"))
+                    .collect();
+                if refs.is_empty() {
+                    return summary.to_string();
+                }
+                let content = format!("{summary}\n\n{MEMBER_LINE_PREFIX}{}", refs.join(", "));
+                if content.len() > MAX_CONTENT_SIZE {
+                    tracing::warn!(
+                        session_id,
+                        "Episode member list exceeds the content limit; storing the summary only"
+                    );
+                    return summary.to_string();
+                }
+                content
+            }
+            (Self::Structured(template), EpisodeLinks::PartOf) => {
+                template.render(graph, session_id, summary)
+            }
+            (Self::Structured(template), EpisodeLinks::Content) => {
+                let mut template = template.clone();
+                if !template.sections.contains(&EpisodeSection::NodeIds) {
+                    template.sections.push(EpisodeSection::NodeIds);
+                }
+                template.render(graph, session_id, summary)
+            }
+        }
+    }
+}
+
+/// Member IDs recorded in an episode's content: the `node_ids` of a
+/// structured record, or the `Members:` line of a text episode.
+pub fn member_ids_in(content: &str) -> Option<Vec<u64>> {
+    if let Some(record) = parse_episode(content) {
+        return serde_json::from_value(record.get("node_ids")?.clone()).ok();
+    }
+    let line = content.lines().last()?.strip_prefix(MEMBER_LINE_PREFIX)?;
+    line.split(", ")
+        .map(|r| r.strip_prefix('#')?.parse().ok())
+        .collect()
+}
+
+/// The structured record stored in an episode node, or `None` for free text.
+pub fn parse_episode(content: &str) -> Option<Map<String, Value>> {
+    match serde_json::from_str(content) {
+        Ok(Value::Object(record))
+            if record.get("schema").and_then(Value::as_str) == Some(EPISODE_SCHEMA) =>
+        {
+            Some(record)
+        }
+        _ => None,
+    }
+}
+
+/// The `limit` most important nodes: decisions and corrections first, then
+/// by confidence, then oldest first.
+fn key_nodes(nodes: &[&CognitiveEvent], limit: usize) -> Vec<Value> {
+    let rank = |event_type: EventType| match event_type {
+        EventType::Decision => 0,
+        EventType::Correction => 1,
+        EventType::Inference => 2,
+        EventType::Skill => 3,
+        EventType::Fact => 4,
+        EventType::Episode => 5,
+    };
+    let mut ranked: Vec<&CognitiveEvent> = nodes.to_vec();
+    ranked.sort_by(|a, b| {
+        rank(a.event_type)
+            .cmp(&rank(b.event_type))
+            .then(b.confidence.total_cmp(&a.confidence))
+            .then(a.id.cmp(&b.id))
+    });
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|node| {
+            json!({
+                "id": node.id,
+                "event_type": node.event_type.name(),
+                "confidence": node.confidence,
+                "content": node.content.chars().take(KEY_NODE_MAX_CHARS).collect::<String>(),
+            })
+        })
+        .collect()
+}
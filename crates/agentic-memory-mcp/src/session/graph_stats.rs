@@ -0,0 +1,77 @@
+//! Graph statistics shared by `memory_stats` and `amem://graph/stats`,
+//! cached per graph version so repeated reads skip the node scan.
+
+use std::sync::Mutex;
+
+use agentic_memory::{EventType, MemoryGraph};
+use serde_json::{json, Value};
+
+/// Counts derived from the graph and its indexes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphStats {
+    pub node_count: usize,
+    pub edge_count: usize,
+    pub dimension: usize,
+    pub session_count: usize,
+    /// Node count per event type, in [`EventType::all`] order.
+    pub type_counts: Vec<(EventType, usize)>,
+    /// Nodes with a non-zero feature vector; zero vectors are placeholders.
+    pub nodes_with_vectors: usize,
+}
+
+impl GraphStats {
+    /// Compute the statistics with a full pass over the nodes.
+    pub fn compute(graph: &MemoryGraph) -> Self {
+        let type_index = graph.type_index();
+        Self {
+            node_count: graph.node_count(),
+            edge_count: graph.edge_count(),
+            dimension: graph.dimension(),
+            session_count: graph.session_index().session_count(),
+            type_counts: EventType::all()
+                .iter()
+                .map(|&t| (t, type_index.count(t)))
+                .collect(),
+            nodes_with_vectors: graph
+                .nodes()
+                .iter()
+                .filter(|n| n.feature_vec.iter().any(|&x| x != 0.0))
+                .count(),
+        }
+    }
+
+    /// Type counts as a JSON object keyed by event type name.
+    pub fn type_counts_json(&self) -> Value {
+        let counts: serde_json::Map<String, Value> = self
+            .type_counts
+            .iter()
+            .map(|(t, count)| (t.name().to_string(), json!(count)))
+            .collect();
+        Value::Object(counts)
+    }
+}
+
+/// The last computed [`GraphStats`] and the graph version they describe.
+///
+/// Lives behind a mutex so readers holding only a shared session lock can
+/// fill it.
+#[derive(Debug, Default)]
+pub struct GraphStatsCache {
+    slot: Mutex<Option<(u64, GraphStats)>>,
+}
+
+impl GraphStatsCache {
+    /// Stats for `version`, computed only if the cached entry describes
+    /// another version.
+    pub fn get_or_compute(&self, version: u64, graph: &MemoryGraph) -> GraphStats {
+        let mut slot = self.slot.lock().unwrap_or_else(|e| e.into_inner());
+        match slot.as_ref() {
+            Some((cached_version, stats)) if *cached_version == version => stats.clone(),
+            _ => {
+                let stats = GraphStats::compute(graph);
+                *slot = Some((version, stats.clone()));
+                stats
+            }
+        }
+    }
+}
@@ -1,19 +1,34 @@
 //! Graph lifecycle management, file I/O, and session tracking.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ffi::OsStr;
 use std::fs::OpenOptions;
 use std::io::Read as _;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime};
 
+use agentic_memory::cache::LruCache;
 use agentic_memory::{
-    AmemReader, AmemWriter, CognitiveEventBuilder, Edge, EdgeType, EventType, MemoryGraph,
-    PatternParams, PatternSort, QueryEngine, WriteEngine,
+    AmemError, AmemReader, AmemWriter, CognitiveEvent, CognitiveEventBuilder, Edge, EdgeType,
+    EventType, MemoryGraph, PatternParams, PatternSort, QueryEngine, WriteEngine,
 };
-use serde_json::Value;
-
-use crate::types::{McpError, McpResult, MemoryMode};
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, RwLock};
+
+use crate::prompts::PromptLocale;
+use crate::protocol::ClientRequester;
+use crate::streaming::progress::progress_notification;
+use crate::types::{JsonRpcNotification, McpError, McpResult, MemoryMode};
+
+use super::blobs::{Blob, BlobStore, DEFAULT_MAX_BLOB_BYTES};
+use super::episode::{EpisodeFormat, EpisodeLinks};
+use super::graph_stats::{GraphStats, GraphStatsCache};
+use super::pins::PinStore;
+use super::session_state::SessionState;
+use super::tags::{TagStore, Tags};
+use super::tool_defaults::ToolDefaults;
+use super::undo::{UndoLog, UndoOp, DEFAULT_UNDO_LOG_CAPACITY};
 
 /// Default auto-save interval.
 const DEFAULT_AUTO_SAVE_SECS: u64 = 30;
@@ -41,6 +56,30 @@ const DEFAULT_STORAGE_BUDGET_HORIZON_YEARS: u32 = 20;
 const DEFAULT_AUTO_CAPTURE_MAX_CHARS: usize = 2048;
 /// Current `.amem` storage version used by this server.
 const CURRENT_AMEM_VERSION: u32 = 1;
+/// How long a `memory_add` idempotency key is remembered.
+const IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(600);
+/// Maximum number of idempotency keys retained at once.
+const IDEMPOTENCY_KEY_CAPACITY: usize = 1024;
+/// Suffix appended to content truncated by the content length limit.
+const TRUNCATION_SUFFIX: &str = "…[truncated]";
+/// Namespace used when none is configured or requested.
+pub const DEFAULT_NAMESPACE: &str = "default";
+/// Path that opens a scratch graph which is never read from or written to disk.
+pub const EPHEMERAL_MEMORY_PATH: &str = ":memory:";
+
+/// Source of "now" in Unix epoch microseconds for new nodes and access stamps.
+pub type Clock = Arc<dyn Fn() -> u64 + Send + Sync>;
+
+/// Outcome of [`SessionManager::compact_chain`].
+#[derive(Debug, Clone)]
+pub struct ChainCompaction {
+    /// The live version the chain was collapsed onto.
+    pub latest_id: u64,
+    /// Older versions that were deleted or archived, ascending.
+    pub collapsed: Vec<u64>,
+    /// Edges of older versions moved onto the latest version.
+    pub rewired_edges: usize,
+}
 
 #[derive(Debug, Clone, Copy)]
 enum AutonomicProfile {
@@ -73,6 +112,22 @@ enum AutoCaptureMode {
     Off,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContentLimitPolicy {
+    /// Reject oversized content with `InvalidParams`.
+    Reject,
+    /// Truncate oversized content at a char boundary and append a suffix.
+    Truncate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfidencePolicy {
+    /// Reject confidence outside `[0.0, 1.0]` with `InvalidParams`.
+    Reject,
+    /// Clamp out-of-range confidence into `[0.0, 1.0]` and log a warning.
+    Clamp,
+}
+
 #[derive(Debug, Clone, Copy)]
 struct ProfileDefaults {
     auto_save_secs: u64,
@@ -181,6 +236,40 @@ impl StorageBudgetMode {
     }
 }
 
+impl ContentLimitPolicy {
+    fn from_env(name: &str) -> Self {
+        let raw = read_env_string(name).unwrap_or_else(|| "reject".to_string());
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "truncate" => Self::Truncate,
+            _ => Self::Reject,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Reject => "reject",
+            Self::Truncate => "truncate",
+        }
+    }
+}
+
+impl ConfidencePolicy {
+    fn from_env(name: &str) -> Self {
+        let raw = read_env_string(name).unwrap_or_else(|| "reject".to_string());
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "clamp" => Self::Clamp,
+            _ => Self::Reject,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Reject => "reject",
+            Self::Clamp => "clamp",
+        }
+    }
+}
+
 impl AutoCaptureMode {
     fn from_env(name: &str) -> Self {
         let raw = read_env_string(name).unwrap_or_else(|| "safe".to_string());
@@ -200,17 +289,50 @@ impl AutoCaptureMode {
     }
 }
 
+/// What decides the concrete resources behind the `amem://session/{id}` and
+/// `amem://types/{type}` templates: the sessions holding nodes and the event
+/// types in use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ResourceShape {
+    sessions: Vec<u32>,
+    types: Vec<EventType>,
+}
+
+impl ResourceShape {
+    fn of(graph: &MemoryGraph) -> Self {
+        let sessions = graph.session_index().session_ids();
+        let types = EventType::all()
+            .iter()
+            .copied()
+            .filter(|t| !graph.type_index().get(*t).is_empty())
+            .collect();
+        Self { sessions, types }
+    }
+}
+
 /// Manages the memory graph lifecycle, file I/O, and session state.
 pub struct SessionManager {
     graph: MemoryGraph,
     query_engine: QueryEngine,
     write_engine: WriteEngine,
     file_path: PathBuf,
+    /// Opened with [`EPHEMERAL_MEMORY_PATH`]: nothing is ever persisted.
+    ephemeral: bool,
     current_session: u32,
+    /// False once the current session was ended with an episode summary.
+    session_active: bool,
     profile: AutonomicProfile,
     migration_policy: StorageMigrationPolicy,
     dirty: bool,
     last_save: Instant,
+    /// Wall-clock time of the last successful save (file mtime at open).
+    last_saved_at: Option<SystemTime>,
+    /// Error from the most recent failed save, cleared by the next success.
+    last_error: Option<String>,
+    /// When `last_error` happened.
+    last_error_at: Option<SystemTime>,
+    /// Saves that have failed in a row since the last success.
+    save_failures: u32,
     auto_save_interval: Duration,
     backup_interval: Duration,
     backup_retention: usize,
@@ -247,14 +369,76 @@ pub struct SessionManager {
     last_file_mtime: Option<SystemTime>,
     /// Multi-context workspace manager for cross-memory queries.
     workspace_manager: super::workspace::WorkspaceManager,
+    /// When set, read tools implicitly filter to the current session unless
+    /// the caller passes an explicit `session_ids` filter.
+    scope_to_current_session: bool,
+    /// Maximum size in bytes accepted for a single node's content.
+    max_content_bytes: usize,
+    content_limit_policy: ContentLimitPolicy,
+    confidence_policy: ConfidencePolicy,
+    /// Recently seen `memory_add` idempotency keys -> (created node ID, seen at).
+    idempotency_keys: HashMap<String, (u64, Instant)>,
+    /// Channel to the connected client for `notifications/message` log events.
+    notification_tx: Option<mpsc::Sender<JsonRpcNotification>>,
+    /// Client that advertised `sampling`, used to generate episode summaries.
+    sampling_client: Option<Arc<ClientRequester>>,
+    /// Also notify the client on successful saves (failures are always reported).
+    notify_on_save: bool,
+    /// Key/value node tags, persisted in the `.tags.json` sidecar.
+    tags: TagStore,
+    /// Binary node attachments, persisted in the `.blobs.json` sidecar.
+    blobs: BlobStore,
+    /// Nodes exempt from forgetting, persisted in the `.pins.json` sidecar.
+    pins: PinStore,
+    /// Resource shape last announced to the client.
+    resource_shape: ResourceShape,
+    /// Maximum decoded size in bytes of a single attachment.
+    max_blob_bytes: usize,
+    /// Name of this graph when serving a memory directory.
+    namespace: String,
+    /// Other graphs opened from the same memory directory, keyed by namespace.
+    namespaces: BTreeMap<String, Arc<RwLock<SessionManager>>>,
+    /// Inverses of recent mutations, consumed by `memory_undo`.
+    undo_log: UndoLog,
+    /// Timestamp source; the system clock unless replaced with [`Self::set_clock`].
+    clock: Clock,
+    /// How `session_end` writes episode content.
+    episode_format: EpisodeFormat,
+    /// Whether episode members get `part_of` edges or are listed in content.
+    episode_links: EpisodeLinks,
+    /// Fallbacks for arguments tool calls omit.
+    tool_defaults: ToolDefaults,
+    /// Keep a term index over node content for BM25 search (`AMEM_TEXT_INDEX`).
+    text_index_enabled: bool,
+    /// Language of the prompt templates' guidance text.
+    prompt_locale: PromptLocale,
+    /// Opaque version of the graph, bumped by every mutation. Seeded from
+    /// the clock on open so versions from before a restart never match.
+    graph_version: u64,
+    /// Results of read-only tool calls keyed by tool, arguments, and graph
+    /// version (`AMEM_QUERY_CACHE_SIZE`); `None` when disabled.
+    query_cache: Option<LruCache<String, Value>>,
+    /// Graph statistics for the current graph version.
+    stats_cache: GraphStatsCache,
 }
 
 impl SessionManager {
-    /// Open or create a memory file at the given path.
+    /// Open or create a memory file at the given path. With
+    /// `AMEM_CREATE_IF_MISSING=false` a missing file is an error instead.
     pub fn open(path: &str) -> McpResult<Self> {
+        Self::open_with(path, read_env_bool("AMEM_CREATE_IF_MISSING", true))
+    }
+
+    /// Open a memory file that must already exist.
+    pub fn open_existing(path: &str) -> McpResult<Self> {
+        Self::open_with(path, false)
+    }
+
+    fn open_with(path: &str, create_if_missing: bool) -> McpResult<Self> {
         let file_path = PathBuf::from(path);
         let dimension = agentic_memory::DEFAULT_DIMENSION;
-        let file_existed = file_path.exists();
+        let ephemeral = path == EPHEMERAL_MEMORY_PATH;
+        let file_existed = !ephemeral && file_path.exists();
         let profile = AutonomicProfile::from_env("AMEM_AUTONOMIC_PROFILE");
         let defaults = profile.defaults();
         let migration_policy = StorageMigrationPolicy::from_env("AMEM_STORAGE_MIGRATION_POLICY");
@@ -265,10 +449,17 @@ impl SessionManager {
         };
         let legacy_version = detected_version.filter(|v| *v < CURRENT_AMEM_VERSION);
 
-        let graph = if file_existed {
+        let mut graph = if file_existed {
             tracing::info!("Opening existing memory file: {}", file_path.display());
             match AmemReader::read_from_file(&file_path) {
                 Ok(g) => g,
+                // A newer format is not corruption: leave the file untouched.
+                Err(agentic_memory::AmemError::UnsupportedVersion(found)) => {
+                    return Err(McpError::IncompatibleFormat {
+                        found,
+                        expected: agentic_memory::FORMAT_VERSION,
+                    });
+                }
                 Err(e) => {
                     tracing::error!("Corrupt memory file, backing up and starting fresh: {e}");
                     let backup = format!("{}.corrupt.bak", file_path.display());
@@ -280,6 +471,17 @@ impl SessionManager {
                     MemoryGraph::new(dimension)
                 }
             }
+        } else if ephemeral {
+            tracing::info!("Opening ephemeral in-memory graph; nothing will be saved");
+            MemoryGraph::new(dimension)
+        } else if !create_if_missing {
+            return Err(McpError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!(
+                    "Memory file {} does not exist and creating it is disabled",
+                    file_path.display()
+                ),
+            )));
         } else {
             tracing::info!("Creating new memory file: {}", file_path.display());
             // Ensure parent directory exists
@@ -294,6 +496,33 @@ impl SessionManager {
             MemoryGraph::new(dimension)
         };
 
+        // Older files and imports can hold repeated edges, which inflate
+        // traversal weights and degrees. Collapse them only when asked to.
+        let deduped_edges = if read_env_bool("AMEM_DEDUP_EDGES", false) {
+            let removed = graph.dedup_edges();
+            if removed > 0 {
+                tracing::info!(
+                    "Removed {removed} duplicate edges from {}",
+                    file_path.display()
+                );
+            }
+            removed
+        } else {
+            let distinct: HashSet<(u64, u64, EdgeType)> = graph
+                .edges()
+                .iter()
+                .map(|e| (e.source_id, e.target_id, e.edge_type))
+                .collect();
+            let duplicates = graph.edge_count() - distinct.len();
+            if duplicates > 0 {
+                tracing::warn!(
+                    "{} has {duplicates} duplicate edges; set AMEM_DEDUP_EDGES=true to collapse them",
+                    file_path.display()
+                );
+            }
+            0
+        };
+
         // Determine the next session ID from existing sessions.
         // Incorporate PID to avoid collisions when multiple MCP instances share
         // the same .amem file (e.g. two Claude Code windows on different projects).
@@ -303,6 +532,9 @@ impl SessionManager {
         let current_session = max_existing.saturating_add(1).saturating_add(pid_component);
 
         tracing::info!(
+            session_id = current_session,
+            node_count = graph.node_count(),
+            edge_count = graph.edge_count(),
             "Session {} started. Graph has {} nodes, {} edges.",
             current_session,
             graph.node_count(),
@@ -362,17 +594,74 @@ impl SessionManager {
             DEFAULT_AUTO_CAPTURE_MAX_CHARS,
         )
         .clamp(256, 16384);
+        let scope_to_current_session = read_env_bool("AMEM_SCOPE_TO_CURRENT_SESSION", false);
+        let notify_on_save = read_env_bool("AMEM_NOTIFY_ON_SAVE", false);
+        let continue_session = !ephemeral && read_env_bool("AMEM_CONTINUE_SESSION", false);
+        let build_index_on_open = read_env_bool("AMEM_BUILD_INDEX_ON_OPEN", false);
+        let undo_log_capacity = read_env_usize("AMEM_UNDO_LOG_SIZE", DEFAULT_UNDO_LOG_CAPACITY);
+        let max_content_bytes =
+            read_env_usize("AMEM_MAX_CONTENT_BYTES", agentic_memory::MAX_CONTENT_SIZE)
+                .clamp(64, agentic_memory::MAX_CONTENT_SIZE);
+        let content_limit_policy = ContentLimitPolicy::from_env("AMEM_CONTENT_LIMIT_POLICY");
+        let confidence_policy = ConfidencePolicy::from_env("AMEM_CONFIDENCE_POLICY");
+        let mut tags = if ephemeral {
+            TagStore::default()
+        } else {
+            TagStore::load(&file_path).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring unreadable tag sidecar: {e}");
+                TagStore::default()
+            })
+        };
+        let orphaned = tags.retain_nodes(|id| graph.get_node(id).is_some());
+        if orphaned > 0 {
+            tracing::warn!("Dropped tags for {orphaned} missing nodes");
+        }
+        let mut blobs = if ephemeral {
+            BlobStore::default()
+        } else {
+            BlobStore::load(&file_path).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring unreadable blob sidecar: {e}");
+                BlobStore::default()
+            })
+        };
+        let orphaned = blobs.retain_nodes(|id| graph.get_node(id).is_some());
+        if orphaned > 0 {
+            tracing::warn!("Dropped attachments for {orphaned} missing nodes");
+        }
+        let mut pins = if ephemeral {
+            PinStore::default()
+        } else {
+            PinStore::load(&file_path).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring unreadable pin sidecar: {e}");
+                PinStore::default()
+            })
+        };
+        let orphaned = pins.retain_nodes(|id| graph.get_node(id).is_some());
+        if orphaned > 0 {
+            tracing::warn!("Dropped pins for {orphaned} missing nodes");
+        }
 
+        let resource_shape = ResourceShape::of(&graph);
         let mut manager = Self {
             graph,
             query_engine: QueryEngine::new(),
             write_engine: WriteEngine::new(dimension),
             file_path,
+            ephemeral,
             current_session,
+            session_active: true,
             profile,
             migration_policy,
-            dirty: false,
+            dirty: deduped_edges > 0,
             last_save: Instant::now(),
+            last_saved_at: if file_existed {
+                std::fs::metadata(path).and_then(|m| m.modified()).ok()
+            } else {
+                None
+            },
+            last_error: None,
+            last_error_at: None,
+            save_failures: 0,
             auto_save_interval: Duration::from_secs(auto_save_secs),
             backup_interval: Duration::from_secs(backup_secs),
             backup_retention,
@@ -411,7 +700,33 @@ impl SessionManager {
                 None
             },
             workspace_manager: super::workspace::WorkspaceManager::new(),
+            scope_to_current_session,
+            max_content_bytes,
+            content_limit_policy,
+            confidence_policy,
+            idempotency_keys: HashMap::new(),
+            notification_tx: None,
+            sampling_client: None,
+            notify_on_save,
+            tags,
+            blobs,
+            pins,
+            resource_shape,
+            max_blob_bytes: read_env_usize("AMEM_MAX_BLOB_BYTES", DEFAULT_MAX_BLOB_BYTES),
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            namespaces: BTreeMap::new(),
+            undo_log: UndoLog::new(undo_log_capacity),
+            clock: Arc::new(agentic_memory::now_micros),
+            episode_format: EpisodeFormat::from_env(),
+            episode_links: EpisodeLinks::from_env(),
+            tool_defaults: ToolDefaults::default(),
+            text_index_enabled: read_env_bool("AMEM_TEXT_INDEX", false),
+            prompt_locale: PromptLocale::from_env(),
+            graph_version: agentic_memory::now_micros(),
+            query_cache: new_query_cache(read_env_usize("AMEM_QUERY_CACHE_SIZE", 0)),
+            stats_cache: GraphStatsCache::default(),
         };
+        manager.apply_text_index_policy();
 
         if let Some(version) = legacy_version {
             match migration_policy {
@@ -447,9 +762,98 @@ impl SessionManager {
             }
         }
 
+        if continue_session {
+            manager.resume_last_session();
+        }
+        if build_index_on_open {
+            manager.prebuild_text_index();
+        }
+
         Ok(manager)
     }
 
+    /// Open every `*.amem` file in `dir` as a namespace named after its file stem.
+    ///
+    /// The returned manager is the `default_namespace` graph (created if
+    /// missing); the others are reachable through [`Self::resolve_namespace`].
+    pub fn open_dir(dir: &str, default_namespace: &str) -> McpResult<Self> {
+        validate_namespace(default_namespace)?;
+        let dir_path = PathBuf::from(dir);
+        std::fs::create_dir_all(&dir_path).map_err(|e| {
+            McpError::Io(std::io::Error::other(format!(
+                "Failed to create memory dir {}: {e}",
+                dir_path.display()
+            )))
+        })?;
+
+        let default_path = dir_path.join(format!("{default_namespace}.amem"));
+        let mut manager = Self::open_with(&default_path.display().to_string(), true)?;
+        manager.namespace = default_namespace.to_string();
+
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&dir_path)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file() && path.extension() == Some(OsStr::new("amem")))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let Some(name) = path.file_stem().and_then(OsStr::to_str) else {
+                continue;
+            };
+            if name == default_namespace {
+                continue;
+            }
+            if let Err(e) = validate_namespace(name) {
+                tracing::warn!("Skipping {}: {e}", path.display());
+                continue;
+            }
+            let mut sibling = Self::open(&path.display().to_string())?;
+            sibling.namespace = name.to_string();
+            tracing::info!("Namespace '{name}': {}", path.display());
+            manager
+                .namespaces
+                .insert(name.to_string(), Arc::new(RwLock::new(sibling)));
+        }
+
+        Ok(manager)
+    }
+
+    /// Name of this graph's namespace.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Other namespaces opened alongside this graph, in name order.
+    pub fn namespaces(&self) -> &BTreeMap<String, Arc<RwLock<SessionManager>>> {
+        &self.namespaces
+    }
+
+    /// Resolve the session serving `namespace`.
+    ///
+    /// `None` or this session's own namespace returns `session` itself; an
+    /// unknown name is an `InvalidParams` error listing the valid ones.
+    pub async fn resolve_namespace(
+        session: &Arc<RwLock<SessionManager>>,
+        namespace: Option<&str>,
+    ) -> McpResult<Arc<RwLock<SessionManager>>> {
+        let Some(name) = namespace else {
+            return Ok(session.clone());
+        };
+        let guard = session.read().await;
+        if name == guard.namespace {
+            return Ok(session.clone());
+        }
+        guard.namespaces.get(name).cloned().ok_or_else(|| {
+            let known: Vec<&str> = std::iter::once(guard.namespace.as_str())
+                .chain(guard.namespaces.keys().map(String::as_str))
+                .collect();
+            McpError::InvalidParams(format!(
+                "Unknown namespace '{name}' (available: {})",
+                known.join(", ")
+            ))
+        })
+    }
+
     /// Get an immutable reference to the graph.
     pub fn graph(&self) -> &MemoryGraph {
         &self.graph
@@ -488,6 +892,28 @@ impl SessionManager {
         self.current_session
     }
 
+    /// Whether read tools are implicitly scoped to the current session.
+    pub fn scope_to_current_session(&self) -> bool {
+        self.scope_to_current_session
+    }
+
+    /// Enable or disable implicit current-session scoping for read tools.
+    pub fn set_scope_to_current_session(&mut self, enabled: bool) {
+        self.scope_to_current_session = enabled;
+    }
+
+    /// Resolve the session filter a read should apply.
+    ///
+    /// An explicit, non-empty `requested` list always wins so clients can
+    /// broaden the view. Otherwise scoping (when enabled) narrows reads to the
+    /// current session; an empty result means "all sessions".
+    pub fn effective_session_ids(&self, requested: Vec<u32>) -> Vec<u32> {
+        if !requested.is_empty() || !self.scope_to_current_session {
+            return requested;
+        }
+        vec![self.current_session]
+    }
+
     /// Apply runtime capture policy based on negotiated memory mode.
     ///
     /// This ties mode to server behavior (not just client instructions):
@@ -517,27 +943,91 @@ impl SessionManager {
         });
 
         self.current_session = session_id;
+        self.session_active = true;
         self.last_temporal_node_id = None;
         self.last_activity = Instant::now();
-        tracing::info!("Started session {session_id}");
+        tracing::info!(session_id, "Started session {session_id}");
         Ok(session_id)
     }
 
+    /// Continue the session that was active at the last save instead of the
+    /// fresh one chosen at open.
+    ///
+    /// Returns the resumed ID, or `None` when nothing was persisted or the
+    /// last session was ended with an episode summary.
+    pub fn resume_last_session(&mut self) -> Option<u32> {
+        if self.ephemeral {
+            return None;
+        }
+        let state = match SessionState::load(&self.file_path) {
+            Ok(state) => state?,
+            Err(e) => {
+                tracing::warn!("Not resuming session: {e}");
+                return None;
+            }
+        };
+        if !state.active {
+            return None;
+        }
+
+        self.current_session = state.session_id;
+        self.session_active = true;
+        self.last_temporal_node_id = self
+            .graph
+            .session_index()
+            .get_session(state.session_id)
+            .iter()
+            .copied()
+            .max();
+        tracing::info!("Resumed session {}", state.session_id);
+        Some(state.session_id)
+    }
+
     /// End a session and optionally create an episode summary.
+    ///
+    /// The episode's content follows the configured [`EpisodeFormat`].
     pub fn end_session_with_episode(&mut self, session_id: u32, summary: &str) -> McpResult<u64> {
+        let format = self.episode_format.clone();
+        self.end_session_with_episode_as(session_id, summary, &format)
+    }
+
+    /// End a session with an episode whose content is written in `format`.
+    pub fn end_session_with_episode_as(
+        &mut self,
+        session_id: u32,
+        summary: &str,
+        format: &EpisodeFormat,
+    ) -> McpResult<u64> {
+        let links = self.episode_links;
+        let content = format.render(&self.graph, session_id, summary, links);
         let episode_id = self
             .write_engine
-            .compress_session(&mut self.graph, session_id, summary)
+            .compress_session_with_links(
+                &mut self.graph,
+                session_id,
+                &content,
+                links.links_members(),
+            )
             .map_err(|e| McpError::AgenticMemory(format!("Failed to compress session: {e}")))?;
 
+        if session_id == self.current_session {
+            self.session_active = false;
+        }
         self.dirty = true;
         self.last_activity = Instant::now();
         self.record_mutation();
         self.save()?;
 
-        tracing::info!("Ended session {session_id}, created episode node {episode_id}");
+        tracing::info!(
+            session_id,
+            node_id = episode_id,
+            "Ended session {session_id}, created episode node {episode_id}"
+        );
 
         // Write auto-context files for next session bootstrap.
+        if self.ephemeral {
+            return Ok(episode_id);
+        }
         if let Err(e) = self.write_context_files(summary) {
             tracing::warn!("Failed to write context files: {e}");
         }
@@ -569,6 +1059,7 @@ impl SessionManager {
                     created_after: None,
                     created_before: None,
                     min_decay_score: None,
+                    max_decay_score: None,
                     max_results: 5,
                     sort_by: PatternSort::MostRecent,
                 },
@@ -587,6 +1078,7 @@ impl SessionManager {
                     created_after: None,
                     created_before: None,
                     min_decay_score: None,
+                    max_decay_score: None,
                     max_results: 5,
                     sort_by: PatternSort::MostRecent,
                 },
@@ -658,11 +1150,63 @@ impl SessionManager {
     /// 2. Checks if the file was modified externally (by another instance)
     /// 3. If so, re-reads the disk graph and merges our session's new nodes
     /// 4. Writes the merged graph and releases the lock
+    ///
+    /// The outcome is reported to the client as a `notifications/message` when a
+    /// notification channel is attached (failures always, successes only when
+    /// `AMEM_NOTIFY_ON_SAVE` is set).
+    ///
+    /// Ephemeral sessions are never written; saving just clears the dirty flag.
     pub fn save(&mut self) -> McpResult<()> {
         if !self.dirty {
             return Ok(());
         }
+        if self.ephemeral {
+            self.dirty = false;
+            return Ok(());
+        }
 
+        let result = self.write_to_disk();
+        match &result {
+            Ok(()) if self.notify_on_save => self.notify_log(
+                "info",
+                json!({
+                    "event": "memory_saved",
+                    "path": self.file_path.display().to_string(),
+                    "node_count": self.graph.node_count(),
+                }),
+            ),
+            Ok(()) => {}
+            Err(e) => {
+                tracing::error!(
+                    "Failed to save memory file {}: {e}",
+                    self.file_path.display()
+                );
+                self.notify_log(
+                    "error",
+                    json!({
+                        "event": "memory_save_failed",
+                        "path": self.file_path.display().to_string(),
+                        "error": format!("memory could not be saved: {e}"),
+                    }),
+                );
+            }
+        }
+        match &result {
+            Ok(()) => {
+                self.last_error = None;
+                self.last_error_at = None;
+                self.save_failures = 0;
+            }
+            Err(e) => {
+                self.last_error = Some(e.to_string());
+                self.last_error_at = Some(SystemTime::now());
+                self.save_failures = self.save_failures.saturating_add(1);
+            }
+        }
+        result
+    }
+
+    fn write_to_disk(&mut self) -> McpResult<()> {
         let _lock = FileLock::acquire(&self.file_path)?;
 
         // Detect external modifications from concurrent sessions.
@@ -682,6 +1226,14 @@ impl SessionManager {
         writer
             .write_to_file(&self.graph, &self.file_path)
             .map_err(|e| McpError::AgenticMemory(format!("Failed to write memory file: {e}")))?;
+        self.tags.save(&self.file_path)?;
+        self.blobs.save(&self.file_path)?;
+        self.pins.save(&self.file_path)?;
+        SessionState {
+            session_id: self.current_session,
+            active: self.session_active,
+        }
+        .save(&self.file_path)?;
 
         // Update our mtime tracking after successful write.
         self.last_file_mtime = std::fs::metadata(&self.file_path)
@@ -690,16 +1242,213 @@ impl SessionManager {
 
         self.dirty = false;
         self.last_save = Instant::now();
+        self.last_saved_at = Some(SystemTime::now());
         self.save_generation = self.save_generation.saturating_add(1);
-        tracing::debug!("Saved memory file: {}", self.file_path.display());
+        tracing::debug!(
+            session_id = self.current_session,
+            node_count = self.graph.node_count(),
+            "Saved memory file: {}",
+            self.file_path.display()
+        );
+        Ok(())
+    }
+
+    /// Tags of a node (empty if untagged).
+    pub fn node_tags(&self, node_id: u64) -> Tags {
+        self.tags.get(node_id)
+    }
+
+    /// Whether a node carries every key/value pair in `filter`.
+    pub fn node_matches_tags(&self, node_id: u64, filter: &HashMap<String, String>) -> bool {
+        self.tags.matches(node_id, filter)
+    }
+
+    /// Add/overwrite `set` and delete `remove` tags on a node; returns its tag set.
+    pub fn tag_node(
+        &mut self,
+        node_id: u64,
+        set: HashMap<String, String>,
+        remove: &[String],
+    ) -> McpResult<Tags> {
+        if self.graph.get_node(node_id).is_none() {
+            return Err(McpError::NodeNotFound(node_id));
+        }
+        let tags = self.tags.update(node_id, set, remove);
+        self.dirty = true;
+        self.last_activity = Instant::now();
+        self.record_mutation();
+        self.maybe_auto_save()?;
+        Ok(tags)
+    }
+
+    /// Whether a node is pinned, exempting it from forgetting.
+    pub fn is_pinned(&self, node_id: u64) -> bool {
+        self.pins.contains(node_id)
+    }
+
+    /// IDs of all pinned nodes, ascending.
+    pub fn pinned_nodes(&self) -> Vec<u64> {
+        self.pins.ids().collect()
+    }
+
+    /// Pin or unpin a node. Returns false if it already had that state.
+    pub fn set_pinned(&mut self, node_id: u64, pinned: bool) -> McpResult<bool> {
+        if self.graph.get_node(node_id).is_none() {
+            return Err(McpError::NodeNotFound(node_id));
+        }
+        if !self.pins.set(node_id, pinned) {
+            return Ok(false);
+        }
+        self.dirty = true;
+        self.last_activity = Instant::now();
+        self.record_mutation();
+        self.maybe_auto_save()?;
+        Ok(true)
+    }
+
+    /// Reject removing pinned nodes; they must be unpinned first.
+    fn check_unpinned(&self, node_ids: &[u64]) -> McpResult<()> {
+        match node_ids.iter().find(|id| self.pins.contains(**id)) {
+            Some(id) => Err(McpError::InvalidParams(format!(
+                "Node {id} is pinned; unpin it with memory_unpin before removing it"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Binary attachment of a node, if any.
+    pub fn node_blob(&self, node_id: u64) -> Option<&Blob> {
+        self.blobs.get(node_id)
+    }
+
+    /// Maximum decoded size in bytes of a single attachment.
+    pub fn max_blob_bytes(&self) -> usize {
+        self.max_blob_bytes
+    }
+
+    /// Override the attachment size limit.
+    pub fn set_max_blob_bytes(&mut self, max_bytes: usize) {
+        self.max_blob_bytes = max_bytes;
+    }
+
+    /// Reject an attachment over the configured size limit.
+    pub fn check_blob_size(&self, len: usize) -> McpResult<()> {
+        if len > self.max_blob_bytes {
+            return Err(McpError::InvalidParams(format!(
+                "blob is {len} bytes, exceeding the {} byte limit",
+                self.max_blob_bytes
+            )));
+        }
         Ok(())
     }
 
+    /// Attach a binary payload to a node, replacing any previous one.
+    pub fn attach_blob(
+        &mut self,
+        node_id: u64,
+        bytes: &[u8],
+        mime_type: Option<String>,
+    ) -> McpResult<&Blob> {
+        if self.graph.get_node(node_id).is_none() {
+            return Err(McpError::NodeNotFound(node_id));
+        }
+        self.check_blob_size(bytes.len())?;
+        self.blobs.insert(node_id, Blob::new(bytes, mime_type));
+        self.dirty = true;
+        self.last_activity = Instant::now();
+        self.record_mutation();
+        self.maybe_auto_save()?;
+        self.blobs
+            .get(node_id)
+            .ok_or(McpError::NodeNotFound(node_id))
+    }
+
+    /// Attach the channel used to push log notifications to the client.
+    pub fn set_notification_sender(&mut self, tx: mpsc::Sender<JsonRpcNotification>) {
+        self.notification_tx = Some(tx);
+    }
+
+    /// Set (or clear) the client used for `sampling/createMessage` requests.
+    pub fn set_sampling_client(&mut self, client: Option<Arc<ClientRequester>>) {
+        self.sampling_client = client;
+    }
+
+    /// The sampling-capable client, if the connected client advertised sampling.
+    pub fn sampling_client(&self) -> Option<Arc<ClientRequester>> {
+        self.sampling_client.clone()
+    }
+
+    /// Send a `notifications/message` to the client, if one is attached.
+    /// Never blocks: a full or closed channel drops the notification.
+    pub fn notify_log(&self, level: &str, data: Value) {
+        let Some(tx) = &self.notification_tx else {
+            return;
+        };
+        let notification = JsonRpcNotification::new(
+            "notifications/message".to_string(),
+            Some(json!({
+                "level": level,
+                "logger": "agentic-memory",
+                "data": data,
+            })),
+        );
+        if tx.try_send(notification).is_err() {
+            tracing::debug!("Dropped {level} notification: client channel unavailable");
+        }
+    }
+
+    /// Report progress of the request being handled, if the client sent a
+    /// `progressToken` with it. Never blocks, like [`Self::notify_log`].
+    pub fn notify_progress(&self, progress: f64, total: Option<f64>) {
+        self.progress_reporter()(progress, total);
+    }
+
+    /// [`Self::notify_progress`] detached from the session, for loops that
+    /// hold a mutable borrow of the graph while they report.
+    pub fn progress_reporter(&self) -> impl Fn(f64, Option<f64>) {
+        let tx = self.notification_tx.clone();
+        move |progress, total| {
+            let (Some(tx), Some(notification)) = (&tx, progress_notification(progress, total))
+            else {
+                return;
+            };
+            if tx.try_send(notification).is_err() {
+                tracing::debug!("Dropped progress notification: client channel unavailable");
+            }
+        }
+    }
+
+    /// Whether a session or event type has appeared or disappeared since the
+    /// last [`Self::notify_resource_list_changes`]. Needs only a read lock.
+    pub fn resource_list_changed(&self) -> bool {
+        ResourceShape::of(&self.graph) != self.resource_shape
+    }
+
+    /// Send `notifications/resources/list_changed` if a session or event type
+    /// has appeared or disappeared since the last check. Returns whether it
+    /// changed.
+    pub fn notify_resource_list_changes(&mut self) -> bool {
+        let shape = ResourceShape::of(&self.graph);
+        if shape == self.resource_shape {
+            return false;
+        }
+        self.resource_shape = shape;
+        if let Some(tx) = &self.notification_tx {
+            let notification =
+                JsonRpcNotification::new("notifications/resources/list_changed".to_string(), None);
+            if tx.try_send(notification).is_err() {
+                tracing::debug!("Dropped list_changed notification: client channel unavailable");
+            }
+        }
+        true
+    }
+
     /// Merge our session's nodes/edges with the latest disk state.
     ///
     /// This handles the case where another MCP instance wrote to the same file
     /// since we last read it. We re-read the disk, then re-add our session's
-    /// nodes on top of the latest state.
+    /// nodes on top of the latest state. The undo log is cleared, since its
+    /// operations refer to node IDs and state from before the merge.
     fn merge_with_disk(&mut self) -> McpResult<()> {
         let disk_graph = AmemReader::read_from_file(&self.file_path)
             .map_err(|e| McpError::AgenticMemory(format!("Failed to re-read for merge: {e}")))?;
@@ -725,6 +1474,8 @@ impl SessionManager {
 
         // Replace our graph with the latest disk state.
         self.graph = disk_graph;
+        self.graph_version += 1;
+        self.apply_text_index_policy();
 
         // Re-add our session's nodes with fresh IDs from the merged graph.
         let mut id_map: HashMap<u64, u64> = HashMap::new();
@@ -742,6 +1493,13 @@ impl SessionManager {
             }
         }
 
+        let disk_tags = TagStore::load(&self.file_path).unwrap_or_default();
+        self.tags = self.tags.merged_onto(disk_tags, &id_map);
+        let disk_blobs = BlobStore::load(&self.file_path).unwrap_or_default();
+        self.blobs = self.blobs.merged_onto(disk_blobs, &id_map);
+        let disk_pins = PinStore::load(&self.file_path).unwrap_or_default();
+        self.pins = self.pins.merged_onto(disk_pins, &id_map);
+
         // Re-add our session's edges with remapped IDs.
         for edge in &our_edges {
             let source = id_map
@@ -757,6 +1515,7 @@ impl SessionManager {
                 tracing::warn!("Merge edge re-add skipped: {e}");
             }
         }
+        self.undo_log.clear();
 
         tracing::info!(
             "Merged {} nodes and {} edges from session {} into disk state",
@@ -769,6 +1528,9 @@ impl SessionManager {
 
     /// Check if auto-save is needed and save if so.
     pub fn maybe_auto_save(&mut self) -> McpResult<()> {
+        if self.ephemeral {
+            return Ok(());
+        }
         if self.dirty && self.last_save.elapsed() >= self.auto_save_interval {
             self.save()?;
         }
@@ -806,7 +1568,7 @@ impl SessionManager {
             return Ok(());
         }
 
-        let now = agentic_memory::now_micros();
+        let now = self.now_micros();
         let decay_report = self
             .write_engine
             .run_decay(&mut self.graph, now)
@@ -815,6 +1577,7 @@ impl SessionManager {
 
         if decay_report.nodes_decayed > 0 || archived_sessions > 0 {
             self.dirty = true;
+            self.graph_version += 1;
             self.save()?;
         }
 
@@ -839,13 +1602,28 @@ impl SessionManager {
         if self.save_generation <= self.last_backup_generation {
             return Ok(());
         }
-        if !self.file_path.exists() {
+        if self.ephemeral || !self.file_path.exists() {
             return Ok(());
         }
 
         std::fs::create_dir_all(&self.backups_dir).map_err(McpError::Io)?;
         let backup_path = self.next_backup_path();
         std::fs::copy(&self.file_path, &backup_path).map_err(McpError::Io)?;
+        let tags_path = TagStore::sidecar_path(&self.file_path);
+        if tags_path.exists() {
+            std::fs::copy(&tags_path, TagStore::sidecar_path(&backup_path))
+                .map_err(McpError::Io)?;
+        }
+        let blobs_path = BlobStore::sidecar_path(&self.file_path);
+        if blobs_path.exists() {
+            std::fs::copy(&blobs_path, BlobStore::sidecar_path(&backup_path))
+                .map_err(McpError::Io)?;
+        }
+        let pins_path = PinStore::sidecar_path(&self.file_path);
+        if pins_path.exists() {
+            std::fs::copy(&pins_path, PinStore::sidecar_path(&backup_path))
+                .map_err(McpError::Io)?;
+        }
         self.last_backup_generation = self.save_generation;
         self.last_backup = Instant::now();
         self.prune_old_backups()?;
@@ -860,11 +1638,127 @@ impl SessionManager {
         self.record_mutation();
     }
 
+    /// Current graph version. Every mutation changes it; compare it with
+    /// [`Self::check_version`] to detect concurrent writers.
+    pub fn graph_version(&self) -> u64 {
+        self.graph_version
+    }
+
+    /// Fail with a version conflict if `expected` is set and the graph has
+    /// changed since that version was read.
+    pub fn check_version(&self, expected: Option<u64>) -> McpResult<()> {
+        match expected {
+            Some(expected) if expected != self.graph_version => Err(McpError::VersionConflict {
+                expected,
+                actual: self.graph_version,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Cache key for a read-only tool call against the current graph, or
+    /// `None` when the query cache is disabled.
+    ///
+    /// The key includes the graph version and the session scope, so a
+    /// result is never served after the graph or the scope changed.
+    pub fn query_cache_key(&self, tool: &str, args: &Value) -> Option<String> {
+        self.query_cache.as_ref()?;
+        Some(format!(
+            "{tool}\u{0}{}\u{0}{}\u{0}{}\u{0}{args}",
+            self.graph_version, self.current_session, self.scope_to_current_session
+        ))
+    }
+
+    /// Look up a cached tool result.
+    pub fn cached_query_result(&mut self, key: &str) -> Option<Value> {
+        self.query_cache.as_mut()?.get(&key.to_string())
+    }
+
+    /// Store a tool result under a key from [`Self::query_cache_key`].
+    pub fn cache_query_result(&mut self, key: String, result: Value) {
+        if let Some(cache) = self.query_cache.as_mut() {
+            cache.insert(key, result);
+        }
+    }
+
+    /// Resize the query cache, dropping its entries; `0` disables it.
+    pub fn set_query_cache_size(&mut self, size: usize) {
+        self.query_cache = new_query_cache(size);
+    }
+
+    /// Query cache entry count, hits, and misses; `None` when disabled.
+    pub fn query_cache_stats(&self) -> Option<Value> {
+        let cache = self.query_cache.as_ref()?;
+        Some(json!({
+            "entries": cache.len(),
+            "hits": cache.metrics().hits(),
+            "misses": cache.metrics().misses(),
+        }))
+    }
+
+    /// Counts for `memory_stats` and `amem://graph/stats`, recomputed only
+    /// after the graph version changed.
+    pub fn graph_stats(&self) -> GraphStats {
+        self.stats_cache
+            .get_or_compute(self.graph_version, &self.graph)
+    }
+
+    /// Whether the graph has changes not yet written to disk.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Wall-clock time of the last successful save, or `None` if this
+    /// process created the file and has not saved it yet.
+    pub fn last_save(&self) -> Option<SystemTime> {
+        self.last_saved_at
+    }
+
+    /// Error from the most recent failed save, if the last save did not succeed.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// When the most recent failed save happened, if the last save did not succeed.
+    pub fn last_error_at(&self) -> Option<SystemTime> {
+        self.last_error_at
+    }
+
+    /// Saves that have failed in a row; 0 once a save succeeds.
+    pub fn consecutive_save_failures(&self) -> u32 {
+        self.save_failures
+    }
+
+    /// Whether the memory file can actually be written right now.
+    ///
+    /// Opens an existing file for append (without modifying it), or creates
+    /// and removes a probe file next to a file that does not exist yet.
+    ///
+    /// Always true for an ephemeral session, which never touches the disk.
+    pub fn can_write(&self) -> bool {
+        if self.ephemeral {
+            return true;
+        }
+        if self.file_path.exists() {
+            return OpenOptions::new()
+                .append(true)
+                .open(&self.file_path)
+                .is_ok();
+        }
+        self.file_path.parent().is_some_and(dir_writable)
+    }
+
     /// Get the file path.
     pub fn file_path(&self) -> &PathBuf {
         &self.file_path
     }
 
+    /// Whether this session was opened with [`EPHEMERAL_MEMORY_PATH`] and is
+    /// never persisted.
+    pub fn is_ephemeral(&self) -> bool {
+        self.ephemeral
+    }
+
     /// The ID of the most recent node in the temporal chain for this session.
     pub fn last_temporal_node_id(&self) -> Option<u64> {
         self.last_temporal_node_id
@@ -881,10 +1775,56 @@ impl SessionManager {
         self.graph
             .add_edge(edge)
             .map_err(|e| McpError::AgenticMemory(format!("Failed to add temporal edge: {e}")))?;
+        // Linking a node that was just added is undone by removing that node.
+        if !matches!(self.undo_log.last(), Some(UndoOp::Added { node_id }) if *node_id == next_id) {
+            self.undo_log.push(UndoOp::Linked { edge });
+        }
         self.dirty = true;
+        self.record_mutation();
         Ok(())
     }
 
+    /// Add edges from the existing node `source_id`. Edges it already has are
+    /// skipped; each new one is recorded for undo. Returns how many were created.
+    pub fn add_edges(
+        &mut self,
+        source_id: u64,
+        edges: &[(u64, EdgeType, f32)],
+    ) -> McpResult<usize> {
+        if self.graph.get_node(source_id).is_none() {
+            return Err(McpError::NodeNotFound(source_id));
+        }
+        let mut edge_count = 0;
+        for &(target_id, edge_type, weight) in edges {
+            let edge = Edge::new(source_id, target_id, edge_type, weight);
+            match self.graph.add_edge(edge) {
+                Ok(()) => {
+                    self.undo_log.push(UndoOp::Linked { edge });
+                    edge_count += 1;
+                }
+                Err(AmemError::DuplicateEdge { .. }) => {}
+                Err(e) => return Err(McpError::AgenticMemory(format!("Failed to add edge: {e}"))),
+            }
+        }
+        if edge_count > 0 {
+            self.dirty = true;
+            self.last_activity = Instant::now();
+            self.record_mutation();
+            self.maybe_auto_save()?;
+        }
+        Ok(edge_count)
+    }
+
+    /// Current time from the session clock, in Unix epoch microseconds.
+    pub fn now_micros(&self) -> u64 {
+        (self.clock)()
+    }
+
+    /// Replace the timestamp source, e.g. with a fixed or stepping clock in tests.
+    pub fn set_clock(&mut self, clock: impl Fn() -> u64 + Send + Sync + 'static) {
+        self.clock = Arc::new(clock);
+    }
+
     /// Background maintenance loop interval.
     pub fn maintenance_interval(&self) -> Duration {
         self.auto_save_interval
@@ -907,89 +1847,656 @@ impl SessionManager {
         }
     }
 
-    /// Capture a tool call input context into memory based on capture mode.
-    pub fn capture_tool_call(
+    /// Capture a tool call input context into memory based on capture mode.
+    pub fn capture_tool_call(
+        &mut self,
+        tool_name: &str,
+        arguments: Option<&Value>,
+    ) -> McpResult<Option<u64>> {
+        match self.tool_capture_text(tool_name, arguments)? {
+            Some(text) => self.persist_tool_capture(&text),
+            None => Ok(None),
+        }
+    }
+
+    /// The text [`Self::capture_tool_call`] would record for a tool call, or
+    /// `None` when the capture mode skips it. Needs only a read lock.
+    pub fn tool_capture_text(
+        &self,
+        tool_name: &str,
+        arguments: Option<&Value>,
+    ) -> McpResult<Option<String>> {
+        match self.auto_capture_mode {
+            AutoCaptureMode::Safe => extract_safe_tool_capture_text(tool_name, arguments),
+            AutoCaptureMode::Full => extract_full_tool_capture_text(tool_name, arguments),
+            AutoCaptureMode::Off => Ok(None),
+        }
+    }
+
+    /// Record text from [`Self::tool_capture_text`] as an auto-captured
+    /// inference.
+    pub fn persist_tool_capture(&mut self, text: &str) -> McpResult<Option<u64>> {
+        self.persist_auto_capture(EventType::Inference, text, 0.82)
+    }
+
+    /// Add a cognitive event to the graph.
+    pub fn add_event(
+        &mut self,
+        event_type: EventType,
+        content: &str,
+        confidence: f32,
+        edges: Vec<(u64, EdgeType, f32)>,
+    ) -> McpResult<(u64, usize)> {
+        self.add_event_with_vector(event_type, content, confidence, edges, Vec::new())
+    }
+
+    /// Add a cognitive event carrying a caller-supplied embedding.
+    ///
+    /// An empty `feature_vec` stores a zero placeholder; otherwise its length
+    /// must equal the graph dimension.
+    pub fn add_event_with_vector(
+        &mut self,
+        event_type: EventType,
+        content: &str,
+        confidence: f32,
+        edges: Vec<(u64, EdgeType, f32)>,
+        feature_vec: Vec<f32>,
+    ) -> McpResult<(u64, usize)> {
+        self.add_event_at(event_type, content, confidence, edges, feature_vec, None)
+    }
+
+    /// Add a cognitive event stamped `created_at` (Unix epoch microseconds),
+    /// or with the session clock when `None`. Used to backdate imports.
+    pub fn add_event_at(
+        &mut self,
+        event_type: EventType,
+        content: &str,
+        confidence: f32,
+        edges: Vec<(u64, EdgeType, f32)>,
+        feature_vec: Vec<f32>,
+        created_at: Option<u64>,
+    ) -> McpResult<(u64, usize)> {
+        let confidence = self.normalize_confidence(confidence)?;
+        if !feature_vec.is_empty() {
+            self.check_vector_dimension("feature_vec", feature_vec.len())?;
+        }
+        let event = CognitiveEventBuilder::new(event_type, content.to_string())
+            .session_id(self.current_session)
+            .confidence(confidence)
+            .feature_vec(feature_vec)
+            .created_at(created_at.unwrap_or_else(|| self.now_micros()))
+            .build();
+
+        // First, add the node to get its assigned ID
+        let result = self
+            .write_engine
+            .ingest(&mut self.graph, vec![event], vec![])
+            .map_err(|e| McpError::AgenticMemory(format!("Failed to add event: {e}")))?;
+
+        let node_id = result.new_node_ids.first().copied().ok_or_else(|| {
+            McpError::InternalError("No node ID returned from ingest".to_string())
+        })?;
+        self.undo_log.push(UndoOp::Added { node_id });
+
+        // Then add edges with the correct source_id
+        let mut edge_count = 0;
+        for (target_id, edge_type, weight) in &edges {
+            let edge = Edge::new(node_id, *target_id, *edge_type, *weight);
+            match self.graph.add_edge(edge) {
+                Ok(()) => edge_count += 1,
+                // The same edge listed twice is created once.
+                Err(AmemError::DuplicateEdge { .. }) => {}
+                Err(e) => return Err(McpError::AgenticMemory(format!("Failed to add edge: {e}"))),
+            }
+        }
+
+        self.dirty = true;
+        self.last_activity = Instant::now();
+        self.record_mutation();
+        self.maybe_auto_save()?;
+
+        Ok((node_id, edge_count))
+    }
+
+    /// Reject a vector whose length differs from the graph dimension.
+    pub fn check_vector_dimension(&self, field: &str, len: usize) -> McpResult<()> {
+        let dimension = self.graph.dimension();
+        if len != dimension {
+            return Err(McpError::InvalidParams(format!(
+                "{field} has {len} dimensions but this memory graph uses {dimension}; \
+                 re-embed with a {dimension}-dimension model or run memory_reembed"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Maximum size in bytes accepted for a single node's content.
+    pub fn max_content_bytes(&self) -> usize {
+        self.max_content_bytes
+    }
+
+    /// How `session_end` writes episode content.
+    pub fn episode_format(&self) -> &EpisodeFormat {
+        &self.episode_format
+    }
+
+    /// Override the episode format read from `AMEM_EPISODE_FORMAT`.
+    pub fn set_episode_format(&mut self, format: EpisodeFormat) {
+        self.episode_format = format;
+    }
+
+    /// Whether episode members get `part_of` edges or are listed in content.
+    pub fn episode_links(&self) -> EpisodeLinks {
+        self.episode_links
+    }
+
+    /// Override the link mode read from `AMEM_EPISODE_LINKS`.
+    pub fn set_episode_links(&mut self, links: EpisodeLinks) {
+        self.episode_links = links;
+    }
+
+    /// Fallbacks for arguments tool calls omit.
+    pub fn tool_defaults(&self) -> &ToolDefaults {
+        &self.tool_defaults
+    }
+
+    /// Replace the fallbacks for omitted tool arguments.
+    pub fn set_tool_defaults(&mut self, defaults: ToolDefaults) {
+        self.tool_defaults = defaults;
+    }
+
+    /// Language the prompt templates are expanded in.
+    pub fn prompt_locale(&self) -> PromptLocale {
+        self.prompt_locale
+    }
+
+    /// Override the prompt locale read from `AMEM_PROMPT_LOCALE`.
+    pub fn set_prompt_locale(&mut self, locale: PromptLocale) {
+        self.prompt_locale = locale;
+    }
+
+    /// Whether the content term index is enabled.
+    pub fn text_index_enabled(&self) -> bool {
+        self.text_index_enabled
+    }
+
+    /// Enable or disable the content term index. Disabling frees it.
+    pub fn set_text_index_enabled(&mut self, enabled: bool) {
+        self.text_index_enabled = enabled;
+        self.apply_text_index_policy();
+    }
+
+    /// Build the content term index if it is enabled but not built yet, and
+    /// return how long that took.
+    ///
+    /// Call before BM25 searches; the graph keeps the index current on node
+    /// adds and removals once it exists.
+    pub fn ensure_text_index(&mut self) -> Option<Duration> {
+        if !self.text_index_enabled || self.graph.term_index().is_some() {
+            return None;
+        }
+        let started = Instant::now();
+        self.graph.build_text_index();
+        let elapsed = started.elapsed();
+        tracing::debug!(
+            "Built text index over {} nodes in {:?}",
+            self.graph.node_count(),
+            elapsed
+        );
+        Some(elapsed)
+    }
+
+    /// Build the text index of `session` under a brief write lock if it is
+    /// missing, so BM25 searches can then run under a shared read lock
+    /// alongside other readers.
+    pub async fn prepare_text_index(session: &Arc<RwLock<SessionManager>>) {
+        let missing = {
+            let guard = session.read().await;
+            guard.text_index_enabled && guard.graph.term_index().is_none()
+        };
+        if missing {
+            session.write().await.ensure_text_index();
+        }
+    }
+
+    /// Build the text index now rather than on the first keyword search,
+    /// trading startup time for a fast first query.
+    ///
+    /// Like [`Self::ensure_text_index`], but warns when the index is disabled
+    /// and logs the build time at `info` level.
+    pub fn prebuild_text_index(&mut self) -> Option<Duration> {
+        if !self.text_index_enabled {
+            tracing::warn!(
+                "Index build on open skipped for {}: the text index is disabled \
+                 (set AMEM_TEXT_INDEX=true)",
+                self.file_path.display()
+            );
+            return None;
+        }
+        let elapsed = self.ensure_text_index()?;
+        tracing::info!(
+            "Built text index over {} nodes in {:?} for {}",
+            self.graph.node_count(),
+            elapsed,
+            self.file_path.display()
+        );
+        Some(elapsed)
+    }
+
+    /// Drop the term index read from the file when indexing is disabled, or
+    /// when it does not cover every node, so the next search rebuilds it.
+    fn apply_text_index_policy(&mut self) {
+        let stale = self
+            .graph
+            .term_index()
+            .is_some_and(|index| index.doc_count() != self.graph.node_count() as u64);
+        if !self.text_index_enabled || stale {
+            self.graph.clear_text_index();
+        }
+    }
+
+    /// Override the content length limit and policy (`truncate` or `reject`).
+    pub fn set_content_limit(&mut self, max_bytes: usize, truncate: bool) {
+        self.max_content_bytes = max_bytes.clamp(64, agentic_memory::MAX_CONTENT_SIZE);
+        self.content_limit_policy = if truncate {
+            ContentLimitPolicy::Truncate
+        } else {
+            ContentLimitPolicy::Reject
+        };
+    }
+
+    /// Apply the configured content length limit.
+    ///
+    /// Returns the (possibly truncated) content and whether truncation
+    /// happened, or `InvalidParams` when the policy is `reject`.
+    pub fn enforce_content_limit(&self, content: &str) -> McpResult<(String, bool)> {
+        if content.len() <= self.max_content_bytes {
+            return Ok((content.to_string(), false));
+        }
+        match self.content_limit_policy {
+            ContentLimitPolicy::Reject => Err(McpError::InvalidParams(format!(
+                "content is {} bytes, exceeding the {} byte limit (policy={})",
+                content.len(),
+                self.max_content_bytes,
+                self.content_limit_policy.as_str()
+            ))),
+            ContentLimitPolicy::Truncate => {
+                let budget = self
+                    .max_content_bytes
+                    .saturating_sub(TRUNCATION_SUFFIX.len());
+                let mut cut = budget.min(content.len());
+                while !content.is_char_boundary(cut) {
+                    cut -= 1;
+                }
+                Ok((format!("{}{TRUNCATION_SUFFIX}", &content[..cut]), true))
+            }
+        }
+    }
+
+    /// Override the out-of-range confidence policy (`clamp` or `reject`).
+    pub fn set_confidence_policy(&mut self, clamp: bool) {
+        self.confidence_policy = if clamp {
+            ConfidencePolicy::Clamp
+        } else {
+            ConfidencePolicy::Reject
+        };
+    }
+
+    /// Validate a confidence value against `[0.0, 1.0]`.
+    ///
+    /// NaN is always rejected. Other out-of-range values are rejected with
+    /// `InvalidParams` or clamped, depending on the configured policy.
+    pub fn normalize_confidence(&self, confidence: f32) -> McpResult<f32> {
+        if (0.0..=1.0).contains(&confidence) {
+            return Ok(confidence);
+        }
+        if confidence.is_nan() || self.confidence_policy == ConfidencePolicy::Reject {
+            return Err(McpError::InvalidParams(format!(
+                "confidence must be between 0.0 and 1.0, got {confidence} (policy={})",
+                self.confidence_policy.as_str()
+            )));
+        }
+        let clamped = confidence.clamp(0.0, 1.0);
+        tracing::warn!("Clamped out-of-range confidence {confidence} to {clamped}");
+        Ok(clamped)
+    }
+
+    /// Look up the node created by an earlier write with the same idempotency key.
+    pub fn idempotent_node(&mut self, key: &str) -> Option<u64> {
+        self.idempotency_keys
+            .retain(|_, (_, seen)| seen.elapsed() < IDEMPOTENCY_KEY_TTL);
+        self.idempotency_keys.get(key).map(|(node_id, _)| *node_id)
+    }
+
+    /// Remember the node created for an idempotency key.
+    pub fn record_idempotency_key(&mut self, key: &str, node_id: u64) {
+        if self.idempotency_keys.len() >= IDEMPOTENCY_KEY_CAPACITY {
+            let oldest = self
+                .idempotency_keys
+                .iter()
+                .min_by_key(|(_, (_, seen))| *seen)
+                .map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                self.idempotency_keys.remove(&oldest);
+            }
+        }
+        self.idempotency_keys
+            .insert(key.to_string(), (node_id, Instant::now()));
+    }
+
+    /// Find an existing node with identical `content` and `event_type`.
+    pub fn find_duplicate(&self, event_type: EventType, content: &str) -> Option<u64> {
+        self.graph
+            .type_index()
+            .get(event_type)
+            .iter()
+            .copied()
+            .find(|id| {
+                self.graph
+                    .get_node(*id)
+                    .is_some_and(|node| node.content == content)
+            })
+    }
+
+    /// Record an access on an existing node (bumps `access_count`).
+    pub fn touch_node(&mut self, node_id: u64) -> McpResult<()> {
+        let node = self
+            .graph
+            .get_node_mut(node_id)
+            .ok_or(McpError::NodeNotFound(node_id))?;
+        node.access_count = node.access_count.saturating_add(1);
+        node.last_accessed = (self.clock)();
+
+        self.dirty = true;
+        self.last_activity = Instant::now();
+        self.record_mutation();
+        self.maybe_auto_save()?;
+        Ok(())
+    }
+
+    /// Change the event type of the given nodes, keeping the type index in sync.
+    ///
+    /// Returns the IDs whose type actually changed.
+    pub fn retype_nodes(&mut self, node_ids: &[u64], event_type: EventType) -> McpResult<Vec<u64>> {
+        let mut changed = Vec::new();
+        let mut previous_types = Vec::new();
+        let graph = self.graph_mut();
+        for &id in node_ids {
+            let previous = graph
+                .retype_node(id, event_type)
+                .map_err(|e| McpError::AgenticMemory(format!("Failed to retype node {id}: {e}")))?;
+            if previous != event_type {
+                changed.push(id);
+                previous_types.push((id, previous));
+            }
+        }
+        if !previous_types.is_empty() {
+            self.undo_log.push(UndoOp::Retyped {
+                previous: previous_types,
+            });
+        }
+        self.maybe_auto_save()?;
+        Ok(changed)
+    }
+
+    /// Set the weight of an existing edge, clamped to `[0.0, 1.0]`.
+    ///
+    /// Returns the previous and the new weight.
+    pub fn reweight_edge(
         &mut self,
-        tool_name: &str,
-        arguments: Option<&Value>,
-    ) -> McpResult<Option<u64>> {
-        if self.auto_capture_mode == AutoCaptureMode::Off {
-            return Ok(None);
-        }
+        source_id: u64,
+        target_id: u64,
+        edge_type: EdgeType,
+        weight: f32,
+    ) -> McpResult<(f32, f32)> {
+        let previous_weight = self
+            .graph_mut()
+            .set_edge_weight(source_id, target_id, edge_type, weight)
+            .map_err(|e| McpError::InvalidGraphOp(e.to_string()))?;
+        self.undo_log.push(UndoOp::Reweighted {
+            source_id,
+            target_id,
+            edge_type,
+            previous_weight,
+        });
+        self.maybe_auto_save()?;
+        Ok((previous_weight, weight.clamp(0.0, 1.0)))
+    }
 
-        let text = match self.auto_capture_mode {
-            AutoCaptureMode::Safe => extract_safe_tool_capture_text(tool_name, arguments)?,
-            AutoCaptureMode::Full => extract_full_tool_capture_text(tool_name, arguments)?,
-            AutoCaptureMode::Off => None,
-        };
-        match text {
-            Some(v) => self.persist_auto_capture(EventType::Inference, &v, 0.82),
-            None => Ok(None),
+    /// Correct a previous belief.
+    pub fn correct_node(
+        &mut self,
+        old_node_id: u64,
+        new_content: &str,
+        confidence: f32,
+    ) -> McpResult<u64> {
+        let confidence = self.normalize_confidence(confidence)?;
+        let old_confidence = self
+            .graph
+            .get_node(old_node_id)
+            .map(|node| node.confidence)
+            .ok_or(McpError::NodeNotFound(old_node_id))?;
+        let new_id = self
+            .write_engine
+            .correct(
+                &mut self.graph,
+                old_node_id,
+                new_content,
+                self.current_session,
+            )
+            .map_err(|e| McpError::AgenticMemory(format!("Failed to correct node: {e}")))?;
+        if let Some(node) = self.graph.get_node_mut(new_id) {
+            node.confidence = confidence;
         }
+        self.undo_log.push(UndoOp::Corrected {
+            new_id,
+            old_id: old_node_id,
+            old_confidence,
+        });
+
+        self.dirty = true;
+        self.last_activity = Instant::now();
+        self.record_mutation();
+        self.maybe_auto_save()?;
+
+        Ok(new_id)
     }
 
-    /// Add a cognitive event to the graph.
-    pub fn add_event(
+    /// Collapse the supersedes chain containing `node_id` onto its latest version.
+    ///
+    /// Edges of the older versions are moved to the latest one (skipping
+    /// duplicates and self-edges). With `delete_stale` the older versions are
+    /// removed; if any of them is pinned the whole compaction fails with
+    /// `InvalidParams` and the graph is left unchanged. Otherwise they stay
+    /// archived, each superseded directly by the latest version so resolving
+    /// any of them is a single hop.
+    pub fn compact_chain(
         &mut self,
-        event_type: EventType,
-        content: &str,
-        confidence: f32,
-        edges: Vec<(u64, EdgeType, f32)>,
-    ) -> McpResult<(u64, usize)> {
-        let event = CognitiveEventBuilder::new(event_type, content.to_string())
-            .session_id(self.current_session)
-            .confidence(confidence)
-            .build();
+        node_id: u64,
+        delete_stale: bool,
+    ) -> McpResult<ChainCompaction> {
+        let latest_id = self
+            .query_engine
+            .resolve(&self.graph, node_id)
+            .map_err(|_| McpError::NodeNotFound(node_id))?
+            .id;
+
+        // Every older version reachable from the latest through supersedes edges.
+        let mut chain = HashSet::from([latest_id]);
+        let mut collapsed = Vec::new();
+        let mut frontier = vec![latest_id];
+        while let Some(id) = frontier.pop() {
+            for edge in self.graph.edges_from(id) {
+                if edge.edge_type == EdgeType::Supersedes && chain.insert(edge.target_id) {
+                    collapsed.push(edge.target_id);
+                    frontier.push(edge.target_id);
+                }
+            }
+        }
+        collapsed.sort_unstable();
+        if collapsed.is_empty() {
+            return Ok(ChainCompaction {
+                latest_id,
+                collapsed,
+                rewired_edges: 0,
+            });
+        }
 
-        // First, add the node to get its assigned ID
-        let result = self
-            .write_engine
-            .ingest(&mut self.graph, vec![event], vec![])
-            .map_err(|e| McpError::AgenticMemory(format!("Failed to add event: {e}")))?;
+        let is_stale = |id: u64| id != latest_id && chain.contains(&id);
+        let removed_edges: Vec<Edge> = self
+            .graph
+            .edges()
+            .iter()
+            .filter(|e| is_stale(e.source_id) || is_stale(e.target_id))
+            .copied()
+            .collect();
+        let onto_latest = |id: u64| if is_stale(id) { latest_id } else { id };
+        let replacements: Vec<Edge> = removed_edges
+            .iter()
+            .filter_map(|e| {
+                let within_chain = chain.contains(&e.source_id) && chain.contains(&e.target_id);
+                let (source_id, target_id) = if e.edge_type == EdgeType::Supersedes && within_chain
+                {
+                    if delete_stale {
+                        return None;
+                    }
+                    (latest_id, e.target_id)
+                } else {
+                    (onto_latest(e.source_id), onto_latest(e.target_id))
+                };
+                (source_id != target_id).then_some(Edge {
+                    source_id,
+                    target_id,
+                    ..*e
+                })
+            })
+            .collect();
 
-        let node_id = result.new_node_ids.first().copied().ok_or_else(|| {
-            McpError::InternalError("No node ID returned from ingest".to_string())
-        })?;
+        let mut deleted = Vec::new();
+        if delete_stale {
+            self.check_unpinned(&collapsed)?;
+            for &id in &collapsed {
+                deleted.push(self.graph.remove_node(id).map_err(|e| {
+                    McpError::AgenticMemory(format!("Failed to remove node {id}: {e}"))
+                })?);
+            }
+            if self.last_temporal_node_id.is_some_and(is_stale) {
+                self.last_temporal_node_id = None;
+            }
+        } else {
+            let mut seen = HashSet::new();
+            for e in &removed_edges {
+                if seen.insert((e.source_id, e.target_id, e.edge_type)) {
+                    self.graph
+                        .remove_edge(e.source_id, e.target_id, e.edge_type)
+                        .map_err(|err| {
+                            McpError::AgenticMemory(format!("Failed to remove edge: {err}"))
+                        })?;
+                }
+            }
+        }
 
-        // Then add edges with the correct source_id
-        let mut edge_count = 0;
-        for (target_id, edge_type, weight) in &edges {
-            let edge = Edge::new(node_id, *target_id, *edge_type, *weight);
+        let mut added_edges = Vec::new();
+        for edge in replacements {
+            let exists = self
+                .graph
+                .edges_from(edge.source_id)
+                .iter()
+                .any(|e| e.target_id == edge.target_id && e.edge_type == edge.edge_type);
+            if exists {
+                continue;
+            }
             self.graph
                 .add_edge(edge)
-                .map_err(|e| McpError::AgenticMemory(format!("Failed to add edge: {e}")))?;
-            edge_count += 1;
+                .map_err(|e| McpError::AgenticMemory(format!("Failed to rewire edge: {e}")))?;
+            added_edges.push(edge);
         }
+        let rewired_edges = added_edges
+            .iter()
+            .filter(|e| e.edge_type != EdgeType::Supersedes)
+            .count();
 
+        self.undo_log.push(UndoOp::ChainCompacted {
+            deleted,
+            removed_edges,
+            added_edges,
+        });
         self.dirty = true;
         self.last_activity = Instant::now();
         self.record_mutation();
         self.maybe_auto_save()?;
 
-        Ok((node_id, edge_count))
+        Ok(ChainCompaction {
+            latest_id,
+            collapsed,
+            rewired_edges,
+        })
     }
 
-    /// Correct a previous belief.
-    pub fn correct_node(&mut self, old_node_id: u64, new_content: &str) -> McpResult<u64> {
-        let new_id = self
-            .write_engine
-            .correct(
-                &mut self.graph,
-                old_node_id,
-                new_content,
-                self.current_session,
-            )
-            .map_err(|e| McpError::AgenticMemory(format!("Failed to correct node: {e}")))?;
+    /// Remove a node and its edges, keeping enough to undo the removal.
+    /// Pinned nodes are refused.
+    pub fn delete_node(&mut self, node_id: u64) -> McpResult<CognitiveEvent> {
+        self.check_unpinned(&[node_id])?;
+        let mut edges: Vec<Edge> = self.graph.edges_from(node_id).to_vec();
+        edges.extend(self.graph.edges_to(node_id).into_iter().copied());
+        let removed = self
+            .graph
+            .remove_node(node_id)
+            .map_err(|_| McpError::NodeNotFound(node_id))?;
+        if self.last_temporal_node_id == Some(node_id) {
+            self.last_temporal_node_id = None;
+        }
+        self.undo_log.push(UndoOp::Deleted {
+            node: Box::new(removed.clone()),
+            edges,
+        });
 
         self.dirty = true;
         self.last_activity = Instant::now();
         self.record_mutation();
         self.maybe_auto_save()?;
 
-        Ok(new_id)
+        Ok(removed)
+    }
+
+    /// Revert up to `steps` of the most recent mutations, newest first.
+    ///
+    /// Returns a summary of each reverted operation; fewer than `steps` are
+    /// returned when the log runs out.
+    pub fn undo(&mut self, steps: usize) -> McpResult<Vec<Value>> {
+        let mut undone = Vec::new();
+        for _ in 0..steps {
+            let Some(op) = self.undo_log.pop() else {
+                break;
+            };
+            op.revert(&mut self.graph)?;
+            self.dirty = true;
+            if let Some(last) = self.last_temporal_node_id {
+                if self.graph.get_node(last).is_none() {
+                    self.last_temporal_node_id = None;
+                }
+            }
+            undone.push(op.summary());
+        }
+
+        if !undone.is_empty() {
+            self.last_activity = Instant::now();
+            self.record_mutation();
+            self.maybe_auto_save()?;
+        }
+        Ok(undone)
+    }
+
+    /// Number of mutations that `memory_undo` can still revert.
+    pub fn undo_available(&self) -> usize {
+        self.undo_log.len()
     }
 
     fn record_mutation(&mut self) {
+        self.graph_version += 1;
+        if let Some(cache) = self.query_cache.as_mut() {
+            cache.clear();
+        }
         if self.mutation_window_started.elapsed() >= Duration::from_secs(60) {
             self.mutation_window_started = Instant::now();
             self.mutation_window_count = 0;
@@ -1010,6 +2517,9 @@ impl SessionManager {
     }
 
     fn emit_health_ledger(&mut self, maintenance_mode: &str) -> McpResult<()> {
+        if self.ephemeral {
+            return Ok(());
+        }
         if self.last_health_ledger_emit.elapsed() < self.health_ledger_emit_interval {
             return Ok(());
         }
@@ -1083,6 +2593,9 @@ impl SessionManager {
 
 impl Drop for SessionManager {
     fn drop(&mut self) {
+        if self.ephemeral {
+            return;
+        }
         if self.dirty {
             if let Err(e) = self.save() {
                 tracing::error!("Failed to save on drop: {e}");
@@ -1148,8 +2661,15 @@ impl SessionManager {
                 "Auto-archive session {}: {} events ({} hot / {} warm / {} cold)",
                 session_id, event_nodes, hot, warm, cold
             );
+            let links = self.episode_links;
+            let content = EpisodeFormat::Text.render(&self.graph, session_id, &summary, links);
             self.write_engine
-                .compress_session(&mut self.graph, session_id, &summary)
+                .compress_session_with_links(
+                    &mut self.graph,
+                    session_id,
+                    &content,
+                    links.links_members(),
+                )
                 .map_err(|e| {
                     McpError::AgenticMemory(format!(
                         "Auto-archive failed for session {session_id}: {e}"
@@ -1208,6 +2728,7 @@ impl SessionManager {
             }
             rollup_count += archived;
             self.dirty = true;
+            self.graph_version += 1;
             self.save()?;
             let new_size = self.current_file_size_bytes();
             if new_size <= target_bytes {
@@ -1379,6 +2900,8 @@ impl SessionManager {
         });
         let to_remove = entries.len().saturating_sub(self.backup_retention);
         for entry in entries.into_iter().take(to_remove) {
+            let _ = std::fs::remove_file(TagStore::sidecar_path(&entry.path()));
+            let _ = std::fs::remove_file(PinStore::sidecar_path(&entry.path()));
             let _ = std::fs::remove_file(entry.path());
         }
         Ok(())
@@ -1442,6 +2965,10 @@ fn read_env_f32(name: &str, default_value: f32) -> f32 {
         .unwrap_or(default_value)
 }
 
+fn new_query_cache(size: usize) -> Option<LruCache<String, Value>> {
+    (size > 0).then(|| LruCache::new(size, Duration::MAX))
+}
+
 fn read_env_bool(name: &str, default_value: bool) -> bool {
     std::env::var(name)
         .ok()
@@ -1534,7 +3061,8 @@ fn extract_full_tool_capture_text(
     tool_name: &str,
     arguments: Option<&Value>,
 ) -> McpResult<Option<String>> {
-    if tool_name == "memory_add" {
+    // The content these write is already stored as nodes.
+    if matches!(tool_name, "memory_add" | "memory_import_notes") {
         return Ok(None);
     }
     let args = arguments.unwrap_or(&Value::Null);
@@ -1809,6 +3337,30 @@ impl Drop for FileLock {
     }
 }
 
+/// Whether a file can be created in `dir`, checked with a throwaway probe file.
+pub(crate) fn dir_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".amem-write-probe-{}", std::process::id()));
+    match OpenOptions::new().write(true).create_new(true).open(&probe) {
+        Ok(_) => std::fs::remove_file(&probe).is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Namespaces become file names, so keep them to a portable character set.
+fn validate_namespace(name: &str) -> McpResult<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(McpError::InvalidParams(format!(
+            "Invalid namespace '{name}': use letters, digits, '-' or '_'"
+        )))
+    }
+}
+
 /// Walk up from `start` looking for a directory that contains `.claude/` or `.git/`.
 /// Returns the first such ancestor, or `None` if we reach the filesystem root.
 fn find_project_root(start: &Path) -> Option<PathBuf> {
@@ -1830,6 +3382,272 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn tags_persist_in_sidecar_across_reopen() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let brain = dir.path().join("tags.amem");
+        let path = brain.to_str().expect("test fixture");
+        {
+            let mut manager = SessionManager::open(path).expect("test fixture");
+            let (id, _) = manager
+                .add_event(EventType::Fact, "tagged", 0.9, vec![])
+                .expect("test fixture");
+            let set = HashMap::from([("topic".to_string(), "billing".to_string())]);
+            manager.tag_node(id, set, &[]).expect("test fixture");
+            manager.save().expect("test fixture");
+        }
+        assert!(TagStore::sidecar_path(&brain).exists());
+
+        let manager = SessionManager::open(path).expect("test fixture");
+        assert_eq!(
+            manager.node_tags(0).get("topic").map(String::as_str),
+            Some("billing")
+        );
+    }
+
+    #[test]
+    fn blobs_persist_in_sidecar_across_reopen() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let brain = dir.path().join("blobs.amem");
+        let path = brain.to_str().expect("test fixture");
+        {
+            let mut manager = SessionManager::open(path).expect("test fixture");
+            let (id, _) = manager
+                .add_event(EventType::Fact, "with attachment", 0.9, vec![])
+                .expect("test fixture");
+            manager
+                .attach_blob(id, &[0, 159, 255], Some("image/png".to_string()))
+                .expect("test fixture");
+            manager.set_max_blob_bytes(2);
+            assert!(manager.attach_blob(id, &[1, 2, 3], None).is_err());
+            manager.save().expect("test fixture");
+        }
+        assert!(BlobStore::sidecar_path(&brain).exists());
+
+        let manager = SessionManager::open(path).expect("test fixture");
+        let blob = manager.node_blob(0).expect("test fixture");
+        assert_eq!(blob.mime_type, "image/png");
+        assert_eq!(blob.size, 3);
+        assert_eq!(
+            Blob::decode(&blob.data, "base64").expect("test fixture"),
+            vec![0, 159, 255]
+        );
+    }
+
+    #[test]
+    fn pins_persist_in_sidecar_across_reopen() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let brain = dir.path().join("pins.amem");
+        let path = brain.to_str().expect("test fixture");
+        {
+            let mut manager = SessionManager::open(path).expect("test fixture");
+            let (id, _) = manager
+                .add_event(EventType::Fact, "foundational", 0.9, vec![])
+                .expect("test fixture");
+            assert!(manager.set_pinned(id, true).expect("test fixture"));
+            assert!(manager.set_pinned(42, true).is_err());
+            manager.save().expect("test fixture");
+        }
+        assert!(PinStore::sidecar_path(&brain).exists());
+
+        let mut manager = SessionManager::open(path).expect("test fixture");
+        assert_eq!(manager.pinned_nodes(), vec![0]);
+        assert!(manager.set_pinned(0, false).expect("test fixture"));
+        manager.save().expect("test fixture");
+        assert!(!PinStore::sidecar_path(&brain).exists());
+    }
+
+    #[test]
+    fn tags_survive_drop_without_explicit_save() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let brain = dir.path().join("dropped.amem");
+        let path = brain.to_str().expect("test fixture");
+        let id = {
+            let mut manager = SessionManager::open(path).expect("test fixture");
+            let (id, _) = manager
+                .add_event(EventType::Decision, "tagged then dropped", 0.9, vec![])
+                .expect("test fixture");
+            let set = HashMap::from([("owner".to_string(), "infra".to_string())]);
+            manager.tag_node(id, set, &[]).expect("test fixture");
+            id
+        };
+
+        let manager = SessionManager::open(path).expect("test fixture");
+        assert_eq!(
+            manager.node_tags(id).get("owner").map(String::as_str),
+            Some("infra")
+        );
+        let filter = HashMap::from([("owner".to_string(), "infra".to_string())]);
+        assert!(manager.node_matches_tags(id, &filter));
+    }
+
+    #[test]
+    fn auto_backup_copies_tag_sidecar() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let brain = dir.path().join("backed.amem");
+        let mut manager =
+            SessionManager::open(brain.to_str().expect("test fixture")).expect("test fixture");
+        let (id, _) = manager
+            .add_event(EventType::Fact, "backed up with tags", 0.9, vec![])
+            .expect("test fixture");
+        let set = HashMap::from([("topic".to_string(), "billing".to_string())]);
+        manager.tag_node(id, set, &[]).expect("test fixture");
+        manager.save().expect("test fixture");
+
+        manager.backup_interval = Duration::ZERO;
+        manager.maybe_auto_backup().expect("test fixture");
+
+        let backups: Vec<PathBuf> = std::fs::read_dir(&manager.backups_dir)
+            .expect("test fixture")
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|p| p.to_string_lossy().ends_with(".amem.bak"))
+            .collect();
+        assert_eq!(backups.len(), 1);
+        let restored = TagStore::load(&backups[0]).expect("test fixture");
+        assert_eq!(
+            restored.get(id).get("topic").map(String::as_str),
+            Some("billing")
+        );
+    }
+
+    #[test]
+    fn save_failure_notifies_client() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let sub = dir.path().join("vanishing");
+        let brain = sub.join("brain.amem");
+        let mut manager =
+            SessionManager::open(brain.to_str().expect("test fixture")).expect("test fixture");
+        let (tx, mut rx) = mpsc::channel(4);
+        manager.set_notification_sender(tx);
+
+        manager
+            .add_event(EventType::Fact, "unsaved", 0.9, vec![])
+            .expect("test fixture");
+        std::fs::remove_dir_all(&sub).expect("test fixture");
+        assert!(manager.save().is_err());
+
+        let notification = rx.try_recv().expect("save failure notification");
+        assert_eq!(notification.method, "notifications/message");
+        let params = notification.params.expect("params");
+        assert_eq!(params["level"], "error");
+        assert_eq!(params["data"]["event"], "memory_save_failed");
+        // Keep Drop from retrying against the missing directory noisily.
+        manager.dirty = false;
+    }
+
+    #[test]
+    fn resource_list_changes_on_new_sessions_and_types() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let brain = dir.path().join("shape.amem");
+        let mut manager =
+            SessionManager::open(brain.to_str().expect("test fixture")).expect("test fixture");
+        let (tx, mut rx) = mpsc::channel(16);
+        manager.set_notification_sender(tx);
+        let mut list_changed = move || {
+            std::iter::from_fn(|| rx.try_recv().ok())
+                .filter(|n| n.method == "notifications/resources/list_changed")
+                .count()
+        };
+
+        manager
+            .add_event(EventType::Fact, "first", 0.9, vec![])
+            .expect("test fixture");
+        assert!(manager.notify_resource_list_changes());
+        assert_eq!(list_changed(), 1);
+
+        manager
+            .add_event(EventType::Fact, "same session, same type", 0.9, vec![])
+            .expect("test fixture");
+        assert!(!manager.notify_resource_list_changes());
+        assert_eq!(list_changed(), 0);
+
+        manager
+            .add_event(EventType::Decision, "new type", 0.9, vec![])
+            .expect("test fixture");
+        assert!(manager.notify_resource_list_changes());
+
+        manager.start_session(None).expect("test fixture");
+        manager
+            .add_event(EventType::Fact, "new session", 0.9, vec![])
+            .expect("test fixture");
+        assert!(manager.notify_resource_list_changes());
+        assert_eq!(list_changed(), 2);
+    }
+
+    #[test]
+    fn save_failure_is_recorded_until_a_save_succeeds() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let sub = dir.path().join("vanishing");
+        let brain = sub.join("brain.amem");
+        let mut manager =
+            SessionManager::open(brain.to_str().expect("test fixture")).expect("test fixture");
+        manager
+            .add_event(EventType::Fact, "unsaved", 0.9, vec![])
+            .expect("test fixture");
+        std::fs::remove_dir_all(&sub).expect("test fixture");
+
+        assert!(manager.save().is_err());
+        assert!(manager.save().is_err());
+        assert!(manager.last_error().is_some());
+        assert!(manager.last_error_at().is_some());
+        assert_eq!(manager.consecutive_save_failures(), 2);
+        assert!(manager.is_dirty());
+
+        std::fs::create_dir_all(&sub).expect("test fixture");
+        manager.save().expect("save after recovery");
+        assert!(manager.last_error().is_none());
+        assert!(manager.last_error_at().is_none());
+        assert_eq!(manager.consecutive_save_failures(), 0);
+        assert!(!manager.is_dirty());
+    }
+
+    #[test]
+    fn open_existing_refuses_missing_file() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let brain = dir.path().join("typo").join("brian.amem");
+
+        let err = match SessionManager::open_existing(brain.to_str().expect("test fixture")) {
+            Err(e) => e,
+            Ok(_) => panic!("missing file should not be created"),
+        };
+        assert!(matches!(&err, McpError::Io(e) if e.kind() == std::io::ErrorKind::NotFound));
+        assert!(err.to_string().contains("brian.amem"));
+        assert!(!brain.exists());
+        assert!(!brain.parent().expect("test fixture").exists());
+
+        let mut created =
+            SessionManager::open(brain.to_str().expect("test fixture")).expect("open");
+        created.dirty = true;
+        created.save().expect("save");
+        drop(created);
+        let reopened = SessionManager::open_existing(brain.to_str().expect("test fixture"));
+        assert!(reopened.is_ok());
+    }
+
+    #[test]
+    fn open_rejects_newer_format_without_backup() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let brain = dir.path().join("future.amem");
+        let mut bytes = vec![0u8; 128];
+        bytes[0..4].copy_from_slice(b"AMEM");
+        bytes[4..8].copy_from_slice(&99u32.to_le_bytes());
+        std::fs::write(&brain, &bytes).expect("test fixture");
+
+        let err = match SessionManager::open(brain.to_str().expect("test fixture")) {
+            Err(e) => e,
+            Ok(_) => panic!("newer format should not open"),
+        };
+        assert!(matches!(
+            err,
+            McpError::IncompatibleFormat {
+                found: 99,
+                expected: agentic_memory::FORMAT_VERSION
+            }
+        ));
+        assert!(brain.exists(), "file must not be moved aside as corrupt");
+    }
+
     #[test]
     fn budget_projection_available_with_timeline() {
         let dir = tempfile::tempdir().expect("test fixture");
@@ -2024,4 +3842,103 @@ mod tests {
         });
         assert!(has_edge, "memory_add node should be linked into chain");
     }
+
+    #[test]
+    fn content_limit_truncates_on_char_boundary() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let brain = dir.path().join("limit.amem");
+        let mut manager =
+            SessionManager::open(brain.to_str().expect("test fixture")).expect("test fixture");
+        manager.set_content_limit(64, true);
+
+        let content = "é".repeat(100);
+        let (stored, truncated) = manager
+            .enforce_content_limit(&content)
+            .expect("test fixture");
+        assert!(truncated);
+        assert!(stored.len() <= 64);
+        assert!(stored.ends_with(TRUNCATION_SUFFIX));
+
+        let (short, truncated) = manager
+            .enforce_content_limit("short")
+            .expect("test fixture");
+        assert_eq!(short, "short");
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn content_limit_rejects_by_default_policy() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let brain = dir.path().join("reject.amem");
+        let mut manager =
+            SessionManager::open(brain.to_str().expect("test fixture")).expect("test fixture");
+        manager.set_content_limit(64, false);
+
+        let err = manager
+            .enforce_content_limit(&"x".repeat(65))
+            .expect_err("oversized content should be rejected");
+        assert!(matches!(err, McpError::InvalidParams(_)));
+    }
+
+    #[test]
+    fn confidence_boundaries_and_policy() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let brain = dir.path().join("confidence.amem");
+        let mut manager =
+            SessionManager::open(brain.to_str().expect("test fixture")).expect("test fixture");
+        manager.set_confidence_policy(false);
+
+        assert_eq!(
+            manager.normalize_confidence(0.0).expect("test fixture"),
+            0.0
+        );
+        assert_eq!(
+            manager.normalize_confidence(1.0).expect("test fixture"),
+            1.0
+        );
+        for bad in [1.5, -0.2, f32::NAN] {
+            let err = manager
+                .normalize_confidence(bad)
+                .expect_err("out-of-range confidence should be rejected");
+            assert!(matches!(err, McpError::InvalidParams(_)));
+        }
+        assert!(manager
+            .add_event(EventType::Fact, "too sure", 1.5, vec![])
+            .is_err());
+        assert_eq!(manager.graph().node_count(), 0);
+
+        manager.set_confidence_policy(true);
+        assert_eq!(
+            manager.normalize_confidence(1.5).expect("test fixture"),
+            1.0
+        );
+        assert_eq!(
+            manager.normalize_confidence(-0.2).expect("test fixture"),
+            0.0
+        );
+        assert!(manager.normalize_confidence(f32::NAN).is_err());
+
+        let (id, _) = manager
+            .add_event(EventType::Fact, "too sure", 1.5, vec![])
+            .expect("test fixture");
+        let fixed = manager
+            .correct_node(id, "less sure", -0.2)
+            .expect("test fixture");
+        assert_eq!(
+            manager
+                .graph()
+                .get_node(id)
+                .expect("test fixture")
+                .confidence,
+            0.0
+        );
+        assert_eq!(
+            manager
+                .graph()
+                .get_node(fixed)
+                .expect("test fixture")
+                .confidence,
+            0.0
+        );
+    }
 }
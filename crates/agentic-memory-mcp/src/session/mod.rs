@@ -1,12 +1,23 @@
 //! Session management: graph lifecycle, transactions, and auto-save.
 
 pub mod autosave;
+pub mod blobs;
+pub mod episode;
+pub mod graph_stats;
 pub mod manager;
+pub mod pins;
+pub mod session_state;
+pub mod tags;
 #[cfg(feature = "sse")]
 pub mod tenant;
+pub mod tool_defaults;
 pub mod transaction;
+pub mod undo;
 pub mod workspace;
 
+pub use episode::{EpisodeFormat, EpisodeLinks, EpisodeTemplate};
+pub use graph_stats::GraphStats;
 pub use manager::SessionManager;
+pub use tool_defaults::ToolDefaults;
 pub use transaction::Transaction;
 pub use workspace::WorkspaceManager;
@@ -0,0 +1,88 @@
+//! Pinned nodes: memories exempt from forgetting.
+//!
+//! Like tags, the pin flag has no slot in the binary node record, so the set
+//! of pinned node IDs lives in a JSON sidecar (`<brain>.amem.pins.json`)
+//! written alongside every save.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::types::{McpError, McpResult};
+
+/// IDs of all pinned nodes.
+#[derive(Debug, Default)]
+pub struct PinStore {
+    pinned: BTreeSet<u64>,
+}
+
+impl PinStore {
+    /// Sidecar path for a memory file.
+    pub fn sidecar_path(memory_path: &Path) -> PathBuf {
+        let mut name = memory_path.as_os_str().to_os_string();
+        name.push(".pins.json");
+        PathBuf::from(name)
+    }
+
+    /// Load pins from the sidecar. A missing sidecar yields an empty store.
+    pub fn load(memory_path: &Path) -> McpResult<Self> {
+        let path = Self::sidecar_path(memory_path);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(&path)?;
+        let pinned: BTreeSet<u64> = serde_json::from_str(&raw).map_err(|e| {
+            McpError::AgenticMemory(format!("Invalid pin sidecar {}: {e}", path.display()))
+        })?;
+        Ok(Self { pinned })
+    }
+
+    /// Write the sidecar atomically. An empty store removes it.
+    pub fn save(&self, memory_path: &Path) -> McpResult<()> {
+        let path = Self::sidecar_path(memory_path);
+        if self.pinned.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+            return Ok(());
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec(&self.pinned)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    /// Whether the node is pinned.
+    pub fn contains(&self, node_id: u64) -> bool {
+        self.pinned.contains(&node_id)
+    }
+
+    /// Pin or unpin a node. Returns false if it already had that state.
+    pub fn set(&mut self, node_id: u64, pinned: bool) -> bool {
+        if pinned {
+            self.pinned.insert(node_id)
+        } else {
+            self.pinned.remove(&node_id)
+        }
+    }
+
+    /// Pinned node IDs, ascending.
+    pub fn ids(&self) -> impl Iterator<Item = u64> + '_ {
+        self.pinned.iter().copied()
+    }
+
+    /// Add our pins to `base` (the on-disk store) after a merge that
+    /// renumbered re-added nodes via `id_map` (old ID -> new ID).
+    pub fn merged_onto(&self, mut base: PinStore, id_map: &HashMap<u64, u64>) -> PinStore {
+        for id in &self.pinned {
+            base.pinned.insert(id_map.get(id).copied().unwrap_or(*id));
+        }
+        base
+    }
+
+    /// Drop pins for nodes not accepted by `exists`. Returns how many were dropped.
+    pub fn retain_nodes(&mut self, exists: impl Fn(u64) -> bool) -> usize {
+        let before = self.pinned.len();
+        self.pinned.retain(|id| exists(*id));
+        before - self.pinned.len()
+    }
+}
@@ -0,0 +1,50 @@
+//! Active-session marker kept beside the `.amem` file.
+//!
+//! The session index only records which sessions have nodes, not which one a
+//! server was in when it stopped, so that lives in a JSON sidecar
+//! (`<brain>.amem.session.json`) written alongside every save.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{McpError, McpResult};
+
+/// The session a server was using at its last save.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionState {
+    /// Session ID in use at the last save.
+    pub session_id: u32,
+    /// False once the session was ended with an episode summary.
+    pub active: bool,
+}
+
+impl SessionState {
+    /// Sidecar path for a memory file.
+    pub fn sidecar_path(memory_path: &Path) -> PathBuf {
+        let mut name = memory_path.as_os_str().to_os_string();
+        name.push(".session.json");
+        PathBuf::from(name)
+    }
+
+    /// Load the sidecar. A missing sidecar yields `None`.
+    pub fn load(memory_path: &Path) -> McpResult<Option<Self>> {
+        let path = Self::sidecar_path(memory_path);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let raw = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&raw).map(Some).map_err(|e| {
+            McpError::AgenticMemory(format!("Invalid session sidecar {}: {e}", path.display()))
+        })
+    }
+
+    /// Write the sidecar atomically.
+    pub fn save(&self, memory_path: &Path) -> McpResult<()> {
+        let path = Self::sidecar_path(memory_path);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec(self)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+//! Node tags: free-form key/value labels kept beside the `.amem` file.
+//!
+//! The binary node record has no metadata slot, so tags live in a JSON sidecar
+//! (`<brain>.amem.tags.json`) written alongside every save.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::types::{McpError, McpResult};
+
+/// Tag set of a single node, ordered by key.
+pub type Tags = BTreeMap<String, String>;
+
+/// Tags for all nodes, keyed by node ID.
+#[derive(Debug, Default)]
+pub struct TagStore {
+    tags: BTreeMap<u64, Tags>,
+}
+
+impl TagStore {
+    /// Sidecar path for a memory file.
+    pub fn sidecar_path(memory_path: &Path) -> PathBuf {
+        let mut name = memory_path.as_os_str().to_os_string();
+        name.push(".tags.json");
+        PathBuf::from(name)
+    }
+
+    /// Load tags from the sidecar. A missing sidecar yields an empty store.
+    pub fn load(memory_path: &Path) -> McpResult<Self> {
+        let path = Self::sidecar_path(memory_path);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(&path)?;
+        let tags: BTreeMap<u64, Tags> = serde_json::from_str(&raw).map_err(|e| {
+            McpError::AgenticMemory(format!("Invalid tag sidecar {}: {e}", path.display()))
+        })?;
+        Ok(Self { tags })
+    }
+
+    /// Write the sidecar atomically. An empty store removes it.
+    pub fn save(&self, memory_path: &Path) -> McpResult<()> {
+        let path = Self::sidecar_path(memory_path);
+        if self.tags.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+            return Ok(());
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec(&self.tags)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    /// Tags of a node (empty if untagged).
+    pub fn get(&self, node_id: u64) -> Tags {
+        self.tags.get(&node_id).cloned().unwrap_or_default()
+    }
+
+    /// Set `set` and remove `remove` keys on a node; returns the resulting tag set.
+    pub fn update(
+        &mut self,
+        node_id: u64,
+        set: HashMap<String, String>,
+        remove: &[String],
+    ) -> Tags {
+        let entry = self.tags.entry(node_id).or_default();
+        entry.extend(set);
+        for key in remove {
+            entry.remove(key);
+        }
+        let result = entry.clone();
+        if result.is_empty() {
+            self.tags.remove(&node_id);
+        }
+        result
+    }
+
+    /// Whether the node carries every key/value pair in `filter`.
+    pub fn matches(&self, node_id: u64, filter: &HashMap<String, String>) -> bool {
+        if filter.is_empty() {
+            return true;
+        }
+        self.tags.get(&node_id).is_some_and(|tags| {
+            filter
+                .iter()
+                .all(|(k, v)| tags.get(k).is_some_and(|t| t == v))
+        })
+    }
+
+    /// Overlay our tags onto `base` (the on-disk store) after a merge that
+    /// renumbered re-added nodes via `id_map` (old ID -> new ID).
+    pub fn merged_onto(&self, mut base: TagStore, id_map: &HashMap<u64, u64>) -> TagStore {
+        for (id, tags) in &self.tags {
+            let id = id_map.get(id).copied().unwrap_or(*id);
+            base.tags.insert(id, tags.clone());
+        }
+        base
+    }
+
+    /// Drop tags for nodes not accepted by `exists`. Returns how many were dropped.
+    pub fn retain_nodes(&mut self, exists: impl Fn(u64) -> bool) -> usize {
+        let before = self.tags.len();
+        self.tags.retain(|id, _| exists(*id));
+        before - self.tags.len()
+    }
+}
@@ -4,16 +4,18 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use super::autosave::spawn_maintenance;
-use super::SessionManager;
-use crate::types::{McpResult, MemoryMode};
+use super::{SessionManager, ToolDefaults};
+use crate::types::{McpError, McpResult, MemoryMode};
 
 /// Registry of per-user sessions for multi-tenant mode.
 pub struct TenantRegistry {
     data_dir: PathBuf,
-    sessions: HashMap<String, Arc<Mutex<SessionManager>>>,
+    sessions: HashMap<String, Arc<RwLock<SessionManager>>>,
+    /// Fallbacks for omitted tool arguments, given to every tenant session.
+    tool_defaults: ToolDefaults,
 }
 
 impl TenantRegistry {
@@ -22,38 +24,49 @@ impl TenantRegistry {
         Self {
             data_dir: data_dir.to_path_buf(),
             sessions: HashMap::new(),
+            tool_defaults: ToolDefaults::default(),
         }
     }
 
+    /// Open tenant sessions with `defaults` for omitted tool arguments.
+    pub fn with_tool_defaults(mut self, defaults: ToolDefaults) -> Self {
+        self.tool_defaults = defaults;
+        self
+    }
+
     /// Get or create a session for the given user ID.
     ///
     /// On first access, creates `{data_dir}/{user_id}.amem` and opens a session.
+    /// The user ID comes from a request header, so an ID whose brain file would
+    /// land outside the data directory is rejected with `InvalidRequest`.
     pub fn get_or_create(
         &mut self,
         user_id: &str,
         memory_mode: MemoryMode,
-    ) -> McpResult<Arc<Mutex<SessionManager>>> {
+    ) -> McpResult<Arc<RwLock<SessionManager>>> {
+        validate_user_id(user_id)?;
         if let Some(session) = self.sessions.get(user_id) {
             return Ok(session.clone());
         }
 
         // Ensure data directory exists
         std::fs::create_dir_all(&self.data_dir).map_err(|e| {
-            crate::types::McpError::InternalError(format!(
+            McpError::InternalError(format!(
                 "Failed to create data dir {}: {e}",
                 self.data_dir.display()
             ))
         })?;
 
-        let brain_path = self.data_dir.join(format!("{user_id}.amem"));
+        let brain_path = self.brain_path(user_id)?;
         let path_str = brain_path.display().to_string();
 
         tracing::info!("Opening brain for user '{user_id}': {path_str}");
 
         let mut session = SessionManager::open(&path_str)?;
         session.apply_memory_mode(memory_mode);
+        session.set_tool_defaults(self.tool_defaults.clone());
         let maintenance_interval = session.maintenance_interval();
-        let session = Arc::new(Mutex::new(session));
+        let session = Arc::new(RwLock::new(session));
         let _maintenance_task = spawn_maintenance(session.clone(), maintenance_interval);
         self.sessions.insert(user_id.to_string(), session.clone());
 
@@ -64,4 +77,102 @@ impl TenantRegistry {
     pub fn count(&self) -> usize {
         self.sessions.len()
     }
+
+    /// Resolve `{data_dir}/{user_id}.amem` and check that it stays inside the
+    /// data directory once symlinks are followed.
+    fn brain_path(&self, user_id: &str) -> McpResult<PathBuf> {
+        let root = self.data_dir.canonicalize().map_err(|e| {
+            McpError::InternalError(format!(
+                "Failed to resolve data dir {}: {e}",
+                self.data_dir.display()
+            ))
+        })?;
+        let brain_path = root.join(format!("{user_id}.amem"));
+        // An existing entry may be a symlink (a dangling one fails to resolve);
+        // a new file is checked via its parent.
+        let resolved = if brain_path.symlink_metadata().is_ok() {
+            brain_path.canonicalize().ok()
+        } else {
+            brain_path
+                .parent()
+                .and_then(|parent| parent.canonicalize().ok())
+        };
+        if !resolved.is_some_and(|path| path.starts_with(&root)) {
+            tracing::warn!("Rejected user ID {user_id:?}: brain path escapes the data dir");
+            return Err(invalid_user_id(
+                user_id,
+                "resolves outside the data directory",
+            ));
+        }
+        Ok(brain_path)
+    }
+}
+
+/// Reject user IDs that could name a file outside the data directory.
+fn validate_user_id(user_id: &str) -> McpResult<()> {
+    let reason = if user_id.is_empty() {
+        Some("is empty")
+    } else if user_id.contains(['/', '\\']) {
+        Some("contains a path separator")
+    } else if user_id.contains('\0') {
+        Some("contains a null byte")
+    } else if user_id.starts_with('.') {
+        Some("starts with '.'")
+    } else {
+        None
+    };
+    match reason {
+        Some(reason) => Err(invalid_user_id(user_id, reason)),
+        None => Ok(()),
+    }
+}
+
+fn invalid_user_id(user_id: &str, reason: &str) -> McpError {
+    McpError::InvalidRequest(format!("Invalid user ID {user_id:?}: {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_user_ids_that_escape_the_data_dir() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let mut registry = TenantRegistry::new(dir.path());
+        for user_id in [
+            "",
+            "../../etc/passwd",
+            "..",
+            "a/b",
+            "a\\b",
+            "a\0b",
+            ".hidden",
+        ] {
+            assert!(
+                matches!(
+                    registry.get_or_create(user_id, MemoryMode::Smart),
+                    Err(McpError::InvalidRequest(_))
+                ),
+                "accepted {user_id:?}"
+            );
+        }
+        assert_eq!(registry.count(), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_brain_symlinked_outside_the_data_dir() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let outside = tempfile::tempdir().expect("test fixture");
+        let target = outside.path().join("victim.amem");
+        std::fs::write(&target, b"").expect("test fixture");
+        std::os::unix::fs::symlink(&target, dir.path().join("mallory.amem")).expect("test fixture");
+
+        let registry = TenantRegistry::new(dir.path());
+        assert!(matches!(
+            registry.brain_path("mallory"),
+            Err(McpError::InvalidRequest(_))
+        ));
+        assert!(registry.brain_path("alice").is_ok());
+    }
 }
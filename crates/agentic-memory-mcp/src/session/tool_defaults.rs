@@ -0,0 +1,32 @@
+//! Server-configured fallbacks for arguments a tool call omits.
+
+use agentic_memory::{EdgeType, EventType, SimilarityMetric};
+
+/// Confidence `memory_add` uses when neither the call nor the server config
+/// sets one.
+pub const DEFAULT_CONFIDENCE: f32 = 0.9;
+
+/// Fallbacks for omitted tool arguments, set per session from the server
+/// config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolDefaults {
+    /// `memory_add` event type. Without one, `event_type` stays required.
+    pub event_type: Option<EventType>,
+    /// `memory_add` confidence.
+    pub confidence: f32,
+    /// `memory_similar` scoring metric.
+    pub similarity_metric: SimilarityMetric,
+    /// Edge types `memory_traverse` follows; empty means all.
+    pub traversal_edge_types: Vec<EdgeType>,
+}
+
+impl Default for ToolDefaults {
+    fn default() -> Self {
+        Self {
+            event_type: None,
+            confidence: DEFAULT_CONFIDENCE,
+            similarity_metric: SimilarityMetric::default(),
+            traversal_edge_types: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Undo log: the inverse of each recent graph mutation, newest last.
+//!
+//! This is an in-memory convenience for interactive "undo what I just did";
+//! it is not persisted and is bounded, so the oldest entries fall off.
+
+use std::collections::VecDeque;
+
+use agentic_memory::{CognitiveEvent, Edge, EdgeType, EventType, MemoryGraph};
+use serde_json::{json, Value};
+
+use crate::types::{McpError, McpResult};
+
+/// Default number of operations kept in the undo log.
+pub const DEFAULT_UNDO_LOG_CAPACITY: usize = 100;
+
+/// A recorded mutation and the state needed to reverse it.
+#[derive(Debug, Clone)]
+pub enum UndoOp {
+    /// A node (and the edges created with it) was added.
+    Added { node_id: u64 },
+    /// A node was removed, together with its edges.
+    Deleted {
+        node: Box<CognitiveEvent>,
+        edges: Vec<Edge>,
+    },
+    /// An edge was added between existing nodes.
+    Linked { edge: Edge },
+    /// A correction node superseded `old_id`, whose confidence was lowered.
+    Corrected {
+        new_id: u64,
+        old_id: u64,
+        old_confidence: f32,
+    },
+    /// An edge's weight changed from `previous_weight`.
+    Reweighted {
+        source_id: u64,
+        target_id: u64,
+        edge_type: EdgeType,
+        previous_weight: f32,
+    },
+    /// Nodes changed event type; holds each node's previous type.
+    Retyped { previous: Vec<(u64, EventType)> },
+    /// A supersedes chain was collapsed onto its latest version.
+    ChainCompacted {
+        deleted: Vec<CognitiveEvent>,
+        removed_edges: Vec<Edge>,
+        added_edges: Vec<Edge>,
+    },
+}
+
+impl UndoOp {
+    /// Apply the inverse of this operation to `graph`.
+    pub fn revert(&self, graph: &mut MemoryGraph) -> McpResult<()> {
+        let failed =
+            |e: agentic_memory::AmemError| McpError::AgenticMemory(format!("Undo failed: {e}"));
+        match self {
+            UndoOp::Added { node_id } => {
+                graph.remove_node(*node_id).map_err(failed)?;
+            }
+            UndoOp::Deleted { node, edges } => {
+                graph.restore_node((**node).clone()).map_err(failed)?;
+                for edge in edges {
+                    graph.add_edge(*edge).map_err(failed)?;
+                }
+            }
+            UndoOp::Linked { edge } => {
+                graph
+                    .remove_edge(edge.source_id, edge.target_id, edge.edge_type)
+                    .map_err(failed)?;
+            }
+            UndoOp::Corrected {
+                new_id,
+                old_id,
+                old_confidence,
+            } => {
+                graph.remove_node(*new_id).map_err(failed)?;
+                if let Some(old) = graph.get_node_mut(*old_id) {
+                    old.confidence = *old_confidence;
+                }
+            }
+            UndoOp::Reweighted {
+                source_id,
+                target_id,
+                edge_type,
+                previous_weight,
+            } => {
+                graph
+                    .set_edge_weight(*source_id, *target_id, *edge_type, *previous_weight)
+                    .map_err(failed)?;
+            }
+            UndoOp::Retyped { previous } => {
+                for (id, event_type) in previous {
+                    graph.retype_node(*id, *event_type).map_err(failed)?;
+                }
+            }
+            UndoOp::ChainCompacted {
+                deleted,
+                removed_edges,
+                added_edges,
+            } => {
+                for edge in added_edges {
+                    graph
+                        .remove_edge(edge.source_id, edge.target_id, edge.edge_type)
+                        .map_err(failed)?;
+                }
+                for node in deleted {
+                    graph.restore_node(node.clone()).map_err(failed)?;
+                }
+                for edge in removed_edges {
+                    graph.add_edge(*edge).map_err(failed)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Short description of what undoing this operation did.
+    pub fn summary(&self) -> Value {
+        match self {
+            UndoOp::Added { node_id } => json!({ "op": "add", "removed_node_id": node_id }),
+            UndoOp::Deleted { node, edges } => json!({
+                "op": "delete",
+                "restored_node_id": node.id,
+                "restored_edges": edges.len(),
+            }),
+            UndoOp::Linked { edge } => json!({
+                "op": "link",
+                "removed_edge": {
+                    "source_id": edge.source_id,
+                    "target_id": edge.target_id,
+                    "edge_type": edge.edge_type.name(),
+                },
+            }),
+            UndoOp::Corrected {
+                new_id,
+                old_id,
+                old_confidence,
+            } => json!({
+                "op": "correct",
+                "removed_node_id": new_id,
+                "restored_node_id": old_id,
+                "restored_confidence": old_confidence,
+            }),
+            UndoOp::Reweighted {
+                source_id,
+                target_id,
+                edge_type,
+                previous_weight,
+            } => json!({
+                "op": "reweight",
+                "edge": {
+                    "source_id": source_id,
+                    "target_id": target_id,
+                    "edge_type": edge_type.name(),
+                },
+                "restored_weight": previous_weight,
+            }),
+            UndoOp::Retyped { previous } => json!({
+                "op": "retype",
+                "restored": previous
+                    .iter()
+                    .map(|(id, t)| json!({ "node_id": id, "event_type": t.name() }))
+                    .collect::<Vec<_>>(),
+            }),
+            UndoOp::ChainCompacted {
+                deleted,
+                removed_edges,
+                added_edges,
+            } => json!({
+                "op": "compact_chain",
+                "restored_node_ids": deleted.iter().map(|n| n.id).collect::<Vec<_>>(),
+                "restored_edges": removed_edges.len(),
+                "removed_edges": added_edges.len(),
+            }),
+        }
+    }
+}
+
+/// Bounded stack of undoable operations.
+#[derive(Debug)]
+pub struct UndoLog {
+    ops: VecDeque<UndoOp>,
+    capacity: usize,
+}
+
+impl UndoLog {
+    /// Create a log holding at most `capacity` operations (0 disables undo).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            ops: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Record an operation, dropping the oldest when full.
+    pub fn push(&mut self, op: UndoOp) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.ops.len() >= self.capacity {
+            self.ops.pop_front();
+        }
+        self.ops.push_back(op);
+    }
+
+    /// The most recent operation, without removing it.
+    pub fn last(&self) -> Option<&UndoOp> {
+        self.ops.back()
+    }
+
+    /// Take the most recent operation.
+    pub fn pop(&mut self) -> Option<UndoOp> {
+        self.ops.pop_back()
+    }
+
+    /// Number of operations that can still be undone.
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Whether there is nothing to undo.
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Drop every recorded operation.
+    pub fn clear(&mut self) {
+        self.ops.clear();
+    }
+}
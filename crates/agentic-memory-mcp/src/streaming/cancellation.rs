@@ -0,0 +1,95 @@
+//! Request cancellation: flags raised by `notifications/cancelled` (or
+//! `$/cancelRequest`) and polled by long-running scans.
+//!
+//! The handler registers a flag for each in-flight request and runs the
+//! request with that flag in task-local scope, so tools can check it with
+//! [`is_cancelled`] without it being threaded through every signature.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::types::RequestId;
+
+tokio::task_local! {
+    static CURRENT: Arc<AtomicBool>;
+}
+
+/// Cancellation flags of the requests currently being handled, keyed by
+/// JSON-RPC request ID.
+#[derive(Debug, Default)]
+pub struct CancellationRegistry {
+    active: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl CancellationRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `future` as request `id`, so [`is_cancelled`] inside it reports
+    /// whether that request has been cancelled.
+    pub async fn run<F: Future>(&self, id: &RequestId, future: F) -> F::Output {
+        let key = key(id);
+        let flag = Arc::new(AtomicBool::new(false));
+        self.lock().insert(key.clone(), flag.clone());
+        let output = CURRENT.scope(flag, future).await;
+        self.lock().remove(&key);
+        output
+    }
+
+    /// Cancel request `id`. Returns false if it is not in flight.
+    pub fn cancel(&self, id: &RequestId) -> bool {
+        match self.lock().get(&key(id)) {
+            Some(flag) => {
+                flag.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<AtomicBool>>> {
+        self.active.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Keep `1` and `"1"` apart, since clients may use either form.
+fn key(id: &RequestId) -> String {
+    serde_json::to_string(id).unwrap_or_default()
+}
+
+/// A request's cancellation flag, detached from the task so it can be
+/// polled from a blocking thread.
+#[derive(Debug, Clone, Default)]
+pub struct CancelFlag(Option<Arc<AtomicBool>>);
+
+impl CancelFlag {
+    /// The flag of the request being handled on this task. Never raised
+    /// outside [`CancellationRegistry::run`].
+    pub fn current() -> Self {
+        Self(CURRENT.try_with(Arc::clone).ok())
+    }
+
+    /// Raise the flag, so scans polling it stop early.
+    pub fn cancel(&self) {
+        if let Some(flag) = &self.0 {
+            flag.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Whether the request has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
+}
+
+/// Whether the request being handled on this task has been cancelled.
+/// Always false outside [`CancellationRegistry::run`].
+pub fn is_cancelled() -> bool {
+    CancelFlag::current().is_cancelled()
+}
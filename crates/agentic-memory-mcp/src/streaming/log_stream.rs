@@ -0,0 +1,176 @@
+//! Forward `tracing` events to the client as `notifications/message`.
+//!
+//! Each client subscribes through its protocol handler, and nothing is sent
+//! to it until it picks a level with `logging/setLevel`.
+//! Events from the transport itself are never forwarded: writing a
+//! notification must not produce another one.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, RwLock};
+
+use serde_json::{Map, Value};
+use tokio::sync::mpsc;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::types::{JsonRpcNotification, LogLevel, LogMessageParams, Redactor};
+
+/// Sink level meaning the client has not asked for logs.
+const OFF: u8 = u8::MAX;
+
+/// Targets whose events are never forwarded.
+const SKIPPED_TARGET: &str = "agentic_memory_mcp::transport";
+
+/// One client's channel, the lowest level it asked for (as `LogLevel as u8`),
+/// and the redaction applied to its records.
+#[derive(Debug)]
+struct LogSink {
+    min_level: AtomicU8,
+    tx: mpsc::Sender<JsonRpcNotification>,
+    redactor: Redactor,
+}
+
+/// The clients a [`McpLogLayer`] forwards to, shared between the layer and
+/// the handlers that subscribe their clients.
+#[derive(Debug, Clone, Default)]
+pub struct LogSinks {
+    sinks: Arc<RwLock<Vec<Arc<LogSink>>>>,
+}
+
+impl LogSinks {
+    /// Route forwarded log records into `tx`, masked by `redactor`, until the
+    /// subscription is dropped. Nothing is sent before a level is set.
+    pub fn subscribe(
+        &self,
+        tx: mpsc::Sender<JsonRpcNotification>,
+        redactor: Redactor,
+    ) -> LogSubscription {
+        let sink = Arc::new(LogSink {
+            min_level: AtomicU8::new(OFF),
+            tx,
+            redactor,
+        });
+        self.sinks
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(sink.clone());
+        LogSubscription {
+            sinks: self.clone(),
+            sink,
+        }
+    }
+}
+
+/// A client's registration with [`LogSinks`]; dropping it stops forwarding.
+#[derive(Debug)]
+pub struct LogSubscription {
+    sinks: LogSinks,
+    sink: Arc<LogSink>,
+}
+
+impl LogSubscription {
+    /// Forward records at `level` and above (`logging/setLevel`).
+    pub fn set_level(&self, level: LogLevel) {
+        self.sink.min_level.store(level as u8, Ordering::Relaxed);
+    }
+}
+
+impl Drop for LogSubscription {
+    fn drop(&mut self) {
+        self.sinks
+            .sinks
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|sink| !Arc::ptr_eq(sink, &self.sink));
+    }
+}
+
+/// `tracing` layer that mirrors events to each subscribed client at the
+/// level it chose.
+///
+/// Install it next to the stderr formatter; it filters on its own, so the
+/// stderr log level does not limit what the client can ask for. Hand
+/// [`sinks`](Self::sinks) to the protocol handlers so their clients can
+/// subscribe.
+#[derive(Debug, Clone, Default)]
+pub struct McpLogLayer {
+    sinks: LogSinks,
+}
+
+impl McpLogLayer {
+    /// Create a layer with no subscribed clients.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The clients this layer forwards to.
+    pub fn sinks(&self) -> LogSinks {
+        self.sinks.clone()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for McpLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if metadata.target().starts_with(SKIPPED_TARGET) {
+            return;
+        }
+        let level = LogLevel::from_tracing(*metadata.level());
+
+        let sinks = self.sinks.sinks.read().unwrap_or_else(|e| e.into_inner());
+        let mut fields = None;
+        for sink in sinks.iter() {
+            if (level as u8) < sink.min_level.load(Ordering::Relaxed) {
+                continue;
+            }
+            let collected = fields.get_or_insert_with(|| {
+                let mut collector = FieldCollector(Map::new());
+                event.record(&mut collector);
+                collector.0
+            });
+            let mut data = Value::Object(collected.clone());
+            sink.redactor.value(&mut data);
+
+            let params = LogMessageParams {
+                level,
+                logger: Some(metadata.target().to_string()),
+                data,
+            };
+            let Ok(params) = serde_json::to_value(params) else {
+                continue;
+            };
+            // Never block or log here: a full channel just drops the record.
+            let _ = sink.tx.try_send(JsonRpcNotification::new(
+                "notifications/message".to_string(),
+                Some(params),
+            ));
+        }
+    }
+}
+
+/// Collects an event's fields into a JSON object (`message` for the text).
+struct FieldCollector(Map<String, Value>);
+
+impl Visit for FieldCollector {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), Value::from(format!("{value:?}")));
+    }
+}
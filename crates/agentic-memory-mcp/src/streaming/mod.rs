@@ -1,6 +1,10 @@
 //! Streaming support — progress tracking and chunked responses.
 
+pub mod cancellation;
 pub mod chunked;
+pub mod log_stream;
 pub mod progress;
 
+pub use cancellation::{CancelFlag, CancellationRegistry};
+pub use log_stream::{LogSinks, LogSubscription, McpLogLayer};
 pub use progress::ProgressTracker;
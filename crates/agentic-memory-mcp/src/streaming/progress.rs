@@ -1,12 +1,41 @@
 //! Progress token handling for long-running operations.
 
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 
 use tokio::sync::{mpsc, RwLock};
 
 use crate::types::{JsonRpcNotification, McpResult, ProgressParams, ProgressToken};
 
+tokio::task_local! {
+    static TOKEN: ProgressToken;
+}
+
+/// Run `future` with `token` (from the request's `_meta.progressToken`) as
+/// the progress token of the request being handled on this task.
+pub async fn with_progress_token<F: Future>(token: Option<ProgressToken>, future: F) -> F::Output {
+    match token {
+        Some(token) => TOKEN.scope(token, future).await,
+        None => future.await,
+    }
+}
+
+/// A `notifications/progress` for the request being handled on this task, or
+/// `None` when the client did not ask for progress.
+pub fn progress_notification(progress: f64, total: Option<f64>) -> Option<JsonRpcNotification> {
+    let token = TOKEN.try_with(Clone::clone).ok()?;
+    let params = ProgressParams {
+        progress_token: token,
+        progress,
+        total,
+    };
+    Some(JsonRpcNotification::new(
+        "notifications/progress".to_string(),
+        Some(serde_json::to_value(params).unwrap_or_default()),
+    ))
+}
+
 /// State of a tracked progress operation.
 #[derive(Debug)]
 struct ProgressState {
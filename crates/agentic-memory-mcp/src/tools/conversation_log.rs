@@ -1,7 +1,7 @@
 //! Tool: conversation_log — Log user prompts and agent responses into the conversation thread.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -54,7 +54,7 @@ pub fn definition() -> ToolDefinition {
 /// Execute the conversation_log tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: ConversationLogParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
@@ -77,7 +77,7 @@ pub async fn execute(
         })
     );
 
-    let mut session = session.lock().await;
+    let mut session = session.write().await;
 
     let prev_id = session.last_temporal_node_id();
     let (node_id, _) = session.add_event(EventType::Inference, &content, 0.72, vec![])?;
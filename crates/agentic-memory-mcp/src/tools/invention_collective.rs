@@ -5,7 +5,7 @@ use crate::session::SessionManager;
 use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
 use serde_json::{json, Value};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 fn word_overlap(a: &str, b: &str) -> f64 {
     let a_l = a.to_lowercase();
@@ -37,12 +37,12 @@ pub fn definition_ancestor_list() -> ToolDefinition {
 }
 pub async fn execute_ancestor_list(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
     let max_depth = args.get("max_depth").and_then(|v| v.as_u64()).unwrap_or(10) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let mut ancestors: Vec<Value> = Vec::new();
     let mut current = node_id;
@@ -75,13 +75,13 @@ pub fn definition_ancestor_inherit() -> ToolDefinition {
 }
 pub async fn execute_ancestor_inherit(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
     let ancestor_id = get_u64(&args, "ancestor_id")
         .ok_or_else(|| McpError::InvalidParams("ancestor_id required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let ancestor = graph
         .get_node(ancestor_id)
@@ -106,11 +106,11 @@ pub fn definition_ancestor_verify() -> ToolDefinition {
 }
 pub async fn execute_ancestor_verify(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let node = graph
         .get_node(node_id)
@@ -136,13 +136,13 @@ pub fn definition_ancestor_bequeath() -> ToolDefinition {
 }
 pub async fn execute_ancestor_bequeath(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let from = get_u64(&args, "from_id")
         .ok_or_else(|| McpError::InvalidParams("from_id required".into()))?;
     let to =
         get_u64(&args, "to_id").ok_or_else(|| McpError::InvalidParams("to_id required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let parent = graph.get_node(from).ok_or(McpError::NodeNotFound(from))?;
     let _ = graph.get_node(to).ok_or(McpError::NodeNotFound(to))?;
@@ -161,7 +161,7 @@ pub fn definition_collective_join() -> ToolDefinition {
 }
 pub async fn execute_collective_join(
     args: Value,
-    _session: &Arc<Mutex<SessionManager>>,
+    _session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let pool = get_str(&args, "pool_name")
         .ok_or_else(|| McpError::InvalidParams("pool_name required".into()))?;
@@ -179,13 +179,13 @@ pub fn definition_collective_contribute() -> ToolDefinition {
 }
 pub async fn execute_collective_contribute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
     let pool = get_str(&args, "pool_name")
         .ok_or_else(|| McpError::InvalidParams("pool_name required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let node = session
         .graph()
         .get_node(node_id)
@@ -204,11 +204,11 @@ pub fn definition_collective_query() -> ToolDefinition {
 }
 pub async fn execute_collective_query(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let query =
         get_str(&args, "query").ok_or_else(|| McpError::InvalidParams("query required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let results: Vec<Value> = graph.nodes().iter().filter_map(|n| {
         let sim = word_overlap(&query, &n.content);
@@ -228,11 +228,11 @@ pub fn definition_collective_endorse() -> ToolDefinition {
 }
 pub async fn execute_collective_endorse(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
-    let mut session = session.lock().await;
+    let mut session = session.write().await;
     let node = session
         .graph_mut()
         .get_node_mut(node_id)
@@ -253,13 +253,13 @@ pub fn definition_collective_challenge() -> ToolDefinition {
 }
 pub async fn execute_collective_challenge(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
     let reason = get_str(&args, "reason")
         .ok_or_else(|| McpError::InvalidParams("reason required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let node = session
         .graph()
         .get_node(node_id)
@@ -279,14 +279,14 @@ pub fn definition_fusion_analyze() -> ToolDefinition {
 }
 pub async fn execute_fusion_analyze(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let threshold = args
         .get("threshold")
         .and_then(|v| v.as_f64())
         .unwrap_or(0.7);
     let max_pairs = args.get("max_pairs").and_then(|v| v.as_u64()).unwrap_or(10) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
     let mut pairs: Vec<Value> = Vec::new();
@@ -318,13 +318,13 @@ pub fn definition_fusion_execute() -> ToolDefinition {
 }
 pub async fn execute_fusion_execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let a = get_u64(&args, "node_a")
         .ok_or_else(|| McpError::InvalidParams("node_a required".into()))?;
     let b = get_u64(&args, "node_b")
         .ok_or_else(|| McpError::InvalidParams("node_b required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let na = graph.get_node(a).ok_or(McpError::NodeNotFound(a))?;
     let nb = graph.get_node(b).ok_or(McpError::NodeNotFound(b))?;
@@ -344,14 +344,14 @@ pub fn definition_fusion_resolve() -> ToolDefinition {
 }
 pub async fn execute_fusion_resolve(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let a = get_u64(&args, "node_a")
         .ok_or_else(|| McpError::InvalidParams("node_a required".into()))?;
     let b = get_u64(&args, "node_b")
         .ok_or_else(|| McpError::InvalidParams("node_b required".into()))?;
     let strategy = get_str(&args, "strategy").unwrap_or_else(|| "highest_confidence".into());
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let na = graph.get_node(a).ok_or(McpError::NodeNotFound(a))?;
     let nb = graph.get_node(b).ok_or(McpError::NodeNotFound(b))?;
@@ -380,13 +380,13 @@ pub fn definition_fusion_preview() -> ToolDefinition {
 }
 pub async fn execute_fusion_preview(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let a = get_u64(&args, "node_a")
         .ok_or_else(|| McpError::InvalidParams("node_a required".into()))?;
     let b = get_u64(&args, "node_b")
         .ok_or_else(|| McpError::InvalidParams("node_b required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let na = graph.get_node(a).ok_or(McpError::NodeNotFound(a))?;
     let nb = graph.get_node(b).ok_or(McpError::NodeNotFound(b))?;
@@ -408,7 +408,7 @@ pub fn definition_telepathy_link() -> ToolDefinition {
 }
 pub async fn execute_telepathy_link(
     args: Value,
-    _session: &Arc<Mutex<SessionManager>>,
+    _session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let path = get_str(&args, "target_path")
         .ok_or_else(|| McpError::InvalidParams("target_path required".into()))?;
@@ -426,12 +426,12 @@ pub fn definition_telepathy_sync() -> ToolDefinition {
 }
 pub async fn execute_telepathy_sync(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let path = get_str(&args, "target_path")
         .ok_or_else(|| McpError::InvalidParams("target_path required".into()))?;
     let dir = get_str(&args, "direction").unwrap_or_else(|| "both".into());
-    let session = session.lock().await;
+    let session = session.read().await;
     let count = session.graph().node_count();
     Ok(ToolCallResult::json(
         &json!({"target_path":path,"direction":dir,"local_nodes":count,"status":"sync_ready"}),
@@ -447,11 +447,11 @@ pub fn definition_telepathy_query() -> ToolDefinition {
 }
 pub async fn execute_telepathy_query(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let query =
         get_str(&args, "query").ok_or_else(|| McpError::InvalidParams("query required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let local: Vec<Value> = graph.nodes().iter().filter_map(|n| {
         let sim = word_overlap(&query, &n.content);
@@ -471,7 +471,7 @@ pub fn definition_telepathy_stream() -> ToolDefinition {
 }
 pub async fn execute_telepathy_stream(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let path = get_str(&args, "target_path")
         .ok_or_else(|| McpError::InvalidParams("target_path required".into()))?;
@@ -479,7 +479,7 @@ pub async fn execute_telepathy_stream(
         .get("since_session")
         .and_then(|v| v.as_u64())
         .map(|v| v as u32);
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let pending: Vec<u64> = graph
         .nodes()
@@ -518,7 +518,7 @@ pub fn all_definitions() -> Vec<ToolDefinition> {
 pub async fn try_execute(
     name: &str,
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> Option<McpResult<ToolCallResult>> {
     match name {
         "memory_ancestor_list" => Some(execute_ancestor_list(args, session).await),
@@ -3,7 +3,7 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde_json::{json, Value};
 
@@ -55,9 +55,9 @@ pub fn definition_immortal_stats() -> ToolDefinition {
 
 pub async fn execute_immortal_stats(
     _args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
     let total = nodes.len();
@@ -103,11 +103,11 @@ pub fn definition_immortal_prove() -> ToolDefinition {
 
 pub async fn execute_immortal_prove(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let node = graph
         .get_node(node_id)
@@ -147,11 +147,11 @@ pub fn definition_immortal_project() -> ToolDefinition {
 
 pub async fn execute_immortal_project(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let node = graph
         .get_node(node_id)
@@ -184,13 +184,13 @@ pub fn definition_immortal_tier_move() -> ToolDefinition {
 
 pub async fn execute_immortal_tier_move(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
     let tier =
         get_str(&args, "tier").ok_or_else(|| McpError::InvalidParams("tier required".into()))?;
-    let mut session = session.lock().await;
+    let mut session = session.write().await;
     let graph = session.graph_mut();
     let node = graph
         .get_node_mut(node_id)
@@ -233,10 +233,10 @@ pub fn definition_semantic_compress() -> ToolDefinition {
 
 pub async fn execute_semantic_compress(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let threshold = get_f64(&args, "threshold").unwrap_or(0.5);
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
     let mut clusters: Vec<Vec<u64>> = Vec::new();
@@ -288,14 +288,14 @@ pub fn definition_semantic_dedup() -> ToolDefinition {
 
 pub async fn execute_semantic_dedup(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let threshold = get_f64(&args, "threshold").unwrap_or(0.8);
     let max_results = args
         .get("max_results")
         .and_then(|v| v.as_u64())
         .unwrap_or(20) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
     let mut duplicates: Vec<Value> = Vec::new();
@@ -336,7 +336,7 @@ pub fn definition_semantic_similar() -> ToolDefinition {
 
 pub async fn execute_semantic_similar(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let query =
         get_str(&args, "query").ok_or_else(|| McpError::InvalidParams("query required".into()))?;
@@ -344,7 +344,7 @@ pub async fn execute_semantic_similar(
         .get("max_results")
         .and_then(|v| v.as_u64())
         .unwrap_or(10) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let mut scored: Vec<(u64, f64, &str)> = graph
         .nodes()
@@ -372,13 +372,13 @@ pub fn definition_semantic_cluster() -> ToolDefinition {
 
 pub async fn execute_semantic_cluster(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let num_clusters = args
         .get("num_clusters")
         .and_then(|v| v.as_u64())
         .unwrap_or(5) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
     // Simple keyword-based clustering
@@ -414,7 +414,7 @@ pub fn definition_context_optimize() -> ToolDefinition {
 
 pub async fn execute_context_optimize(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let topic =
         get_str(&args, "topic").ok_or_else(|| McpError::InvalidParams("topic required".into()))?;
@@ -422,7 +422,7 @@ pub async fn execute_context_optimize(
         .get("window_size")
         .and_then(|v| v.as_u64())
         .unwrap_or(10) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let mut scored: Vec<(u64, f64)> = graph
         .nodes()
@@ -459,12 +459,12 @@ pub fn definition_context_expand() -> ToolDefinition {
 
 pub async fn execute_context_expand(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
     let depth = args.get("depth").and_then(|v| v.as_u64()).unwrap_or(2) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let _ = graph
         .get_node(node_id)
@@ -508,7 +508,7 @@ pub fn definition_context_summarize() -> ToolDefinition {
 
 pub async fn execute_context_summarize(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let ids: Vec<u64> = args
         .get("node_ids")
@@ -518,7 +518,7 @@ pub async fn execute_context_summarize(
     if ids.is_empty() {
         return Err(McpError::InvalidParams("node_ids required".into()));
     }
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let mut types: HashMap<String, usize> = HashMap::new();
     let mut contents: Vec<String> = Vec::new();
@@ -551,13 +551,13 @@ pub fn definition_context_navigate() -> ToolDefinition {
 
 pub async fn execute_context_navigate(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let from = get_u64(&args, "from_node")
         .ok_or_else(|| McpError::InvalidParams("from_node required".into()))?;
     let topic = get_str(&args, "to_topic")
         .ok_or_else(|| McpError::InvalidParams("to_topic required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let _ = graph.get_node(from).ok_or(McpError::NodeNotFound(from))?;
     // BFS from from_node, scoring by topic relevance
@@ -602,9 +602,9 @@ pub fn definition_metabolism_status() -> ToolDefinition {
 
 pub async fn execute_metabolism_status(
     _args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
     let total = nodes.len();
@@ -640,13 +640,13 @@ pub fn definition_metabolism_process() -> ToolDefinition {
 
 pub async fn execute_metabolism_process(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let dry_run = args
         .get("dry_run")
         .and_then(|v| v.as_bool())
         .unwrap_or(true);
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
     // Find weak memories that could be consolidated
@@ -679,12 +679,12 @@ pub fn definition_metabolism_strengthen() -> ToolDefinition {
 
 pub async fn execute_metabolism_strengthen(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
     let boost = get_f64(&args, "boost").unwrap_or(0.1) as f32;
-    let mut session = session.lock().await;
+    let mut session = session.write().await;
     let graph = session.graph_mut();
     let node = graph
         .get_node_mut(node_id)
@@ -713,10 +713,10 @@ pub fn definition_metabolism_decay() -> ToolDefinition {
 
 pub async fn execute_metabolism_decay(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let decay_amount = get_f64(&args, "decay_amount").unwrap_or(0.05) as f32;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
     let now = std::time::SystemTime::now()
@@ -751,14 +751,14 @@ pub fn definition_metabolism_consolidate() -> ToolDefinition {
 
 pub async fn execute_metabolism_consolidate(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let threshold = get_f64(&args, "threshold").unwrap_or(0.7);
     let max_groups = args
         .get("max_groups")
         .and_then(|v| v.as_u64())
         .unwrap_or(10) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
     let mut groups: Vec<Vec<u64>> = Vec::new();
@@ -824,7 +824,7 @@ pub fn all_definitions() -> Vec<ToolDefinition> {
 pub async fn try_execute(
     name: &str,
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> Option<McpResult<ToolCallResult>> {
     match name {
         "memory_immortal_stats" => Some(execute_immortal_stats(args, session).await),
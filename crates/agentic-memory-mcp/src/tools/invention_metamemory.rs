@@ -3,7 +3,7 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde_json::{json, Value};
 
@@ -62,10 +62,10 @@ pub fn definition_meta_inventory() -> ToolDefinition {
 
 pub async fn execute_meta_inventory(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let filter_type = get_str(&args, "event_type");
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let type_index = graph.type_index();
 
@@ -150,10 +150,10 @@ pub fn definition_meta_gaps() -> ToolDefinition {
 
 pub async fn execute_meta_gaps(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let _min_coverage = get_f64(&args, "min_coverage").unwrap_or(0.3);
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
     let type_index = graph.type_index();
@@ -231,9 +231,9 @@ pub fn definition_meta_calibration() -> ToolDefinition {
 
 pub async fn execute_meta_calibration(
     _args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
 
@@ -315,9 +315,9 @@ pub fn definition_meta_capabilities() -> ToolDefinition {
 
 pub async fn execute_meta_capabilities(
     _args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let total = graph.node_count();
     let edges = graph.edge_count();
@@ -406,9 +406,9 @@ pub fn definition_dream_status() -> ToolDefinition {
 
 pub async fn execute_dream_status(
     _args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
 
@@ -459,11 +459,11 @@ pub fn definition_dream_start() -> ToolDefinition {
 
 pub async fn execute_dream_start(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let dream_type = get_str(&args, "dream_type").unwrap_or_else(|| "consolidation".into());
     let intensity = get_str(&args, "intensity").unwrap_or_else(|| "deep".into());
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
 
@@ -542,13 +542,13 @@ pub fn definition_dream_wake() -> ToolDefinition {
 
 pub async fn execute_dream_wake(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let apply = args
         .get("apply_insights")
         .and_then(|v| v.as_bool())
         .unwrap_or(true);
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     Ok(ToolCallResult::json(&json!({
@@ -578,10 +578,10 @@ pub fn definition_dream_insights() -> ToolDefinition {
 
 pub async fn execute_dream_insights(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let max_results = get_u64(&args, "max_results").unwrap_or(20) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
 
@@ -634,10 +634,10 @@ pub fn definition_dream_history() -> ToolDefinition {
 
 pub async fn execute_dream_history(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let max_results = get_u64(&args, "max_results").unwrap_or(10) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     // Look for dream-related skill nodes
@@ -679,12 +679,12 @@ pub fn definition_belief_list() -> ToolDefinition {
 
 pub async fn execute_belief_list(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let topic = get_str(&args, "topic");
     let min_confidence = get_f64(&args, "min_confidence").unwrap_or(0.0) as f32;
     let max_results = get_u64(&args, "max_results").unwrap_or(20) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     let mut beliefs: Vec<Value> = Vec::new();
@@ -753,11 +753,11 @@ pub fn definition_belief_history() -> ToolDefinition {
 
 pub async fn execute_belief_history(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     // Walk forward (what did this supersede?)
@@ -841,7 +841,7 @@ pub fn definition_belief_revise() -> ToolDefinition {
 
 pub async fn execute_belief_revise(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let old_id = get_u64(&args, "old_node_id")
         .ok_or_else(|| McpError::InvalidParams("old_node_id required".into()))?;
@@ -850,7 +850,7 @@ pub async fn execute_belief_revise(
     let reason = get_str(&args, "reason").unwrap_or_else(|| "belief revision".into());
     let confidence = get_f64(&args, "confidence").unwrap_or(0.8);
 
-    let mut session = session.lock().await;
+    let mut session = session.write().await;
     let _old = session
         .graph()
         .get_node(old_id)
@@ -885,10 +885,10 @@ pub fn definition_belief_conflicts() -> ToolDefinition {
 
 pub async fn execute_belief_conflicts(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let max_results = get_u64(&args, "max_results").unwrap_or(20) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     let mut conflicts: Vec<Value> = Vec::new();
@@ -934,9 +934,9 @@ pub fn definition_load_status() -> ToolDefinition {
 
 pub async fn execute_load_status(
     _args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let total = graph.node_count();
     let edges = graph.edge_count();
@@ -989,11 +989,11 @@ pub fn definition_load_cache() -> ToolDefinition {
 
 pub async fn execute_load_cache(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let action = get_str(&args, "action").unwrap_or_else(|| "status".into());
     let limit = get_u64(&args, "limit").unwrap_or(20) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
 
@@ -1068,12 +1068,12 @@ pub fn definition_load_prefetch() -> ToolDefinition {
 
 pub async fn execute_load_prefetch(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let topic =
         get_str(&args, "topic").ok_or_else(|| McpError::InvalidParams("topic required".into()))?;
     let max_items = get_u64(&args, "max_items").unwrap_or(10) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     let mut matches: Vec<(f64, Value)> = Vec::new();
@@ -1120,12 +1120,12 @@ pub fn definition_load_optimize() -> ToolDefinition {
 
 pub async fn execute_load_optimize(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let task =
         get_str(&args, "task").ok_or_else(|| McpError::InvalidParams("task required".into()))?;
     let strategy = get_str(&args, "strategy").unwrap_or_else(|| "balanced".into());
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     // Find relevant memories for the task
@@ -1208,7 +1208,7 @@ pub fn all_definitions() -> Vec<ToolDefinition> {
 pub async fn try_execute(
     name: &str,
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> Option<McpResult<ToolCallResult>> {
     match name {
         "memory_meta_inventory" => Some(execute_meta_inventory(args, session).await),
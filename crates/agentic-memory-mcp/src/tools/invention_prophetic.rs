@@ -5,7 +5,7 @@ use crate::session::SessionManager;
 use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
 use serde_json::{json, Value};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 fn word_overlap(a: &str, b: &str) -> f64 {
     let a_lower = a.to_lowercase();
@@ -40,7 +40,7 @@ pub fn definition_predict() -> ToolDefinition {
 }
 pub async fn execute_predict(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let ctx = get_str(&args, "context")
         .ok_or_else(|| McpError::InvalidParams("context required".into()))?;
@@ -48,7 +48,7 @@ pub async fn execute_predict(
         .get("max_predictions")
         .and_then(|v| v.as_u64())
         .unwrap_or(10) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let mut scored: Vec<(u64, f64, String)> = graph
         .nodes()
@@ -85,12 +85,12 @@ pub fn definition_predict_preload() -> ToolDefinition {
 }
 pub async fn execute_predict_preload(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let ctx = get_str(&args, "context")
         .ok_or_else(|| McpError::InvalidParams("context required".into()))?;
     let count = args.get("count").and_then(|v| v.as_u64()).unwrap_or(5) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let mut scored: Vec<(u64, f64)> = graph
         .nodes()
@@ -125,7 +125,7 @@ pub fn definition_predict_accuracy() -> ToolDefinition {
 }
 pub async fn execute_predict_accuracy(
     args: Value,
-    _session: &Arc<Mutex<SessionManager>>,
+    _session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let predicted: std::collections::HashSet<u64> = args
         .get("predicted_ids")
@@ -170,7 +170,7 @@ pub fn definition_predict_feedback() -> ToolDefinition {
 }
 pub async fn execute_predict_feedback(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let pred_id = get_u64(&args, "prediction_id")
         .ok_or_else(|| McpError::InvalidParams("prediction_id required".into()))?;
@@ -179,7 +179,7 @@ pub async fn execute_predict_feedback(
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
     let feedback = get_str(&args, "feedback").unwrap_or_default();
-    let mut session = session.lock().await;
+    let mut session = session.write().await;
     if useful {
         if let Some(n) = session.graph_mut().get_node_mut(pred_id) {
             n.access_count += 1;
@@ -203,10 +203,10 @@ pub fn definition_prophecy() -> ToolDefinition {
 }
 pub async fn execute_prophecy(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let horizon = get_str(&args, "horizon").unwrap_or_else(|| "medium".into());
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
     let decay_multiplier = match horizon.as_str() {
@@ -242,11 +242,11 @@ pub fn definition_prophecy_similar() -> ToolDefinition {
 }
 pub async fn execute_prophecy_similar(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let target = graph
         .get_node(node_id)
@@ -276,9 +276,9 @@ pub fn definition_prophecy_regret() -> ToolDefinition {
 }
 pub async fn execute_prophecy_regret(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let filter_session = args
         .get("session_id")
@@ -304,11 +304,11 @@ pub fn definition_prophecy_track() -> ToolDefinition {
 }
 pub async fn execute_prophecy_track(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let node = graph
         .get_node(node_id)
@@ -331,13 +331,13 @@ pub fn definition_counterfactual_what_if() -> ToolDefinition {
 }
 pub async fn execute_counterfactual_what_if(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
     let alt = get_str(&args, "alternative_content")
         .ok_or_else(|| McpError::InvalidParams("alternative_content required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let node = graph
         .get_node(node_id)
@@ -361,13 +361,13 @@ pub fn definition_counterfactual_compare() -> ToolDefinition {
 }
 pub async fn execute_counterfactual_compare(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
     let a = get_str(&args, "scenario_a").unwrap_or_default();
     let b = get_str(&args, "scenario_b").unwrap_or_default();
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let _ = graph
         .get_node(node_id)
@@ -395,11 +395,11 @@ pub fn definition_counterfactual_insights() -> ToolDefinition {
 }
 pub async fn execute_counterfactual_insights(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let node = graph
         .get_node(node_id)
@@ -423,7 +423,7 @@ pub fn definition_counterfactual_best() -> ToolDefinition {
 }
 pub async fn execute_counterfactual_best(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
@@ -439,7 +439,7 @@ pub async fn execute_counterfactual_best(
     if scenarios.is_empty() {
         return Err(McpError::InvalidParams("scenarios required".into()));
     }
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let _ = graph
         .get_node(node_id)
@@ -478,7 +478,7 @@ pub fn definition_dejavu_check() -> ToolDefinition {
 }
 pub async fn execute_dejavu_check(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let content = get_str(&args, "content")
         .ok_or_else(|| McpError::InvalidParams("content required".into()))?;
@@ -486,7 +486,7 @@ pub async fn execute_dejavu_check(
         .get("threshold")
         .and_then(|v| v.as_f64())
         .unwrap_or(0.6);
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let matches: Vec<Value> = graph.nodes().iter().filter_map(|n| {
         let sim = word_overlap(&content, &n.content);
@@ -506,13 +506,13 @@ pub fn definition_dejavu_history() -> ToolDefinition {
 }
 pub async fn execute_dejavu_history(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let max = args
         .get("max_results")
         .and_then(|v| v.as_u64())
         .unwrap_or(20) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
     let mut recurring: Vec<Value> = Vec::new();
@@ -544,13 +544,13 @@ pub fn definition_dejavu_patterns() -> ToolDefinition {
 }
 pub async fn execute_dejavu_patterns(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let min_occ = args
         .get("min_occurrences")
         .and_then(|v| v.as_u64())
         .unwrap_or(2) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let mut patterns: std::collections::HashMap<String, Vec<u64>> =
         std::collections::HashMap::new();
@@ -582,7 +582,7 @@ pub fn definition_dejavu_feedback() -> ToolDefinition {
 }
 pub async fn execute_dejavu_feedback(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
@@ -590,7 +590,7 @@ pub async fn execute_dejavu_feedback(
         .get("was_true_dejavu")
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
-    let session = session.lock().await;
+    let session = session.read().await;
     let _ = session
         .graph()
         .get_node(node_id)
@@ -625,7 +625,7 @@ pub fn all_definitions() -> Vec<ToolDefinition> {
 pub async fn try_execute(
     name: &str,
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> Option<McpResult<ToolCallResult>> {
     match name {
         "memory_predict" => Some(execute_predict(args, session).await),
@@ -3,7 +3,7 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde_json::{json, Value};
 
@@ -63,14 +63,14 @@ pub fn definition_archaeology_dig() -> ToolDefinition {
 
 pub async fn execute_archaeology_dig(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let topic =
         get_str(&args, "topic").ok_or_else(|| McpError::InvalidParams("topic required".into()))?;
     let max_depth = get_u64(&args, "max_depth").unwrap_or(3) as usize;
     let time_start = get_u64(&args, "time_start");
     let time_end = get_u64(&args, "time_end");
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
 
@@ -162,12 +162,12 @@ pub fn definition_archaeology_artifacts() -> ToolDefinition {
 
 pub async fn execute_archaeology_artifacts(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
     let radius = get_u64(&args, "radius").unwrap_or(2) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let _center = graph
         .get_node(node_id)
@@ -228,7 +228,7 @@ pub fn definition_archaeology_reconstruct() -> ToolDefinition {
 
 pub async fn execute_archaeology_reconstruct(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let topic =
         get_str(&args, "topic").ok_or_else(|| McpError::InvalidParams("topic required".into()))?;
@@ -238,7 +238,7 @@ pub async fn execute_archaeology_reconstruct(
         .map(|arr| arr.iter().filter_map(|v| v.as_u64()).collect())
         .unwrap_or_default();
 
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
 
@@ -325,11 +325,11 @@ pub fn definition_archaeology_verify() -> ToolDefinition {
 
 pub async fn execute_archaeology_verify(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let node = graph
         .get_node(node_id)
@@ -408,9 +408,9 @@ pub fn definition_holographic_status() -> ToolDefinition {
 
 pub async fn execute_holographic_status(
     _args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let total = graph.node_count();
     let edges = graph.edge_count();
@@ -460,11 +460,11 @@ pub fn definition_holographic_reconstruct() -> ToolDefinition {
 
 pub async fn execute_holographic_reconstruct(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let node = graph
         .get_node(node_id)
@@ -527,11 +527,11 @@ pub fn definition_holographic_simulate() -> ToolDefinition {
 
 pub async fn execute_holographic_simulate(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let loss_pct = get_f64(&args, "loss_percentage")
         .ok_or_else(|| McpError::InvalidParams("loss_percentage required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let total = graph.node_count();
     let nodes_lost = ((total as f64 * loss_pct / 100.0).round() as usize).min(total);
@@ -579,9 +579,9 @@ pub fn definition_holographic_distribute() -> ToolDefinition {
 
 pub async fn execute_holographic_distribute(
     _args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let type_index = graph.type_index();
 
@@ -636,9 +636,9 @@ pub fn definition_immune_status() -> ToolDefinition {
 
 pub async fn execute_immune_status(
     _args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
     let total = nodes.len();
@@ -695,11 +695,11 @@ pub fn definition_immune_scan() -> ToolDefinition {
 
 pub async fn execute_immune_scan(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let scan_type = get_str(&args, "scan_type").unwrap_or_else(|| "full".into());
     let max_results = get_u64(&args, "max_results").unwrap_or(20) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
 
@@ -773,12 +773,12 @@ pub fn definition_immune_quarantine() -> ToolDefinition {
 
 pub async fn execute_immune_quarantine(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
     let reason = get_str(&args, "reason").unwrap_or_else(|| "manual quarantine".into());
-    let mut session = session.lock().await;
+    let mut session = session.write().await;
     let graph = session.graph_mut();
     let node = graph
         .get_node_mut(node_id)
@@ -806,12 +806,12 @@ pub fn definition_immune_release() -> ToolDefinition {
 
 pub async fn execute_immune_release(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_id = get_u64(&args, "node_id")
         .ok_or_else(|| McpError::InvalidParams("node_id required".into()))?;
     let confidence = get_f64(&args, "confidence").unwrap_or(0.5) as f32;
-    let mut session = session.lock().await;
+    let mut session = session.write().await;
     let graph = session.graph_mut();
     let node = graph
         .get_node_mut(node_id)
@@ -840,7 +840,7 @@ pub fn definition_immune_train() -> ToolDefinition {
 
 pub async fn execute_immune_train(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let threat_pattern = get_str(&args, "threat_pattern")
         .ok_or_else(|| McpError::InvalidParams("threat_pattern required".into()))?;
@@ -857,7 +857,7 @@ pub async fn execute_immune_train(
         "[ANTIBODY] type={} pattern={} examples={:?}",
         threat_type, threat_pattern, example_ids
     );
-    let mut session = session.lock().await;
+    let mut session = session.write().await;
     let (node_id, _) = session.add_event(EventType::Skill, &content, 0.95, vec![])?;
 
     Ok(ToolCallResult::json(&json!({
@@ -887,12 +887,12 @@ pub fn definition_phoenix_initiate() -> ToolDefinition {
 
 pub async fn execute_phoenix_initiate(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let reason = get_str(&args, "reason")
         .ok_or_else(|| McpError::InvalidParams("reason required".into()))?;
     let recovery_target = get_str(&args, "recovery_target").unwrap_or_else(|| "all".into());
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let current_state = graph.node_count();
 
@@ -929,11 +929,11 @@ pub fn definition_phoenix_gather() -> ToolDefinition {
 
 pub async fn execute_phoenix_gather(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let source = get_str(&args, "source").unwrap_or_else(|| "all".into());
     let max_traces = get_u64(&args, "max_traces").unwrap_or(50) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     let mut traces: Vec<Value> = Vec::new();
@@ -1015,11 +1015,11 @@ pub fn definition_phoenix_reconstruct() -> ToolDefinition {
 
 pub async fn execute_phoenix_reconstruct(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let strategy = get_str(&args, "strategy").unwrap_or_else(|| "balanced".into());
     let min_confidence = get_f64(&args, "min_confidence").unwrap_or(0.3) as f32;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
 
@@ -1080,9 +1080,9 @@ pub fn definition_phoenix_status() -> ToolDefinition {
 
 pub async fn execute_phoenix_status(
     _args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let total = graph.node_count();
     let edges = graph.edge_count();
@@ -1143,7 +1143,7 @@ pub fn all_definitions() -> Vec<ToolDefinition> {
 pub async fn try_execute(
     name: &str,
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> Option<McpResult<ToolCallResult>> {
     match name {
         "memory_archaeology_dig" => Some(execute_archaeology_dig(args, session).await),
@@ -3,7 +3,7 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde_json::{json, Value};
 
@@ -57,9 +57,9 @@ pub fn definition_singularity_status() -> ToolDefinition {
 
 pub async fn execute_singularity_status(
     _args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let total = graph.node_count();
     let edges = graph.edge_count();
@@ -113,12 +113,12 @@ pub fn definition_singularity_query() -> ToolDefinition {
 
 pub async fn execute_singularity_query(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let query =
         get_str(&args, "query").ok_or_else(|| McpError::InvalidParams("query required".into()))?;
     let max_results = get_u64(&args, "max_results").unwrap_or(20) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     let mut results: Vec<Value> = Vec::new();
@@ -167,7 +167,7 @@ pub fn definition_singularity_contribute() -> ToolDefinition {
 
 pub async fn execute_singularity_contribute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let node_ids: Vec<u64> = args
         .get("node_ids")
@@ -180,7 +180,7 @@ pub async fn execute_singularity_contribute(
         return Err(McpError::InvalidParams("node_ids required".into()));
     }
 
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let mut contributed: Vec<Value> = Vec::new();
     for &id in &node_ids {
@@ -217,12 +217,12 @@ pub fn definition_singularity_trust() -> ToolDefinition {
 
 pub async fn execute_singularity_trust(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let action = get_str(&args, "action").unwrap_or_else(|| "status".into());
     let source = get_str(&args, "source");
     let trust_level = get_f64(&args, "trust_level");
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     match action.as_str() {
@@ -270,12 +270,12 @@ pub fn definition_temporal_travel() -> ToolDefinition {
 
 pub async fn execute_temporal_travel(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let timestamp = get_u64(&args, "timestamp")
         .ok_or_else(|| McpError::InvalidParams("timestamp required".into()))?;
     let range = get_u64(&args, "range_seconds").unwrap_or(3600);
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     let start = timestamp.saturating_sub(range);
@@ -332,11 +332,11 @@ pub fn definition_temporal_project() -> ToolDefinition {
 
 pub async fn execute_temporal_project(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let hours = get_u64(&args, "hours_ahead").unwrap_or(24);
     let scenario = get_str(&args, "scenario").unwrap_or_else(|| "current_rate".into());
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
     let total = nodes.len();
@@ -392,13 +392,13 @@ pub fn definition_temporal_compare() -> ToolDefinition {
 
 pub async fn execute_temporal_compare(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let time_a = get_u64(&args, "time_a")
         .ok_or_else(|| McpError::InvalidParams("time_a required".into()))?;
     let time_b = get_u64(&args, "time_b")
         .ok_or_else(|| McpError::InvalidParams("time_b required".into()))?;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     let before_a: Vec<&_> = graph
@@ -452,10 +452,10 @@ pub fn definition_temporal_paradox() -> ToolDefinition {
 
 pub async fn execute_temporal_paradox(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let max_results = get_u64(&args, "max_results").unwrap_or(20) as usize;
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     let mut paradoxes: Vec<Value> = Vec::new();
@@ -534,7 +534,7 @@ pub fn definition_crystal_create() -> ToolDefinition {
 
 pub async fn execute_crystal_create(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let name =
         get_str(&args, "name").ok_or_else(|| McpError::InvalidParams("name required".into()))?;
@@ -548,7 +548,7 @@ pub async fn execute_crystal_create(
         })
         .unwrap_or_default();
 
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
 
@@ -627,13 +627,13 @@ pub fn definition_crystal_transfer() -> ToolDefinition {
 
 pub async fn execute_crystal_transfer(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let crystal_name = get_str(&args, "crystal_name")
         .ok_or_else(|| McpError::InvalidParams("crystal_name required".into()))?;
     let transfer_type = get_str(&args, "transfer_type").unwrap_or_else(|| "copy".into());
     let target = get_str(&args, "target").unwrap_or_else(|| "unspecified".into());
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     Ok(ToolCallResult::json(&json!({
@@ -661,12 +661,12 @@ pub fn definition_crystal_inspect() -> ToolDefinition {
 
 pub async fn execute_crystal_inspect(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let crystal_name = get_str(&args, "crystal_name")
         .ok_or_else(|| McpError::InvalidParams("crystal_name required".into()))?;
     let aspect = get_str(&args, "aspect").unwrap_or_else(|| "overview".into());
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let nodes = graph.nodes();
 
@@ -746,7 +746,7 @@ pub fn definition_crystal_merge() -> ToolDefinition {
 
 pub async fn execute_crystal_merge(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let names: Vec<String> = args
         .get("crystal_names")
@@ -758,7 +758,7 @@ pub async fn execute_crystal_merge(
         })
         .unwrap_or_default();
     let strategy = get_str(&args, "merge_strategy").unwrap_or_else(|| "union".into());
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     Ok(ToolCallResult::json(&json!({
@@ -789,9 +789,9 @@ pub fn definition_transcend_status() -> ToolDefinition {
 
 pub async fn execute_transcend_status(
     _args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let total = graph.node_count();
     let edges = graph.edge_count();
@@ -852,7 +852,7 @@ pub fn definition_transcend_distribute() -> ToolDefinition {
 
 pub async fn execute_transcend_distribute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let targets: Vec<String> = args
         .get("target_substrates")
@@ -864,7 +864,7 @@ pub async fn execute_transcend_distribute(
         })
         .unwrap_or_else(|| vec!["cloud".into(), "edge".into(), "peer".into()]);
 
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let total = graph.node_count();
 
@@ -902,9 +902,9 @@ pub fn definition_transcend_verify() -> ToolDefinition {
 
 pub async fn execute_transcend_verify(
     _args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let total = graph.node_count();
     let edges = graph.edge_count();
@@ -951,7 +951,7 @@ pub fn definition_transcend_eternal() -> ToolDefinition {
 
 pub async fn execute_transcend_eternal(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let aspect_name = get_str(&args, "aspect_name")
         .ok_or_else(|| McpError::InvalidParams("aspect_name required".into()))?;
@@ -962,7 +962,7 @@ pub async fn execute_transcend_eternal(
         .unwrap_or_default();
     let method = get_str(&args, "preservation_method").unwrap_or_else(|| "replication".into());
 
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
 
     let preserved: Vec<Value> = if node_ids.is_empty() {
@@ -1034,7 +1034,7 @@ pub fn all_definitions() -> Vec<ToolDefinition> {
 pub async fn try_execute(
     name: &str,
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> Option<McpResult<ToolCallResult>> {
     match name {
         "memory_singularity_status" => Some(execute_singularity_status(args, session).await),
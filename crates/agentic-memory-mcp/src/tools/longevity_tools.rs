@@ -4,7 +4,7 @@
 //! consolidation, integrity, significance, backup, and embedding status.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde_json::Value;
 
@@ -126,7 +126,7 @@ pub fn all_definitions() -> Vec<ToolDefinition> {
 pub async fn try_execute(
     name: &str,
     args: Value,
-    _session: &Arc<Mutex<SessionManager>>,
+    _session: &Arc<RwLock<SessionManager>>,
 ) -> Option<McpResult<ToolCallResult>> {
     match name {
         "memory_longevity_stats" => Some(execute_longevity_stats(args).await),
@@ -164,12 +164,7 @@ fn ok_result(content: Value) -> McpResult<ToolCallResult> {
 }
 
 fn err_result(msg: &str) -> McpResult<ToolCallResult> {
-    Ok(ToolCallResult {
-        content: vec![crate::types::ToolContent::Text {
-            text: msg.to_string(),
-        }],
-        is_error: Some(true),
-    })
+    Ok(ToolCallResult::error(msg.to_string()))
 }
 
 async fn execute_longevity_stats(args: Value) -> McpResult<ToolCallResult> {
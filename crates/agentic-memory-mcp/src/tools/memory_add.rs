@@ -1,25 +1,38 @@
 //! Tool: memory_add — Add a cognitive event to the memory graph.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
 
 use agentic_memory::{EdgeType, EventType};
 
-use crate::session::SessionManager;
-use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+use crate::session::blobs::Blob;
+use crate::session::{SessionManager, ToolDefaults};
+use crate::types::{
+    node_json, parse_edge_types, McpError, McpResult, ToolCallResult, ToolDefinition,
+};
 
 /// Input parameters for memory_add.
 #[derive(Debug, Deserialize)]
 struct AddParams {
-    event_type: String,
+    event_type: Option<String>,
     content: String,
-    #[serde(default = "default_confidence")]
-    confidence: f32,
+    confidence: Option<f32>,
     #[serde(default)]
     edges: Vec<EdgeParam>,
+    #[serde(default)]
+    dedupe: bool,
+    idempotency_key: Option<String>,
+    #[serde(default)]
+    feature_vec: Vec<f32>,
+    created_at: Option<u64>,
+    blob: Option<String>,
+    #[serde(default = "default_blob_encoding")]
+    blob_encoding: String,
+    blob_mime_type: Option<String>,
+    expected_version: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -30,16 +43,31 @@ struct EdgeParam {
     weight: f32,
 }
 
-fn default_confidence() -> f32 {
-    0.9
-}
-
 fn default_weight() -> f32 {
     1.0
 }
 
+fn default_blob_encoding() -> String {
+    "base64".to_string()
+}
+
 /// Return the tool definition for memory_add.
 pub fn definition() -> ToolDefinition {
+    definition_with(&ToolDefaults::default())
+}
+
+/// The tool definition for memory_add as seen by a session with `defaults`.
+pub fn definition_with(defaults: &ToolDefaults) -> ToolDefinition {
+    let (event_type_description, required) = match defaults.event_type {
+        Some(event_type) => (
+            format!("Type of cognitive event (default: {})", event_type.name()),
+            json!(["content"]),
+        ),
+        None => (
+            "Type of cognitive event".to_string(),
+            json!(["event_type", "content"]),
+        ),
+    };
     ToolDefinition {
         name: "memory_add".to_string(),
         description: Some("Add a new cognitive event to the memory graph".to_string()),
@@ -49,7 +77,7 @@ pub fn definition() -> ToolDefinition {
                 "event_type": {
                     "type": "string",
                     "enum": ["fact", "decision", "inference", "correction", "skill", "episode"],
-                    "description": "Type of cognitive event"
+                    "description": event_type_description
                 },
                 "content": {
                     "type": "string",
@@ -59,7 +87,7 @@ pub fn definition() -> ToolDefinition {
                     "type": "number",
                     "minimum": 0.0,
                     "maximum": 1.0,
-                    "default": 0.9,
+                    "default": defaults.confidence,
                     "description": "Confidence level (0.0 to 1.0)"
                 },
                 "edges": {
@@ -76,9 +104,45 @@ pub fn definition() -> ToolDefinition {
                         },
                         "required": ["target_id", "edge_type"]
                     }
+                },
+                "dedupe": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "Reuse an existing node with identical content and event_type instead of creating a duplicate"
+                },
+                "idempotency_key": {
+                    "type": "string",
+                    "description": "Client-chosen key; a retry with the same key returns the original node instead of creating another"
+                },
+                "feature_vec": {
+                    "type": "array",
+                    "items": { "type": "number" },
+                    "description": "Optional embedding; its length must equal the graph dimension"
+                },
+                "created_at": {
+                    "type": "integer",
+                    "description": "Creation time in Unix epoch microseconds, to preserve original timestamps on import (default: now)"
+                },
+                "blob": {
+                    "type": "string",
+                    "description": "Optional binary attachment (e.g. an external embedding, a thumbnail, or a structured record), encoded per blob_encoding"
+                },
+                "blob_encoding": {
+                    "type": "string",
+                    "enum": ["base64", "hex"],
+                    "default": "base64",
+                    "description": "Encoding of blob"
+                },
+                "blob_mime_type": {
+                    "type": "string",
+                    "description": "MIME type of the attachment (default: application/octet-stream)"
+                },
+                "expected_version": {
+                    "type": "integer",
+                    "description": "Fail with a version conflict unless the graph is still at this graph_version"
                 }
             },
-            "required": ["event_type", "content"]
+            "required": required
         }),
     }
 }
@@ -86,37 +150,97 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_add tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: AddParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
 
-    // Validate confidence is within [0.0, 1.0]
-    if !(0.0..=1.0).contains(&params.confidence) {
-        return Err(McpError::InvalidParams(format!(
-            "confidence must be between 0.0 and 1.0, got {}",
-            params.confidence
-        )));
-    }
-
-    let event_type = EventType::from_name(&params.event_type).ok_or_else(|| {
-        McpError::InvalidParams(format!("Unknown event type: {}", params.event_type))
-    })?;
+    let defaults = session.read().await.tool_defaults().clone();
+    let event_type = match params.event_type.as_deref() {
+        Some(name) => EventType::from_name(name)
+            .ok_or_else(|| McpError::InvalidParams(format!("Unknown event type: {name}")))?,
+        None => defaults.event_type.ok_or_else(|| {
+            McpError::InvalidParams(
+                "event_type is required (no default event type is configured)".to_string(),
+            )
+        })?,
+    };
 
+    let edge_names: Vec<&str> = params.edges.iter().map(|e| e.edge_type.as_str()).collect();
     let edges: Vec<(u64, EdgeType, f32)> = params
         .edges
         .iter()
-        .map(|e| {
-            let edge_type = EdgeType::from_name(&e.edge_type).ok_or_else(|| {
-                McpError::InvalidParams(format!("Unknown edge type: {}", e.edge_type))
-            })?;
-            Ok((e.target_id, edge_type, e.weight))
-        })
-        .collect::<McpResult<Vec<_>>>()?;
+        .zip(parse_edge_types(&edge_names)?)
+        .map(|(e, edge_type)| (e.target_id, edge_type, e.weight))
+        .collect();
+    let blob = params
+        .blob
+        .as_deref()
+        .map(|data| Blob::decode(data, &params.blob_encoding))
+        .transpose()?;
+
+    let mut session = session.write().await;
+
+    if let Some(key) = params.idempotency_key.as_deref() {
+        if let Some(existing_id) = session.idempotent_node(key) {
+            return Ok(ToolCallResult::json(&json!({
+                "node_id": existing_id,
+                "event_type": event_type.name(),
+                "edges_created": 0,
+                "idempotent_replay": true,
+                "node": stored_node(&session, existing_id),
+                "graph_version": session.graph_version(),
+            })));
+        }
+    }
+    session.check_version(params.expected_version)?;
+
+    let (content, truncated) = session.enforce_content_limit(&params.content)?;
+    let confidence =
+        session.normalize_confidence(params.confidence.unwrap_or(defaults.confidence))?;
+    if !params.feature_vec.is_empty() {
+        session.check_vector_dimension("feature_vec", params.feature_vec.len())?;
+    }
+    if let Some(bytes) = &blob {
+        session.check_blob_size(bytes.len())?;
+    }
 
-    let mut session = session.lock().await;
-    let (node_id, mut edges_created) =
-        session.add_event(event_type, &params.content, params.confidence, edges)?;
+    if params.dedupe {
+        if let Some(existing_id) = session.find_duplicate(event_type, &content) {
+            session.touch_node(existing_id)?;
+            // The caller's edges and attachment still apply to the node reused.
+            let edges_created = session.add_edges(existing_id, &edges)?;
+            let blob_bytes = blob
+                .map(|bytes| {
+                    session
+                        .attach_blob(existing_id, &bytes, params.blob_mime_type)
+                        .map(|b| b.size)
+                })
+                .transpose()?;
+            if let Some(key) = params.idempotency_key.as_deref() {
+                session.record_idempotency_key(key, existing_id);
+            }
+            return Ok(ToolCallResult::json(&json!({
+                "node_id": existing_id,
+                "event_type": event_type.name(),
+                "edges_created": edges_created,
+                "deduped": true,
+                "truncated": truncated,
+                "blob_bytes": blob_bytes,
+                "node": stored_node(&session, existing_id),
+                "graph_version": session.graph_version(),
+            })));
+        }
+    }
+
+    let (node_id, mut edges_created) = session.add_event_at(
+        event_type,
+        &content,
+        confidence,
+        edges,
+        params.feature_vec,
+        params.created_at,
+    )?;
 
     // Splice this explicit add into the temporal chain.
     if let Some(prev_id) = session.last_temporal_node_id() {
@@ -126,9 +250,36 @@ pub async fn execute(
     }
     session.advance_temporal_chain(node_id);
 
+    let blob_bytes = blob
+        .map(|bytes| {
+            session
+                .attach_blob(node_id, &bytes, params.blob_mime_type)
+                .map(|b| b.size)
+        })
+        .transpose()?;
+
+    if let Some(key) = params.idempotency_key.as_deref() {
+        session.record_idempotency_key(key, node_id);
+    }
+
     Ok(ToolCallResult::json(&json!({
         "node_id": node_id,
-        "event_type": params.event_type,
-        "edges_created": edges_created
+        "event_type": event_type.name(),
+        "edges_created": edges_created,
+        "confidence": confidence,
+        "deduped": false,
+        "truncated": truncated,
+        "blob_bytes": blob_bytes,
+        "node": stored_node(&session, node_id),
+        "graph_version": session.graph_version(),
     })))
 }
+
+/// The node as persisted, so clients need no follow-up read.
+fn stored_node(session: &SessionManager, id: u64) -> Value {
+    session
+        .graph()
+        .get_node(id)
+        .map(node_json)
+        .unwrap_or(Value::Null)
+}
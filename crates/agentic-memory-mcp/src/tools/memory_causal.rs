@@ -1,7 +1,7 @@
 //! Tool: memory_causal — Impact analysis: what depends on this node?
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -9,13 +9,15 @@ use serde_json::{json, Value};
 use agentic_memory::{CausalParams, EdgeType};
 
 use crate::session::SessionManager;
-use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+use crate::types::{parse_edge_types, McpError, McpResult, ToolCallResult, ToolDefinition};
 
 #[derive(Debug, Deserialize)]
 struct CausalInputParams {
     node_id: u64,
     #[serde(default = "default_max_depth")]
     max_depth: u32,
+    #[serde(default)]
+    dependency_types: Vec<String>,
 }
 
 fn default_max_depth() -> u32 {
@@ -33,7 +35,13 @@ pub fn definition() -> ToolDefinition {
             "type": "object",
             "properties": {
                 "node_id": { "type": "integer" },
-                "max_depth": { "type": "integer", "default": 5 }
+                "max_depth": { "type": "integer", "default": 5 },
+                "dependency_types": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "default": ["caused_by", "supports"],
+                    "description": "Edge types that mean \"depends on\"; a node depends on the root when it has one of these edges pointing at it"
+                }
             },
             "required": ["node_id"]
         }),
@@ -43,18 +51,24 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_causal tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: CausalInputParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
 
+    let dependency_types: Vec<EdgeType> = if params.dependency_types.is_empty() {
+        vec![EdgeType::CausedBy, EdgeType::Supports]
+    } else {
+        parse_edge_types(&params.dependency_types)?
+    };
+
     let causal_params = CausalParams {
         node_id: params.node_id,
         max_depth: params.max_depth,
-        dependency_types: vec![EdgeType::CausedBy, EdgeType::Supports],
+        dependency_types: dependency_types.clone(),
     };
 
-    let session = session.lock().await;
+    let session = session.read().await;
 
     let result = session
         .query_engine()
@@ -78,9 +92,13 @@ pub async fn execute(
 
     Ok(ToolCallResult::json(&json!({
         "root_id": result.root_id,
+        "dependency_types": dependency_types.iter().map(EdgeType::name).collect::<Vec<_>>(),
         "dependent_count": result.dependents.len(),
         "affected_decisions": result.affected_decisions,
         "affected_inferences": result.affected_inferences,
         "dependents": dependents,
+        "complete": result.frontier == 0,
+        "truncated_at_depth": result.truncated_at_depth,
+        "frontier_size": result.frontier,
     })))
 }
@@ -0,0 +1,232 @@
+//! Tool: memory_clusters — Partition memories into connected topic clusters.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use agentic_memory::{CognitiveEvent, EdgeType};
+
+use crate::session::SessionManager;
+use crate::types::{
+    parse_edge_types, parse_event_types, McpError, McpResult, ToolCallResult, ToolDefinition,
+};
+
+#[derive(Debug, Deserialize)]
+struct ClustersParams {
+    #[serde(default)]
+    edge_types: Vec<String>,
+    #[serde(default)]
+    event_types: Vec<String>,
+    #[serde(default)]
+    session_ids: Vec<u32>,
+    #[serde(default = "default_min_size")]
+    min_size: usize,
+    #[serde(default = "default_max_clusters")]
+    max_clusters: usize,
+    #[serde(default = "default_representative")]
+    representative: String,
+}
+
+fn default_min_size() -> usize {
+    2
+}
+
+fn default_max_clusters() -> usize {
+    20
+}
+
+fn default_representative() -> String {
+    "degree".to_string()
+}
+
+/// Return the tool definition for memory_clusters.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_clusters".to_string(),
+        description: Some(
+            "Group memories into connected clusters (topics) over the chosen edge types, \
+             largest first, each with its size and a representative node"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "edge_types": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Edge types that connect a cluster (default: all except temporal_next)"
+                },
+                "event_types": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Only cluster nodes of these event types"
+                },
+                "session_ids": { "type": "array", "items": { "type": "integer" } },
+                "min_size": {
+                    "type": "integer",
+                    "default": 2,
+                    "description": "Smallest cluster listed; 1 also lists isolated nodes"
+                },
+                "max_clusters": { "type": "integer", "default": 20 },
+                "representative": {
+                    "type": "string",
+                    "enum": ["degree", "decay_score"],
+                    "default": "degree",
+                    "description": "Pick each cluster's representative by most connections or highest decay score"
+                }
+            }
+        }),
+    }
+}
+
+/// Execute the memory_clusters tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: ClustersParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+    let edge_types = if params.edge_types.is_empty() {
+        EdgeType::all()
+            .iter()
+            .copied()
+            .filter(|t| *t != EdgeType::TemporalNext)
+            .collect()
+    } else {
+        parse_edge_types(&params.edge_types)?
+    };
+    let event_types = parse_event_types(&params.event_types)?;
+    let by_decay_score = match params.representative.as_str() {
+        "degree" => false,
+        "decay_score" => true,
+        other => {
+            return Err(McpError::InvalidParams(format!(
+                "representative must be 'degree' or 'decay_score', got '{other}'"
+            )))
+        }
+    };
+
+    let session = session.read().await;
+    let session_ids = session.effective_session_ids(params.session_ids);
+    let graph = session.graph();
+
+    let members: Vec<&CognitiveEvent> = graph
+        .nodes()
+        .iter()
+        .filter(|n| event_types.is_empty() || event_types.contains(&n.event_type))
+        .filter(|n| session_ids.is_empty() || session_ids.contains(&n.session_id))
+        .collect();
+    let index: HashMap<u64, usize> = members.iter().enumerate().map(|(i, n)| (n.id, i)).collect();
+
+    let mut sets = UnionFind::new(members.len());
+    let mut degree = vec![0usize; members.len()];
+    for edge in graph.edges() {
+        if !edge_types.contains(&edge.edge_type) {
+            continue;
+        }
+        let (Some(&a), Some(&b)) = (index.get(&edge.source_id), index.get(&edge.target_id)) else {
+            continue;
+        };
+        degree[a] += 1;
+        degree[b] += 1;
+        sets.union(a, b);
+    }
+
+    let mut components: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..members.len() {
+        components.entry(sets.find(i)).or_default().push(i);
+    }
+    let singletons = components.values().filter(|c| c.len() == 1).count();
+    let mut clusters: Vec<Vec<usize>> = components
+        .into_values()
+        .filter(|c| c.len() >= params.min_size.max(1))
+        .collect();
+    // Largest first; ties by lowest node ID so the order is stable.
+    clusters.sort_by_key(|c| (std::cmp::Reverse(c.len()), members[c[0]].id));
+    let total_clusters = clusters.len();
+
+    let listed: Vec<Value> = clusters
+        .iter()
+        .take(params.max_clusters)
+        .map(|cluster| {
+            let rep = cluster
+                .iter()
+                .copied()
+                .max_by(|&a, &b| {
+                    let primary = if by_decay_score {
+                        members[a]
+                            .decay_score
+                            .partial_cmp(&members[b].decay_score)
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                    } else {
+                        degree[a].cmp(&degree[b])
+                    };
+                    primary.then(members[b].id.cmp(&members[a].id))
+                })
+                .unwrap_or(cluster[0]);
+            let mut type_counts: BTreeMap<&str, usize> = BTreeMap::new();
+            for &i in cluster {
+                *type_counts.entry(members[i].event_type.name()).or_default() += 1;
+            }
+            let node = members[rep];
+            json!({
+                "size": cluster.len(),
+                "type_counts": type_counts,
+                "representative": {
+                    "node_id": node.id,
+                    "event_type": node.event_type.name(),
+                    "content": node.content,
+                    "degree": degree[rep],
+                    "decay_score": node.decay_score,
+                },
+            })
+        })
+        .collect();
+
+    Ok(ToolCallResult::json(&json!({
+        "node_count": members.len(),
+        "total_clusters": total_clusters,
+        "singletons": singletons,
+        "count": listed.len(),
+        "clusters": listed,
+    })))
+}
+
+/// Disjoint sets over `0..n` with path halving and union by size.
+struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+    }
+}
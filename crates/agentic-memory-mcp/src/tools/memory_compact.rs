@@ -6,7 +6,7 @@
 use std::sync::Arc;
 
 use serde_json::{json, Value};
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use crate::session::SessionManager;
 use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
@@ -14,8 +14,9 @@ use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
 use super::{
     conversation_log, invention_collective, invention_infinite, invention_metamemory,
     invention_prophetic, invention_resurrection, invention_transcendent, memory_add, memory_causal,
-    memory_context, memory_correct, memory_evidence, memory_ground, memory_quality, memory_query,
-    memory_resolve, memory_session_resume, memory_similar, memory_stats, memory_suggest,
+    memory_context, memory_correct, memory_evidence, memory_find_connected, memory_ground,
+    memory_health, memory_quality, memory_query, memory_resolve, memory_retrieve,
+    memory_session_resume, memory_similar, memory_stats, memory_suggest, memory_tag,
     memory_temporal, memory_traverse, memory_workspace_add, memory_workspace_compare,
     memory_workspace_create, memory_workspace_list, memory_workspace_query, memory_workspace_xref,
     session_end, session_start,
@@ -50,7 +51,7 @@ pub fn definitions() -> Vec<ToolDefinition> {
         ToolDefinition {
             name: "memory_core".to_string(),
             description: Some(
-                "Compact core facade: add/query/quality/traverse/correct/resolve/context/similar/causal/temporal/stats/conversation_log".to_string(),
+                "Compact core facade: add/query/quality/traverse/correct/resolve/tag/context/similar/causal/temporal/stats/health/conversation_log".to_string(),
             ),
             input_schema: op_schema(
                 &[
@@ -59,13 +60,17 @@ pub fn definitions() -> Vec<ToolDefinition> {
                     "query".to_string(),
                     "quality".to_string(),
                     "traverse".to_string(),
+                    "find_connected".to_string(),
                     "correct".to_string(),
                     "resolve".to_string(),
+                    "tag".to_string(),
                     "context".to_string(),
                     "similar".to_string(),
+                    "retrieve".to_string(),
                     "causal".to_string(),
                     "temporal".to_string(),
                     "stats".to_string(),
+                    "health".to_string(),
                 ],
                 "Core memory operation",
             ),
@@ -188,7 +193,7 @@ fn with_memory_prefix(op: &str) -> String {
 pub async fn try_execute(
     name: &str,
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> Option<McpResult<ToolCallResult>> {
     if !matches!(
         name,
@@ -218,13 +223,17 @@ pub async fn try_execute(
             "query" => memory_query::execute(params, session).await,
             "quality" => memory_quality::execute(params, session).await,
             "traverse" => memory_traverse::execute(params, session).await,
+            "find_connected" => memory_find_connected::execute(params, session).await,
             "correct" => memory_correct::execute(params, session).await,
             "resolve" => memory_resolve::execute(params, session).await,
+            "tag" => memory_tag::execute(params, session).await,
             "context" => memory_context::execute(params, session).await,
             "similar" => memory_similar::execute(params, session).await,
+            "retrieve" => memory_retrieve::execute(params, session).await,
             "causal" => memory_causal::execute(params, session).await,
             "temporal" => memory_temporal::execute(params, session).await,
             "stats" => memory_stats::execute(params, session).await,
+            "health" => memory_health::execute(params, session).await,
             _ => Err(McpError::InvalidParams(format!(
                 "Unknown memory_core operation: {operation}"
             ))),
@@ -0,0 +1,84 @@
+//! Tool: memory_compact_chain — Collapse a supersedes chain onto its latest version.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::session::SessionManager;
+use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+
+#[derive(Debug, Deserialize)]
+struct CompactChainParams {
+    node_id: u64,
+    #[serde(default = "default_stale")]
+    stale: String,
+    expected_version: Option<u64>,
+}
+
+fn default_stale() -> String {
+    "archive".to_string()
+}
+
+/// Return the tool definition for memory_compact_chain.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_compact_chain".to_string(),
+        description: Some(
+            "Collapse a chain of corrections onto its latest version: move the older \
+             versions' edges to it and delete or archive the superseded intermediates"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "node_id": { "type": "integer", "description": "Any version in the chain" },
+                "stale": {
+                    "type": "string",
+                    "enum": ["archive", "delete"],
+                    "default": "archive",
+                    "description": "Keep older versions superseded directly by the latest one, or remove them"
+                },
+                "expected_version": {
+                    "type": "integer",
+                    "description": "Fail with a version conflict unless the graph is still at this graph_version"
+                }
+            },
+            "required": ["node_id"]
+        }),
+    }
+}
+
+/// Execute the memory_compact_chain tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: CompactChainParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+    let delete_stale = match params.stale.as_str() {
+        "archive" => false,
+        "delete" => true,
+        other => {
+            return Err(McpError::InvalidParams(format!(
+                "stale must be 'archive' or 'delete', got '{other}'"
+            )))
+        }
+    };
+
+    let mut session = session.write().await;
+    session.check_version(params.expected_version)?;
+    let compaction = session.compact_chain(params.node_id, delete_stale)?;
+
+    Ok(ToolCallResult::json(&json!({
+        "node_id": params.node_id,
+        "latest_id": compaction.latest_id,
+        "stale": params.stale,
+        "collapsed_count": compaction.collapsed.len(),
+        "collapsed_ids": compaction.collapsed,
+        "rewired_edges": compaction.rewired_edges,
+        "graph_version": session.graph_version(),
+    })))
+}
@@ -0,0 +1,80 @@
+//! Tool: memory_compare — Cosine similarity between two nodes' embeddings.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use agentic_memory::cosine_similarity;
+
+use crate::session::SessionManager;
+use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+
+#[derive(Debug, Deserialize)]
+struct CompareParams {
+    node_a: u64,
+    node_b: u64,
+}
+
+/// Return the tool definition for memory_compare.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_compare".to_string(),
+        description: Some(
+            "Cosine similarity between the stored embeddings of two specific nodes".to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "node_a": { "type": "integer", "description": "First node ID" },
+                "node_b": { "type": "integer", "description": "Second node ID" }
+            },
+            "required": ["node_a", "node_b"]
+        }),
+    }
+}
+
+/// Execute the memory_compare tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: CompareParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+    let session = session.read().await;
+    let graph = session.graph();
+    let a = graph
+        .get_node(params.node_a)
+        .ok_or(McpError::NodeNotFound(params.node_a))?;
+    let b = graph
+        .get_node(params.node_b)
+        .ok_or(McpError::NodeNotFound(params.node_b))?;
+
+    let is_zero = |v: &[f32]| v.iter().all(|&x| x == 0.0);
+    let a_missing = is_zero(&a.feature_vec);
+    let b_missing = is_zero(&b.feature_vec);
+
+    // A zero vector scores 0.0, which would read as "unrelated"; report no score instead.
+    let (similarity, reason) = if a_missing || b_missing {
+        (None, Some("missing_vector"))
+    } else if a.feature_vec.len() != b.feature_vec.len() {
+        (None, Some("dimension_mismatch"))
+    } else {
+        (
+            Some(cosine_similarity(&a.feature_vec, &b.feature_vec)),
+            None,
+        )
+    };
+
+    Ok(ToolCallResult::json(&json!({
+        "node_a": params.node_a,
+        "node_b": params.node_b,
+        "similarity": similarity,
+        "missing_vector": a_missing || b_missing,
+        "node_a_has_vector": !a_missing,
+        "node_b_has_vector": !b_missing,
+        "reason": reason,
+    })))
+}
@@ -1,19 +1,23 @@
 //! Tool: memory_context — Get full context (subgraph) around a node.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
 
 use crate::session::SessionManager;
-use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+use crate::types::{
+    edge_json, parse_edge_types, McpError, McpResult, ToolCallResult, ToolDefinition,
+};
 
 #[derive(Debug, Deserialize)]
 struct ContextParams {
     node_id: u64,
     #[serde(default = "default_depth")]
     depth: u32,
+    #[serde(default)]
+    edge_types: Vec<String>,
 }
 
 fn default_depth() -> u32 {
@@ -29,7 +33,12 @@ pub fn definition() -> ToolDefinition {
             "type": "object",
             "properties": {
                 "node_id": { "type": "integer" },
-                "depth": { "type": "integer", "default": 2, "minimum": 1, "maximum": 5 }
+                "depth": { "type": "integer", "default": 2, "minimum": 1, "maximum": 5 },
+                "edge_types": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Only expand along these edge types (default: all)"
+                }
             },
             "required": ["node_id"]
         }),
@@ -39,17 +48,28 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_context tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: ContextParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
 
-    let session = session.lock().await;
+    let edge_types = parse_edge_types(&params.edge_types)?;
 
-    let subgraph = session
-        .query_engine()
-        .context(session.graph(), params.node_id, params.depth)
-        .map_err(|e| McpError::AgenticMemory(format!("Context query failed: {e}")))?;
+    let session = session.read().await;
+
+    let subgraph = if edge_types.is_empty() {
+        session
+            .query_engine()
+            .context(session.graph(), params.node_id, params.depth)
+    } else {
+        session.query_engine().context_with_edge_types(
+            session.graph(),
+            params.node_id,
+            params.depth,
+            &edge_types,
+        )
+    }
+    .map_err(|e| McpError::AgenticMemory(format!("Context query failed: {e}")))?;
 
     let nodes: Vec<Value> = subgraph
         .nodes
@@ -65,22 +85,12 @@ pub async fn execute(
         })
         .collect();
 
-    let edges: Vec<Value> = subgraph
-        .edges
-        .iter()
-        .map(|e| {
-            json!({
-                "source_id": e.source_id,
-                "target_id": e.target_id,
-                "edge_type": e.edge_type.name(),
-                "weight": e.weight,
-            })
-        })
-        .collect();
+    let edges: Vec<Value> = subgraph.edges.iter().map(edge_json).collect();
 
     Ok(ToolCallResult::json(&json!({
         "center_id": subgraph.center_id,
         "depth": params.depth,
+        "edge_types": params.edge_types,
         "node_count": nodes.len(),
         "edge_count": edges.len(),
         "nodes": nodes,
@@ -1,28 +1,43 @@
 //! Tool: memory_correct — Record a correction to a previous belief.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
 
+use agentic_memory::{EdgeType, MemoryGraph, TextSearchParams};
+
 use crate::session::SessionManager;
 use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
 
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 struct CorrectParams {
-    old_node_id: u64,
+    old_node_id: Option<u64>,
+    old_content_query: Option<String>,
+    #[serde(default = "default_match_threshold")]
+    match_threshold: f32,
     new_content: String,
     #[serde(default = "default_confidence")]
     confidence: f32,
     reason: Option<String>,
+    #[serde(default)]
+    dry_run: bool,
+    expected_version: Option<u64>,
 }
 
 fn default_confidence() -> f32 {
     0.95
 }
 
+fn default_match_threshold() -> f32 {
+    0.3
+}
+
+/// Candidates listed in an ambiguous-match error.
+const MAX_AMBIGUOUS_CANDIDATES: usize = 5;
+
 /// Return the tool definition for memory_correct.
 pub fn definition() -> ToolDefinition {
     ToolDefinition {
@@ -35,11 +50,15 @@ pub fn definition() -> ToolDefinition {
             "type": "object",
             "properties": {
                 "old_node_id": { "type": "integer", "description": "ID of the node being corrected" },
+                "old_content_query": { "type": "string", "description": "Describe the belief to correct when its ID is unknown; must match exactly one live node" },
+                "match_threshold": { "type": "number", "default": 0.3, "description": "Minimum BM25 score for old_content_query matches" },
                 "new_content": { "type": "string", "description": "The correct information" },
                 "confidence": { "type": "number", "default": 0.95 },
-                "reason": { "type": "string", "description": "Optional explanation for the correction" }
+                "reason": { "type": "string", "description": "Optional explanation for the correction" },
+                "dry_run": { "type": "boolean", "default": false, "description": "Report which node would be superseded without changing anything" },
+                "expected_version": { "type": "integer", "description": "Fail with a version conflict unless the graph is still at this graph_version" }
             },
-            "required": ["old_node_id", "new_content"]
+            "required": ["new_content"]
         }),
     }
 }
@@ -47,24 +66,149 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_correct tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: CorrectParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
 
-    let mut session = session.lock().await;
+    let mut session = session.write().await;
+    session.check_version(params.expected_version)?;
+
+    let (old_node_id, matched_by) = match (params.old_node_id, &params.old_content_query) {
+        (Some(id), _) => {
+            // Verify the old node exists
+            if session.graph().get_node(id).is_none() {
+                return Err(McpError::NodeNotFound(id));
+            }
+            (id, "id")
+        }
+        (None, Some(query)) => {
+            session.ensure_text_index();
+            find_by_content(&session, query, params.match_threshold)?
+        }
+        (None, None) => {
+            return Err(McpError::InvalidParams(
+                "Either old_node_id or old_content_query is required".to_string(),
+            ))
+        }
+    };
 
-    // Verify the old node exists
-    if session.graph().get_node(params.old_node_id).is_none() {
-        return Err(McpError::NodeNotFound(params.old_node_id));
+    if params.dry_run {
+        let confidence = session.normalize_confidence(params.confidence)?;
+        let old_confidence = session.graph().get_node(old_node_id).map(|n| n.confidence);
+        return Ok(ToolCallResult::json(&json!({
+            "dry_run": true,
+            "new_node_id": null,
+            "old_node_id": old_node_id,
+            "matched_by": matched_by,
+            "supersedes": true,
+            "confidence": confidence,
+            "old_confidence": { "before": old_confidence, "after": 0.0 },
+            "reason": params.reason,
+            "graph_version": session.graph_version(),
+        })));
     }
 
-    let new_id = session.correct_node(params.old_node_id, &params.new_content)?;
+    let new_id = session.correct_node(old_node_id, &params.new_content, params.confidence)?;
 
     Ok(ToolCallResult::json(&json!({
+        "dry_run": false,
         "new_node_id": new_id,
-        "old_node_id": params.old_node_id,
+        "old_node_id": old_node_id,
+        "matched_by": matched_by,
         "supersedes": true,
+        "confidence": session.graph().get_node(new_id).map(|n| n.confidence),
         "reason": params.reason,
+        "graph_version": session.graph_version(),
     })))
 }
+
+/// Find the single live (not yet superseded) node described by `query`.
+///
+/// A case-insensitive substring match wins when exactly one node contains the
+/// query; otherwise BM25 matches scoring at least `threshold` are considered.
+/// More than one candidate is an error listing them so the caller can retry
+/// with an ID.
+fn find_by_content(
+    session: &SessionManager,
+    query: &str,
+    threshold: f32,
+) -> McpResult<(u64, &'static str)> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(McpError::InvalidParams(
+            "old_content_query must not be empty".to_string(),
+        ));
+    }
+    let graph = session.graph();
+
+    let needle = query.to_lowercase();
+    let substring: Vec<u64> = graph
+        .nodes()
+        .iter()
+        .filter(|n| is_live(graph, n.id) && n.content.to_lowercase().contains(&needle))
+        .map(|n| n.id)
+        .collect();
+    if substring.len() == 1 {
+        return Ok((substring[0], "substring"));
+    }
+    if substring.len() > 1 {
+        return Err(ambiguous(graph, query, &substring));
+    }
+
+    let matches = session
+        .query_engine()
+        .text_search(
+            graph,
+            graph.term_index.as_ref(),
+            graph.doc_lengths.as_ref(),
+            TextSearchParams {
+                query: query.to_string(),
+                max_results: 50,
+                event_types: Vec::new(),
+                session_ids: Vec::new(),
+                min_score: threshold,
+            },
+        )
+        .map_err(|e| McpError::AgenticMemory(format!("Content match failed: {e}")))?;
+    let scored: Vec<u64> = matches
+        .iter()
+        .map(|m| m.node_id)
+        .filter(|&id| is_live(graph, id))
+        .collect();
+
+    match scored.as_slice() {
+        [id] => Ok((*id, "text_search")),
+        [] => Err(McpError::InvalidParams(format!(
+            "No live memory matches '{query}'"
+        ))),
+        _ => Err(ambiguous(graph, query, &scored)),
+    }
+}
+
+fn is_live(graph: &MemoryGraph, id: u64) -> bool {
+    !graph
+        .edges_to(id)
+        .iter()
+        .any(|e| e.edge_type == EdgeType::Supersedes)
+}
+
+fn ambiguous(graph: &MemoryGraph, query: &str, ids: &[u64]) -> McpError {
+    let candidates: Vec<String> = ids
+        .iter()
+        .take(MAX_AMBIGUOUS_CANDIDATES)
+        .filter_map(|&id| graph.get_node(id))
+        .map(|n| {
+            format!(
+                "{} ({:?})",
+                n.id,
+                n.content.chars().take(60).collect::<String>()
+            )
+        })
+        .collect();
+    McpError::InvalidParams(format!(
+        "'{query}' matches {} live memories; pass old_node_id. Candidates: {}",
+        ids.len(),
+        candidates.join(", ")
+    ))
+}
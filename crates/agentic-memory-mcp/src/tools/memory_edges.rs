@@ -0,0 +1,115 @@
+//! Tool: memory_edges — List edges across the graph, filtered by type and weight.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::session::SessionManager;
+use crate::types::{
+    edge_json, parse_edge_types, McpError, McpResult, ToolCallResult, ToolDefinition,
+};
+
+#[derive(Debug, Deserialize)]
+struct EdgesParams {
+    #[serde(default)]
+    edge_types: Vec<String>,
+    min_weight: Option<f32>,
+    max_weight: Option<f32>,
+    node_id: Option<u64>,
+    #[serde(default = "default_max_results")]
+    max_results: usize,
+}
+
+fn default_max_results() -> usize {
+    100
+}
+
+/// Return the tool definition for memory_edges.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_edges".to_string(),
+        description: Some(
+            "List edges filtered by type and weight range, strongest first".to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "edge_types": {
+                    "type": "array",
+                    "items": {
+                        "type": "string",
+                        "enum": ["caused_by", "derived_from", "supports", "contradicts", "supersedes", "related_to", "part_of", "temporal_next"]
+                    },
+                    "description": "Only these edge types (default: all)"
+                },
+                "min_weight": { "type": "number", "minimum": 0.0, "maximum": 1.0 },
+                "max_weight": { "type": "number", "minimum": 0.0, "maximum": 1.0 },
+                "node_id": {
+                    "type": "integer",
+                    "description": "Only edges that start or end at this node"
+                },
+                "max_results": { "type": "integer", "default": 100 }
+            }
+        }),
+    }
+}
+
+/// Execute the memory_edges tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: EdgesParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+    let edge_types = parse_edge_types(&params.edge_types)?;
+    let min_weight = params.min_weight.unwrap_or(0.0);
+    let max_weight = params.max_weight.unwrap_or(1.0);
+    if min_weight > max_weight {
+        return Err(McpError::InvalidParams(format!(
+            "min_weight ({min_weight}) is greater than max_weight ({max_weight})"
+        )));
+    }
+
+    let session = session.read().await;
+    let graph = session.graph();
+    if let Some(node_id) = params.node_id {
+        if graph.get_node(node_id).is_none() {
+            return Err(McpError::NodeNotFound(node_id));
+        }
+    }
+
+    let mut matches: Vec<_> = graph
+        .edges()
+        .iter()
+        .filter(|e| edge_types.is_empty() || edge_types.contains(&e.edge_type))
+        .filter(|e| e.weight >= min_weight && e.weight <= max_weight)
+        .filter(|e| {
+            params
+                .node_id
+                .is_none_or(|id| e.source_id == id || e.target_id == id)
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        b.weight
+            .partial_cmp(&a.weight)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.source_id.cmp(&b.source_id))
+            .then(a.target_id.cmp(&b.target_id))
+    });
+
+    let total = matches.len();
+    let edges: Vec<Value> = matches
+        .into_iter()
+        .take(params.max_results)
+        .map(edge_json)
+        .collect();
+
+    Ok(ToolCallResult::json(&json!({
+        "total": total,
+        "count": edges.len(),
+        "edges": edges,
+    })))
+}
@@ -0,0 +1,166 @@
+//! Tool: memory_ego — The N-hop neighborhood of a node as an indexed
+//! adjacency payload for graph visualization.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use agentic_memory::{Edge, EdgeType};
+
+use crate::session::SessionManager;
+use crate::types::{parse_edge_types, McpError, McpResult, ToolCallResult, ToolDefinition};
+
+/// Largest radius accepted, matching `memory_context`'s depth limit.
+const MAX_RADIUS: u32 = 5;
+
+#[derive(Debug, Deserialize)]
+struct EgoParams {
+    center_id: u64,
+    #[serde(default = "default_radius")]
+    radius: u32,
+    #[serde(default)]
+    edge_types: Vec<String>,
+}
+
+fn default_radius() -> u32 {
+    1
+}
+
+/// Return the tool definition for memory_ego.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_ego".to_string(),
+        description: Some(
+            "Get the nodes within N hops of a node as a compact node list plus an edge \
+             list indexing into it, ready for graph visualization libraries"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "center_id": { "type": "integer", "description": "Node at the center" },
+                "radius": {
+                    "type": "integer",
+                    "default": 1,
+                    "minimum": 1,
+                    "maximum": MAX_RADIUS,
+                    "description": "Hops to include, following edges in either direction"
+                },
+                "edge_types": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Only follow and return these edge types (default: all)"
+                }
+            },
+            "required": ["center_id"]
+        }),
+    }
+}
+
+/// Execute the memory_ego tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: EgoParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+    if !(1..=MAX_RADIUS).contains(&params.radius) {
+        return Err(McpError::InvalidParams(format!(
+            "radius must be between 1 and {MAX_RADIUS}"
+        )));
+    }
+    let edge_types = if params.edge_types.is_empty() {
+        EdgeType::all().to_vec()
+    } else {
+        parse_edge_types(&params.edge_types)?
+    };
+
+    let session = session.read().await;
+    if session.graph().get_node(params.center_id).is_none() {
+        return Err(McpError::NodeNotFound(params.center_id));
+    }
+    let subgraph = session
+        .query_engine()
+        .context_with_edge_types(
+            session.graph(),
+            params.center_id,
+            params.radius,
+            &edge_types,
+        )
+        .map_err(|e| McpError::AgenticMemory(format!("Ego network query failed: {e}")))?;
+
+    let hops = hop_distances(params.center_id, &subgraph.edges);
+    let mut nodes: Vec<_> = subgraph.nodes.iter().collect();
+    nodes.sort_by_key(|node| (hops.get(&node.id).copied().unwrap_or(u32::MAX), node.id));
+    let index: HashMap<u64, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.id, i))
+        .collect();
+
+    let node_list: Vec<Value> = nodes
+        .iter()
+        .map(|node| {
+            json!({
+                "id": node.id,
+                "event_type": node.event_type.name(),
+                "content": node.content,
+                "confidence": node.confidence,
+                "hop": hops.get(&node.id),
+            })
+        })
+        .collect();
+    let edge_list: Vec<Value> = subgraph
+        .edges
+        .iter()
+        .filter_map(|edge| {
+            Some(json!({
+                "source": index.get(&edge.source_id)?,
+                "target": index.get(&edge.target_id)?,
+                "edge_type": edge.edge_type.name(),
+                "weight": edge.weight,
+            }))
+        })
+        .collect();
+
+    Ok(ToolCallResult::json(&json!({
+        "center_id": params.center_id,
+        "radius": params.radius,
+        "node_count": node_list.len(),
+        "edge_count": edge_list.len(),
+        "nodes": node_list,
+        "edges": edge_list,
+    })))
+}
+
+/// Fewest hops from `center` to each node, treating edges as undirected.
+fn hop_distances(center: u64, edges: &[Edge]) -> HashMap<u64, u32> {
+    let mut neighbors: HashMap<u64, Vec<u64>> = HashMap::new();
+    for edge in edges {
+        neighbors
+            .entry(edge.source_id)
+            .or_default()
+            .push(edge.target_id);
+        neighbors
+            .entry(edge.target_id)
+            .or_default()
+            .push(edge.source_id);
+    }
+
+    let mut hops = HashMap::from([(center, 0)]);
+    let mut queue = VecDeque::from([center]);
+    while let Some(id) = queue.pop_front() {
+        let next = hops[&id] + 1;
+        for &neighbor in neighbors.get(&id).into_iter().flatten() {
+            if let Entry::Vacant(slot) = hops.entry(neighbor) {
+                slot.insert(next);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    hops
+}
@@ -1,7 +1,7 @@
 //! Tool: memory_evidence — Get detailed evidence for a claim from memory.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -9,7 +9,7 @@ use serde_json::{json, Value};
 use agentic_memory::TextSearchParams;
 
 use crate::session::SessionManager;
-use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+use crate::types::{edge_json, McpError, McpResult, ToolCallResult, ToolDefinition};
 
 #[derive(Debug, Deserialize)]
 struct EvidenceParams {
@@ -52,7 +52,7 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_evidence tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: EvidenceParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
@@ -64,7 +64,8 @@ pub async fn execute(
         })));
     }
 
-    let session = session.lock().await;
+    SessionManager::prepare_text_index(session).await;
+    let session = session.read().await;
     let graph = session.graph();
 
     let results = session
@@ -88,28 +89,13 @@ pub async fn execute(
         .filter_map(|m| {
             graph.get_node(m.node_id).map(|node| {
                 // Get related edges
-                let outgoing: Vec<Value> = graph
-                    .edges_from(node.id)
-                    .iter()
-                    .map(|e| {
-                        json!({
-                            "target_id": e.target_id,
-                            "edge_type": e.edge_type.name(),
-                            "weight": e.weight,
-                        })
-                    })
-                    .collect();
+                let outgoing: Vec<Value> =
+                    graph.edges_from(node.id).iter().map(edge_json).collect();
 
                 let incoming: Vec<Value> = graph
                     .edges_to(node.id)
                     .iter()
-                    .map(|e| {
-                        json!({
-                            "source_id": e.source_id,
-                            "edge_type": e.edge_type.name(),
-                            "weight": e.weight,
-                        })
-                    })
+                    .map(|e| edge_json(e))
                     .collect();
 
                 json!({
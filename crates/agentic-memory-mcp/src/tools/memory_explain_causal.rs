@@ -0,0 +1,307 @@
+//! Tool: memory_explain_causal — Why do I believe this? A node's causes and
+//! support, rendered as a reasoning chain.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use agentic_memory::{CognitiveEvent, EdgeType, MemoryGraph};
+
+use crate::session::SessionManager;
+use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+
+#[derive(Debug, Deserialize)]
+struct ExplainCausalParams {
+    node_id: u64,
+    #[serde(default = "default_max_depth")]
+    max_depth: u32,
+    #[serde(default = "default_include_content")]
+    include_content: bool,
+}
+
+fn default_max_depth() -> u32 {
+    5
+}
+
+fn default_include_content() -> bool {
+    true
+}
+
+/// Longest content excerpt quoted in the explanation, in characters.
+const EXCERPT_CHARS: usize = 60;
+
+/// Return the tool definition for memory_explain_causal.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_explain_causal".to_string(),
+        description: Some(
+            "Explain why a node is believed: walk what caused it and what supports it, \
+             and return the ordered chain with a natural-language explanation"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "node_id": { "type": "integer" },
+                "max_depth": { "type": "integer", "default": 5 },
+                "include_content": {
+                    "type": "boolean",
+                    "default": true,
+                    "description": "Quote a short excerpt of each node's content in the explanation"
+                }
+            },
+            "required": ["node_id"]
+        }),
+    }
+}
+
+/// The reasons behind one node: what it was caused by (its outgoing
+/// `caused_by` edges) and what supports it (incoming `supports` edges).
+struct Reasons {
+    caused_by: Vec<u64>,
+    supported_by: Vec<u64>,
+}
+
+impl Reasons {
+    fn of(graph: &MemoryGraph, node_id: u64) -> Self {
+        let mut seen = HashSet::new();
+        let caused_by = graph
+            .edges_from(node_id)
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::CausedBy && e.target_id != node_id)
+            .map(|e| e.target_id)
+            .filter(|id| graph.get_node(*id).is_some() && seen.insert(*id))
+            .collect();
+        seen.clear();
+        let supported_by = graph
+            .edges_to(node_id)
+            .into_iter()
+            .filter(|e| e.edge_type == EdgeType::Supports && e.source_id != node_id)
+            .map(|e| e.source_id)
+            .filter(|id| graph.get_node(*id).is_some() && seen.insert(*id))
+            .collect();
+        Self {
+            caused_by,
+            supported_by,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.caused_by.is_empty() && self.supported_by.is_empty()
+    }
+
+    fn ids(&self) -> impl Iterator<Item = u64> + '_ {
+        self.caused_by.iter().chain(&self.supported_by).copied()
+    }
+}
+
+/// Execute the memory_explain_causal tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: ExplainCausalParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+    let session = session.read().await;
+    let graph = session.graph();
+    if graph.get_node(params.node_id).is_none() {
+        return Err(McpError::NodeNotFound(params.node_id));
+    }
+
+    // Breadth-first over reasons, so the chain lists nearer causes first.
+    let mut order: Vec<(u64, u32)> = Vec::new();
+    let mut reasons: HashMap<u64, Reasons> = HashMap::new();
+    let mut visited: HashSet<u64> = HashSet::from([params.node_id]);
+    let mut queue: VecDeque<(u64, u32)> = VecDeque::from([(params.node_id, 0)]);
+    let mut frontier: HashSet<u64> = HashSet::new();
+    while let Some((id, depth)) = queue.pop_front() {
+        order.push((id, depth));
+        let found = Reasons::of(graph, id);
+        if depth >= params.max_depth {
+            frontier.extend(found.ids().filter(|r| !visited.contains(r)));
+            continue;
+        }
+        for reason in found.ids() {
+            if visited.insert(reason) {
+                queue.push_back((reason, depth + 1));
+            }
+        }
+        reasons.insert(id, found);
+    }
+
+    let chain: Vec<Value> = order
+        .iter()
+        .filter_map(|&(id, depth)| {
+            let node = graph.get_node(id)?;
+            let found = reasons.get(&id);
+            Some(json!({
+                "id": node.id,
+                "event_type": node.event_type.name(),
+                "content": node.content,
+                "confidence": node.confidence,
+                "depth": depth,
+                "caused_by": found.map(|r| r.caused_by.clone()).unwrap_or_default(),
+                "supported_by": found.map(|r| r.supported_by.clone()).unwrap_or_default(),
+            }))
+        })
+        .collect();
+
+    let mut writer = Explainer {
+        graph,
+        reasons: &reasons,
+        include_content: params.include_content,
+        mentioned: HashSet::new(),
+        explained: HashSet::new(),
+    };
+    let explanation = writer.explain(params.node_id, &order);
+
+    Ok(ToolCallResult::json(&json!({
+        "node_id": params.node_id,
+        "explanation": explanation,
+        "count": chain.len(),
+        "chain": chain,
+        "complete": frontier.is_empty(),
+        "truncated_at_depth": (!frontier.is_empty()).then_some(params.max_depth),
+        "frontier_size": frontier.len(),
+    })))
+}
+
+/// Renders the reasoning chain as sentences, naming each node with a
+/// content excerpt the first time it appears.
+struct Explainer<'a> {
+    graph: &'a MemoryGraph,
+    reasons: &'a HashMap<u64, Reasons>,
+    include_content: bool,
+    mentioned: HashSet<u64>,
+    explained: HashSet<u64>,
+}
+
+impl Explainer<'_> {
+    fn explain(&mut self, root: u64, order: &[(u64, u32)]) -> String {
+        let reasons = self.reasons;
+        let has_reasons = |id: &u64| reasons.get(id).is_some_and(|r| !r.is_empty());
+        if !has_reasons(&root) {
+            let label = self.label(root);
+            return format!("{label} has no recorded causes or support.");
+        }
+        let pending: Vec<u64> = order
+            .iter()
+            .map(|&(id, _)| id)
+            .filter(has_reasons)
+            .collect();
+        let mut sentences = Vec::new();
+        for id in pending {
+            if self.explained.contains(&id) {
+                continue;
+            }
+            let subject = self.label(id);
+            let clause = self.clause(id);
+            sentences.push(format!("{subject} {clause}."));
+        }
+        sentences.join(" ")
+    }
+
+    /// "was caused by X and supported by Y", continued with ", which ..."
+    /// while the chain runs through a single reason.
+    fn clause(&mut self, id: u64) -> String {
+        self.explained.insert(id);
+        let reasons = self.reasons;
+        let Some(found) = reasons.get(&id) else {
+            return String::new();
+        };
+        let mut parts = Vec::new();
+        if !found.caused_by.is_empty() {
+            parts.push(format!("was caused by {}", self.list(&found.caused_by)));
+        }
+        if !found.supported_by.is_empty() {
+            let verb = if parts.is_empty() {
+                "was supported by"
+            } else {
+                "supported by"
+            };
+            parts.push(format!("{verb} {}", self.list(&found.supported_by)));
+        }
+        let mut clause = parts.join(" and ");
+
+        let only: Vec<u64> = found.ids().collect();
+        if let [next] = only[..] {
+            let continues = reasons.get(&next).is_some_and(|r| !r.is_empty());
+            if continues && !self.explained.contains(&next) {
+                clause.push_str(", which ");
+                clause.push_str(&self.clause(next));
+            }
+        }
+        clause
+    }
+
+    /// "Facts #2 and #3", grouping consecutive nodes of one event type.
+    fn list(&mut self, ids: &[u64]) -> String {
+        let mut groups: Vec<(String, Vec<u64>)> = Vec::new();
+        for &id in ids {
+            let type_name = self.type_name(id);
+            match groups.last_mut() {
+                Some((name, members)) if *name == type_name => members.push(id),
+                _ => groups.push((type_name, vec![id])),
+            }
+        }
+        let rendered: Vec<String> = groups
+            .into_iter()
+            .map(|(name, members)| {
+                let plural = if members.len() > 1 { "s" } else { "" };
+                let refs: Vec<String> = members.iter().map(|&id| self.reference(id)).collect();
+                format!("{name}{plural} {}", join_and(&refs))
+            })
+            .collect();
+        join_and(&rendered)
+    }
+
+    fn label(&mut self, id: u64) -> String {
+        format!("{} {}", self.type_name(id), self.reference(id))
+    }
+
+    /// `#7`, with a quoted excerpt on first mention.
+    fn reference(&mut self, id: u64) -> String {
+        let first = self.mentioned.insert(id);
+        match self.graph.get_node(id) {
+            Some(node) if first && self.include_content => {
+                format!("#{id} (\"{}\")", excerpt(node))
+            }
+            _ => format!("#{id}"),
+        }
+    }
+
+    fn type_name(&self, id: u64) -> String {
+        let name = self
+            .graph
+            .get_node(id)
+            .map(|n| n.event_type.name())
+            .unwrap_or("node");
+        let mut chars = name.chars();
+        chars
+            .next()
+            .map(|c| c.to_uppercase().chain(chars).collect())
+            .unwrap_or_default()
+    }
+}
+
+fn excerpt(node: &CognitiveEvent) -> String {
+    let content = node.content.trim().replace('"', "'");
+    if content.chars().count() <= EXCERPT_CHARS {
+        return content;
+    }
+    let cut: String = content.chars().take(EXCERPT_CHARS).collect();
+    format!("{}...", cut.trim_end())
+}
+
+/// "a", "a and b", "a, b and c".
+fn join_and(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {last}", rest.join(", ")),
+    }
+}
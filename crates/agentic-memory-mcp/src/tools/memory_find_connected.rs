@@ -0,0 +1,197 @@
+//! Tool: memory_find_connected — Find the nearest reachable nodes of given types.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use agentic_memory::{EdgeType, TraversalDirection, TraversalParams};
+
+use crate::session::SessionManager;
+use crate::types::{
+    parse_edge_types, parse_event_types, McpError, McpResult, ToolCallResult, ToolDefinition,
+};
+
+#[derive(Debug, Deserialize)]
+struct FindConnectedParams {
+    start_id: u64,
+    event_types: Vec<String>,
+    #[serde(default)]
+    edge_types: Vec<String>,
+    #[serde(default = "default_direction")]
+    direction: String,
+    #[serde(default = "default_max_depth")]
+    max_depth: u32,
+    #[serde(default = "default_stop_at_first")]
+    stop_at_first: bool,
+    #[serde(default = "default_max_results")]
+    max_results: usize,
+    min_confidence: Option<f32>,
+}
+
+fn default_direction() -> String {
+    "forward".to_string()
+}
+
+fn default_max_depth() -> u32 {
+    5
+}
+
+fn default_stop_at_first() -> bool {
+    true
+}
+
+fn default_max_results() -> usize {
+    20
+}
+
+/// Return the tool definition for memory_find_connected.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_find_connected".to_string(),
+        description: Some(
+            "Search outward from a node for the nearest reachable nodes of the given event types, e.g. the decision a fact ultimately supports"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "start_id": { "type": "integer", "description": "Starting node ID" },
+                "event_types": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Event types to look for (e.g. [\"decision\"])"
+                },
+                "edge_types": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Edge types to follow (default: all)"
+                },
+                "direction": { "type": "string", "enum": ["forward", "backward", "both"], "default": "forward" },
+                "max_depth": { "type": "integer", "default": 5 },
+                "stop_at_first": {
+                    "type": "boolean",
+                    "default": true,
+                    "description": "Return only the nearest match instead of every match"
+                },
+                "max_results": { "type": "integer", "default": 20 },
+                "min_confidence": { "type": "number" }
+            },
+            "required": ["start_id", "event_types"]
+        }),
+    }
+}
+
+/// Execute the memory_find_connected tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: FindConnectedParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+    let targets = parse_event_types(&params.event_types)?;
+    if targets.is_empty() {
+        return Err(McpError::InvalidParams(
+            "event_types must name at least one event type".to_string(),
+        ));
+    }
+
+    let edge_types: Vec<EdgeType> = if params.edge_types.is_empty() {
+        vec![
+            EdgeType::CausedBy,
+            EdgeType::Supports,
+            EdgeType::Contradicts,
+            EdgeType::Supersedes,
+            EdgeType::RelatedTo,
+            EdgeType::PartOf,
+            EdgeType::TemporalNext,
+        ]
+    } else {
+        parse_edge_types(&params.edge_types)?
+    };
+
+    let direction = match params.direction.as_str() {
+        "backward" => TraversalDirection::Backward,
+        "both" => TraversalDirection::Both,
+        _ => TraversalDirection::Forward,
+    };
+
+    let session = session.read().await;
+    let graph = session.graph();
+    if graph.get_node(params.start_id).is_none() {
+        return Err(McpError::NodeNotFound(params.start_id));
+    }
+
+    let result = session
+        .query_engine()
+        .traverse(
+            graph,
+            TraversalParams {
+                start_id: params.start_id,
+                edge_types,
+                direction,
+                max_depth: params.max_depth,
+                max_results: usize::MAX,
+                min_confidence: params.min_confidence.unwrap_or(0.0),
+            },
+        )
+        .map_err(|e| McpError::AgenticMemory(format!("Traversal failed: {e}")))?;
+
+    // Each traversed edge discovered one node one level deeper; remember the
+    // node it was reached from so the path back to the start can be rebuilt.
+    let mut parent: HashMap<u64, u64> = HashMap::new();
+    for edge in &result.edges_traversed {
+        let source_depth = result.depths.get(&edge.source_id).copied();
+        let target_depth = result.depths.get(&edge.target_id).copied();
+        match (source_depth, target_depth) {
+            (Some(s), Some(t)) if s + 1 == t => parent.insert(edge.target_id, edge.source_id),
+            _ => parent.insert(edge.source_id, edge.target_id),
+        };
+    }
+    let path_to = |id: u64| {
+        let mut path = vec![id];
+        let mut current = id;
+        while let Some(&prev) = parent.get(&current) {
+            path.push(prev);
+            current = prev;
+        }
+        path.reverse();
+        path
+    };
+
+    let limit = if params.stop_at_first {
+        1
+    } else {
+        params.max_results
+    };
+    // BFS order visits nearer nodes first, so the first match is the nearest.
+    let matches: Vec<Value> = result
+        .visited
+        .iter()
+        .filter(|&&id| id != params.start_id)
+        .filter_map(|&id| graph.get_node(id))
+        .filter(|node| targets.contains(&node.event_type))
+        .take(limit)
+        .map(|node| {
+            json!({
+                "id": node.id,
+                "event_type": node.event_type.name(),
+                "content": node.content,
+                "confidence": node.confidence,
+                "depth": result.depths.get(&node.id).copied().unwrap_or(0),
+                "path": path_to(node.id),
+            })
+        })
+        .collect();
+
+    Ok(ToolCallResult::json(&json!({
+        "start_id": params.start_id,
+        "visited_count": result.visited.len(),
+        "found": !matches.is_empty(),
+        "count": matches.len(),
+        "matches": matches,
+    })))
+}
@@ -1,7 +1,7 @@
 //! Tool: memory_ground — Verify a claim has memory backing (anti-hallucination).
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -52,7 +52,7 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_ground tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: GroundParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
@@ -66,7 +66,8 @@ pub async fn execute(
         })));
     }
 
-    let session = session.lock().await;
+    SessionManager::prepare_text_index(session).await;
+    let session = session.read().await;
     let graph = session.graph();
 
     // Use BM25 text search to find matching memories
@@ -0,0 +1,60 @@
+//! Tool: memory_health — Readiness of the memory graph for reads and writes.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde_json::{json, Value};
+
+use crate::session::SessionManager;
+use crate::types::{McpResult, ToolCallResult, ToolDefinition};
+
+/// Return the tool definition for memory_health.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_health".to_string(),
+        description: Some(
+            "Check readiness: graph loaded, memory file writable, last save error".to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {}
+        }),
+    }
+}
+
+/// Execute the memory_health tool.
+pub async fn execute(
+    _args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let session = session.read().await;
+    Ok(ToolCallResult::json(&report(&session)))
+}
+
+/// Readiness report shared with the HTTP `/health` and `/ready` endpoints.
+///
+/// `status` is `"ok"` only when the file is writable and the last save
+/// succeeded; otherwise it is `"degraded"`.
+pub fn report(session: &SessionManager) -> Value {
+    let can_write = session.can_write();
+    let last_error = session.last_error();
+    let status = if can_write && last_error.is_none() {
+        "ok"
+    } else {
+        "degraded"
+    };
+
+    json!({
+        "status": status,
+        "can_write": can_write,
+        "ephemeral": session.is_ephemeral(),
+        "file_exists": !session.is_ephemeral() && session.file_path().exists(),
+        "node_count": session.graph().node_count(),
+        "last_error": last_error,
+        "last_error_unix": session
+            .last_error_at()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs()),
+        "consecutive_save_failures": session.consecutive_save_failures(),
+    })
+}
@@ -0,0 +1,88 @@
+//! Tool: memory_history — List the versions of a belief along its supersedes chain.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::session::SessionManager;
+use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+
+#[derive(Debug, Deserialize)]
+struct HistoryParams {
+    node_id: u64,
+    #[serde(default)]
+    include_earlier: bool,
+}
+
+/// Return the tool definition for memory_history.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_history".to_string(),
+        description: Some(
+            "List every version of a belief along its supersedes chain, oldest first, \
+             to explain how it changed over time"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "node_id": { "type": "integer", "description": "Any version of the belief" },
+                "include_earlier": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "Also walk back to the original version instead of starting at node_id"
+                }
+            },
+            "required": ["node_id"]
+        }),
+    }
+}
+
+/// Execute the memory_history tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: HistoryParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+    let session = session.read().await;
+    let graph = session.graph();
+    if graph.get_node(params.node_id).is_none() {
+        return Err(McpError::NodeNotFound(params.node_id));
+    }
+
+    let versions = session
+        .query_engine()
+        .history(graph, params.node_id, params.include_earlier)
+        .map_err(|e| McpError::AgenticMemory(format!("History failed: {e}")))?;
+
+    let first_id = versions.first().map(|n| n.id).unwrap_or(params.node_id);
+    let latest_id = versions.last().map(|n| n.id).unwrap_or(params.node_id);
+    let versions: Vec<Value> = versions
+        .iter()
+        .enumerate()
+        .map(|(index, node)| {
+            json!({
+                "version": index + 1,
+                "id": node.id,
+                "event_type": node.event_type.name(),
+                "content": node.content,
+                "confidence": node.confidence,
+                "session_id": node.session_id,
+                "created_at": node.created_at,
+            })
+        })
+        .collect();
+
+    Ok(ToolCallResult::json(&json!({
+        "node_id": params.node_id,
+        "first_id": first_id,
+        "latest_id": latest_id,
+        "is_latest": latest_id == params.node_id,
+        "count": versions.len(),
+        "versions": versions,
+    })))
+}
@@ -0,0 +1,229 @@
+//! Tool: memory_import_notes — Seed memory from a plaintext or markdown
+//! document, one fact per paragraph or heading section.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use agentic_memory::EventType;
+
+use crate::session::SessionManager;
+use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+
+/// Most chunks a single import may create.
+const MAX_IMPORT_CHUNKS: usize = 1000;
+
+#[derive(Debug, Deserialize)]
+struct ImportNotesParams {
+    text: String,
+    #[serde(default = "default_split")]
+    split: String,
+    #[serde(default = "default_confidence")]
+    confidence: f32,
+    #[serde(default = "default_link")]
+    link: bool,
+}
+
+fn default_split() -> String {
+    "paragraph".to_string()
+}
+
+fn default_confidence() -> f32 {
+    0.9
+}
+
+fn default_link() -> bool {
+    true
+}
+
+/// A piece of the document that becomes one node.
+#[derive(Debug, PartialEq)]
+struct Chunk {
+    /// Text of the heading that opens the chunk, without the `#` marks.
+    heading: Option<String>,
+    /// Heading depth (number of `#`), 0 for text before the first heading.
+    level: usize,
+    text: String,
+}
+
+/// Return the tool definition for memory_import_notes.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_import_notes".to_string(),
+        description: Some(
+            "Import a plaintext or markdown document as fact nodes, one per paragraph \
+             or heading section, linked in document order"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "text": {
+                    "type": "string",
+                    "description": "The document to import"
+                },
+                "split": {
+                    "type": "string",
+                    "enum": ["paragraph", "heading"],
+                    "default": "paragraph",
+                    "description": "Chunk at blank lines, or at markdown headings (each section keeps its heading)"
+                },
+                "confidence": {
+                    "type": "number",
+                    "minimum": 0.0,
+                    "maximum": 1.0,
+                    "default": 0.9,
+                    "description": "Confidence of every imported fact"
+                },
+                "link": {
+                    "type": "boolean",
+                    "default": true,
+                    "description": "Link each chunk to the next with a temporal_next edge"
+                }
+            },
+            "required": ["text"]
+        }),
+    }
+}
+
+/// Execute the memory_import_notes tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: ImportNotesParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+    let chunks = match params.split.as_str() {
+        "paragraph" => split_paragraphs(&params.text),
+        "heading" => split_headings(&params.text),
+        other => {
+            return Err(McpError::InvalidParams(format!(
+                "Unknown split: {other} (valid: paragraph, heading)"
+            )))
+        }
+    };
+    if chunks.is_empty() {
+        return Err(McpError::InvalidParams(
+            "text contains nothing to import".to_string(),
+        ));
+    }
+    if chunks.len() > MAX_IMPORT_CHUNKS {
+        return Err(McpError::InvalidParams(format!(
+            "text splits into {} chunks, more than the {MAX_IMPORT_CHUNKS} allowed per import",
+            chunks.len()
+        )));
+    }
+
+    let mut session = session.write().await;
+    let confidence = session.normalize_confidence(params.confidence)?;
+
+    // Check every chunk first so a rejected one leaves the graph untouched.
+    let contents = chunks
+        .iter()
+        .map(|chunk| session.enforce_content_limit(&chunk.text))
+        .collect::<McpResult<Vec<_>>>()?;
+
+    let mut node_ids = Vec::with_capacity(chunks.len());
+    let mut structure = Vec::with_capacity(chunks.len());
+    let mut edges_created = 0;
+    for (index, (chunk, (content, truncated))) in chunks.iter().zip(contents).enumerate() {
+        let (node_id, _) = session.add_event(EventType::Fact, &content, confidence, Vec::new())?;
+        if params.link {
+            if let Some(&prev_id) = node_ids.last() {
+                session.link_temporal(prev_id, node_id)?;
+                edges_created += 1;
+            }
+        }
+        node_ids.push(node_id);
+        structure.push(json!({
+            "index": index,
+            "node_id": node_id,
+            "heading": chunk.heading,
+            "level": chunk.level,
+            "truncated": truncated,
+        }));
+    }
+
+    Ok(ToolCallResult::json(&json!({
+        "count": node_ids.len(),
+        "node_ids": node_ids,
+        "edges_created": edges_created,
+        "chunks": structure,
+        "graph_version": session.graph_version(),
+    })))
+}
+
+/// Split at blank lines; each run of non-blank lines is one chunk.
+fn split_paragraphs(text: &str) -> Vec<Chunk> {
+    let mut chunks = Vec::new();
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines().chain(std::iter::once("")) {
+        if line.trim().is_empty() {
+            if !lines.is_empty() {
+                chunks.push(Chunk {
+                    heading: None,
+                    level: 0,
+                    text: lines.join("\n"),
+                });
+                lines.clear();
+            }
+        } else {
+            lines.push(line.trim_end());
+        }
+    }
+    chunks
+}
+
+/// Split before every markdown heading. Text ahead of the first heading
+/// becomes a chunk of its own; sections with only a heading are kept.
+fn split_headings(text: &str) -> Vec<Chunk> {
+    let mut chunks = Vec::new();
+    let mut current = Chunk {
+        heading: None,
+        level: 0,
+        text: String::new(),
+    };
+    let mut in_fence = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        let heading = if in_fence { None } else { parse_heading(line) };
+        if let Some((level, title)) = heading {
+            push_section(&mut chunks, current);
+            current = Chunk {
+                heading: Some(title.to_string()),
+                level,
+                text: String::new(),
+            };
+        }
+        current.text.push_str(line.trim_end());
+        current.text.push('\n');
+    }
+    push_section(&mut chunks, current);
+    chunks
+}
+
+fn push_section(chunks: &mut Vec<Chunk>, mut chunk: Chunk) {
+    chunk.text = chunk.text.trim().to_string();
+    if !chunk.text.is_empty() {
+        chunks.push(chunk);
+    }
+}
+
+/// `## Title` → `(2, "Title")`. Needs one to six `#` followed by a space.
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some((level, rest.trim().trim_end_matches('#').trim_end()))
+}
@@ -0,0 +1,67 @@
+//! Tool: memory_pin — Protect a node from forgetting.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::session::SessionManager;
+use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+
+#[derive(Debug, Deserialize)]
+struct PinParams {
+    node_id: u64,
+    expected_version: Option<u64>,
+}
+
+/// Return the tool definition for memory_pin.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_pin".to_string(),
+        description: Some(
+            "Pin a node so decay-based forgetting never removes it and amem://graph/important always lists it"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "node_id": { "type": "integer", "description": "Node to pin" },
+                "expected_version": {
+                    "type": "integer",
+                    "description": "Fail with a version conflict unless the graph is still at this graph_version"
+                }
+            },
+            "required": ["node_id"]
+        }),
+    }
+}
+
+/// Execute the memory_pin tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    set_pinned(args, session, true).await
+}
+
+/// Shared by memory_pin and memory_unpin.
+pub(crate) async fn set_pinned(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+    pinned: bool,
+) -> McpResult<ToolCallResult> {
+    let params: PinParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+    let mut session = session.write().await;
+    session.check_version(params.expected_version)?;
+    let changed = session.set_pinned(params.node_id, pinned)?;
+
+    Ok(ToolCallResult::json(&json!({
+        "node_id": params.node_id,
+        "pinned": pinned,
+        "changed": changed,
+        "graph_version": session.graph_version(),
+    })))
+}
@@ -1,7 +1,7 @@
 //! Tool: memory_quality — Graph quality and reliability summary.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -51,12 +51,12 @@ pub fn definition() -> ToolDefinition {
 
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: QualityParams = serde_json::from_value(args)
         .map_err(|e| McpError::InvalidParams(format!("invalid params: {e}")))?;
 
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let qe = QueryEngine::new();
     let report = qe
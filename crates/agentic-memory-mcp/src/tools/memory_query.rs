@@ -1,15 +1,20 @@
 //! Tool: memory_query — Pattern query for matching nodes.
 
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
 
-use agentic_memory::{EventType, PatternParams, PatternSort};
+use agentic_memory::{CognitiveEvent, PatternParams, PatternSort};
 
 use crate::session::SessionManager;
-use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+use crate::types::{
+    parse_event_types, McpError, McpResult, NodeFieldSelection, ToolCallResult, ToolDefinition,
+    SELECTABLE_NODE_FIELDS,
+};
 
 #[derive(Debug, Deserialize)]
 struct QueryParams {
@@ -21,10 +26,16 @@ struct QueryParams {
     session_ids: Vec<u32>,
     created_after: Option<u64>,
     created_before: Option<u64>,
+    min_decay_score: Option<f32>,
+    max_decay_score: Option<f32>,
     #[serde(default = "default_max_results")]
     max_results: usize,
     #[serde(default = "default_sort")]
     sort_by: String,
+    #[serde(default)]
+    tags: HashMap<String, String>,
+    cursor: Option<String>,
+    fields: Option<Vec<String>>,
 }
 
 fn default_max_results() -> usize {
@@ -49,11 +60,33 @@ pub fn definition() -> ToolDefinition {
                 "session_ids": { "type": "array", "items": { "type": "integer" } },
                 "created_after": { "type": "integer" },
                 "created_before": { "type": "integer" },
+                "min_decay_score": {
+                    "type": "number",
+                    "description": "Only nodes whose decay score is at least this (inclusive)"
+                },
+                "max_decay_score": {
+                    "type": "number",
+                    "description": "Only nodes whose decay score is at most this (inclusive), e.g. stale candidates for forgetting"
+                },
                 "max_results": { "type": "integer", "default": 20 },
                 "sort_by": {
                     "type": "string",
                     "enum": ["most_recent", "highest_confidence", "most_accessed", "most_important"],
                     "default": "most_recent"
+                },
+                "tags": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string" },
+                    "description": "Only nodes carrying all of these key/value tags (see memory_tag)"
+                },
+                "cursor": {
+                    "type": "string",
+                    "description": "next_cursor from the previous page; repeat the same filters and sort_by"
+                },
+                "fields": {
+                    "type": "array",
+                    "items": { "type": "string", "enum": SELECTABLE_NODE_FIELDS },
+                    "description": "Node properties to return besides id and event_type (default: all)"
                 }
             }
         }),
@@ -63,16 +96,17 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_query tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: QueryParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
 
-    let event_types: Vec<EventType> = params
-        .event_types
-        .iter()
-        .filter_map(|name| EventType::from_name(name))
-        .collect();
+    let event_types = parse_event_types(&params.event_types)?;
+    let fields = params
+        .fields
+        .as_deref()
+        .map(NodeFieldSelection::parse)
+        .transpose()?;
 
     let sort_by = match params.sort_by.as_str() {
         "highest_confidence" => PatternSort::HighestConfidence,
@@ -81,28 +115,54 @@ pub async fn execute(
         _ => PatternSort::MostRecent,
     };
 
+    let after = params
+        .cursor
+        .as_deref()
+        .map(|cursor| decode_cursor(cursor, sort_by))
+        .transpose()?;
+
+    let session = session.read().await;
+    let session_ids = session.effective_session_ids(params.session_ids);
+
     let pattern = PatternParams {
         event_types,
         min_confidence: params.min_confidence,
         max_confidence: params.max_confidence,
-        session_ids: params.session_ids,
+        session_ids,
         created_after: params.created_after,
         created_before: params.created_before,
-        min_decay_score: None,
-        max_results: params.max_results,
+        min_decay_score: params.min_decay_score,
+        max_decay_score: params.max_decay_score,
+        // Cursor and tag filtering happen after the pattern query, so don't truncate early.
+        max_results: usize::MAX,
         sort_by,
     };
 
-    let session = session.lock().await;
     let results = session
         .query_engine()
         .pattern(session.graph(), pattern)
         .map_err(|e| McpError::AgenticMemory(format!("Pattern query failed: {e}")))?;
 
-    let nodes: Vec<Value> = results
+    let mut page = results
+        .iter()
+        .filter(|event| {
+            after.is_none_or(|after| {
+                PatternSort::compare_keys((sort_by.key(event), event.id), after)
+                    == Ordering::Greater
+            })
+        })
+        .filter(|event| session.node_matches_tags(event.id, &params.tags));
+    let selected: Vec<_> = page.by_ref().take(params.max_results).collect();
+    let next_cursor = match (selected.last(), page.next()) {
+        (Some(last), Some(_)) => Some(encode_cursor(sort_by, last)),
+        _ => None,
+    };
+
+    let nodes: Vec<Value> = selected
         .iter()
         .map(|event| {
-            json!({
+            let tags = session.node_tags(event.id);
+            let mut entry = json!({
                 "id": event.id,
                 "event_type": event.event_type.name(),
                 "content": event.content,
@@ -111,12 +171,52 @@ pub async fn execute(
                 "created_at": event.created_at,
                 "decay_score": event.decay_score,
                 "access_count": event.access_count,
-            })
+                "tags": tags,
+            });
+            if let Some(fields) = &fields {
+                fields.apply(&mut entry, event);
+            }
+            entry
         })
         .collect();
 
     Ok(ToolCallResult::json(&json!({
         "count": nodes.len(),
-        "nodes": nodes
+        "nodes": nodes,
+        "next_cursor": next_cursor,
+        "graph_version": session.graph_version(),
     })))
 }
+
+fn sort_name(sort: PatternSort) -> &'static str {
+    match sort {
+        PatternSort::MostRecent => "most_recent",
+        PatternSort::HighestConfidence => "highest_confidence",
+        PatternSort::MostAccessed => "most_accessed",
+        PatternSort::MostImportant => "most_important",
+    }
+}
+
+/// Cursor naming the last node of a page by its position in the sort order
+/// (`sort:key:id`), so the next page resumes right after it even if that
+/// node has since been deleted.
+fn encode_cursor(sort: PatternSort, last: &CognitiveEvent) -> String {
+    format!("{}:{}:{}", sort_name(sort), sort.key(last), last.id)
+}
+
+fn decode_cursor(cursor: &str, sort: PatternSort) -> McpResult<(f64, u64)> {
+    let invalid = || McpError::InvalidParams(format!("Invalid cursor: {cursor}"));
+    let mut parts = cursor.splitn(3, ':');
+    let (Some(name), Some(key), Some(id)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(invalid());
+    };
+    if name != sort_name(sort) {
+        return Err(McpError::InvalidParams(format!(
+            "Cursor was issued for sort_by={name}, not {}",
+            sort_name(sort)
+        )));
+    }
+    let key: f64 = key.parse().map_err(|_| invalid())?;
+    let id: u64 = id.parse().map_err(|_| invalid())?;
+    Ok((key, id))
+}
@@ -0,0 +1,162 @@
+//! Tool: memory_reembed — Repair node vectors after a dimension or model change.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::session::SessionManager;
+use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+
+#[derive(Debug, Deserialize)]
+struct ReembedParams {
+    #[serde(default = "default_provider")]
+    provider: String,
+    #[serde(default = "default_only_mismatched")]
+    only_mismatched: bool,
+    expected_version: Option<u64>,
+}
+
+fn default_provider() -> String {
+    "none".to_string()
+}
+
+/// Repairing only broken vectors keeps an argument-less call from wiping
+/// every embedding.
+fn default_only_mismatched() -> bool {
+    true
+}
+
+/// Progress notifications sent over a full run, when the client asks for them.
+const PROGRESS_STEPS: usize = 10;
+
+/// Return the tool definition for memory_reembed.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_reembed".to_string(),
+        description: Some(
+            "Clear and recompute node vectors at the graph's dimension (repairs vectors left by a model swap)"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "provider": {
+                    "type": "string",
+                    "enum": ["none", "tfidf"],
+                    "default": "none",
+                    "description": "none: reset to zero placeholders (skipped by memory_similar); tfidf: local term-frequency embedding fitted on all node content (requires the v3 feature)"
+                },
+                "only_mismatched": {
+                    "type": "boolean",
+                    "default": true,
+                    "description": "Only touch nodes whose vector length differs from the graph dimension; set false to recompute every node"
+                },
+                "expected_version": {
+                    "type": "integer",
+                    "description": "Fail with a version conflict unless the graph is still at this graph_version"
+                }
+            }
+        }),
+    }
+}
+
+/// Execute the memory_reembed tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: ReembedParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+    let mut session = session.write().await;
+    session.check_version(params.expected_version)?;
+    let dimension = session.graph().dimension();
+
+    let mismatched = session
+        .graph()
+        .nodes()
+        .iter()
+        .filter(|n| n.feature_vec.len() != dimension)
+        .count();
+    let targets: Vec<(u64, String)> = session
+        .graph()
+        .nodes()
+        .iter()
+        .filter(|n| !params.only_mismatched || n.feature_vec.len() != dimension)
+        .map(|n| (n.id, n.content.clone()))
+        .collect();
+
+    let vectors: Vec<Vec<f32>> = match params.provider.as_str() {
+        "none" => vec![vec![0.0; dimension]; targets.len()],
+        "tfidf" => tfidf_vectors(&session, &targets, dimension)?,
+        other => {
+            return Err(McpError::InvalidParams(format!(
+                "Unknown embedding provider '{other}' (expected 'none' or 'tfidf')"
+            )))
+        }
+    };
+
+    if !targets.is_empty() {
+        let total = targets.len();
+        let step = total.div_ceil(PROGRESS_STEPS);
+        let report = session.progress_reporter();
+        let graph = session.graph_mut();
+        for (done, ((id, _), vector)) in targets.iter().zip(vectors).enumerate() {
+            if let Some(node) = graph.get_node_mut(*id) {
+                node.feature_vec = vector;
+            }
+            if (done + 1) % step == 0 || done + 1 == total {
+                report((done + 1) as f64, Some(total as f64));
+            }
+        }
+        session.maybe_auto_save()?;
+    }
+
+    Ok(ToolCallResult::json(&json!({
+        "provider": params.provider,
+        "dimension": dimension,
+        "mismatched_before": mismatched,
+        "reembedded_count": targets.len(),
+        "graph_version": session.graph_version(),
+    })))
+}
+
+#[cfg(feature = "v3")]
+fn tfidf_vectors(
+    session: &SessionManager,
+    targets: &[(u64, String)],
+    dimension: usize,
+) -> McpResult<Vec<Vec<f32>>> {
+    use agentic_memory::v3::embeddings::{EmbeddingProvider, TfIdfEmbedding};
+
+    // Fit on the whole corpus so partial repairs share one vocabulary.
+    let corpus: Vec<&str> = session
+        .graph()
+        .nodes()
+        .iter()
+        .map(|n| n.content.as_str())
+        .collect();
+    let mut provider = TfIdfEmbedding::new(dimension);
+    provider.fit(&corpus);
+    Ok(targets
+        .iter()
+        .map(|(_, content)| {
+            provider
+                .embed(content)
+                .unwrap_or_else(|| vec![0.0; dimension])
+        })
+        .collect())
+}
+
+#[cfg(not(feature = "v3"))]
+fn tfidf_vectors(
+    _session: &SessionManager,
+    _targets: &[(u64, String)],
+    _dimension: usize,
+) -> McpResult<Vec<Vec<f32>>> {
+    Err(McpError::InvalidParams(
+        "Embedding provider 'tfidf' requires building with the v3 feature".to_string(),
+    ))
+}
@@ -1,7 +1,7 @@
 //! Tool: memory_resolve — Follow supersedes chain to get the latest version.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -34,12 +34,12 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_resolve tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: ResolveParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
 
-    let session = session.lock().await;
+    let session = session.read().await;
 
     let resolved = session
         .query_engine()
@@ -0,0 +1,200 @@
+//! Tool: memory_retrieve — Structural filter plus similarity ranking in one call.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use agentic_memory::{
+    PatternParams, PatternSort, SimilarityMetric, SimilarityParams, TextSearchParams,
+};
+
+use crate::session::SessionManager;
+use crate::types::{parse_event_types, McpError, McpResult, ToolCallResult, ToolDefinition};
+
+#[derive(Debug, Deserialize)]
+struct RetrieveParams {
+    query_text: Option<String>,
+    query_vec: Option<Vec<f32>>,
+    #[serde(default)]
+    event_types: Vec<String>,
+    #[serde(default)]
+    session_ids: Vec<u32>,
+    min_confidence: Option<f32>,
+    max_confidence: Option<f32>,
+    created_after: Option<u64>,
+    created_before: Option<u64>,
+    #[serde(default)]
+    tags: HashMap<String, String>,
+    #[serde(default = "default_top_k")]
+    top_k: usize,
+    #[serde(default)]
+    min_similarity: f32,
+}
+
+fn default_top_k() -> usize {
+    10
+}
+
+/// Return the tool definition for memory_retrieve.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_retrieve".to_string(),
+        description: Some(
+            "Filter memories structurally (type, session, confidence, time, tags) and rank the filtered set by similarity to query_vec or query_text"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "query_vec": {
+                    "type": "array",
+                    "items": { "type": "number" },
+                    "description": "Rank by cosine similarity to this vector"
+                },
+                "query_text": {
+                    "type": "string",
+                    "description": "Rank by BM25 text relevance (used when query_vec is absent)"
+                },
+                "event_types": { "type": "array", "items": { "type": "string" } },
+                "session_ids": { "type": "array", "items": { "type": "integer" } },
+                "min_confidence": { "type": "number" },
+                "max_confidence": { "type": "number" },
+                "created_after": { "type": "integer" },
+                "created_before": { "type": "integer" },
+                "tags": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string" },
+                    "description": "Only nodes carrying all of these key/value tags"
+                },
+                "top_k": { "type": "integer", "default": 10 },
+                "min_similarity": { "type": "number", "default": 0.0 }
+            }
+        }),
+    }
+}
+
+/// Execute the memory_retrieve tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: RetrieveParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+    if params.query_vec.is_none() && params.query_text.is_none() {
+        return Err(McpError::InvalidParams(
+            "Either query_vec or query_text is required".to_string(),
+        ));
+    }
+
+    let event_types = parse_event_types(&params.event_types)?;
+
+    SessionManager::prepare_text_index(session).await;
+    let session = session.read().await;
+    let session_ids = session.effective_session_ids(params.session_ids);
+    let graph = session.graph();
+
+    // 1. Structural filter over the whole graph.
+    let filtered: HashSet<u64> = session
+        .query_engine()
+        .pattern(
+            graph,
+            PatternParams {
+                event_types: event_types.clone(),
+                min_confidence: params.min_confidence,
+                max_confidence: params.max_confidence,
+                session_ids: session_ids.clone(),
+                created_after: params.created_after,
+                created_before: params.created_before,
+                min_decay_score: None,
+                max_decay_score: None,
+                max_results: usize::MAX,
+                sort_by: PatternSort::MostRecent,
+            },
+        )
+        .map_err(|e| McpError::AgenticMemory(format!("Pattern query failed: {e}")))?
+        .iter()
+        .filter(|event| session.node_matches_tags(event.id, &params.tags))
+        .map(|event| event.id)
+        .collect();
+
+    // 2. Rank only the filtered subset.
+    let scan_k = graph.node_count().max(1);
+    let (mode, ranked): (&str, Vec<(u64, f32)>) = if let Some(query_vec) = params.query_vec {
+        session.check_vector_dimension("query_vec", query_vec.len())?;
+        let results = session
+            .query_engine()
+            .similarity(
+                graph,
+                SimilarityParams {
+                    query_vec,
+                    top_k: scan_k,
+                    min_similarity: params.min_similarity,
+                    event_types,
+                    skip_zero_vectors: true,
+                    metric: SimilarityMetric::Cosine,
+                },
+            )
+            .map_err(|e| McpError::AgenticMemory(format!("Similarity search failed: {e}")))?;
+        (
+            "vector",
+            results
+                .iter()
+                .filter(|m| filtered.contains(&m.node_id))
+                .map(|m| (m.node_id, m.similarity))
+                .collect(),
+        )
+    } else {
+        let results = session
+            .query_engine()
+            .text_search(
+                graph,
+                graph.term_index(),
+                graph.doc_lengths(),
+                TextSearchParams {
+                    query: params.query_text.unwrap_or_default(),
+                    max_results: scan_k,
+                    event_types,
+                    session_ids,
+                    min_score: params.min_similarity,
+                },
+            )
+            .map_err(|e| McpError::AgenticMemory(format!("Text search failed: {e}")))?;
+        (
+            "text",
+            results
+                .iter()
+                .filter(|m| filtered.contains(&m.node_id))
+                .map(|m| (m.node_id, m.score))
+                .collect(),
+        )
+    };
+
+    let matches: Vec<Value> = ranked
+        .iter()
+        .take(params.top_k)
+        .filter_map(|(id, score)| {
+            graph.get_node(*id).map(|node| {
+                json!({
+                    "node_id": node.id,
+                    "score": score,
+                    "event_type": node.event_type.name(),
+                    "content": node.content,
+                    "confidence": node.confidence,
+                    "session_id": node.session_id,
+                    "created_at": node.created_at,
+                })
+            })
+        })
+        .collect();
+
+    Ok(ToolCallResult::json(&json!({
+        "mode": mode,
+        "filtered_count": filtered.len(),
+        "count": matches.len(),
+        "matches": matches,
+    })))
+}
@@ -0,0 +1,137 @@
+//! Tool: memory_retype — Change the event type of existing nodes in bulk.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use agentic_memory::EventType;
+
+use crate::session::SessionManager;
+use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+
+#[derive(Debug, Deserialize)]
+struct RetypeParams {
+    #[serde(default)]
+    node_ids: Vec<u64>,
+    from_event_type: Option<String>,
+    #[serde(default)]
+    session_ids: Vec<u32>,
+    event_type: String,
+    #[serde(default)]
+    dry_run: bool,
+    expected_version: Option<u64>,
+}
+
+/// Return the tool definition for memory_retype.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_retype".to_string(),
+        description: Some(
+            "Change the event type of nodes selected by ID or by filter (e.g. relabel inferences as decisions)"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "node_ids": {
+                    "type": "array",
+                    "items": { "type": "integer" },
+                    "description": "Explicit node IDs to retype"
+                },
+                "from_event_type": {
+                    "type": "string",
+                    "enum": ["fact", "decision", "inference", "correction", "skill", "episode"],
+                    "description": "Filter: retype every node currently of this type"
+                },
+                "session_ids": {
+                    "type": "array",
+                    "items": { "type": "integer" },
+                    "description": "Filter: restrict from_event_type selection to these sessions"
+                },
+                "event_type": {
+                    "type": "string",
+                    "enum": ["fact", "decision", "inference", "correction", "skill", "episode"],
+                    "description": "Target event type"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "List the nodes that would change without retyping them"
+                },
+                "expected_version": {
+                    "type": "integer",
+                    "description": "Fail with a version conflict unless the graph is still at this graph_version"
+                }
+            },
+            "required": ["event_type"]
+        }),
+    }
+}
+
+/// Execute the memory_retype tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: RetypeParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+    let target = EventType::from_name(&params.event_type).ok_or_else(|| {
+        McpError::InvalidParams(format!("Unknown event type: {}", params.event_type))
+    })?;
+    let from = params
+        .from_event_type
+        .as_deref()
+        .map(|name| {
+            EventType::from_name(name)
+                .ok_or_else(|| McpError::InvalidParams(format!("Unknown event type: {name}")))
+        })
+        .transpose()?;
+
+    if params.node_ids.is_empty() && from.is_none() {
+        return Err(McpError::InvalidParams(
+            "Either node_ids or from_event_type is required".to_string(),
+        ));
+    }
+
+    let mut session = session.write().await;
+    session.check_version(params.expected_version)?;
+
+    let mut node_ids = params.node_ids;
+    for id in &node_ids {
+        if session.graph().get_node(*id).is_none() {
+            return Err(McpError::NodeNotFound(*id));
+        }
+    }
+    if let Some(from) = from {
+        let graph = session.graph();
+        node_ids.extend(graph.type_index().get(from).iter().copied().filter(|id| {
+            params.session_ids.is_empty()
+                || graph
+                    .get_node(*id)
+                    .is_some_and(|n| params.session_ids.contains(&n.session_id))
+        }));
+    }
+    node_ids.sort_unstable();
+    node_ids.dedup();
+
+    let retyped = if params.dry_run {
+        let graph = session.graph();
+        node_ids
+            .into_iter()
+            .filter(|id| graph.get_node(*id).is_some_and(|n| n.event_type != target))
+            .collect()
+    } else {
+        session.retype_nodes(&node_ids, target)?
+    };
+
+    Ok(ToolCallResult::json(&json!({
+        "dry_run": params.dry_run,
+        "event_type": target.name(),
+        "retyped_count": retyped.len(),
+        "node_ids": retyped,
+        "graph_version": session.graph_version(),
+    })))
+}
@@ -0,0 +1,104 @@
+//! Tool: memory_reweight — Set or adjust the weight of an existing edge.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::session::SessionManager;
+use crate::types::{parse_edge_types, McpError, McpResult, ToolCallResult, ToolDefinition};
+
+#[derive(Debug, Deserialize)]
+struct ReweightParams {
+    source_id: u64,
+    target_id: u64,
+    edge_type: String,
+    weight: Option<f32>,
+    delta: Option<f32>,
+    expected_version: Option<u64>,
+}
+
+/// Return the tool definition for memory_reweight.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_reweight".to_string(),
+        description: Some(
+            "Set or adjust the weight of an existing edge, e.g. strengthen a supports edge \
+             each time the belief is re-confirmed; the result is clamped to 0.0-1.0"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "source_id": { "type": "integer", "description": "Source node of the edge" },
+                "target_id": { "type": "integer", "description": "Target node of the edge" },
+                "edge_type": { "type": "string", "description": "Type of the edge" },
+                "weight": { "type": "number", "description": "New absolute weight" },
+                "delta": { "type": "number", "description": "Amount to add to the current weight (negative to weaken)" },
+                "expected_version": {
+                    "type": "integer",
+                    "description": "Fail with a version conflict unless the graph is still at this graph_version"
+                }
+            },
+            "required": ["source_id", "target_id", "edge_type"]
+        }),
+    }
+}
+
+/// Execute the memory_reweight tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: ReweightParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+    let edge_type = parse_edge_types(&[params.edge_type.as_str()])?[0];
+
+    let mut session = session.write().await;
+    session.check_version(params.expected_version)?;
+
+    let weight = match (params.weight, params.delta) {
+        (Some(weight), None) => weight,
+        (None, Some(delta)) => {
+            let current = session
+                .graph()
+                .edges_from(params.source_id)
+                .iter()
+                .find(|e| e.target_id == params.target_id && e.edge_type == edge_type)
+                .map(|e| e.weight)
+                .ok_or_else(|| {
+                    McpError::InvalidGraphOp(format!(
+                        "Edge {} -> {} ({}) not found",
+                        params.source_id,
+                        params.target_id,
+                        edge_type.name()
+                    ))
+                })?;
+            current + delta
+        }
+        _ => {
+            return Err(McpError::InvalidParams(
+                "Exactly one of weight or delta is required".to_string(),
+            ))
+        }
+    };
+    if !weight.is_finite() {
+        return Err(McpError::InvalidParams(format!(
+            "Weight must be a finite number, got {weight}"
+        )));
+    }
+
+    let (previous_weight, new_weight) =
+        session.reweight_edge(params.source_id, params.target_id, edge_type, weight)?;
+
+    Ok(ToolCallResult::json(&json!({
+        "source_id": params.source_id,
+        "target_id": params.target_id,
+        "edge_type": edge_type.name(),
+        "previous_weight": previous_weight,
+        "weight": new_weight,
+        "clamped": new_weight != weight,
+        "graph_version": session.graph_version(),
+    })))
+}
@@ -0,0 +1,50 @@
+//! Tool: memory_save — Write pending changes to disk now.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde_json::{json, Value};
+
+use crate::session::SessionManager;
+use crate::types::{McpResult, ToolCallResult, ToolDefinition};
+
+/// Return the tool definition for memory_save.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_save".to_string(),
+        description: Some(
+            "Save pending changes to the memory file now and report whether it worked, \
+             e.g. to retry after freeing disk space"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {}
+        }),
+    }
+}
+
+/// Execute the memory_save tool.
+pub async fn execute(
+    _args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let mut session = session.write().await;
+    let had_changes = session.is_dirty() && !session.is_ephemeral();
+    let result = session.save();
+
+    let last_saved_unix = session
+        .last_save()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs());
+
+    Ok(ToolCallResult::json(&json!({
+        "success": result.is_ok(),
+        "saved": had_changes && result.is_ok(),
+        "error": result.err().map(|e| e.to_string()),
+        "dirty": session.is_dirty(),
+        "consecutive_save_failures": session.consecutive_save_failures(),
+        "last_saved_unix": last_saved_unix,
+        "file_path": session.file_path().display().to_string(),
+    })))
+}
@@ -0,0 +1,211 @@
+//! Tool: memory_session_diff — Compare what two sessions know.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use agentic_memory::{cosine_similarity, CognitiveEvent, EdgeType, MemoryGraph};
+
+use crate::session::SessionManager;
+use crate::types::{edge_json, McpError, McpResult, ToolCallResult, ToolDefinition};
+
+#[derive(Debug, Deserialize)]
+struct SessionDiffParams {
+    session_a: u32,
+    session_b: u32,
+    #[serde(default = "default_threshold")]
+    similarity_threshold: f32,
+}
+
+fn default_threshold() -> f32 {
+    0.8
+}
+
+/// Return the tool definition for memory_session_diff.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_session_diff".to_string(),
+        description: Some(
+            "Compare two sessions: nodes unique to each, topics both cover, and \
+             contradictions or corrections between them"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "session_a": { "type": "integer", "minimum": 0, "description": "Baseline session ID" },
+                "session_b": { "type": "integer", "minimum": 0, "description": "Session to compare against the baseline" },
+                "similarity_threshold": {
+                    "type": "number",
+                    "minimum": 0.0,
+                    "maximum": 1.0,
+                    "default": 0.8,
+                    "description": "Minimum cosine similarity for two nodes to count as the same topic"
+                }
+            },
+            "required": ["session_a", "session_b"]
+        }),
+    }
+}
+
+/// Execute the memory_session_diff tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: SessionDiffParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+    if !(0.0..=1.0).contains(&params.similarity_threshold) {
+        return Err(McpError::InvalidParams(
+            "similarity_threshold must be between 0.0 and 1.0".to_string(),
+        ));
+    }
+    if params.session_a == params.session_b {
+        return Err(McpError::InvalidParams(
+            "session_a and session_b must differ".to_string(),
+        ));
+    }
+
+    let session = session.read().await;
+    let graph = session.graph();
+    let index = graph.session_index();
+    for sid in [params.session_a, params.session_b] {
+        if index.node_count(sid) == 0 {
+            return Err(McpError::SessionNotFound(sid));
+        }
+    }
+
+    let nodes_a = session_nodes(graph, params.session_a);
+    let nodes_b = session_nodes(graph, params.session_b);
+
+    // Pair every cross-session node pair on the same topic.
+    let mut pairs: Vec<(u64, u64, f32)> = Vec::new();
+    for a in &nodes_a {
+        for b in &nodes_b {
+            if let Some(sim) = topic_similarity(a, b) {
+                if sim >= params.similarity_threshold {
+                    pairs.push((a.id, b.id, sim));
+                }
+            }
+        }
+    }
+
+    let topics = cluster_topics(&pairs, params.session_a, graph);
+    let matched: HashSet<u64> = pairs.iter().flat_map(|&(a, b, _)| [a, b]).collect();
+    let only_in_a = unmatched(&nodes_a, &matched);
+    let only_in_b = unmatched(&nodes_b, &matched);
+
+    let ids_a: HashSet<u64> = nodes_a.iter().map(|n| n.id).collect();
+    let ids_b: HashSet<u64> = nodes_b.iter().map(|n| n.id).collect();
+    let conflicts: Vec<Value> = graph
+        .edges()
+        .iter()
+        .filter(|e| matches!(e.edge_type, EdgeType::Contradicts | EdgeType::Supersedes))
+        .filter(|e| {
+            (ids_a.contains(&e.source_id) && ids_b.contains(&e.target_id))
+                || (ids_b.contains(&e.source_id) && ids_a.contains(&e.target_id))
+        })
+        .map(edge_json)
+        .collect();
+
+    Ok(ToolCallResult::json(&json!({
+        "session_a": params.session_a,
+        "session_b": params.session_b,
+        "similarity_threshold": params.similarity_threshold,
+        "only_in_a": { "count": only_in_a.len(), "nodes": only_in_a },
+        "only_in_b": { "count": only_in_b.len(), "nodes": only_in_b },
+        "shared_topics": { "count": topics.len(), "topics": topics },
+        "contradictions": { "count": conflicts.len(), "edges": conflicts },
+    })))
+}
+
+fn session_nodes(graph: &MemoryGraph, session_id: u32) -> Vec<&CognitiveEvent> {
+    graph
+        .session_index()
+        .get_session(session_id)
+        .iter()
+        .filter_map(|&id| graph.get_node(id))
+        .collect()
+}
+
+/// Cosine similarity of two nodes, falling back to exact content equality
+/// when either has no embedding.
+fn topic_similarity(a: &CognitiveEvent, b: &CognitiveEvent) -> Option<f32> {
+    let is_zero = |v: &[f32]| v.iter().all(|&x| x == 0.0);
+    if is_zero(&a.feature_vec) || is_zero(&b.feature_vec) {
+        let same = a.content.trim().eq_ignore_ascii_case(b.content.trim());
+        return same.then_some(1.0);
+    }
+    if a.feature_vec.len() != b.feature_vec.len() {
+        return None;
+    }
+    Some(cosine_similarity(&a.feature_vec, &b.feature_vec))
+}
+
+/// Group matched pairs into connected components, one per shared topic.
+fn cluster_topics(pairs: &[(u64, u64, f32)], session_a: u32, graph: &MemoryGraph) -> Vec<Value> {
+    let mut parent: HashMap<u64, u64> = HashMap::new();
+    for &(a, b, _) in pairs {
+        let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+        if ra != rb {
+            parent.insert(ra.max(rb), ra.min(rb));
+        }
+    }
+
+    let mut clusters: BTreeMap<u64, (Vec<u64>, Vec<u64>, f32)> = BTreeMap::new();
+    for &(a, b, sim) in pairs {
+        let root = find(&mut parent, a);
+        let entry = clusters
+            .entry(root)
+            .or_insert((Vec::new(), Vec::new(), 0.0));
+        entry.2 = entry.2.max(sim);
+        for id in [a, b] {
+            let in_a = graph.get_node(id).map(|n| n.session_id) == Some(session_a);
+            let side = if in_a { &mut entry.0 } else { &mut entry.1 };
+            if !side.contains(&id) {
+                side.push(id);
+            }
+        }
+    }
+
+    clusters
+        .into_values()
+        .map(|(mut in_a, mut in_b, max_similarity)| {
+            in_a.sort_unstable();
+            in_b.sort_unstable();
+            json!({
+                "session_a_nodes": in_a,
+                "session_b_nodes": in_b,
+                "max_similarity": max_similarity,
+            })
+        })
+        .collect()
+}
+
+/// Union-find root lookup with path compression.
+fn find(parent: &mut HashMap<u64, u64>, id: u64) -> u64 {
+    let p = *parent.entry(id).or_insert(id);
+    if p == id {
+        return id;
+    }
+    let root = find(parent, p);
+    parent.insert(id, root);
+    root
+}
+
+fn unmatched(nodes: &[&CognitiveEvent], matched: &HashSet<u64>) -> Vec<Value> {
+    nodes
+        .iter()
+        .filter(|n| !matched.contains(&n.id))
+        .map(|n| {
+            json!({
+                "id": n.id,
+                "event_type": n.event_type.name(),
+                "content": n.content,
+            })
+        })
+        .collect()
+}
@@ -6,7 +6,7 @@
 //! can resume with full context.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -53,12 +53,12 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_session_resume tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: ResumeParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
 
-    let session = session.lock().await;
+    let session = session.read().await;
     let graph = session.graph();
     let query = session.query_engine();
 
@@ -71,6 +71,7 @@ pub async fn execute(
         created_after: None,
         created_before: None,
         min_decay_score: None,
+        max_decay_score: None,
         max_results: 1,
         sort_by: PatternSort::MostRecent,
     };
@@ -96,6 +97,7 @@ pub async fn execute(
         created_after: None,
         created_before: None,
         min_decay_score: None,
+        max_decay_score: None,
         max_results: params.limit / 3,
         sort_by: PatternSort::MostRecent,
     };
@@ -125,6 +127,7 @@ pub async fn execute(
         created_after: None,
         created_before: None,
         min_decay_score: None,
+        max_decay_score: None,
         max_results: params.limit / 3,
         sort_by: PatternSort::MostRecent,
     };
@@ -154,6 +157,7 @@ pub async fn execute(
         created_after: None,
         created_before: None,
         min_decay_score: None,
+        max_decay_score: None,
         max_results: params.limit / 3,
         sort_by: PatternSort::MostRecent,
     };
@@ -186,6 +190,7 @@ pub async fn execute(
         created_after: None,
         created_before: None,
         min_decay_score: None,
+        max_decay_score: None,
         max_results: remaining.max(3),
         sort_by: PatternSort::MostRecent,
     };
@@ -0,0 +1,108 @@
+//! Tool: memory_session_window — A session's nodes in the order they were
+//! created, optionally filtered and cut to the first or last few.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use agentic_memory::CognitiveEvent;
+
+use crate::session::SessionManager;
+use crate::types::{node_json, McpError, McpResult, ToolCallResult, ToolDefinition};
+
+#[derive(Debug, Deserialize)]
+struct SessionWindowParams {
+    session_id: Option<u32>,
+    first: Option<usize>,
+    last: Option<usize>,
+    contains: Option<String>,
+}
+
+/// Return the tool definition for memory_session_window.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_session_window".to_string(),
+        description: Some(
+            "List a session's nodes in chronological order, optionally only the first \
+             or last N, or those whose content contains a phrase"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "session_id": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Session to list (default: the current session)"
+                },
+                "first": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Only the earliest N matching nodes"
+                },
+                "last": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Only the latest N matching nodes"
+                },
+                "contains": {
+                    "type": "string",
+                    "description": "Only nodes whose content contains this text, ignoring case"
+                }
+            }
+        }),
+    }
+}
+
+/// Execute the memory_session_window tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: SessionWindowParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+    if params.first.is_some() && params.last.is_some() {
+        return Err(McpError::InvalidParams(
+            "Pass at most one of first and last".to_string(),
+        ));
+    }
+
+    let session = session.read().await;
+    let session_id = params
+        .session_id
+        .unwrap_or_else(|| session.current_session_id());
+    let graph = session.graph();
+    let node_ids = graph.session_index().get_session(session_id);
+    // The current session may not have stored anything yet.
+    if node_ids.is_empty() && session_id != session.current_session_id() {
+        return Err(McpError::SessionNotFound(session_id));
+    }
+
+    let needle = params.contains.as_deref().map(str::to_lowercase);
+    let mut nodes: Vec<&CognitiveEvent> = node_ids
+        .iter()
+        .filter_map(|id| graph.get_node(*id))
+        .filter(|node| match &needle {
+            Some(needle) => node.content.to_lowercase().contains(needle.as_str()),
+            None => true,
+        })
+        .collect();
+    nodes.sort_by_key(|node| (node.created_at, node.id));
+
+    let matched = nodes.len();
+    let window = match (params.first, params.last) {
+        (Some(n), _) => &nodes[..n.min(matched)],
+        (_, Some(n)) => &nodes[matched - n.min(matched)..],
+        _ => &nodes[..],
+    };
+
+    Ok(ToolCallResult::json(&json!({
+        "session_id": session_id,
+        "total": node_ids.len(),
+        "matched": matched,
+        "count": window.len(),
+        "nodes": window.iter().map(|node| node_json(node)).collect::<Vec<_>>(),
+    })))
+}
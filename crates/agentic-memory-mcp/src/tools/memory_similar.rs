@@ -1,15 +1,23 @@
 //! Tool: memory_similar — Find semantically similar memories.
 
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::{OwnedRwLockReadGuard, RwLock};
 
 use serde::Deserialize;
 use serde_json::{json, Value};
 
-use agentic_memory::{EventType, SimilarityParams, TextSearchParams};
+use agentic_memory::{
+    cosine_similarity, CognitiveEvent, EdgeType, EventType, MemoryGraph, SimilarityMatchResult,
+    SimilarityMetric, SimilarityParams, TextSearchParams, TraversalDirection, TraversalParams,
+};
 
 use crate::session::SessionManager;
-use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+use crate::streaming::CancelFlag;
+use crate::types::{
+    parse_event_types, McpError, McpResult, NodeFieldSelection, ToolCallResult, ToolDefinition,
+    SELECTABLE_NODE_FIELDS,
+};
 
 #[derive(Debug, Deserialize)]
 struct SimilarParams {
@@ -17,20 +25,65 @@ struct SimilarParams {
     query_vec: Option<Vec<f32>>,
     #[serde(default = "default_top_k")]
     top_k: usize,
-    #[serde(default = "default_min_similarity")]
-    min_similarity: f32,
+    min_similarity: Option<f32>,
+    metric: Option<String>,
     #[serde(default)]
     event_types: Vec<String>,
+    #[serde(default)]
+    session_ids: Vec<u32>,
+    within: Option<WithinParams>,
+    #[serde(default)]
+    explain: bool,
+    #[serde(default)]
+    recency_boost: f32,
+    #[serde(default = "default_recency_half_life_days")]
+    recency_half_life_days: f64,
+    fields: Option<Vec<String>>,
 }
 
+/// Restricts candidates to the neighborhood of an anchor node.
+#[derive(Debug, Deserialize)]
+struct WithinParams {
+    anchor_id: u64,
+    #[serde(default = "default_max_hops")]
+    max_hops: u32,
+}
+
+const ALL_EDGE_TYPES: [EdgeType; 7] = [
+    EdgeType::CausedBy,
+    EdgeType::Supports,
+    EdgeType::Contradicts,
+    EdgeType::Supersedes,
+    EdgeType::RelatedTo,
+    EdgeType::PartOf,
+    EdgeType::TemporalNext,
+];
+
 fn default_top_k() -> usize {
     10
 }
 
-fn default_min_similarity() -> f32 {
-    0.5
+/// Cosine threshold when the call omits `min_similarity`. Dot and Euclidean
+/// scores have no fixed range, so those metrics apply no threshold by default.
+const DEFAULT_MIN_COSINE: f32 = 0.5;
+
+fn default_max_hops() -> u32 {
+    2
 }
 
+fn default_recency_half_life_days() -> f64 {
+    30.0
+}
+
+const MICROS_PER_DAY: f64 = 86_400_000_000.0;
+
+/// Top-contributing dimensions listed per match in explain mode.
+const EXPLAIN_TOP_DIMS: usize = 5;
+/// Filtered-out candidates listed in explain mode.
+const EXPLAIN_NEAR_MISSES: usize = 5;
+/// Share of the positive dot product that `contributing_dims` must cover.
+const EXPLAIN_CONTRIBUTION_SHARE: f32 = 0.8;
+
 /// Return the tool definition for memory_similar.
 pub fn definition() -> ToolDefinition {
     ToolDefinition {
@@ -42,8 +95,46 @@ pub fn definition() -> ToolDefinition {
                 "query_text": { "type": "string" },
                 "query_vec": { "type": "array", "items": { "type": "number" } },
                 "top_k": { "type": "integer", "default": 10 },
-                "min_similarity": { "type": "number", "default": 0.5 },
-                "event_types": { "type": "array", "items": { "type": "string" } }
+                "min_similarity": {
+                    "type": "number",
+                    "description": "Lowest score returned (default: 0.5 for cosine, no threshold for dot and euclidean)"
+                },
+                "metric": {
+                    "type": "string",
+                    "enum": ["cosine", "dot", "euclidean"],
+                    "description": "Vector scoring: cosine, dot product, or negative Euclidean distance (default: server setting, normally cosine)"
+                },
+                "event_types": { "type": "array", "items": { "type": "string" } },
+                "session_ids": { "type": "array", "items": { "type": "integer" } },
+                "within": {
+                    "type": "object",
+                    "description": "Only consider nodes within max_hops of anchor_id (any edge type, either direction)",
+                    "properties": {
+                        "anchor_id": { "type": "integer" },
+                        "max_hops": { "type": "integer", "default": 2 }
+                    },
+                    "required": ["anchor_id"]
+                },
+                "explain": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "Vector mode: add per-match score breakdowns and report which candidates were filtered out and why"
+                },
+                "recency_boost": {
+                    "type": "number",
+                    "default": 0,
+                    "description": "Weight of recency in the ranking: each match scores similarity + recency_boost * recency, where recency runs from 1 (just created or accessed) toward 0. 0 ranks by similarity alone"
+                },
+                "recency_half_life_days": {
+                    "type": "number",
+                    "default": 30,
+                    "description": "Days after which a node's recency factor has halved"
+                },
+                "fields": {
+                    "type": "array",
+                    "items": { "type": "string", "enum": SELECTABLE_NODE_FIELDS },
+                    "description": "Node properties to return besides id and event_type (default: content and confidence)"
+                }
             }
         }),
     }
@@ -52,56 +143,183 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_similar tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: SimilarParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
 
-    let event_types: Vec<EventType> = params
-        .event_types
-        .iter()
-        .filter_map(|name| EventType::from_name(name))
-        .collect();
+    let event_types = parse_event_types(&params.event_types)?;
+    let fields = params
+        .fields
+        .as_deref()
+        .map(NodeFieldSelection::parse)
+        .transpose()?;
+    let metric = match params.metric.as_deref() {
+        Some(name) => SimilarityMetric::from_name(name).ok_or_else(|| {
+            McpError::InvalidParams(format!(
+                "metric must be 'cosine', 'dot', or 'euclidean', got '{name}'"
+            ))
+        })?,
+        None => session.read().await.tool_defaults().similarity_metric,
+    };
+    let min_similarity = params.min_similarity.unwrap_or(match metric {
+        SimilarityMetric::Cosine => DEFAULT_MIN_COSINE,
+        _ => f32::NEG_INFINITY,
+    });
+    if !params.recency_boost.is_finite() || params.recency_boost < 0.0 {
+        return Err(McpError::InvalidParams(format!(
+            "recency_boost must be 0 or greater, got {}",
+            params.recency_boost
+        )));
+    }
+    if !params.recency_half_life_days.is_finite() || params.recency_half_life_days <= 0.0 {
+        return Err(McpError::InvalidParams(format!(
+            "recency_half_life_days must be greater than 0, got {}",
+            params.recency_half_life_days
+        )));
+    }
 
-    let session = session.lock().await;
+    SessionManager::prepare_text_index(session).await;
+    let mut session = Arc::clone(session).read_owned().await;
+    let session_ids = session.effective_session_ids(params.session_ids);
+    let boost = (params.recency_boost > 0.0).then(|| RecencyBoost {
+        weight: params.recency_boost,
+        half_life_days: params.recency_half_life_days,
+        now: session.now_micros(),
+    });
 
-    if let Some(query_vec) = params.query_vec {
+    // Collect the anchor neighborhood (node id -> hop distance) when `within` is set.
+    let neighborhood: Option<HashMap<u64, u32>> = match &params.within {
+        Some(within) => {
+            let traversal = TraversalParams {
+                start_id: within.anchor_id,
+                edge_types: ALL_EDGE_TYPES.to_vec(),
+                direction: TraversalDirection::Both,
+                max_depth: within.max_hops,
+                max_results: session.graph().node_count(),
+                min_confidence: 0.0,
+            };
+            let result = session
+                .query_engine()
+                .traverse(session.graph(), traversal)
+                .map_err(|e| {
+                    McpError::AgenticMemory(format!("Neighborhood traversal failed: {e}"))
+                })?;
+            Some(result.depths)
+        }
+        None => None,
+    };
+
+    // The scans have no neighborhood filter (and the vector scan no session
+    // filter), and know nothing of recency, so widen the candidate pool and
+    // filter and rerank before truncating to top_k.
+    let scan_k = if session_ids.is_empty() && neighborhood.is_none() && boost.is_none() {
+        params.top_k
+    } else {
+        session.graph().node_count()
+    };
+
+    let mut vector_scan = None;
+    if let Some(query_vec) = params.query_vec.clone() {
+        session.check_vector_dimension("query_vec", query_vec.len())?;
         let similarity_params = SimilarityParams {
-            query_vec,
-            top_k: params.top_k,
-            min_similarity: params.min_similarity,
-            event_types,
+            query_vec: query_vec.clone(),
+            top_k: scan_k,
+            min_similarity,
+            event_types: event_types.clone(),
             skip_zero_vectors: true,
+            metric,
         };
+        let (guard, results, cancelled) = scan_vectors(session, similarity_params).await?;
+        session = guard;
+        vector_scan = Some((query_vec, results, cancelled));
+    }
 
-        let results = session
-            .query_engine()
-            .similarity(session.graph(), similarity_params)
-            .map_err(|e| McpError::AgenticMemory(format!("Similarity search failed: {e}")))?;
+    let in_scope = |node_id: u64| -> bool {
+        if let Some(hood) = &neighborhood {
+            if !hood.contains_key(&node_id) {
+                return false;
+            }
+        }
+        session_ids.is_empty()
+            || session
+                .graph()
+                .get_node(node_id)
+                .is_some_and(|n| session_ids.contains(&n.session_id))
+    };
 
-        let matches: Vec<Value> = results
-            .iter()
+    let annotate = |mut entry: Value, node: &CognitiveEvent, similarity: f32| -> Value {
+        if let (Some(within), Some(hood)) = (&params.within, &neighborhood) {
+            entry["anchor_id"] = json!(within.anchor_id);
+            entry["hops"] = json!(hood.get(&node.id).copied().unwrap_or(0));
+        }
+        if let Some(boost) = &boost {
+            entry["recency"] = json!(boost.factor(node));
+            entry["score"] = json!(boost.score(similarity, node));
+        }
+        if let Some(fields) = &fields {
+            fields.apply(&mut entry, node);
+        }
+        entry
+    };
+
+    if let Some((query_vec, results, cancelled)) = vector_scan {
+        let mut hits: Vec<&SimilarityMatchResult> =
+            results.iter().filter(|m| in_scope(m.node_id)).collect();
+        if let Some(boost) = &boost {
+            boost.rerank(session.graph(), &mut hits, |m| (m.node_id, m.similarity));
+        }
+        let matches: Vec<Value> = hits
+            .into_iter()
+            .take(params.top_k)
             .filter_map(|m| {
                 session.graph().get_node(m.node_id).map(|node| {
-                    json!({
+                    let mut entry = json!({
                         "node_id": m.node_id,
                         "similarity": m.similarity,
                         "event_type": node.event_type.name(),
                         "content": node.content,
                         "confidence": node.confidence,
-                    })
+                    });
+                    if params.explain {
+                        entry["explain"] = explain_match(&query_vec, &node.feature_vec, metric);
+                    }
+                    annotate(entry, node, m.similarity)
                 })
             })
             .collect();
 
-        return Ok(ToolCallResult::json(&json!({
+        let mut response = json!({
             "mode": "vector",
+            "metric": metric.name(),
             "count": matches.len(),
             "matches": matches,
-        })));
+        });
+        if boost.is_some() {
+            response["recency_boost"] = json!(params.recency_boost);
+        }
+        if cancelled {
+            // Partial results; the candidate breakdown would need another full scan.
+            response["cancelled"] = json!(true);
+        } else if params.explain {
+            let returned: Vec<u64> = matches
+                .iter()
+                .filter_map(|m| m["node_id"].as_u64())
+                .collect();
+            response["explain"] = explain_candidates(
+                session.graph(),
+                &query_vec,
+                metric,
+                min_similarity,
+                &event_types,
+                &in_scope,
+                &returned,
+            );
+        }
+        return Ok(ToolCallResult::json(&response));
     }
 
-    let query_text = params.query_text.ok_or_else(|| {
+    let query_text = params.query_text.clone().ok_or_else(|| {
         McpError::InvalidParams("Either query_vec or query_text is required".to_string())
     })?;
 
@@ -109,38 +327,213 @@ pub async fn execute(
         .query_engine()
         .text_search(
             session.graph(),
-            None,
-            None,
+            session.graph().term_index(),
+            session.graph().doc_lengths(),
             TextSearchParams {
                 query: query_text,
-                max_results: params.top_k,
+                max_results: scan_k,
                 event_types,
-                session_ids: Vec::new(),
+                session_ids: session_ids.clone(),
                 min_score: 0.0,
             },
         )
         .map_err(|e| McpError::AgenticMemory(format!("Text similarity fallback failed: {e}")))?;
 
-    let matches: Vec<Value> = text_results
+    let mut hits: Vec<_> = text_results
         .iter()
+        .filter(|m| in_scope(m.node_id))
+        .collect();
+    if let Some(boost) = &boost {
+        boost.rerank(session.graph(), &mut hits, |m| (m.node_id, m.score));
+    }
+    let matches: Vec<Value> = hits
+        .into_iter()
+        .take(params.top_k)
         .filter_map(|m| {
             session.graph().get_node(m.node_id).map(|node| {
-                json!({
-                    "node_id": m.node_id,
-                    "similarity": m.score,
-                    "text_score": m.score,
-                    "matched_terms": m.matched_terms,
-                    "event_type": node.event_type.name(),
-                    "content": node.content,
-                    "confidence": node.confidence,
-                })
+                annotate(
+                    json!({
+                        "node_id": m.node_id,
+                        "similarity": m.score,
+                        "text_score": m.score,
+                        "matched_terms": m.matched_terms,
+                        "event_type": node.event_type.name(),
+                        "content": node.content,
+                        "confidence": node.confidence,
+                    }),
+                    node,
+                    m.score,
+                )
             })
         })
         .collect();
 
-    Ok(ToolCallResult::json(&json!({
+    let mut response = json!({
         "mode": "text_fallback",
         "count": matches.len(),
         "matches": matches,
-    })))
+    });
+    if boost.is_some() {
+        response["recency_boost"] = json!(params.recency_boost);
+    }
+    Ok(ToolCallResult::json(&response))
+}
+
+/// Blends similarity with how recently a node was created or accessed.
+struct RecencyBoost {
+    weight: f32,
+    half_life_days: f64,
+    now: u64,
+}
+
+impl RecencyBoost {
+    /// 1 for a node created or accessed just now, halving every
+    /// `half_life_days` since the later of the two.
+    fn factor(&self, node: &CognitiveEvent) -> f32 {
+        let touched = node.created_at.max(node.last_accessed);
+        let age_days = self.now.saturating_sub(touched) as f64 / MICROS_PER_DAY;
+        0.5f64.powf(age_days / self.half_life_days) as f32
+    }
+
+    fn score(&self, similarity: f32, node: &CognitiveEvent) -> f32 {
+        similarity + self.weight * self.factor(node)
+    }
+
+    /// Sort `hits` by blended score, best first. `key` gives each hit's node
+    /// ID and similarity.
+    fn rerank<T>(&self, graph: &MemoryGraph, hits: &mut [T], key: impl Fn(&T) -> (u64, f32)) {
+        let score = |hit: &T| {
+            let (node_id, similarity) = key(hit);
+            match graph.get_node(node_id) {
+                Some(node) => self.score(similarity, node),
+                None => similarity,
+            }
+        };
+        hits.sort_by(|a, b| score(b).total_cmp(&score(a)));
+    }
+}
+
+/// Run the vector scan on a blocking thread so the transport can still
+/// deliver a cancellation for this request; the scan stops at the next
+/// candidate once it arrives. The session read lock travels with the scan and
+/// is handed back along with the matches and whether the scan was cut short.
+async fn scan_vectors(
+    session: OwnedRwLockReadGuard<SessionManager>,
+    params: SimilarityParams,
+) -> McpResult<(
+    OwnedRwLockReadGuard<SessionManager>,
+    Vec<SimilarityMatchResult>,
+    bool,
+)> {
+    let flag = CancelFlag::current();
+    let (session, scan) = tokio::task::spawn_blocking(move || {
+        let scan = session
+            .query_engine()
+            .similarity_cancellable(session.graph(), params, &|| flag.is_cancelled());
+        (session, scan)
+    })
+    .await
+    .map_err(|e| McpError::InternalError(format!("Similarity scan panicked: {e}")))?;
+    let (results, cancelled) =
+        scan.map_err(|e| McpError::AgenticMemory(format!("Similarity search failed: {e}")))?;
+    Ok((session, results, cancelled))
+}
+
+/// Per-match breakdown: the score under `metric`, the raw cosine score, and
+/// which dimensions drive the cosine.
+fn explain_match(query: &[f32], vector: &[f32], metric: SimilarityMetric) -> Value {
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let denom = norm(query) * norm(vector);
+    let mut contributions: Vec<(usize, f32)> = query
+        .iter()
+        .zip(vector)
+        .enumerate()
+        .map(|(i, (q, v))| (i, if denom == 0.0 { 0.0 } else { q * v / denom }))
+        .collect();
+    contributions.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Fewest top dimensions that together reach the target share of the positive mass.
+    let positive: f32 = contributions.iter().map(|c| c.1.max(0.0)).sum();
+    let mut covered = 0.0;
+    let contributing_dims = contributions
+        .iter()
+        .take_while(|c| c.1 > 0.0)
+        .take_while(|c| {
+            let needed = covered < positive * EXPLAIN_CONTRIBUTION_SHARE;
+            covered += c.1;
+            needed
+        })
+        .count();
+
+    json!({
+        "score": metric.score(query, vector),
+        "cosine": cosine_similarity(query, vector),
+        "contributing_dims": contributing_dims,
+        "top_dims": contributions
+            .iter()
+            .take(EXPLAIN_TOP_DIMS)
+            .map(|(dim, contribution)| json!({ "dim": dim, "contribution": contribution }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Why candidates did not make the result list, checked in the same order as
+/// the search applies its filters.
+fn explain_candidates(
+    graph: &MemoryGraph,
+    query: &[f32],
+    metric: SimilarityMetric,
+    min_similarity: f32,
+    event_types: &[EventType],
+    in_scope: &dyn Fn(u64) -> bool,
+    returned: &[u64],
+) -> Value {
+    let mut skipped_zero_vectors = 0usize;
+    let mut filtered_event_types = 0usize;
+    let mut filtered_min_similarity = 0usize;
+    let mut filtered_scope = 0usize;
+    let mut near_misses: Vec<(u64, f32, f32, &str)> = Vec::new();
+
+    for node in graph.nodes() {
+        if returned.contains(&node.id) {
+            continue;
+        }
+        if node.feature_vec.iter().all(|&x| x == 0.0) {
+            skipped_zero_vectors += 1;
+            continue;
+        }
+        let score = metric.score(query, &node.feature_vec);
+        let reason = if !event_types.is_empty() && !event_types.contains(&node.event_type) {
+            filtered_event_types += 1;
+            "event_types"
+        } else if score < min_similarity {
+            filtered_min_similarity += 1;
+            "min_similarity"
+        } else if !in_scope(node.id) {
+            filtered_scope += 1;
+            "scope"
+        } else {
+            // Passed every filter but ranked below top_k.
+            "top_k"
+        };
+        let cosine = cosine_similarity(query, &node.feature_vec);
+        near_misses.push((node.id, score, cosine, reason));
+    }
+
+    near_misses.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    near_misses.truncate(EXPLAIN_NEAR_MISSES);
+
+    json!({
+        "candidates": graph.node_count(),
+        "skipped_zero_vectors": skipped_zero_vectors,
+        "filtered_event_types": filtered_event_types,
+        "filtered_min_similarity": filtered_min_similarity,
+        "filtered_scope": filtered_scope,
+        "near_misses": near_misses
+            .iter()
+            .map(|(id, score, cosine, reason)| {
+                json!({ "node_id": id, "score": score, "cosine": cosine, "filtered_by": reason })
+            })
+            .collect::<Vec<_>>(),
+    })
 }
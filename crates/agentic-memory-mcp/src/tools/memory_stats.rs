@@ -1,14 +1,32 @@
 //! Tool: memory_stats — Get statistics about the memory graph.
 
+use std::collections::HashSet;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
+use serde::Deserialize;
 use serde_json::{json, Value};
 
-use agentic_memory::EventType;
+use agentic_memory::{EventType, MemoryGraph};
 
 use crate::session::SessionManager;
-use crate::types::{McpResult, ToolCallResult, ToolDefinition};
+use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+
+/// Most sessions listed per page of the per-session breakdown.
+const MAX_SESSIONS_PER_PAGE: usize = 1000;
+
+#[derive(Debug, Deserialize)]
+struct StatsParams {
+    #[serde(default)]
+    per_session: bool,
+    #[serde(default = "default_max_sessions")]
+    max_sessions: usize,
+    after_session: Option<u32>,
+}
+
+fn default_max_sessions() -> usize {
+    100
+}
 
 /// Return the tool definition for memory_stats.
 pub fn definition() -> ToolDefinition {
@@ -17,43 +35,167 @@ pub fn definition() -> ToolDefinition {
         description: Some("Get statistics about the memory graph".to_string()),
         input_schema: json!({
             "type": "object",
-            "properties": {}
+            "properties": {
+                "per_session": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "Add a per-session breakdown: node count and whether the session has an episode"
+                },
+                "max_sessions": {
+                    "type": "integer",
+                    "default": 100,
+                    "description": "Sessions per page of the breakdown (at most 1000)"
+                },
+                "after_session": {
+                    "type": "integer",
+                    "description": "next_after_session from the previous page"
+                }
+            }
         }),
     }
 }
 
 /// Execute the memory_stats tool.
 pub async fn execute(
-    _args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
-    let session = session.lock().await;
+    let params: StatsParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+    let session = session.read().await;
     let graph = session.graph();
+    let counts = session.graph_stats();
 
-    let type_index = graph.type_index();
-    let session_index = graph.session_index();
+    // Zero vectors are placeholders; `memory_similar` skips them.
+    let nodes_with_vectors = counts.nodes_with_vectors;
+    let nodes_without_vectors = counts.node_count - nodes_with_vectors;
+    let coverage = if counts.node_count == 0 {
+        0.0
+    } else {
+        nodes_with_vectors as f64 / counts.node_count as f64
+    };
 
-    let type_counts = json!({
-        "fact": type_index.count(EventType::Fact),
-        "decision": type_index.count(EventType::Decision),
-        "inference": type_index.count(EventType::Inference),
-        "correction": type_index.count(EventType::Correction),
-        "skill": type_index.count(EventType::Skill),
-        "episode": type_index.count(EventType::Episode),
-    });
+    // The term index is built on the first text search after open.
+    let text_index = match graph.term_index() {
+        Some(index) => json!({
+            "enabled": session.text_index_enabled(),
+            "built": true,
+            "indexed_nodes": index.doc_count(),
+            "term_count": index.term_count(),
+            "coverage": if graph.node_count() == 0 {
+                1.0
+            } else {
+                index.doc_count() as f64 / graph.node_count() as f64
+            },
+        }),
+        None => json!({
+            "enabled": session.text_index_enabled(),
+            "built": false,
+            "indexed_nodes": 0,
+            "term_count": 0,
+            "coverage": 0.0,
+        }),
+    };
+
+    let last_saved_unix = session
+        .last_save()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs());
 
-    let file_size = std::fs::metadata(session.file_path())
-        .map(|m| m.len())
-        .unwrap_or(0);
+    let file_size = if session.is_ephemeral() {
+        0
+    } else {
+        std::fs::metadata(session.file_path())
+            .map(|m| m.len())
+            .unwrap_or(0)
+    };
 
-    Ok(ToolCallResult::json(&json!({
-        "node_count": graph.node_count(),
-        "edge_count": graph.edge_count(),
-        "dimension": graph.dimension(),
-        "session_count": session_index.session_count(),
+    let mut namespaces = serde_json::Map::new();
+    if !session.namespaces().is_empty() {
+        namespaces.insert(session.namespace().to_string(), namespace_counts(&session));
+        for (name, sibling) in session.namespaces() {
+            namespaces.insert(name.clone(), namespace_counts(&*sibling.read().await));
+        }
+    }
+
+    let mut stats = json!({
+        "namespace": session.namespace(),
+        "node_count": counts.node_count,
+        "edge_count": counts.edge_count,
+        "dimension": counts.dimension,
+        "session_count": counts.session_count,
         "current_session": session.current_session_id(),
-        "type_counts": type_counts,
+        "session_scope": {
+            "scope_to_current_session": session.scope_to_current_session(),
+            "session_ids": session.effective_session_ids(Vec::new()),
+        },
+        "type_counts": counts.type_counts_json(),
+        "embedding_coverage": {
+            "nodes_with_vectors": nodes_with_vectors,
+            "nodes_without_vectors": nodes_without_vectors,
+            "coverage": coverage,
+            "similarity_index": "linear_scan",
+        },
+        "text_index": text_index,
+        "query_cache": session.query_cache_stats(),
+        "dirty": session.is_dirty(),
+        "graph_version": session.graph_version(),
+        "last_saved_unix": last_saved_unix,
+        "last_save_error": session.last_error(),
+        "consecutive_save_failures": session.consecutive_save_failures(),
         "file_size_bytes": file_size,
         "file_path": session.file_path().display().to_string(),
-    })))
+        "ephemeral": session.is_ephemeral(),
+        "namespaces": namespaces,
+    });
+    if params.per_session {
+        stats["sessions"] = session_breakdown(graph, &params);
+    }
+    Ok(ToolCallResult::json(&stats))
+}
+
+/// One page of per-session node counts, in session ID order. Counts come from
+/// the session index; only episode nodes are looked up.
+fn session_breakdown(graph: &MemoryGraph, params: &StatsParams) -> Value {
+    let session_index = graph.session_index();
+    let with_episode: HashSet<u32> = graph
+        .type_index()
+        .get(EventType::Episode)
+        .iter()
+        .filter_map(|&id| graph.get_node(id).map(|n| n.session_id))
+        .collect();
+
+    let page_size = params.max_sessions.clamp(1, MAX_SESSIONS_PER_PAGE);
+    let mut remaining = session_index
+        .session_ids()
+        .into_iter()
+        .filter(|&sid| params.after_session.is_none_or(|after| sid > after));
+    let page: Vec<u32> = remaining.by_ref().take(page_size).collect();
+    let next_after_session = match (page.last(), remaining.next()) {
+        (Some(&last), Some(_)) => Some(last),
+        _ => None,
+    };
+
+    json!({
+        "total": session_index.session_count(),
+        "items": page
+            .iter()
+            .map(|&sid| json!({
+                "session_id": sid,
+                "node_count": session_index.node_count(sid),
+                "has_episode": with_episode.contains(&sid),
+            }))
+            .collect::<Vec<_>>(),
+        "next_after_session": next_after_session,
+    })
+}
+
+fn namespace_counts(session: &SessionManager) -> Value {
+    json!({
+        "node_count": session.graph().node_count(),
+        "edge_count": session.graph().edge_count(),
+        "dirty": session.is_dirty(),
+        "file_path": session.file_path().display().to_string(),
+    })
 }
@@ -1,7 +1,7 @@
 //! Tool: memory_suggest — Find similar memories for corrections/suggestions.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -52,7 +52,7 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_suggest tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: SuggestParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
@@ -65,7 +65,8 @@ pub async fn execute(
         })));
     }
 
-    let session = session.lock().await;
+    SessionManager::prepare_text_index(session).await;
+    let session = session.read().await;
     let graph = session.graph();
 
     // Use text search with low threshold to catch partial matches
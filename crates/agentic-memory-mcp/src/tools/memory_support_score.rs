@@ -0,0 +1,81 @@
+//! Tool: memory_support_score — How well does the graph support a belief?
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use agentic_memory::SupportContribution;
+
+use crate::session::SessionManager;
+use crate::types::{weight_json, McpError, McpResult, ToolCallResult, ToolDefinition};
+
+#[derive(Debug, Deserialize)]
+struct SupportScoreParams {
+    node_id: u64,
+}
+
+/// Return the tool definition for memory_support_score.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_support_score".to_string(),
+        description: Some(
+            "Aggregate evidential support and opposition for a node from incoming \
+             supports/contradicts edges, weighted by edge weight and source confidence"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "node_id": { "type": "integer", "description": "Node to score" }
+            },
+            "required": ["node_id"]
+        }),
+    }
+}
+
+/// Execute the memory_support_score tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: SupportScoreParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+    let session = session.read().await;
+    let graph = session.graph();
+    if graph.get_node(params.node_id).is_none() {
+        return Err(McpError::NodeNotFound(params.node_id));
+    }
+
+    let score = session
+        .query_engine()
+        .support_score(graph, params.node_id)
+        .map_err(|e| McpError::AgenticMemory(format!("Support scoring failed: {e}")))?;
+
+    Ok(ToolCallResult::json(&json!({
+        "node_id": score.node_id,
+        "support": score.support,
+        "opposition": score.opposition,
+        "net": score.net,
+        "supporter_count": score.supporters.len(),
+        "opposer_count": score.opposers.len(),
+        "supporters": contributions(&score.supporters),
+        "opposers": contributions(&score.opposers),
+    })))
+}
+
+fn contributions(items: &[SupportContribution]) -> Vec<Value> {
+    items
+        .iter()
+        .map(|c| {
+            json!({
+                "node_id": c.node_id,
+                "confidence": c.confidence,
+                "weight": weight_json(c.weight),
+                "strength": c.strength,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,78 @@
+//! Tool: memory_tag — Attach key/value tags to a node.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::session::SessionManager;
+use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+
+#[derive(Debug, Deserialize)]
+struct TagParams {
+    node_id: u64,
+    #[serde(default)]
+    tags: HashMap<String, String>,
+    #[serde(default)]
+    remove: Vec<String>,
+    expected_version: Option<u64>,
+}
+
+/// Return the tool definition for memory_tag.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_tag".to_string(),
+        description: Some(
+            "Attach or remove key/value tags on a node (e.g. source=user, topic=billing); returns the node's tag set"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "node_id": { "type": "integer", "description": "Node to tag" },
+                "tags": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string" },
+                    "description": "Tags to add or overwrite"
+                },
+                "remove": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Tag keys to remove"
+                },
+                "expected_version": {
+                    "type": "integer",
+                    "description": "Fail with a version conflict unless the graph is still at this graph_version"
+                }
+            },
+            "required": ["node_id"]
+        }),
+    }
+}
+
+/// Execute the memory_tag tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: TagParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+
+    if params.tags.keys().any(|k| k.trim().is_empty()) {
+        return Err(McpError::InvalidParams(
+            "Tag keys must be non-empty".to_string(),
+        ));
+    }
+
+    let mut session = session.write().await;
+    session.check_version(params.expected_version)?;
+    let tags = session.tag_node(params.node_id, params.tags, &params.remove)?;
+
+    Ok(ToolCallResult::json(&json!({
+        "node_id": params.node_id,
+        "tags": tags,
+        "graph_version": session.graph_version(),
+    })))
+}
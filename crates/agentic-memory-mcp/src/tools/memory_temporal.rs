@@ -1,7 +1,7 @@
 //! Tool: memory_temporal — Compare knowledge across time periods.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -123,7 +123,7 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_temporal tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: TemporalInputParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
@@ -133,7 +133,7 @@ pub async fn execute(
         range_b: params.range_b.to_time_range(),
     };
 
-    let session = session.lock().await;
+    let session = session.read().await;
 
     let result = session
         .query_engine()
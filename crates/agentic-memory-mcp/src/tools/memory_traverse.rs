@@ -1,19 +1,25 @@
-//! Tool: memory_traverse — Walk the graph from a starting node.
+//! Tool: memory_traverse — Walk the graph from one or more starting nodes.
 
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
 
-use agentic_memory::{EdgeType, TraversalDirection, TraversalParams};
+use agentic_memory::{Edge, EdgeType, TraversalDirection, TraversalParams};
 
 use crate::session::SessionManager;
-use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+use crate::types::{
+    edge_json, parse_edge_types, McpError, McpResult, NodeFieldSelection, ToolCallResult,
+    ToolDefinition, SELECTABLE_NODE_FIELDS,
+};
 
 #[derive(Debug, Deserialize)]
 struct TraverseParams {
-    start_id: u64,
+    start_id: Option<u64>,
+    #[serde(default)]
+    start_ids: Vec<u64>,
     #[serde(default)]
     edge_types: Vec<String>,
     #[serde(default = "default_direction")]
@@ -23,6 +29,34 @@ struct TraverseParams {
     #[serde(default = "default_max_results")]
     max_results: usize,
     min_confidence: Option<f32>,
+    #[serde(default = "default_format")]
+    format: String,
+    #[serde(default = "default_fields")]
+    fields: FieldsParam,
+}
+
+fn default_format() -> String {
+    "flat".to_string()
+}
+
+fn default_fields() -> FieldsParam {
+    FieldsParam::Preset("full".to_string())
+}
+
+/// `fields` is either a preset name or a list of node properties.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum FieldsParam {
+    Preset(String),
+    Select(Vec<String>),
+}
+
+/// How much of each reached node is serialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeFields {
+    Full,
+    IdAndType,
+    IdsOnly,
 }
 
 fn default_direction() -> String {
@@ -42,19 +76,48 @@ pub fn definition() -> ToolDefinition {
     ToolDefinition {
         name: "memory_traverse".to_string(),
         description: Some(
-            "Walk the graph from a starting node, following edges of specified types".to_string(),
+            "Walk the graph from one or more starting nodes, following edges of specified types"
+                .to_string(),
         ),
         input_schema: json!({
             "type": "object",
             "properties": {
                 "start_id": { "type": "integer", "description": "Starting node ID" },
-                "edge_types": { "type": "array", "items": { "type": "string" } },
+                "start_ids": {
+                    "type": "array",
+                    "items": { "type": "integer" },
+                    "description": "Several starting node IDs; each reached node is reported once, at its shortest depth from any of them, with the source_id it was reached from"
+                },
+                "edge_types": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Edge types to follow (default: the server's default_traversal_edge_types, or all types)"
+                },
                 "direction": { "type": "string", "enum": ["forward", "backward", "both"], "default": "forward" },
                 "max_depth": { "type": "integer", "default": 5 },
                 "max_results": { "type": "integer", "default": 20 },
-                "min_confidence": { "type": "number" }
-            },
-            "required": ["start_id"]
+                "min_confidence": { "type": "number" },
+                "format": {
+                    "type": "string",
+                    "enum": ["flat", "tree"],
+                    "default": "flat",
+                    "description": "flat: visited list plus edges; tree: nested nodes with children and the edge used to reach each"
+                },
+                "fields": {
+                    "oneOf": [
+                        {
+                            "type": "string",
+                            "enum": ["full", "id_and_type", "ids_only"]
+                        },
+                        {
+                            "type": "array",
+                            "items": { "type": "string", "enum": SELECTABLE_NODE_FIELDS }
+                        }
+                    ],
+                    "default": "full",
+                    "description": "Per-node payload: full content, id/event_type/depth only, or bare ids (flat visited becomes a list of integers); or a list of node properties to return besides id, event_type and depth"
+                }
+            }
         }),
     }
 }
@@ -62,29 +125,63 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_traverse tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: TraverseParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+    let as_tree = match params.format.as_str() {
+        "flat" => false,
+        "tree" => true,
+        other => {
+            return Err(McpError::InvalidParams(format!(
+                "Unknown format '{other}' (expected 'flat' or 'tree')"
+            )))
+        }
+    };
+    let (fields, selection) = match &params.fields {
+        FieldsParam::Preset(preset) => match preset.as_str() {
+            "full" => (NodeFields::Full, None),
+            "id_and_type" => (NodeFields::IdAndType, None),
+            "ids_only" => (NodeFields::IdsOnly, None),
+            other => {
+                return Err(McpError::InvalidParams(format!(
+                    "Unknown fields '{other}' (expected 'full', 'id_and_type' or 'ids_only')"
+                )))
+            }
+        },
+        FieldsParam::Select(names) => (NodeFields::Full, Some(NodeFieldSelection::parse(names)?)),
+    };
 
     let edge_types: Vec<EdgeType> = if params.edge_types.is_empty() {
-        vec![
-            EdgeType::CausedBy,
-            EdgeType::Supports,
-            EdgeType::Contradicts,
-            EdgeType::Supersedes,
-            EdgeType::RelatedTo,
-            EdgeType::PartOf,
-            EdgeType::TemporalNext,
-        ]
+        // The session's configured default; none configured means all.
+        let configured = session
+            .read()
+            .await
+            .tool_defaults()
+            .traversal_edge_types
+            .clone();
+        if configured.is_empty() {
+            EdgeType::all().to_vec()
+        } else {
+            configured
+        }
     } else {
-        params
-            .edge_types
-            .iter()
-            .filter_map(|name| EdgeType::from_name(name))
-            .collect()
+        parse_edge_types(&params.edge_types)?
     };
 
+    let mut start_ids: Vec<u64> = params.start_id.into_iter().collect();
+    for id in &params.start_ids {
+        if !start_ids.contains(id) {
+            start_ids.push(*id);
+        }
+    }
+    if start_ids.is_empty() {
+        return Err(McpError::InvalidParams(
+            "start_id or start_ids is required".to_string(),
+        ));
+    }
+    let multi_source = !params.start_ids.is_empty();
+
     let direction = match params.direction.as_str() {
         "backward" => TraversalDirection::Backward,
         "both" => TraversalDirection::Both,
@@ -92,7 +189,7 @@ pub async fn execute(
     };
 
     let traversal = TraversalParams {
-        start_id: params.start_id,
+        start_id: start_ids[0],
         edge_types,
         direction,
         max_depth: params.max_depth,
@@ -100,45 +197,142 @@ pub async fn execute(
         min_confidence: params.min_confidence.unwrap_or(0.0),
     };
 
-    let session = session.lock().await;
+    let session = session.read().await;
     let result = session
         .query_engine()
-        .traverse(session.graph(), traversal)
+        .traverse_multi(session.graph(), &start_ids, traversal)
         .map_err(|e| McpError::AgenticMemory(format!("Traversal failed: {e}")))?;
 
-    let visited: Vec<Value> = result
-        .visited
-        .iter()
-        .filter_map(|id| {
-            session.graph().get_node(*id).map(|node| {
-                json!({
+    let graph = session.graph();
+    let node_json = |id: u64| {
+        graph.get_node(id).map(|node| {
+            let depth = result.depths.get(&id).copied().unwrap_or(0);
+            let mut value = match fields {
+                NodeFields::Full => json!({
                     "id": node.id,
                     "event_type": node.event_type.name(),
                     "content": node.content,
                     "confidence": node.confidence,
-                    "depth": result.depths.get(id).copied().unwrap_or(0),
-                })
-            })
-        })
-        .collect();
-
-    let edges: Vec<Value> = result
-        .edges_traversed
-        .iter()
-        .map(|e| {
-            json!({
-                "source_id": e.source_id,
-                "target_id": e.target_id,
-                "edge_type": e.edge_type.name(),
-                "weight": e.weight,
-            })
+                    "depth": depth,
+                }),
+                NodeFields::IdAndType => json!({
+                    "id": node.id,
+                    "event_type": node.event_type.name(),
+                    "depth": depth,
+                }),
+                NodeFields::IdsOnly => return json!({ "id": node.id }),
+            };
+            if let Some(selection) = &selection {
+                selection.apply(&mut value, node);
+            }
+            if multi_source {
+                value["source_id"] = json!(result.sources.get(&id).copied().unwrap_or(id));
+            }
+            value
         })
-        .collect();
+    };
+
+    let back_edges: Vec<Value> = result.back_edges.iter().map(edge_json).collect();
+
+    if as_tree {
+        // Each traversed edge discovered exactly one node: the endpoint one level deeper.
+        let mut children: HashMap<u64, Vec<(u64, &Edge)>> = HashMap::new();
+        for edge in &result.edges_traversed {
+            let source_depth = result.depths.get(&edge.source_id).copied();
+            let target_depth = result.depths.get(&edge.target_id).copied();
+            let (parent, child) = match (source_depth, target_depth) {
+                (Some(s), Some(t)) if s + 1 == t => (edge.source_id, edge.target_id),
+                _ => (edge.target_id, edge.source_id),
+            };
+            children.entry(parent).or_default().push((child, edge));
+        }
+
+        if multi_source {
+            let trees: Vec<Value> = start_ids
+                .iter()
+                .map(|id| build_tree(*id, None, &children, &node_json))
+                .collect();
+            return Ok(ToolCallResult::json(&json!({
+                "start_ids": start_ids,
+                "visited_count": result.visited.len(),
+                "format": "tree",
+                "trees": trees,
+                "cycles_detected": !back_edges.is_empty(),
+                "back_edges": back_edges,
+                "complete": result.is_complete(),
+                "truncated_at_depth": result.truncated_at_depth,
+                "frontier_size": result.frontier,
+            })));
+        }
+
+        let tree = build_tree(start_ids[0], None, &children, &node_json);
+        return Ok(ToolCallResult::json(&json!({
+            "start_id": start_ids[0],
+            "visited_count": result.visited.len(),
+            "format": "tree",
+            "tree": tree,
+            "cycles_detected": !back_edges.is_empty(),
+            "back_edges": back_edges,
+            "complete": result.is_complete(),
+            "truncated_at_depth": result.truncated_at_depth,
+            "frontier_size": result.frontier,
+        })));
+    }
+
+    let visited: Vec<Value> = if fields == NodeFields::IdsOnly {
+        result
+            .visited
+            .iter()
+            .filter(|id| graph.get_node(**id).is_some())
+            .map(|id| json!(id))
+            .collect()
+    } else {
+        result
+            .visited
+            .iter()
+            .filter_map(|id| node_json(*id))
+            .collect()
+    };
+    let edges: Vec<Value> = result.edges_traversed.iter().map(edge_json).collect();
 
-    Ok(ToolCallResult::json(&json!({
-        "start_id": params.start_id,
+    let mut response = json!({
         "visited_count": visited.len(),
         "visited": visited,
         "edges_traversed": edges,
-    })))
+        "cycles_detected": !back_edges.is_empty(),
+        "back_edges": back_edges,
+        "complete": result.is_complete(),
+        "truncated_at_depth": result.truncated_at_depth,
+        "frontier_size": result.frontier,
+    });
+    if multi_source {
+        response["start_ids"] = json!(start_ids);
+    } else {
+        response["start_id"] = json!(start_ids[0]);
+    }
+    Ok(ToolCallResult::json(&response))
+}
+
+/// Nest `id` and its BFS descendants; `via` is the edge that reached `id`.
+fn build_tree(
+    id: u64,
+    via: Option<&Edge>,
+    children: &HashMap<u64, Vec<(u64, &Edge)>>,
+    node_json: &dyn Fn(u64) -> Option<Value>,
+) -> Value {
+    let mut node = node_json(id).unwrap_or_else(|| json!({ "id": id }));
+    if let Some(edge) = via {
+        node["edge"] = edge_json(edge);
+    }
+    node["children"] = Value::Array(
+        children
+            .get(&id)
+            .map(|kids| {
+                kids.iter()
+                    .map(|(child, edge)| build_tree(*child, Some(*edge), children, node_json))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    );
+    node
 }
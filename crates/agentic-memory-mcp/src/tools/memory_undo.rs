@@ -0,0 +1,72 @@
+//! Tool: memory_undo — Reverse the most recent graph mutations.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::session::SessionManager;
+use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+
+#[derive(Debug, Deserialize)]
+struct UndoParams {
+    #[serde(default = "default_steps")]
+    steps: usize,
+    expected_version: Option<u64>,
+}
+
+fn default_steps() -> usize {
+    1
+}
+
+/// Return the tool definition for memory_undo.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_undo".to_string(),
+        description: Some(
+            "Undo the last mutation(s) made in this server run: adds, corrections, links, retypes, deletes"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "steps": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "default": 1,
+                    "description": "How many of the most recent mutations to undo"
+                },
+                "expected_version": {
+                    "type": "integer",
+                    "description": "Fail with a version conflict unless the graph is still at this graph_version"
+                }
+            }
+        }),
+    }
+}
+
+/// Execute the memory_undo tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    let params: UndoParams =
+        serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
+    if params.steps == 0 {
+        return Err(McpError::InvalidParams(
+            "steps must be at least 1".to_string(),
+        ));
+    }
+
+    let mut session = session.write().await;
+    session.check_version(params.expected_version)?;
+    let undone = session.undo(params.steps)?;
+
+    Ok(ToolCallResult::json(&json!({
+        "undone_count": undone.len(),
+        "undone": undone,
+        "remaining": session.undo_available(),
+        "graph_version": session.graph_version(),
+    })))
+}
@@ -0,0 +1,41 @@
+//! Tool: memory_unpin — Let a pinned node be forgotten again.
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use serde_json::{json, Value};
+
+use crate::session::SessionManager;
+use crate::types::{McpResult, ToolCallResult, ToolDefinition};
+
+use super::memory_pin;
+
+/// Return the tool definition for memory_unpin.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "memory_unpin".to_string(),
+        description: Some(
+            "Unpin a node so it is subject to decay-based forgetting and deletion again"
+                .to_string(),
+        ),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "node_id": { "type": "integer", "description": "Node to unpin" },
+                "expected_version": {
+                    "type": "integer",
+                    "description": "Fail with a version conflict unless the graph is still at this graph_version"
+                }
+            },
+            "required": ["node_id"]
+        }),
+    }
+}
+
+/// Execute the memory_unpin tool.
+pub async fn execute(
+    args: Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> McpResult<ToolCallResult> {
+    memory_pin::set_pinned(args, session, false).await
+}
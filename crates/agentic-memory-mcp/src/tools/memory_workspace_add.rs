@@ -1,7 +1,7 @@
 //! Tool: memory_workspace_add — Add an .amem file to a workspace.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -62,7 +62,7 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_workspace_add tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: AddParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
@@ -74,7 +74,7 @@ pub async fn execute(
         ))
     })?;
 
-    let mut session = session.lock().await;
+    let mut session = session.write().await;
     let ctx_id = session.workspace_manager_mut().add_context(
         &params.workspace_id,
         &params.path,
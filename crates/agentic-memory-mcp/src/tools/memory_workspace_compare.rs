@@ -1,7 +1,7 @@
 //! Tool: memory_workspace_compare — Compare a topic across memory contexts.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -55,12 +55,12 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_workspace_compare tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: CompareParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
 
-    let session = session.lock().await;
+    let session = session.read().await;
     let comparison = session.workspace_manager().compare(
         &params.workspace_id,
         &params.item,
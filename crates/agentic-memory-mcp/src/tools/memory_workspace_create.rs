@@ -1,7 +1,7 @@
 //! Tool: memory_workspace_create — Create a multi-memory workspace.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -39,12 +39,12 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_workspace_create tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: CreateParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
 
-    let mut session = session.lock().await;
+    let mut session = session.write().await;
     let id = session.workspace_manager_mut().create(&params.name);
 
     Ok(ToolCallResult::json(&json!({
@@ -1,7 +1,7 @@
 //! Tool: memory_workspace_list — List loaded memory contexts in a workspace.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -39,12 +39,12 @@ pub fn definition() -> ToolDefinition {
 /// Execute the memory_workspace_list tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: ListParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
 
-    let session = session.lock().await;
+    let session = session.read().await;
     let contexts = session.workspace_manager().list(&params.workspace_id)?;
 
     let items: Vec<Value> = contexts
@@ -3,20 +3,42 @@
 pub mod conversation_log;
 pub mod memory_add;
 pub mod memory_causal;
+pub mod memory_clusters;
 pub mod memory_compact;
+pub mod memory_compact_chain;
+pub mod memory_compare;
 pub mod memory_context;
 pub mod memory_correct;
+pub mod memory_edges;
+pub mod memory_ego;
 pub mod memory_evidence;
+pub mod memory_explain_causal;
+pub mod memory_find_connected;
 pub mod memory_ground;
+pub mod memory_health;
+pub mod memory_history;
+pub mod memory_import_notes;
+pub mod memory_pin;
 pub mod memory_quality;
 pub mod memory_query;
+pub mod memory_reembed;
 pub mod memory_resolve;
+pub mod memory_retrieve;
+pub mod memory_retype;
+pub mod memory_reweight;
+pub mod memory_save;
+pub mod memory_session_diff;
 pub mod memory_session_resume;
+pub mod memory_session_window;
 pub mod memory_similar;
 pub mod memory_stats;
 pub mod memory_suggest;
+pub mod memory_support_score;
+pub mod memory_tag;
 pub mod memory_temporal;
 pub mod memory_traverse;
+pub mod memory_undo;
+pub mod memory_unpin;
 pub mod memory_workspace_add;
 pub mod memory_workspace_compare;
 pub mod memory_workspace_create;
@@ -26,6 +48,7 @@ pub mod memory_workspace_xref;
 pub mod registry;
 pub mod session_end;
 pub mod session_start;
+pub mod timeout;
 
 // 24 Inventions — INFINITUS
 pub mod invention_collective; // Inventions 9-12:  Ancestor Memory, Collective Memory, Memory Fusion, Memory Telepathy
@@ -41,4 +64,4 @@ pub mod v3_tools;
 #[cfg(feature = "longevity")]
 pub mod longevity_tools;
 
-pub use registry::ToolRegistry;
+pub use registry::{CustomTools, ToolFuture, ToolHandler, ToolRegistry};
@@ -1,12 +1,17 @@
 //! Tool registration and dispatch.
 
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde_json::Value;
 
-use crate::session::SessionManager;
-use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
+use crate::session::{SessionManager, ToolDefaults};
+use crate::types::{McpError, McpResult, ToolCallResult, ToolContent, ToolDefinition};
+
+/// Read-only tools whose results may be served from the session's query cache.
+const CACHEABLE_TOOLS: &[&str] = &["memory_query", "memory_similar"];
 
 /// Token conservation parameters injected into every tool's input schema.
 fn inject_token_conservation_params(tools: &mut [ToolDefinition]) {
@@ -16,7 +21,8 @@ fn inject_token_conservation_params(tools: &mut [ToolDefinition]) {
         "since": { "type": "integer", "description": "Only return changes since this Unix timestamp" },
         "token_budget": { "type": "integer", "description": "Maximum token budget for response" },
         "max_results": { "type": "integer", "default": 10, "description": "Maximum number of results" },
-        "cursor": { "type": "string", "description": "Pagination cursor for next page" }
+        "cursor": { "type": "string", "description": "Pagination cursor for next page" },
+        "namespace": { "type": "string", "description": "Memory namespace to address (default: the server's default namespace)" }
     });
     for tool in tools.iter_mut() {
         if let Some(props) = tool.input_schema.get_mut("properties") {
@@ -42,20 +48,42 @@ use super::{
     invention_transcendent,
     memory_add,
     memory_causal,
+    memory_clusters,
     memory_compact,
+    memory_compact_chain,
+    memory_compare,
     memory_context,
     memory_correct,
+    memory_edges,
+    memory_ego,
     memory_evidence,
+    memory_explain_causal,
+    memory_find_connected,
     memory_ground,
+    memory_health,
+    memory_history,
+    memory_import_notes,
+    memory_pin,
     memory_quality,
     memory_query,
+    memory_reembed,
     memory_resolve,
+    memory_retrieve,
+    memory_retype,
+    memory_reweight,
+    memory_save,
+    memory_session_diff,
     memory_session_resume,
+    memory_session_window,
     memory_similar,
     memory_stats,
     memory_suggest,
+    memory_support_score,
+    memory_tag,
     memory_temporal,
     memory_traverse,
+    memory_undo,
+    memory_unpin,
     memory_workspace_add,
     memory_workspace_compare,
     memory_workspace_create,
@@ -66,25 +94,168 @@ use super::{
     session_start,
 };
 
+/// Future returned by a [`ToolHandler`].
+pub type ToolFuture = Pin<Box<dyn Future<Output = McpResult<ToolCallResult>> + Send>>;
+
+/// Handler for a tool registered at runtime with [`CustomTools::register`].
+///
+/// Implemented for any `Fn(Value, Arc<RwLock<SessionManager>>) -> impl Future`
+/// closure, so most callers never implement it by hand.
+pub trait ToolHandler: Send + Sync {
+    /// Execute the tool with its JSON arguments.
+    fn call(&self, args: Value, session: Arc<RwLock<SessionManager>>) -> ToolFuture;
+}
+
+impl<F, Fut> ToolHandler for F
+where
+    F: Fn(Value, Arc<RwLock<SessionManager>>) -> Fut + Send + Sync,
+    Fut: Future<Output = McpResult<ToolCallResult>> + Send + 'static,
+{
+    fn call(&self, args: Value, session: Arc<RwLock<SessionManager>>) -> ToolFuture {
+        Box::pin(self(args, session))
+    }
+}
+
+struct CustomTool {
+    definition: ToolDefinition,
+    handler: Arc<dyn ToolHandler>,
+}
+
+/// Tools registered at runtime by crates embedding this server.
+///
+/// Clones share one table, so a tool registered after the handler was built
+/// is still listed and dispatched.
+#[derive(Clone, Default)]
+pub struct CustomTools {
+    tools: Arc<std::sync::RwLock<Vec<CustomTool>>>,
+}
+
+impl CustomTools {
+    /// Register a custom tool, listed after the built-ins on every tool surface.
+    ///
+    /// The name is trimmed. Fails with `InvalidParams` if it is empty or
+    /// already taken by a built-in or previously registered tool.
+    pub fn register(
+        &self,
+        mut definition: ToolDefinition,
+        handler: impl ToolHandler + 'static,
+    ) -> McpResult<()> {
+        let name = definition.name.trim().to_string();
+        if name.is_empty() {
+            return Err(McpError::InvalidParams(
+                "Tool name must not be empty".to_string(),
+            ));
+        }
+        if ToolRegistry::builtin_tools(&ToolDefaults::default())
+            .iter()
+            .any(|t| t.name == name)
+            || memory_compact::definitions().iter().any(|t| t.name == name)
+        {
+            return Err(McpError::InvalidParams(format!(
+                "Tool name '{name}' is reserved by a built-in tool"
+            )));
+        }
+        let mut tools = self.tools.write().unwrap_or_else(|e| e.into_inner());
+        if tools.iter().any(|t| t.definition.name == name) {
+            return Err(McpError::InvalidParams(format!(
+                "Tool '{name}' is already registered"
+            )));
+        }
+        definition.name = name;
+        tools.push(CustomTool {
+            definition,
+            handler: Arc::new(handler),
+        });
+        Ok(())
+    }
+
+    /// Remove a custom tool. Returns false if no such tool was registered.
+    pub fn unregister(&self, name: &str) -> bool {
+        let mut tools = self.tools.write().unwrap_or_else(|e| e.into_inner());
+        let before = tools.len();
+        tools.retain(|t| t.definition.name != name);
+        tools.len() != before
+    }
+
+    /// Definitions of every registered tool, in registration order.
+    pub fn definitions(&self) -> Vec<ToolDefinition> {
+        self.tools
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|t| t.definition.clone())
+            .collect()
+    }
+
+    fn handler(&self, name: &str) -> Option<Arc<dyn ToolHandler>> {
+        self.tools
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|t| t.definition.name == name)
+            .map(|t| t.handler.clone())
+    }
+}
+
+impl std::fmt::Debug for CustomTools {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.definitions().iter().map(|t| &t.name))
+            .finish()
+    }
+}
+
 /// Registry of all available MCP tools.
 pub struct ToolRegistry;
 
 impl ToolRegistry {
     /// List all available tool definitions.
     pub fn list_tools() -> Vec<ToolDefinition> {
+        Self::list_tools_with(&ToolDefaults::default())
+    }
+
+    /// List all available tool definitions, with schemas describing the
+    /// session's `defaults`.
+    pub fn list_tools_with(defaults: &ToolDefaults) -> Vec<ToolDefinition> {
+        Self::builtin_tools(defaults)
+    }
+
+    fn builtin_tools(defaults: &ToolDefaults) -> Vec<ToolDefinition> {
         let mut tools = vec![
             conversation_log::definition(),
-            memory_add::definition(),
+            memory_add::definition_with(defaults),
+            memory_import_notes::definition(),
             memory_query::definition(),
             memory_quality::definition(),
             memory_traverse::definition(),
+            memory_find_connected::definition(),
             memory_correct::definition(),
             memory_resolve::definition(),
+            memory_history::definition(),
+            memory_compact_chain::definition(),
+            memory_retype::definition(),
+            memory_reembed::definition(),
+            memory_reweight::definition(),
+            memory_tag::definition(),
+            memory_pin::definition(),
+            memory_unpin::definition(),
             memory_context::definition(),
+            memory_edges::definition(),
+            memory_ego::definition(),
             memory_similar::definition(),
+            memory_compare::definition(),
+            memory_retrieve::definition(),
             memory_causal::definition(),
+            memory_explain_causal::definition(),
+            memory_support_score::definition(),
             memory_temporal::definition(),
+            memory_session_diff::definition(),
+            memory_session_window::definition(),
+            memory_clusters::definition(),
             memory_stats::definition(),
+            memory_health::definition(),
+            memory_save::definition(),
+            memory_undo::definition(),
             // V2: Grounding (anti-hallucination)
             memory_ground::definition(),
             memory_evidence::definition(),
@@ -128,31 +299,73 @@ impl ToolRegistry {
         memory_compact::definitions()
     }
 
-    /// Dispatch a tool call to the appropriate handler.
+    /// Dispatch a tool call to the appropriate built-in handler.
     pub async fn call(
         name: &str,
         arguments: Option<Value>,
-        session: &Arc<Mutex<SessionManager>>,
+        session: &Arc<RwLock<SessionManager>>,
+    ) -> McpResult<ToolCallResult> {
+        Self::call_with(&CustomTools::default(), name, arguments, session).await
+    }
+
+    /// Dispatch a tool call, trying `custom` tools before the built-ins.
+    pub async fn call_with(
+        custom: &CustomTools,
+        name: &str,
+        arguments: Option<Value>,
+        session: &Arc<RwLock<SessionManager>>,
     ) -> McpResult<ToolCallResult> {
         let args = arguments.unwrap_or(Value::Object(serde_json::Map::new()));
+        let namespace = args.get("namespace").and_then(Value::as_str);
+        let session = &SessionManager::resolve_namespace(session, namespace).await?;
+
+        if let Some(handler) = custom.handler(name) {
+            return handler.call(args, session.clone()).await;
+        }
 
         if let Some(result) = memory_compact::try_execute(name, args.clone(), session).await {
             return result;
         }
 
+        if CACHEABLE_TOOLS.contains(&name) {
+            return Self::call_cached(name, args, session).await;
+        }
+
         match name {
             "conversation_log" => conversation_log::execute(args, session).await,
             "memory_add" => memory_add::execute(args, session).await,
+            "memory_import_notes" => memory_import_notes::execute(args, session).await,
             "memory_query" => memory_query::execute(args, session).await,
             "memory_quality" => memory_quality::execute(args, session).await,
             "memory_traverse" => memory_traverse::execute(args, session).await,
+            "memory_find_connected" => memory_find_connected::execute(args, session).await,
             "memory_correct" => memory_correct::execute(args, session).await,
             "memory_resolve" => memory_resolve::execute(args, session).await,
+            "memory_history" => memory_history::execute(args, session).await,
+            "memory_compact_chain" => memory_compact_chain::execute(args, session).await,
+            "memory_retype" => memory_retype::execute(args, session).await,
+            "memory_reembed" => memory_reembed::execute(args, session).await,
+            "memory_reweight" => memory_reweight::execute(args, session).await,
+            "memory_tag" => memory_tag::execute(args, session).await,
+            "memory_pin" => memory_pin::execute(args, session).await,
+            "memory_unpin" => memory_unpin::execute(args, session).await,
             "memory_context" => memory_context::execute(args, session).await,
+            "memory_edges" => memory_edges::execute(args, session).await,
+            "memory_ego" => memory_ego::execute(args, session).await,
             "memory_similar" => memory_similar::execute(args, session).await,
+            "memory_compare" => memory_compare::execute(args, session).await,
+            "memory_retrieve" => memory_retrieve::execute(args, session).await,
             "memory_causal" => memory_causal::execute(args, session).await,
+            "memory_explain_causal" => memory_explain_causal::execute(args, session).await,
+            "memory_support_score" => memory_support_score::execute(args, session).await,
             "memory_temporal" => memory_temporal::execute(args, session).await,
+            "memory_session_diff" => memory_session_diff::execute(args, session).await,
+            "memory_session_window" => memory_session_window::execute(args, session).await,
+            "memory_clusters" => memory_clusters::execute(args, session).await,
             "memory_stats" => memory_stats::execute(args, session).await,
+            "memory_health" => memory_health::execute(args, session).await,
+            "memory_save" => memory_save::execute(args, session).await,
+            "memory_undo" => memory_undo::execute(args, session).await,
             // V2: Grounding
             "memory_ground" => memory_ground::execute(args, session).await,
             "memory_evidence" => memory_evidence::execute(args, session).await,
@@ -220,4 +433,44 @@ impl ToolRegistry {
             }
         }
     }
+
+    /// Run a read-only tool through the session's query cache, tagging the
+    /// result with `cached`. Falls through to a plain call when disabled.
+    async fn call_cached(
+        name: &str,
+        args: Value,
+        session: &Arc<RwLock<SessionManager>>,
+    ) -> McpResult<ToolCallResult> {
+        // Captured before the call, so a result computed while a mutation
+        // lands is filed under the version it was read from.
+        let key = session.read().await.query_cache_key(name, &args);
+        if let Some(key) = &key {
+            if let Some(mut value) = session.write().await.cached_query_result(key) {
+                value["cached"] = Value::Bool(true);
+                return Ok(ToolCallResult::json(&value));
+            }
+        }
+
+        let result = match name {
+            "memory_similar" => memory_similar::execute(args, session).await?,
+            _ => memory_query::execute(args, session).await?,
+        };
+        let Some(key) = key else {
+            return Ok(result);
+        };
+        if result.is_error.is_some() {
+            return Ok(result);
+        }
+        let value = match result.content.first() {
+            Some(ToolContent::Text { text }) => serde_json::from_str::<Value>(text).ok(),
+            _ => None,
+        };
+        // A cancelled scan is partial and must not answer later calls.
+        let Some(mut value) = value.filter(|v| v.is_object() && v["cancelled"] != true) else {
+            return Ok(result);
+        };
+        value["cached"] = Value::Bool(false);
+        session.write().await.cache_query_result(key, value.clone());
+        Ok(ToolCallResult::json(&value))
+    }
 }
@@ -1,12 +1,12 @@
 //! Tool: session_end — End a session and optionally create an episode summary.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
 
-use crate::session::SessionManager;
+use crate::session::{EpisodeFormat, SessionManager};
 use crate::types::{McpError, McpResult, ToolCallResult, ToolDefinition};
 
 #[derive(Debug, Deserialize)]
@@ -15,6 +15,7 @@ struct EndParams {
     #[serde(default = "default_create_episode")]
     create_episode: bool,
     summary: Option<String>,
+    format: Option<String>,
 }
 
 fn default_create_episode() -> bool {
@@ -33,39 +34,131 @@ pub fn definition() -> ToolDefinition {
             "properties": {
                 "session_id": { "type": "integer" },
                 "create_episode": { "type": "boolean", "default": true },
-                "summary": { "type": "string", "description": "Episode summary content" }
+                "summary": {
+                    "type": "string",
+                    "description": "Episode summary content. When omitted and the client supports sampling, the client's model is asked to write one"
+                },
+                "format": {
+                    "type": "string",
+                    "enum": ["text", "structured"],
+                    "description": "Store the episode as the summary text, or as a JSON record with the session's node IDs, type counts, and key nodes (default: server setting)"
+                }
             }
         }),
     }
 }
 
+/// Maximum number of session memories sent to the client for summarization.
+const SAMPLING_MAX_NODES: usize = 200;
+/// Maximum characters of each memory included in the sampling prompt.
+const SAMPLING_MAX_CHARS_PER_NODE: usize = 280;
+/// Token budget requested for a sampled summary.
+const SAMPLING_MAX_TOKENS: u32 = 400;
+
+const SAMPLING_SYSTEM_PROMPT: &str =
+    "You write episode summaries for an agent's long-term memory. \
+     Reply with the summary only: the main goal, key facts learned, decisions made, \
+     corrections, and the outcome, in at most five sentences.";
+
+/// Ask the client's model for an episode summary of `session_id`.
+///
+/// Returns `None` when the client did not advertise sampling, the session has
+/// no memories, or the request fails; callers fall back to a generic summary.
+async fn sample_summary(session: &Arc<RwLock<SessionManager>>, session_id: u32) -> Option<String> {
+    let (client, prompt) = {
+        let session = session.read().await;
+        let client = session.sampling_client()?;
+        let graph = session.graph();
+        let node_ids = graph.session_index().get_session(session_id);
+        let lines: Vec<String> = node_ids
+            .iter()
+            .rev()
+            .take(SAMPLING_MAX_NODES)
+            .rev()
+            .filter_map(|id| graph.get_node(*id))
+            .map(|node| {
+                let content: String = node
+                    .content
+                    .chars()
+                    .take(SAMPLING_MAX_CHARS_PER_NODE)
+                    .collect();
+                format!("- [{}] {content}", node.event_type.name())
+            })
+            .collect();
+        if lines.is_empty() {
+            return None;
+        }
+        let prompt = format!(
+            "Summarize session {session_id} from its {} memories:\n\n{}",
+            node_ids.len(),
+            lines.join("\n")
+        );
+        (client, prompt)
+    };
+
+    // The session lock is released while waiting so the transport can keep
+    // serving requests and deliver the client's reply.
+    match client
+        .create_message(SAMPLING_SYSTEM_PROMPT, &prompt, SAMPLING_MAX_TOKENS)
+        .await
+    {
+        Ok(summary) => Some(summary),
+        Err(e) => {
+            tracing::warn!("Sampling episode summary failed, using default: {e}");
+            None
+        }
+    }
+}
+
 /// Execute the session_end tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: EndParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
 
-    let mut session = session.lock().await;
-    let session_id = params
-        .session_id
-        .unwrap_or_else(|| session.current_session_id());
+    let session_id = match params.session_id {
+        Some(id) => id,
+        None => session.read().await.current_session_id(),
+    };
+
+    let format = match params.format.as_deref() {
+        None => None,
+        Some("text") => Some(EpisodeFormat::Text),
+        Some("structured") => Some(match session.read().await.episode_format() {
+            EpisodeFormat::Structured(template) => EpisodeFormat::Structured(template.clone()),
+            EpisodeFormat::Text => EpisodeFormat::Structured(Default::default()),
+        }),
+        Some(other) => {
+            return Err(McpError::InvalidParams(format!(
+                "format must be 'text' or 'structured', got '{other}'"
+            )))
+        }
+    };
 
     if params.create_episode {
-        let summary = params
-            .summary
-            .unwrap_or_else(|| format!("Session {session_id} completed"));
+        let (summary, summary_source) = match params.summary {
+            Some(summary) => (summary, "caller"),
+            None => match sample_summary(session, session_id).await {
+                Some(summary) => (summary, "sampling"),
+                None => (format!("Session {session_id} completed"), "default"),
+            },
+        };
 
-        let episode_id = session.end_session_with_episode(session_id, &summary)?;
+        let mut session = session.write().await;
+        let format = format.unwrap_or_else(|| session.episode_format().clone());
+        let episode_id = session.end_session_with_episode_as(session_id, &summary, &format)?;
 
         Ok(ToolCallResult::json(&json!({
             "session_id": session_id,
             "episode_node_id": episode_id,
             "summary": summary,
+            "summary_source": summary_source,
+            "format": format.name(),
         })))
     } else {
-        session.save()?;
+        session.write().await.save()?;
         Ok(ToolCallResult::json(&json!({
             "session_id": session_id,
             "episode_node_id": null,
@@ -5,7 +5,7 @@
 //! gap information) so the agent doesn't start completely blank.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -40,12 +40,12 @@ pub fn definition() -> ToolDefinition {
 /// Execute the session_start tool.
 pub async fn execute(
     args: Value,
-    session: &Arc<Mutex<SessionManager>>,
+    session: &Arc<RwLock<SessionManager>>,
 ) -> McpResult<ToolCallResult> {
     let params: StartParams =
         serde_json::from_value(args).map_err(|e| McpError::InvalidParams(e.to_string()))?;
 
-    let mut session = session.lock().await;
+    let mut session = session.write().await;
     let session_id = session.start_session(params.session_id)?;
 
     // ── Retrieve last session context (bootstrap problem solver) ──────────
@@ -64,6 +64,7 @@ pub async fn execute(
         created_after: None,
         created_before: None,
         min_decay_score: None,
+        max_decay_score: None,
         max_results: 1,
         sort_by: PatternSort::MostRecent,
     };
@@ -0,0 +1,121 @@
+//! Per-tool call timeouts.
+//!
+//! Every tool belongs to a class — read, write, or long-running — with its
+//! own default limit, and single tools can be given their own. The handler
+//! wraps each call in the limit its [`ToolTimeouts`] give so one slow call
+//! cannot keep the session lock (and every call queued behind it) forever.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Broad cost class of a tool, used to pick its default timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolClass {
+    /// Queries and reports that leave the graph unchanged.
+    Read,
+    /// Calls that change the graph. Also the class of unlisted tools.
+    Write,
+    /// Whole-graph rewrites, saves, and cross-file workspace scans.
+    Long,
+}
+
+const READ_TOOLS: &[&str] = &[
+    "memory_query",
+    "memory_quality",
+    "memory_traverse",
+    "memory_find_connected",
+    "memory_history",
+    "memory_context",
+    "memory_edges",
+    "memory_ego",
+    "memory_similar",
+    "memory_compare",
+    "memory_retrieve",
+    "memory_causal",
+    "memory_explain_causal",
+    "memory_support_score",
+    "memory_temporal",
+    "memory_session_diff",
+    "memory_session_window",
+    "memory_clusters",
+    "memory_stats",
+    "memory_health",
+    "memory_ground",
+    "memory_evidence",
+    "memory_suggest",
+    "memory_session_resume",
+    "memory_workspace_list",
+];
+
+const LONG_TOOLS: &[&str] = &[
+    "memory_reembed",
+    "memory_save",
+    "memory_compact_chain",
+    "memory_workspace_add",
+    "memory_workspace_query",
+    "memory_workspace_compare",
+    "memory_workspace_xref",
+    // May wait up to `SAMPLING_TIMEOUT` for the client to write the summary.
+    "session_end",
+];
+
+impl ToolClass {
+    /// Class of the named tool.
+    pub fn of(tool: &str) -> Self {
+        if READ_TOOLS.contains(&tool) {
+            ToolClass::Read
+        } else if LONG_TOOLS.contains(&tool) {
+            ToolClass::Long
+        } else {
+            ToolClass::Write
+        }
+    }
+}
+
+/// Timeout limits. `None` means the call may run as long as it takes.
+#[derive(Debug, Clone)]
+pub struct ToolTimeouts {
+    /// Limit for read tools.
+    pub read: Option<Duration>,
+    /// Limit for write tools.
+    pub write: Option<Duration>,
+    /// Limit for long-running tools.
+    pub long: Option<Duration>,
+    /// Per-tool limits that take precedence over the class limit.
+    pub tools: HashMap<String, Option<Duration>>,
+}
+
+impl Default for ToolTimeouts {
+    fn default() -> Self {
+        Self {
+            read: Some(Duration::from_secs(30)),
+            write: Some(Duration::from_secs(60)),
+            long: Some(Duration::from_secs(600)),
+            tools: HashMap::new(),
+        }
+    }
+}
+
+impl ToolTimeouts {
+    /// The same limit for every tool.
+    pub fn uniform(limit: Option<Duration>) -> Self {
+        Self {
+            read: limit,
+            write: limit,
+            long: limit,
+            tools: HashMap::new(),
+        }
+    }
+
+    /// Limit for the named tool.
+    pub fn limit_for(&self, tool: &str) -> Option<Duration> {
+        if let Some(limit) = self.tools.get(tool) {
+            return *limit;
+        }
+        match ToolClass::of(tool) {
+            ToolClass::Read => self.read,
+            ToolClass::Write => self.write,
+            ToolClass::Long => self.long,
+        }
+    }
+}
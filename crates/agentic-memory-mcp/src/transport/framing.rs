@@ -2,6 +2,9 @@
 
 use crate::types::{JsonRpcMessage, McpError, McpResult};
 
+/// Default cap on a single inbound JSON-RPC message (8 MiB), for both transports.
+pub const DEFAULT_MAX_REQUEST_BYTES: usize = 8 * 1024 * 1024;
+
 /// Parse a single line of text as a JSON-RPC message.
 pub fn parse_message(line: &str) -> McpResult<JsonRpcMessage> {
     let trimmed = line.trim();
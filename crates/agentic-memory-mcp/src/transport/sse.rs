@@ -1,29 +1,56 @@
-//! SSE transport — HTTP server with auth, multi-tenant routing, and /health.
+//! SSE transport — HTTP server with auth, multi-tenant routing, /health, and /ready.
 
+#[cfg(feature = "sse")]
+use std::convert::Infallible;
 #[cfg(feature = "sse")]
 use std::path::PathBuf;
 #[cfg(feature = "sse")]
 use std::sync::Arc;
+#[cfg(feature = "sse")]
+use std::time::Duration;
 
 #[cfg(feature = "sse")]
 use axum::{
-    extract::State,
+    extract::{rejection::JsonRejection, DefaultBodyLimit, State},
     http::{HeaderMap, StatusCode},
     middleware,
-    response::{IntoResponse, Json as AxumJson, Response},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json as AxumJson, Response,
+    },
     routing::{get, post},
     Router,
 };
 
 #[cfg(feature = "sse")]
-use tokio::sync::Mutex;
+use tokio::sync::{broadcast, mpsc, Mutex};
+#[cfg(feature = "sse")]
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+#[cfg(feature = "sse")]
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+
+#[cfg(feature = "sse")]
+use crate::config::CorsConfig;
 
 #[cfg(feature = "sse")]
-use crate::protocol::ProtocolHandler;
+use crate::protocol::{HandlerSettings, ProtocolHandler};
+#[cfg(feature = "sse")]
+use crate::session::manager::dir_writable;
 #[cfg(feature = "sse")]
 use crate::session::tenant::TenantRegistry;
 #[cfg(feature = "sse")]
-use crate::types::{McpResult, MemoryMode};
+use crate::tools::memory_health;
+#[cfg(feature = "sse")]
+use crate::transport::framing::DEFAULT_MAX_REQUEST_BYTES;
+#[cfg(feature = "sse")]
+use crate::types::{JsonRpcNotification, McpError, McpResult, MemoryMode};
+
+/// Default interval between keepalive comments on an idle event stream.
+#[cfg(feature = "sse")]
+pub const DEFAULT_KEEPALIVE: Duration = Duration::from_secs(15);
+/// Notifications buffered per event-stream subscriber before lagging ones are dropped.
+#[cfg(feature = "sse")]
+const EVENT_BUFFER: usize = 64;
 
 /// Server operating mode.
 #[cfg(feature = "sse")]
@@ -35,6 +62,8 @@ pub enum ServerMode {
         data_dir: PathBuf,
         registry: Arc<Mutex<TenantRegistry>>,
         memory_mode: MemoryMode,
+        /// Applied to the handler built for each request.
+        settings: HandlerSettings,
     },
 }
 
@@ -43,43 +72,116 @@ pub enum ServerMode {
 pub struct ServerState {
     pub token: Option<String>,
     pub mode: ServerMode,
+    /// Interval between keepalive comments on `GET /mcp` event streams.
+    pub keepalive: Duration,
+    /// Largest accepted `POST /mcp` body; larger requests get HTTP 413.
+    pub max_request_bytes: usize,
+    /// Fan-out of server notifications to connected event streams.
+    pub events: broadcast::Sender<JsonRpcNotification>,
+}
+
+#[cfg(feature = "sse")]
+impl ServerState {
+    fn new(
+        token: Option<String>,
+        mode: ServerMode,
+        keepalive: Duration,
+        max_request_bytes: usize,
+    ) -> Self {
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        Self {
+            token,
+            mode,
+            keepalive,
+            max_request_bytes,
+            events,
+        }
+    }
 }
 
 /// SSE transport for web-based MCP clients.
 #[cfg(feature = "sse")]
 pub struct SseTransport {
-    state: Arc<ServerState>,
+    token: Option<String>,
+    mode: ServerMode,
+    keepalive: Duration,
+    max_request_bytes: usize,
+    cors: CorsConfig,
 }
 
 #[cfg(feature = "sse")]
 impl SseTransport {
     /// Create a single-user SSE transport (backward compatible).
     pub fn new(handler: ProtocolHandler) -> Self {
-        Self {
-            state: Arc::new(ServerState {
-                token: None,
-                mode: ServerMode::Single(Arc::new(handler)),
-            }),
-        }
+        Self::with_config(None, ServerMode::Single(Arc::new(handler)))
     }
 
     /// Create an SSE transport with full configuration.
     pub fn with_config(token: Option<String>, mode: ServerMode) -> Self {
         Self {
-            state: Arc::new(ServerState { token, mode }),
+            token,
+            mode,
+            keepalive: DEFAULT_KEEPALIVE,
+            max_request_bytes: DEFAULT_MAX_REQUEST_BYTES,
+            cors: CorsConfig::default(),
         }
     }
 
+    /// Set the cross-origin policy applied to all routes.
+    pub fn with_cors(mut self, cors: CorsConfig) -> Self {
+        self.cors = cors;
+        self
+    }
+
+    /// Set the keepalive interval for event streams so idle proxies keep them open.
+    pub fn with_keepalive(mut self, interval: Duration) -> Self {
+        self.keepalive = interval;
+        self
+    }
+
+    /// Set the largest accepted request body in bytes.
+    pub fn with_max_request_bytes(mut self, max: usize) -> Self {
+        self.max_request_bytes = max;
+        self
+    }
+
     /// Run the HTTP server on the given address.
-    pub async fn run(&self, addr: &str) -> McpResult<()> {
-        let state = self.state.clone();
+    pub async fn run(self, addr: &str) -> McpResult<()> {
+        let state = Arc::new(ServerState::new(
+            self.token,
+            self.mode,
+            self.keepalive,
+            self.max_request_bytes,
+        ));
 
-        let app = Router::new()
-            .route("/mcp", post(handle_request))
+        // Forward notifications from the single-user session to event streams.
+        if let ServerMode::Single(handler) = &state.mode {
+            let (tx, mut rx) = mpsc::channel::<JsonRpcNotification>(EVENT_BUFFER);
+            handler.attach_notifications(tx).await;
+            let events = state.events.clone();
+            tokio::spawn(async move {
+                while let Some(notification) = rx.recv().await {
+                    // No subscribers is fine; the notification is simply dropped.
+                    let _ = events.send(notification);
+                }
+            });
+        }
+
+        let body_limit = DefaultBodyLimit::max(state.max_request_bytes);
+        let mut app = Router::new()
+            .route("/mcp", post(handle_request).get(handle_stream))
             .layer(middleware::from_fn_with_state(state.clone(), auth_layer))
             .route("/health", get(handle_health))
+            .route("/ready", get(handle_ready))
+            .layer(body_limit)
             .with_state(state);
 
+        // CORS wraps auth so browser preflight (OPTIONS) never needs a token.
+        if let Some(cors) = build_cors_layer(&self.cors)? {
+            tracing::info!("CORS enabled for origins: {:?}", self.cors.allowed_origins);
+            app = app.layer(cors);
+        }
+
         let listener = tokio::net::TcpListener::bind(addr)
             .await
             .map_err(crate::types::McpError::Io)?;
@@ -94,8 +196,68 @@ impl SseTransport {
     }
 }
 
+/// Build a `CorsLayer` from config, or `None` when cross-origin access is disabled.
+#[cfg(feature = "sse")]
+fn build_cors_layer(cfg: &CorsConfig) -> McpResult<Option<CorsLayer>> {
+    use axum::http::{HeaderName, HeaderValue, Method};
+
+    if !cfg.is_enabled() {
+        return Ok(None);
+    }
+    let invalid = |what: &str, value: &str| {
+        crate::types::McpError::InvalidParams(format!("Invalid CORS {what}: {value}"))
+    };
+
+    let origins = if cfg.allowed_origins.iter().any(|o| o == "*") {
+        AllowOrigin::any()
+    } else {
+        let values = cfg
+            .allowed_origins
+            .iter()
+            .map(|o| HeaderValue::from_str(o).map_err(|_| invalid("origin", o)))
+            .collect::<McpResult<Vec<_>>>()?;
+        AllowOrigin::list(values)
+    };
+
+    let methods = if cfg.allowed_methods.is_empty() {
+        AllowMethods::list([Method::GET, Method::POST])
+    } else {
+        let values = cfg
+            .allowed_methods
+            .iter()
+            .map(|m| {
+                Method::from_bytes(m.to_ascii_uppercase().as_bytes())
+                    .map_err(|_| invalid("method", m))
+            })
+            .collect::<McpResult<Vec<_>>>()?;
+        AllowMethods::list(values)
+    };
+
+    let headers = if cfg.allowed_headers.is_empty() {
+        AllowHeaders::list([
+            axum::http::header::AUTHORIZATION,
+            axum::http::header::CONTENT_TYPE,
+            HeaderName::from_static("x-user-id"),
+        ])
+    } else {
+        let values = cfg
+            .allowed_headers
+            .iter()
+            .map(|h| HeaderName::from_bytes(h.as_bytes()).map_err(|_| invalid("header", h)))
+            .collect::<McpResult<Vec<_>>>()?;
+        AllowHeaders::list(values)
+    };
+
+    Ok(Some(
+        CorsLayer::new()
+            .allow_origin(origins)
+            .allow_methods(methods)
+            .allow_headers(headers),
+    ))
+}
+
 /// Auth middleware — checks Bearer token if configured.
-/// /health is handled by a separate route that bypasses this layer.
+/// /health and /ready are handled by separate routes that bypass this layer.
 #[cfg(feature = "sse")]
 async fn auth_layer(
     State(state): State<Arc<ServerState>>,
@@ -134,14 +296,32 @@ async fn auth_layer(
 async fn handle_request(
     State(state): State<Arc<ServerState>>,
     headers: HeaderMap,
-    AxumJson(body): AxumJson<serde_json::Value>,
+    body: Result<AxumJson<serde_json::Value>, JsonRejection>,
 ) -> Result<AxumJson<serde_json::Value>, Response> {
+    let body = match body {
+        Ok(AxumJson(body)) => body,
+        Err(rejection) if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+            let error = McpError::ContentTooLarge {
+                size: content_length(&headers).unwrap_or(0),
+                max: state.max_request_bytes,
+            };
+            tracing::warn!("Rejected HTTP request: {error}");
+            return Err((
+                StatusCode::PAYLOAD_TOO_LARGE,
+                AxumJson(error.to_json_rpc_error(crate::types::RequestId::Null)),
+            )
+                .into_response());
+        }
+        Err(rejection) => return Err(rejection.into_response()),
+    };
+
     let handler = match &state.mode {
         ServerMode::Single(handler) => handler.clone(),
         ServerMode::MultiTenant {
             data_dir: _,
             registry,
             memory_mode,
+            settings,
         } => {
             let user_id = headers
                 .get("x-user-id")
@@ -164,6 +344,17 @@ async fn handle_request(
             let session = {
                 let mut reg = registry.lock().await;
                 reg.get_or_create(user_id, *memory_mode).map_err(|e| {
+                    if let McpError::InvalidRequest(message) = &e {
+                        return (
+                            StatusCode::BAD_REQUEST,
+                            AxumJson(serde_json::json!({
+                                "jsonrpc": "2.0",
+                                "id": null,
+                                "error": { "code": e.code(), "message": message }
+                            })),
+                        )
+                            .into_response();
+                    }
                     (
                         StatusCode::INTERNAL_SERVER_ERROR,
                         AxumJson(serde_json::json!({
@@ -179,7 +370,9 @@ async fn handle_request(
                 })?
             };
 
-            Arc::new(ProtocolHandler::with_mode(session, *memory_mode))
+            Arc::new(
+                ProtocolHandler::with_mode(session, *memory_mode).with_settings(settings.clone()),
+            )
         }
     };
 
@@ -204,6 +397,33 @@ async fn handle_request(
     }
 }
 
+/// Declared request body size, when the client sent a `Content-Length` header.
+#[cfg(feature = "sse")]
+fn content_length(headers: &HeaderMap) -> Option<usize> {
+    headers
+        .get(axum::http::header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+/// Server-to-client event stream (`GET /mcp`).
+///
+/// Carries server notifications as `message` events and sends a keepalive
+/// comment whenever the stream has been idle for the configured interval.
+/// In multi-tenant mode the stream only carries keepalives.
+#[cfg(feature = "sse")]
+async fn handle_stream(
+    State(state): State<Arc<ServerState>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = BroadcastStream::new(state.events.subscribe()).filter_map(|item| {
+        let notification = item.ok()?;
+        let data = serde_json::to_string(&notification).ok()?;
+        Some(Ok(Event::default().event("message").data(data)))
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::new().interval(state.keepalive).text("keepalive"))
+}
+
 /// Health check endpoint — no auth required.
 #[cfg(feature = "sse")]
 async fn handle_health(State(state): State<Arc<ServerState>>) -> AxumJson<serde_json::Value> {
@@ -220,20 +440,146 @@ async fn handle_health(State(state): State<Arc<ServerState>>) -> AxumJson<serde_
         .or_else(|| std::env::var("AGENTRA_HEALTH_LEDGER_DIR").ok())
         .unwrap_or_else(|| "~/.agentra/health-ledger".to_string());
 
-    let mut health = serde_json::json!({
-        "status": "ok",
-        "version": env!("CARGO_PKG_VERSION"),
-        "autonomic": {
-            "profile": profile,
-            "migration_policy": migration_policy,
-            "health_ledger_dir": ledger_dir,
-        }
+    let mut health = readiness(&state).await;
+    health["version"] = serde_json::json!(env!("CARGO_PKG_VERSION"));
+    health["autonomic"] = serde_json::json!({
+        "profile": profile,
+        "migration_policy": migration_policy,
+        "health_ledger_dir": ledger_dir,
     });
 
-    if let ServerMode::MultiTenant { registry, .. } = &state.mode {
-        let reg = registry.lock().await;
-        health["users"] = serde_json::json!(reg.count());
+    AxumJson(health)
+}
+
+/// Readiness probe — no auth required. Responds 503 unless the graph is usable.
+#[cfg(feature = "sse")]
+async fn handle_ready(State(state): State<Arc<ServerState>>) -> Response {
+    let ready = readiness(&state).await;
+    let code = if ready["status"] == "ok" {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (code, AxumJson(ready)).into_response()
+}
+
+/// The `memory_health` report for the single-user graph. In multi-tenant
+/// mode graphs load lazily, so readiness means the data directory is writable.
+#[cfg(feature = "sse")]
+async fn readiness(state: &ServerState) -> serde_json::Value {
+    match &state.mode {
+        ServerMode::Single(handler) => memory_health::report(&*handler.session().read().await),
+        ServerMode::MultiTenant {
+            data_dir, registry, ..
+        } => {
+            let can_write = std::fs::create_dir_all(data_dir).is_ok() && dir_writable(data_dir);
+            serde_json::json!({
+                "status": if can_write { "ok" } else { "degraded" },
+                "can_write": can_write,
+                "users": registry.lock().await.count(),
+            })
+        }
     }
+}
 
-    AxumJson(health)
+#[cfg(all(test, feature = "sse"))]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{header, Method, Request};
+    use tower::ServiceExt;
+
+    fn cors(origins: &[&str]) -> CorsConfig {
+        CorsConfig {
+            allowed_origins: origins.iter().map(|o| o.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    async fn preflight(layer: CorsLayer, origin: &str) -> Response {
+        let app = Router::new()
+            .route("/mcp", post(|| async { "ok" }))
+            .layer(layer);
+        let request = Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/mcp")
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .body(Body::empty())
+            .expect("test fixture");
+        app.oneshot(request).await.expect("test fixture")
+    }
+
+    #[test]
+    fn cors_disabled_by_default() {
+        let layer = build_cors_layer(&CorsConfig::default()).expect("test fixture");
+        assert!(layer.is_none());
+    }
+
+    #[tokio::test]
+    async fn cors_allows_listed_origins() {
+        let layer = build_cors_layer(&cors(&["https://app.example.com"]))
+            .expect("test fixture")
+            .expect("CORS enabled");
+
+        let allowed = preflight(layer.clone(), "https://app.example.com").await;
+        assert_eq!(
+            allowed.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://app.example.com"
+        );
+        let other = preflight(layer, "https://evil.example.com").await;
+        assert!(other
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn idle_stream_sends_keepalive_comments() {
+        let dir = tempfile::tempdir().expect("test fixture");
+        let path = dir.path().join("stream.amem").display().to_string();
+        let session = crate::session::SessionManager::open(&path).expect("test fixture");
+        let handler = ProtocolHandler::new(Arc::new(tokio::sync::RwLock::new(session)));
+        let transport = SseTransport::new(handler).with_keepalive(Duration::from_millis(20));
+        let state = ServerState::new(
+            transport.token,
+            transport.mode,
+            transport.keepalive,
+            transport.max_request_bytes,
+        );
+
+        let response = handle_stream(State(Arc::new(state))).await.into_response();
+        let mut body = response.into_body().into_data_stream();
+        let chunk = tokio::time::timeout(Duration::from_secs(5), body.next())
+            .await
+            .expect("no keepalive within 5s")
+            .expect("stream ended")
+            .expect("test fixture");
+        assert_eq!(&chunk[..], b":keepalive\n\n");
+    }
+
+    #[test]
+    fn cors_rejects_invalid_values() {
+        let bad_origin = cors(&["https://app.example.com\n"]);
+        let bad_method = CorsConfig {
+            allowed_methods: vec!["GE T".to_string()],
+            ..cors(&["*"])
+        };
+        let bad_header = CorsConfig {
+            allowed_headers: vec!["x user".to_string()],
+            ..cors(&["*"])
+        };
+        for (cfg, what) in [
+            (bad_origin, "origin"),
+            (bad_method, "method"),
+            (bad_header, "header"),
+        ] {
+            match build_cors_layer(&cfg) {
+                Err(McpError::InvalidParams(message)) => {
+                    assert!(message.contains(what), "{message}");
+                }
+                other => panic!("expected an invalid {what} error, got {:?}", other.is_ok()),
+            }
+        }
+    }
 }
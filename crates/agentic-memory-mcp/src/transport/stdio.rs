@@ -1,112 +1,154 @@
 //! Stdio transport — reads JSON-RPC from stdin, writes to stdout.
 
-use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
 
 use crate::protocol::ProtocolHandler;
 use crate::transport::capture::TransportCapture;
-use crate::types::{JsonRpcError, McpError, McpResult, RequestId, JSONRPC_VERSION};
+use crate::types::{
+    JsonRpcMessage, JsonRpcNotification, JsonRpcRequest, McpError, McpResult, RequestId,
+};
 
 use super::framing;
 
-/// Hard limit for framed stdio payloads (8 MiB).
-const MAX_CONTENT_LENGTH_BYTES: usize = 8 * 1024 * 1024;
+/// Buffered server-initiated notifications awaiting delivery.
+const NOTIFICATION_BUFFER: usize = 64;
+/// Buffered stdin messages read ahead of the transport loop.
+const INBOUND_BUFFER: usize = 64;
 
 /// Stdio transport for desktop MCP clients.
 pub struct StdioTransport {
     handler: ProtocolHandler,
+    max_request_bytes: usize,
 }
 
 impl StdioTransport {
     /// Create a new stdio transport with the given handler.
     pub fn new(handler: ProtocolHandler) -> Self {
-        Self { handler }
+        Self {
+            handler,
+            max_request_bytes: framing::DEFAULT_MAX_REQUEST_BYTES,
+        }
+    }
+
+    /// Set the largest message accepted on stdin. Larger lines or framed
+    /// bodies are discarded and answered with a `ContentTooLarge` error.
+    pub fn with_max_request_bytes(mut self, max: usize) -> Self {
+        self.max_request_bytes = max;
+        self
     }
 
     /// Run the transport loop — reads from stdin, writes to stdout.
+    ///
+    /// End of input (including a client that disappears mid-message) is a
+    /// clean shutdown. Any other failure, typically a broken pipe on stdout,
+    /// still ends the session and saves before the error is returned.
     pub async fn run(&self) -> McpResult<()> {
-        let stdin = tokio::io::stdin();
+        let result = self.serve().await;
+        if let Err(e) = &result {
+            tracing::error!("Stdio transport failed: {e}; saving before exit");
+            self.handler.cleanup().await;
+        }
+        if let Err(e) = self.handler.save_pending().await {
+            tracing::error!("Final save failed: {e}");
+            return result.and(Err(e));
+        }
+        result
+    }
+
+    async fn serve(&self) -> McpResult<()> {
         let mut stdout = tokio::io::stdout();
-        let mut reader = BufReader::new(stdin);
-        let mut line = String::new();
-        let mut content_length: Option<usize> = None;
         let mut framed_output = false;
         let mut capture = TransportCapture::from_env().map_err(McpError::Io)?;
+        let (notification_tx, mut notifications) = mpsc::channel(NOTIFICATION_BUFFER);
+        self.handler.attach_notifications(notification_tx).await;
+        let (client_request_tx, mut client_requests) = mpsc::channel(NOTIFICATION_BUFFER);
+        self.handler.attach_client_requests(client_request_tx);
+
+        // Stdin is read on a plain thread: a blocked read must not keep the
+        // runtime alive on shutdown, and reading has to continue while a
+        // request is in flight so client replies (e.g. sampling) get through.
+        let (inbound_tx, mut inbound) = mpsc::channel(INBOUND_BUFFER);
+        let max_request_bytes = self.max_request_bytes;
+        std::thread::spawn(move || read_stdin(inbound_tx, max_request_bytes));
+        let mut deferred = VecDeque::new();
 
         tracing::info!("Stdio transport started");
 
         loop {
-            line.clear();
-            let bytes_read = reader.read_line(&mut line).await.map_err(McpError::Io)?;
-
-            if bytes_read == 0 {
-                tracing::info!("EOF on stdin, running cleanup");
-                self.handler.cleanup().await;
-                tracing::info!("Cleanup complete, shutting down");
-                break;
-            }
-
-            let trimmed = line.trim_end_matches(['\r', '\n']);
-
-            let lower = trimmed.to_ascii_lowercase();
-            if lower.starts_with("content-length:") {
-                let rest = trimmed.split_once(':').map(|(_, rhs)| rhs).unwrap_or("");
-                match rest.trim().parse::<usize>() {
-                    Ok(n) if n <= MAX_CONTENT_LENGTH_BYTES => {
-                        content_length = Some(n);
-                        framed_output = true;
+            let next = match deferred.pop_front() {
+                Some(message) => message,
+                // Notifications raised between requests (e.g. by auto-save)
+                // go out while we wait for the next message.
+                None => loop {
+                    tokio::select! {
+                        next = inbound.recv() => break next.unwrap_or(Ok(Inbound::Eof))?,
+                        Some(notification) = notifications.recv() => {
+                            self.write_notification(
+                                &mut stdout,
+                                notification,
+                                framed_output,
+                                &mut capture,
+                            )
+                            .await?;
+                        }
                     }
-                    Ok(n) => {
-                        tracing::warn!(
-                            "Content-Length {n} exceeds max frame size of {MAX_CONTENT_LENGTH_BYTES} bytes"
-                        );
-                        return Err(McpError::ParseError(format!(
-                            "Content-Length exceeds max frame size ({MAX_CONTENT_LENGTH_BYTES} bytes)"
-                        )));
-                    }
-                    Err(_) => {
-                        tracing::warn!("Invalid Content-Length header: {trimmed}");
-                        return Err(McpError::ParseError(
-                            "Invalid Content-Length header".to_string(),
-                        ));
-                    }
-                }
-                continue;
-            }
+                },
+            };
 
-            if let Some(n) = content_length {
-                // Skip optional header separator line.
-                if trimmed.is_empty() {
-                    let mut body = vec![0u8; n];
-                    reader.read_exact(&mut body).await.map_err(McpError::Io)?;
-                    capture.capture_inbound(&body).map_err(McpError::Io)?;
-                    let payload = String::from_utf8_lossy(&body).to_string();
-
-                    if self
-                        .process_message(&payload, framed_output, &mut stdout, &mut capture)
-                        .await?
+            let payload = match next {
+                Inbound::Eof => {
+                    tracing::info!("EOF on stdin, running cleanup");
+                    self.handler.cleanup().await;
+                    // The client usually closes both pipes; a dead stdout here is expected.
+                    if let Err(e) = self
+                        .flush_notifications(
+                            &mut notifications,
+                            &mut stdout,
+                            framed_output,
+                            &mut capture,
+                        )
+                        .await
                     {
-                        capture.sync().map_err(McpError::Io)?;
-                        break;
+                        tracing::debug!("Dropped final notifications: {e}");
                     }
-                    content_length = None;
+                    tracing::info!("Cleanup complete, shutting down");
+                    break;
+                }
+                Inbound::Message { payload, framed } => {
+                    framed_output |= framed;
+                    payload
+                }
+                Inbound::Rejected { error, framed } => {
+                    framed_output |= framed;
+                    tracing::warn!("Rejected stdin message: {error}");
+                    self.write_error(&mut stdout, &error, framed_output, &mut capture)
+                        .await?;
                     continue;
                 }
-
-                // Ignore any remaining header lines (e.g. Content-Type).
-                continue;
-            }
-
-            if trimmed.is_empty() {
-                continue;
-            }
+            };
 
             capture
-                .capture_inbound(trimmed.as_bytes())
+                .capture_inbound(payload.as_bytes())
                 .map_err(McpError::Io)?;
-            if self
-                .process_message(trimmed, framed_output, &mut stdout, &mut capture)
-                .await?
-            {
+            let done = self
+                .process_message(
+                    &payload,
+                    framed_output,
+                    &mut stdout,
+                    &mut capture,
+                    &mut inbound,
+                    &mut client_requests,
+                    &mut notifications,
+                    &mut deferred,
+                )
+                .await?;
+            self.flush_notifications(&mut notifications, &mut stdout, framed_output, &mut capture)
+                .await?;
+            if done {
                 capture.sync().map_err(McpError::Io)?;
                 break;
             }
@@ -116,16 +158,48 @@ impl StdioTransport {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn process_message(
         &self,
         input: &str,
         framed_output: bool,
         stdout: &mut tokio::io::Stdout,
         capture: &mut TransportCapture,
+        inbound: &mut mpsc::Receiver<McpResult<Inbound>>,
+        client_requests: &mut mpsc::Receiver<JsonRpcRequest>,
+        notifications: &mut mpsc::Receiver<JsonRpcNotification>,
+        deferred: &mut VecDeque<Inbound>,
     ) -> McpResult<bool> {
         match framing::parse_message(input.trim()) {
             Ok(msg) => {
-                if let Some(response) = self.handler.handle_message(msg).await {
+                let handled = self.handler.handle_message(msg);
+                tokio::pin!(handled);
+                let mut reading = true;
+                // While the handler runs, forward its requests and
+                // notifications (e.g. progress) to the client and feed the
+                // client's replies back in. Other client messages wait until
+                // this one has been answered.
+                let response = loop {
+                    tokio::select! {
+                        response = &mut handled => break response,
+                        Some(request) = client_requests.recv() => {
+                            let value = serde_json::to_value(request).map_err(McpError::Json)?;
+                            self.write_response(stdout, &value, framed_output, capture)
+                                .await?;
+                        }
+                        Some(notification) = notifications.recv() => {
+                            self.write_notification(stdout, notification, framed_output, capture)
+                                .await?;
+                        }
+                        next = inbound.recv(), if reading => {
+                            reading = self.accept_while_busy(next, capture, deferred).await?;
+                        }
+                    }
+                };
+                // Anything the request emitted goes out before its response.
+                self.flush_notifications(notifications, stdout, framed_output, capture)
+                    .await?;
+                if let Some(response) = response {
                     self.write_response(stdout, &response, framed_output, capture)
                         .await?;
                 }
@@ -136,24 +210,100 @@ impl StdioTransport {
             }
             Err(e) => {
                 tracing::warn!("Parse error: {e}");
-                let error_response = JsonRpcError {
-                    jsonrpc: JSONRPC_VERSION.to_string(),
-                    id: RequestId::Null,
-                    error: crate::types::JsonRpcErrorObject {
-                        code: e.code(),
-                        message: e.to_string(),
-                        data: None,
-                    },
-                };
-                let value = serde_json::to_value(error_response)
-                    .map_err(|err| McpError::InternalError(err.to_string()))?;
-                self.write_response(stdout, &value, framed_output, capture)
-                    .await?;
+                self.write_error(stdout, &e, framed_output, capture).await?;
             }
         }
         Ok(false)
     }
 
+    /// Handle a stdin message that arrives while a request is in flight:
+    /// client replies and cancellations are routed immediately, anything else
+    /// waits its turn. Returns false once stdin is exhausted.
+    async fn accept_while_busy(
+        &self,
+        next: Option<McpResult<Inbound>>,
+        capture: &mut TransportCapture,
+        deferred: &mut VecDeque<Inbound>,
+    ) -> McpResult<bool> {
+        match next {
+            Some(Ok(Inbound::Message { payload, framed })) => {
+                match framing::parse_message(&payload) {
+                    Ok(reply @ (JsonRpcMessage::Response(_) | JsonRpcMessage::Error(_))) => {
+                        capture
+                            .capture_inbound(payload.as_bytes())
+                            .map_err(McpError::Io)?;
+                        self.handler.handle_message(reply).await;
+                    }
+                    Ok(JsonRpcMessage::Notification(cancel))
+                        if matches!(
+                            cancel.method.as_str(),
+                            "notifications/cancelled" | "$/cancelRequest"
+                        ) =>
+                    {
+                        capture
+                            .capture_inbound(payload.as_bytes())
+                            .map_err(McpError::Io)?;
+                        self.handler
+                            .handle_message(JsonRpcMessage::Notification(cancel))
+                            .await;
+                    }
+                    _ => deferred.push_back(Inbound::Message { payload, framed }),
+                }
+                Ok(true)
+            }
+            Some(Ok(rejected @ Inbound::Rejected { .. })) => {
+                deferred.push_back(rejected);
+                Ok(true)
+            }
+            Some(Err(e)) => Err(e),
+            Some(Ok(Inbound::Eof)) | None => {
+                deferred.push_back(Inbound::Eof);
+                Ok(false)
+            }
+        }
+    }
+
+    /// Deliver every notification queued so far.
+    async fn flush_notifications(
+        &self,
+        notifications: &mut mpsc::Receiver<JsonRpcNotification>,
+        stdout: &mut tokio::io::Stdout,
+        framed_output: bool,
+        capture: &mut TransportCapture,
+    ) -> McpResult<()> {
+        while let Ok(notification) = notifications.try_recv() {
+            self.write_notification(stdout, notification, framed_output, capture)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn write_notification(
+        &self,
+        stdout: &mut tokio::io::Stdout,
+        notification: JsonRpcNotification,
+        framed_output: bool,
+        capture: &mut TransportCapture,
+    ) -> McpResult<()> {
+        let value = serde_json::to_value(notification).map_err(McpError::Json)?;
+        self.write_response(stdout, &value, framed_output, capture)
+            .await
+    }
+
+    /// Answer a message that could not be handled with a JSON-RPC error.
+    async fn write_error(
+        &self,
+        stdout: &mut tokio::io::Stdout,
+        error: &McpError,
+        framed_output: bool,
+        capture: &mut TransportCapture,
+    ) -> McpResult<()> {
+        let value = serde_json::to_value(error.to_json_rpc_error(RequestId::Null))
+            .map_err(|err| McpError::InternalError(err.to_string()))?;
+        self.write_response(stdout, &value, framed_output, capture)
+            .await
+    }
+
     async fn write_response(
         &self,
         stdout: &mut tokio::io::Stdout,
@@ -189,3 +339,192 @@ impl StdioTransport {
         Ok(())
     }
 }
+
+/// A message read from stdin, or end of input.
+enum Inbound {
+    Message {
+        payload: String,
+        framed: bool,
+    },
+    /// A message that was read and discarded, answered with `error`.
+    Rejected {
+        error: McpError,
+        framed: bool,
+    },
+    Eof,
+}
+
+/// Read newline-delimited or `Content-Length`-framed messages from stdin and
+/// hand them to the transport loop. Runs on its own thread.
+fn read_stdin(tx: mpsc::Sender<McpResult<Inbound>>, max_request_bytes: usize) {
+    let mut reader = std::io::stdin().lock();
+    let mut line = Vec::new();
+    let mut content_length: Option<usize> = None;
+    // Room for the line terminator on top of a maximum-size message.
+    let line_cap = max_request_bytes.saturating_add(2);
+
+    loop {
+        line.clear();
+        let next = match read_line_capped(&mut reader, &mut line, line_cap) {
+            Ok(0) => {
+                let _ = tx.blocking_send(Ok(Inbound::Eof));
+                return;
+            }
+            Ok(n) if n > line.len() => {
+                content_length = None;
+                Ok(Some(Inbound::Rejected {
+                    error: McpError::ContentTooLarge {
+                        size: n,
+                        max: max_request_bytes,
+                    },
+                    framed: false,
+                }))
+            }
+            Ok(_) => parse_stdin_line(
+                &String::from_utf8_lossy(&line),
+                &mut content_length,
+                &mut reader,
+                max_request_bytes,
+            ),
+            Err(e) => Err(McpError::Io(e)),
+        };
+        let next = match next {
+            Err(McpError::Io(e)) if is_disconnect(&e) => {
+                tracing::info!("Client disconnected from stdin: {e}");
+                Ok(Some(Inbound::Eof))
+            }
+            other => other,
+        };
+        let done = next.is_err() || matches!(next, Ok(Some(Inbound::Eof)));
+        match next {
+            Ok(None) => continue,
+            Ok(Some(message)) => {
+                if tx.blocking_send(Ok(message)).is_err() {
+                    return;
+                }
+            }
+            Err(e) => {
+                let _ = tx.blocking_send(Err(e));
+            }
+        }
+        if done {
+            return;
+        }
+    }
+}
+
+/// Read errors that mean the client went away rather than that stdin broke.
+fn is_disconnect(e: &std::io::Error) -> bool {
+    matches!(
+        e.kind(),
+        std::io::ErrorKind::BrokenPipe
+            | std::io::ErrorKind::ConnectionReset
+            | std::io::ErrorKind::UnexpectedEof
+    )
+}
+
+/// Read one line into `buf`, keeping at most `cap` bytes of it.
+///
+/// The rest of an over-long line is consumed and dropped, so memory stays
+/// bounded. Returns the full line length in bytes, or 0 at end of input.
+fn read_line_capped(
+    reader: &mut impl BufRead,
+    buf: &mut Vec<u8>,
+    cap: usize,
+) -> std::io::Result<usize> {
+    let mut total = 0;
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if available.is_empty() {
+            return Ok(total);
+        }
+        let (len, done) = match available.iter().position(|&b| b == b'\n') {
+            Some(i) => (i + 1, true),
+            None => (available.len(), false),
+        };
+        let room = cap.saturating_sub(buf.len()).min(len);
+        buf.extend_from_slice(&available[..room]);
+        reader.consume(len);
+        total += len;
+        if done {
+            return Ok(total);
+        }
+    }
+}
+
+/// Interpret one stdin line, reading a framed body when the headers end.
+fn parse_stdin_line(
+    line: &str,
+    content_length: &mut Option<usize>,
+    reader: &mut impl BufRead,
+    max_request_bytes: usize,
+) -> McpResult<Option<Inbound>> {
+    let trimmed = line.trim_end_matches(['\r', '\n']);
+
+    let lower = trimmed.to_ascii_lowercase();
+    if lower.starts_with("content-length:") {
+        let rest = trimmed.split_once(':').map(|(_, rhs)| rhs).unwrap_or("");
+        return match rest.trim().parse::<usize>() {
+            Ok(n) => {
+                *content_length = Some(n);
+                Ok(None)
+            }
+            Err(_) => {
+                tracing::warn!("Invalid Content-Length header: {trimmed}");
+                Err(McpError::ParseError(
+                    "Invalid Content-Length header".to_string(),
+                ))
+            }
+        };
+    }
+
+    if let Some(n) = *content_length {
+        // Ignore any remaining header lines (e.g. Content-Type) until the separator.
+        if !trimmed.is_empty() {
+            return Ok(None);
+        }
+        *content_length = None;
+        if n > max_request_bytes {
+            // Skip the body so the next frame is read from the right offset.
+            std::io::copy(
+                &mut std::io::Read::take(&mut *reader, n as u64),
+                &mut std::io::sink(),
+            )
+            .map_err(McpError::Io)?;
+            return Ok(Some(Inbound::Rejected {
+                error: McpError::ContentTooLarge {
+                    size: n,
+                    max: max_request_bytes,
+                },
+                framed: true,
+            }));
+        }
+        let mut body = vec![0u8; n];
+        reader.read_exact(&mut body).map_err(McpError::Io)?;
+        return Ok(Some(Inbound::Message {
+            payload: String::from_utf8_lossy(&body).to_string(),
+            framed: true,
+        }));
+    }
+
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+    if trimmed.len() > max_request_bytes {
+        return Ok(Some(Inbound::Rejected {
+            error: McpError::ContentTooLarge {
+                size: trimmed.len(),
+                max: max_request_bytes,
+            },
+            framed: false,
+        }));
+    }
+    Ok(Some(Inbound::Message {
+        payload: trimmed.to_string(),
+        framed: false,
+    }))
+}
@@ -19,6 +19,23 @@ pub struct Implementation {
     pub name: String,
     /// Version string.
     pub version: String,
+    /// Human-readable display name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// The server identity sent to clients. `None` fields keep the crate name
+/// and version; without a `title` none is sent.
+pub fn server_info(
+    name: Option<String>,
+    version: Option<String>,
+    title: Option<String>,
+) -> Implementation {
+    Implementation {
+        name: name.unwrap_or_else(|| SERVER_NAME.to_string()),
+        version: version.unwrap_or_else(|| SERVER_VERSION.to_string()),
+        title,
+    }
 }
 
 /// Client capabilities sent during initialization.
@@ -38,6 +55,9 @@ pub struct ClientCapabilities {
 /// Server capabilities advertised during initialization.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ServerCapabilities {
+    /// Argument completion capability.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completions: Option<CompletionsCapability>,
     /// Experimental capabilities.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub experimental: Option<HashMap<String, serde_json::Value>>,
@@ -67,6 +87,10 @@ pub struct RootsCapability {
     pub list_changed: bool,
 }
 
+/// Completions capability marker.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompletionsCapability {}
+
 /// Logging capability marker.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LoggingCapability {}
@@ -86,7 +110,7 @@ pub struct ResourcesCapability {
     #[serde(default)]
     pub subscribe: bool,
     /// Whether the server supports resources/list_changed notifications.
-    #[serde(default)]
+    #[serde(default, rename = "listChanged", alias = "list_changed")]
     pub list_changed: bool,
 }
 
@@ -129,6 +153,7 @@ impl ServerCapabilities {
     /// Build the default capabilities for this server.
     pub fn default_capabilities() -> Self {
         Self {
+            completions: Some(CompletionsCapability {}),
             experimental: None,
             logging: Some(LoggingCapability {}),
             prompts: Some(PromptsCapability {
@@ -136,7 +161,7 @@ impl ServerCapabilities {
             }),
             resources: Some(ResourcesCapability {
                 subscribe: true,
-                list_changed: false,
+                list_changed: true,
             }),
             tools: Some(ToolsCapability {
                 list_changed: false,
@@ -228,10 +253,7 @@ impl InitializeResult {
         Self {
             protocol_version: MCP_VERSION.to_string(),
             capabilities: ServerCapabilities::default_capabilities(),
-            server_info: Implementation {
-                name: SERVER_NAME.to_string(),
-                version: SERVER_VERSION.to_string(),
-            },
+            server_info: server_info(None, None, None),
             instructions: Some(mode.instructions().to_string()),
         }
     }
@@ -0,0 +1,45 @@
+//! Shared JSON shape for graph edges in tool and resource output.
+
+use agentic_memory::{Edge, EdgeType};
+use serde_json::{json, Value};
+
+use super::node_json::parse_type_names;
+use super::McpResult;
+
+/// Serialize an edge with every field, `weight` always a plain number.
+pub fn edge_json(edge: &Edge) -> Value {
+    json!({
+        "source_id": edge.source_id,
+        "target_id": edge.target_id,
+        "edge_type": edge.edge_type.name(),
+        "weight": weight_json(edge.weight),
+        "created_at": edge.created_at,
+    })
+}
+
+/// An edge weight as a JSON number.
+///
+/// The `f32` is widened through its shortest decimal form, so `0.8` prints as
+/// `0.8` rather than `0.800000011920929`. Non-finite weights (only possible in
+/// a damaged file) are reported as `0.0` instead of `null`.
+pub fn weight_json(weight: f32) -> Value {
+    if !weight.is_finite() {
+        return json!(0.0);
+    }
+    let widened: f64 = weight.to_string().parse().unwrap_or(weight as f64);
+    json!(widened)
+}
+
+/// Parse edge type names from tool arguments.
+///
+/// Every unrecognized name is reported in one `InvalidParams` error together
+/// with the valid names, instead of being dropped.
+pub fn parse_edge_types<S: AsRef<str>>(names: &[S]) -> McpResult<Vec<EdgeType>> {
+    parse_type_names(
+        "edge",
+        names,
+        EdgeType::from_name,
+        EdgeType::all(),
+        EdgeType::name,
+    )
+}
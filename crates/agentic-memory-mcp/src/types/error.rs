@@ -34,6 +34,10 @@ pub mod mcp_error_codes {
     pub const SESSION_NOT_FOUND: i32 = -32851;
     /// AgenticMemory specific: Invalid graph operation.
     pub const INVALID_GRAPH_OP: i32 = -32852;
+    /// AgenticMemory specific: Memory file format version not supported.
+    pub const INCOMPATIBLE_FORMAT: i32 = -32853;
+    /// AgenticMemory specific: Graph changed since the client's expected version.
+    pub const VERSION_CONFLICT: i32 = -32854;
 
     /// Server: Unauthorized (missing or invalid bearer token).
     pub const UNAUTHORIZED: i32 = -32900;
@@ -41,6 +45,8 @@ pub mod mcp_error_codes {
     pub const USER_NOT_FOUND: i32 = -32901;
     /// Server: Rate limited.
     pub const RATE_LIMITED: i32 = -32902;
+    /// Server: Tool call exceeded its timeout.
+    pub const TOOL_TIMEOUT: i32 = -32903;
 }
 
 /// All errors that can occur in the MCP server.
@@ -103,6 +109,36 @@ pub enum McpError {
     #[error("Invalid graph operation: {0}")]
     InvalidGraphOp(String),
 
+    /// Memory file was written by an incompatible `.amem` format version.
+    #[error(
+        "Incompatible memory file format: version {found}, this build supports up to {expected}. \
+         Upgrade agentic-memory-mcp or migrate the file"
+    )]
+    IncompatibleFormat {
+        /// Version recorded in the file header.
+        found: u32,
+        /// Newest version this build can read.
+        expected: u32,
+    },
+
+    /// The graph changed since the version the client read.
+    #[error("Version conflict: expected graph version {expected}, current version is {actual}")]
+    VersionConflict {
+        /// Version the client expected.
+        expected: u64,
+        /// Current graph version.
+        actual: u64,
+    },
+
+    /// Tool call ran past its timeout and was abandoned.
+    #[error("Tool {tool} timed out after {elapsed_ms} ms")]
+    ToolTimeout {
+        /// Tool that was called.
+        tool: String,
+        /// Timeout that expired, in milliseconds.
+        elapsed_ms: u64,
+    },
+
     /// Transport-level error.
     #[error("Transport error: {0}")]
     Transport(String),
@@ -143,6 +179,7 @@ impl McpError {
                 | McpError::ContentTooLarge { .. }
                 | McpError::ResourceNotFound(_)
                 | McpError::PromptNotFound(_)
+                | McpError::ToolTimeout { .. }
                 | McpError::Unauthorized
                 | McpError::UserNotFound(_)
         )
@@ -166,6 +203,9 @@ impl McpError {
             McpError::NodeNotFound(_) => NODE_NOT_FOUND,
             McpError::SessionNotFound(_) => SESSION_NOT_FOUND,
             McpError::InvalidGraphOp(_) => INVALID_GRAPH_OP,
+            McpError::IncompatibleFormat { .. } => INCOMPATIBLE_FORMAT,
+            McpError::VersionConflict { .. } => VERSION_CONFLICT,
+            McpError::ToolTimeout { .. } => TOOL_TIMEOUT,
             McpError::Transport(_) => INTERNAL_ERROR,
             McpError::Io(_) => INTERNAL_ERROR,
             McpError::Json(_) => PARSE_ERROR,
@@ -191,7 +231,13 @@ impl McpError {
 
 impl From<agentic_memory::AmemError> for McpError {
     fn from(e: agentic_memory::AmemError) -> Self {
-        McpError::AgenticMemory(e.to_string())
+        match e {
+            agentic_memory::AmemError::UnsupportedVersion(found) => McpError::IncompatibleFormat {
+                found,
+                expected: agentic_memory::FORMAT_VERSION,
+            },
+            other => McpError::AgenticMemory(other.to_string()),
+        }
     }
 }
 
@@ -14,7 +14,8 @@ pub enum RequestId {
     String(String),
     /// Numeric identifier.
     Number(i64),
-    /// Null identifier (for notifications that shouldn't have one).
+    /// Null identifier. Only sent in error responses to messages whose id is
+    /// unknown or invalid; an incoming request with a null id is rejected.
     Null,
 }
 
@@ -1,16 +1,22 @@
 //! All MCP data types used by the server.
 
 pub mod capabilities;
+pub mod edge_json;
 pub mod error;
 pub mod message;
+pub mod node_json;
 pub mod notification;
+pub mod redaction;
 pub mod request;
 pub mod response;
 
 // Re-export commonly used types for convenience.
 pub use capabilities::*;
+pub use edge_json::*;
 pub use error::*;
 pub use message::*;
+pub use node_json::*;
 pub use notification::*;
+pub use redaction::*;
 pub use request::*;
 pub use response::*;
@@ -0,0 +1,135 @@
+//! Shared JSON shape for a stored node in tool and resource output.
+
+use agentic_memory::{CognitiveEvent, EventType};
+use serde_json::{json, Value};
+
+use super::{McpError, McpResult};
+
+/// Serialize a node as persisted, without its edges or embedding.
+pub fn node_json(node: &CognitiveEvent) -> Value {
+    json!({
+        "id": node.id,
+        "event_type": node.event_type.name(),
+        "content": node.content,
+        "confidence": node.confidence,
+        "session_id": node.session_id,
+        "created_at": node.created_at,
+        "access_count": node.access_count,
+        "last_accessed": node.last_accessed,
+        "decay_score": node.decay_score,
+    })
+}
+
+/// Node properties a `fields` parameter can select.
+pub const SELECTABLE_NODE_FIELDS: [&str; 6] = [
+    "content",
+    "confidence",
+    "session_id",
+    "created_at",
+    "decay_score",
+    "access_count",
+];
+
+/// The node properties a caller kept with a `fields` parameter.
+///
+/// `id`, `event_type`, and values a tool computes itself (scores, depths)
+/// are always returned; only the properties in [`SELECTABLE_NODE_FIELDS`]
+/// are subject to selection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeFieldSelection {
+    fields: Vec<&'static str>,
+}
+
+impl NodeFieldSelection {
+    /// Parse a `fields` list. Unknown names fail with `InvalidParams`
+    /// listing the valid ones.
+    pub fn parse<S: AsRef<str>>(names: &[S]) -> McpResult<Self> {
+        let mut fields = Vec::with_capacity(names.len());
+        let mut unknown = Vec::new();
+        for name in names {
+            match SELECTABLE_NODE_FIELDS
+                .iter()
+                .find(|field| **field == name.as_ref())
+            {
+                Some(field) if !fields.contains(field) => fields.push(*field),
+                Some(_) => {}
+                None => unknown.push(name.as_ref()),
+            }
+        }
+        if !unknown.is_empty() {
+            return Err(McpError::InvalidParams(format!(
+                "Unknown fields: {} (valid: {})",
+                unknown.join(", "),
+                SELECTABLE_NODE_FIELDS.join(", ")
+            )));
+        }
+        Ok(Self { fields })
+    }
+
+    /// Drop the unselected properties from a serialized node and add the
+    /// selected ones it lacks.
+    pub fn apply(&self, entry: &mut Value, node: &CognitiveEvent) {
+        let Some(object) = entry.as_object_mut() else {
+            return;
+        };
+        for field in SELECTABLE_NODE_FIELDS {
+            if !self.fields.contains(&field) {
+                object.remove(field);
+                continue;
+            }
+            let value = match field {
+                "content" => json!(node.content),
+                "confidence" => json!(node.confidence),
+                "session_id" => json!(node.session_id),
+                "created_at" => json!(node.created_at),
+                "decay_score" => json!(node.decay_score),
+                _ => json!(node.access_count),
+            };
+            object.insert(field.to_string(), value);
+        }
+    }
+}
+
+/// Parse event type names from tool arguments.
+///
+/// Every unrecognized name is reported in one `InvalidParams` error together
+/// with the valid names, instead of being dropped.
+pub fn parse_event_types<S: AsRef<str>>(names: &[S]) -> McpResult<Vec<EventType>> {
+    parse_type_names(
+        "event",
+        names,
+        EventType::from_name,
+        EventType::all(),
+        EventType::name,
+    )
+}
+
+/// Resolve `names` with `from_name`, or fail with an `InvalidParams` error
+/// naming the `kind` of type, every unknown name, and the valid ones.
+pub(crate) fn parse_type_names<T, S: AsRef<str>>(
+    kind: &str,
+    names: &[S],
+    from_name: fn(&str) -> Option<T>,
+    all: &[T],
+    name_of: fn(&T) -> &'static str,
+) -> McpResult<Vec<T>> {
+    let mut types = Vec::with_capacity(names.len());
+    let mut unknown = Vec::new();
+    for name in names {
+        match from_name(name.as_ref()) {
+            Some(parsed) => types.push(parsed),
+            None => unknown.push(name.as_ref()),
+        }
+    }
+    if unknown.is_empty() {
+        return Ok(types);
+    }
+
+    let valid: Vec<&str> = all.iter().map(name_of).collect();
+    let noun = if unknown.len() == 1 { "type" } else { "types" };
+    Err(McpError::InvalidParams(format!(
+        "Unknown {kind} {noun}: {} (valid: {})",
+        unknown.join(", "),
+        valid.join(", ")
+    )))
+}
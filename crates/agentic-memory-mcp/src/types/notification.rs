@@ -38,18 +38,39 @@ pub struct LogMessageParams {
     pub data: Value,
 }
 
-/// Log levels for MCP logging.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Log levels for MCP logging, in ascending severity (RFC 5424 names).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
+#[repr(u8)]
 pub enum LogLevel {
     /// Debug level.
     Debug,
     /// Info level.
     Info,
+    /// Normal but significant events.
+    Notice,
     /// Warning level.
     Warning,
     /// Error level.
     Error,
+    /// Critical conditions.
+    Critical,
+    /// Action must be taken immediately.
+    Alert,
+    /// System is unusable.
+    Emergency,
+}
+
+impl LogLevel {
+    /// The MCP level for a `tracing` level; `TRACE` maps to `debug`.
+    pub fn from_tracing(level: tracing::Level) -> Self {
+        match level {
+            tracing::Level::ERROR => Self::Error,
+            tracing::Level::WARN => Self::Warning,
+            tracing::Level::INFO => Self::Info,
+            _ => Self::Debug,
+        }
+    }
 }
 
 /// Resource updated notification (server → client).
@@ -0,0 +1,113 @@
+//! Output redaction: regex patterns whose matches are masked in everything
+//! the server sends to clients. The stored graph is never modified.
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use regex::Regex;
+use serde_json::Value;
+
+use super::{McpError, McpResult, ResourceContent};
+
+/// Replacement text for redacted matches.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A compiled set of redaction patterns. The default masks nothing.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    patterns: Arc<Vec<Regex>>,
+}
+
+impl Redactor {
+    /// Compile `patterns`. An invalid pattern is an error; an empty list
+    /// disables redaction.
+    pub fn new(patterns: &[String]) -> McpResult<Self> {
+        let compiled = patterns
+            .iter()
+            .map(|p| {
+                Regex::new(p).map_err(|e| {
+                    McpError::InvalidParams(format!("Invalid redaction pattern '{p}': {e}"))
+                })
+            })
+            .collect::<McpResult<Vec<_>>>()?;
+        Ok(Self {
+            patterns: Arc::new(compiled),
+        })
+    }
+
+    /// Whether any pattern is configured.
+    pub fn is_enabled(&self) -> bool {
+        !self.patterns.is_empty()
+    }
+
+    /// Mask every pattern match in `text`.
+    pub fn text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut out = Cow::Borrowed(text);
+        for pattern in self.patterns.iter() {
+            if let Cow::Owned(replaced) = pattern.replace_all(&out, REDACTED) {
+                out = Cow::Owned(replaced);
+            }
+        }
+        out
+    }
+
+    /// Mask pattern matches in every string inside `value` (object keys excluded).
+    pub fn value(&self, value: &mut Value) {
+        match value {
+            Value::String(s) => {
+                if let Cow::Owned(replaced) = self.text(s) {
+                    *s = replaced;
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.value(item)),
+            Value::Object(map) => map.values_mut().for_each(|item| self.value(item)),
+            _ => {}
+        }
+    }
+
+    /// Mask pattern matches in the text of resource contents; blobs go out
+    /// verbatim.
+    ///
+    /// JSON text is redacted value by value and serialized again, so the
+    /// output stays valid JSON no matter what the patterns match.
+    pub fn resource_contents(&self, contents: &mut [ResourceContent]) {
+        if !self.is_enabled() {
+            return;
+        }
+        for content in contents {
+            let is_json = content
+                .mime_type
+                .as_deref()
+                .is_some_and(|mime| mime.ends_with("json"));
+            let Some(text) = content.text.as_mut() else {
+                continue;
+            };
+            let parsed = if is_json {
+                serde_json::from_str::<Value>(text).ok()
+            } else {
+                None
+            };
+            match parsed {
+                Some(mut value) => {
+                    self.value(&mut value);
+                    *text = json_text(&value, text.contains('\n'));
+                }
+                None => {
+                    if let Cow::Owned(replaced) = self.text(text) {
+                        *text = replaced;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Serialize `value` as pretty or compact JSON text.
+pub(crate) fn json_text(value: &Value, pretty: bool) -> String {
+    if pretty {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    }
+    .unwrap_or_else(|e| e.to_string())
+}
@@ -11,13 +11,23 @@ pub struct ToolCallParams {
     /// Tool arguments.
     #[serde(default)]
     pub arguments: Option<Value>,
+    /// Request metadata, e.g. the client's `progressToken`.
+    #[serde(default, rename = "_meta", skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
 }
 
 /// Parameters for resources/read.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceReadParams {
     /// Resource URI.
+    #[serde(default)]
     pub uri: String,
+    /// Additional URIs to read in the same call (batch read).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub uris: Vec<String>,
+    /// Memory namespace to read from (defaults to the server's default).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
 }
 
 /// Parameters for resources/subscribe.
@@ -55,6 +65,51 @@ pub struct CancelRequestParams {
     pub reason: Option<String>,
 }
 
+/// Parameters for logging/setLevel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetLevelParams {
+    /// Lowest level the client wants to receive.
+    pub level: super::LogLevel,
+}
+
+/// Reference to the prompt or resource template a completion is for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum CompletionReference {
+    /// A resource URI template, e.g. `amem://node/{id}`.
+    #[serde(rename = "ref/resource")]
+    Resource {
+        /// The template's URI.
+        uri: String,
+    },
+    /// A prompt, by name.
+    #[serde(rename = "ref/prompt")]
+    Prompt {
+        /// The prompt's name.
+        name: String,
+    },
+}
+
+/// The argument being completed and what has been typed so far.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionArgument {
+    /// Argument (template parameter) name.
+    pub name: String,
+    /// Partial value; completions start with it.
+    #[serde(default)]
+    pub value: String,
+}
+
+/// Parameters for completion/complete.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompleteParams {
+    /// What the argument belongs to.
+    #[serde(rename = "ref")]
+    pub reference: CompletionReference,
+    /// The argument to complete.
+    pub argument: CompletionArgument,
+}
+
 /// Cursor-based pagination for list operations.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ListParams {
@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use super::redaction::{json_text, Redactor};
+
 /// Content types that can be returned by tools.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -38,6 +40,27 @@ pub struct ToolCallResult {
     /// Whether the tool call errored.
     #[serde(default, rename = "isError", skip_serializing_if = "Option::is_none")]
     pub is_error: Option<bool>,
+    /// The JSON result as an object, so clients need not parse the text blob.
+    #[serde(
+        default,
+        rename = "structuredContent",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub structured_content: Option<Value>,
+    /// The value behind a [`json`](Self::json) result, kept so it can be
+    /// rendered again for the client.
+    #[serde(skip)]
+    value: Option<Value>,
+}
+
+/// How JSON tool results are rendered for a client.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutputFormat {
+    /// Compact rather than pretty JSON text.
+    pub compact: bool,
+    /// Also return the value as `structuredContent`; the text block is kept
+    /// for older clients.
+    pub structured: bool,
 }
 
 impl ToolCallResult {
@@ -46,13 +69,61 @@ impl ToolCallResult {
         Self {
             content: vec![ToolContent::Text { text }],
             is_error: None,
+            structured_content: None,
+            value: None,
         }
     }
 
-    /// Create a JSON result.
+    /// Create a JSON result, rendered as pretty JSON until
+    /// [`render`](Self::render) picks the client's format.
     pub fn json(value: &impl Serialize) -> Self {
-        let text = serde_json::to_string_pretty(value).unwrap_or_else(|e| e.to_string());
-        Self::text(text)
+        let (text, value) = match serde_json::to_value(value) {
+            Ok(value) => (json_text(&value, true), Some(value)),
+            Err(e) => (e.to_string(), None),
+        };
+        Self {
+            content: vec![ToolContent::Text { text }],
+            is_error: None,
+            structured_content: None,
+            value,
+        }
+    }
+
+    /// Mask `redactor`'s pattern matches in the result's text.
+    pub fn redact(mut self, redactor: &Redactor) -> Self {
+        if !redactor.is_enabled() {
+            return self;
+        }
+        if let Some(value) = self.value.as_mut() {
+            // Redact the value rather than its text so the JSON stays valid.
+            redactor.value(value);
+            self.content = vec![ToolContent::Text {
+                text: json_text(value, true),
+            }];
+            return self;
+        }
+        for content in &mut self.content {
+            if let ToolContent::Text { text } = content {
+                *text = redactor.text(text).into_owned();
+            }
+        }
+        self
+    }
+
+    /// Render a JSON result in `format`; other results are unchanged.
+    pub fn render(mut self, format: OutputFormat) -> Self {
+        let Some(value) = &self.value else {
+            return self;
+        };
+        if format.compact {
+            self.content = vec![ToolContent::Text {
+                text: json_text(value, false),
+            }];
+        }
+        if format.structured {
+            self.structured_content = Some(value.clone());
+        }
+        self
     }
 
     /// Create an error result.
@@ -60,6 +131,8 @@ impl ToolCallResult {
         Self {
             content: vec![ToolContent::Text { text: message }],
             is_error: Some(true),
+            structured_content: None,
+            value: None,
         }
     }
 }
@@ -152,6 +225,43 @@ pub struct ResourceListResult {
     pub next_cursor: Option<String>,
 }
 
+/// Maximum number of values returned by one completion/complete call.
+pub const MAX_COMPLETION_VALUES: usize = 100;
+
+/// Completion values for one argument.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Completion {
+    /// Matching values, at most [`MAX_COMPLETION_VALUES`].
+    pub values: Vec<String>,
+    /// Total number of matches, including those not returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<usize>,
+    /// Whether more matches exist than were returned.
+    #[serde(default, rename = "hasMore")]
+    pub has_more: bool,
+}
+
+impl Completion {
+    /// Build a completion from every match, keeping the first
+    /// [`MAX_COMPLETION_VALUES`].
+    pub fn from_matches(mut values: Vec<String>) -> Self {
+        let total = values.len();
+        values.truncate(MAX_COMPLETION_VALUES);
+        Self {
+            values,
+            total: Some(total),
+            has_more: total > MAX_COMPLETION_VALUES,
+        }
+    }
+}
+
+/// Result from completion/complete.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompleteResult {
+    /// The completion values.
+    pub completion: Completion,
+}
+
 /// Result from resources/templates/list.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceTemplateListResult {
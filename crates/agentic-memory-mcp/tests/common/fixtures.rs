@@ -1,12 +1,15 @@
 //! Test data fixtures for MCP server tests.
+#![allow(dead_code)]
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use agentic_memory_mcp::session::SessionManager;
+use agentic_memory_mcp::tools::ToolRegistry;
+use agentic_memory_mcp::types::ToolContent;
 
 /// Create a temporary session manager for testing.
-pub fn create_test_session() -> Arc<Mutex<SessionManager>> {
+pub fn create_test_session() -> Arc<RwLock<SessionManager>> {
     let dir = tempfile::tempdir().expect("Failed to create temp dir");
     let path = dir.path().join("test.amem");
     // Keep the tempdir alive by leaking it (tests are short-lived).
@@ -14,5 +17,20 @@ pub fn create_test_session() -> Arc<Mutex<SessionManager>> {
     std::mem::forget(dir);
 
     let session = SessionManager::open(&path_str).expect("Failed to create test session");
-    Arc::new(Mutex::new(session))
+    Arc::new(RwLock::new(session))
+}
+
+/// Call a tool and parse its text result as JSON, panicking on failure.
+pub async fn call_tool_json(
+    name: &str,
+    args: serde_json::Value,
+    session: &Arc<RwLock<SessionManager>>,
+) -> serde_json::Value {
+    let result = ToolRegistry::call(name, Some(args), session)
+        .await
+        .unwrap_or_else(|e| panic!("{name} failed: {e}"));
+    match &result.content[0] {
+        ToolContent::Text { text } => serde_json::from_str(text).unwrap(),
+        _ => panic!("Expected text content"),
+    }
 }
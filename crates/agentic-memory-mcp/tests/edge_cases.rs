@@ -7,12 +7,12 @@ mod common;
 
 use serde_json::json;
 
-use agentic_memory_mcp::protocol::ProtocolHandler;
+use agentic_memory_mcp::protocol::{HandlerSettings, ProtocolHandler};
 use agentic_memory_mcp::resources::ResourceRegistry;
 use agentic_memory_mcp::tools::ToolRegistry;
 use agentic_memory_mcp::types::*;
 
-use common::fixtures::create_test_session;
+use common::fixtures::{call_tool_json, create_test_session};
 use common::mock_client::MockClient;
 
 fn create_client() -> MockClient {
@@ -91,8 +91,21 @@ async fn test_request_with_null_id() {
         params: None,
     });
 
+    // JSON-RPC requests need a non-null id: reject instead of answering.
     let response = handler.handle_message(msg).await.unwrap();
-    assert!(response["result"].is_object());
+    assert!(response.get("result").is_none());
+    assert_eq!(response["error"]["code"], error_codes::INVALID_REQUEST);
+    assert!(response["id"].is_null());
+
+    // The same holds for a raw message as the transports parse it, and a
+    // message without an id is still a notification.
+    let raw: JsonRpcMessage =
+        serde_json::from_value(json!({"jsonrpc": "2.0", "id": null, "method": "ping"})).unwrap();
+    let response = handler.handle_message(raw).await.unwrap();
+    assert_eq!(response["error"]["code"], error_codes::INVALID_REQUEST);
+    let raw: JsonRpcMessage =
+        serde_json::from_value(json!({"jsonrpc": "2.0", "method": "ping"})).unwrap();
+    assert!(handler.handle_message(raw).await.is_none());
 }
 
 #[tokio::test]
@@ -124,6 +137,40 @@ async fn test_cancel_notification() {
     assert!(response.is_none());
 }
 
+#[tokio::test]
+async fn test_initialize_advertises_configured_server_info() {
+    let handler = ProtocolHandler::new(create_test_session()).with_settings(HandlerSettings {
+        server_info: server_info(
+            Some("acme-recall".to_string()),
+            None,
+            Some("Acme Recall".to_string()),
+        ),
+        ..Default::default()
+    });
+    let initialize = JsonRpcMessage::Request(JsonRpcRequest {
+        jsonrpc: "2.0".to_string(),
+        id: RequestId::Number(1),
+        method: "initialize".to_string(),
+        params: Some(json!({
+            "protocolVersion": MCP_VERSION,
+            "capabilities": {},
+            "clientInfo": {"name": "test", "version": "1.0"}
+        })),
+    });
+
+    let response = handler.handle_message(initialize.clone()).await.unwrap();
+
+    let info = &response["result"]["serverInfo"];
+    assert_eq!(info["name"], "acme-recall");
+    assert_eq!(info["version"], SERVER_VERSION);
+    assert_eq!(info["title"], "Acme Recall");
+
+    let handler = ProtocolHandler::new(create_test_session());
+    let response = handler.handle_message(initialize).await.unwrap();
+    assert_eq!(response["result"]["serverInfo"]["name"], SERVER_NAME);
+    assert!(response["result"]["serverInfo"].get("title").is_none());
+}
+
 // ============================================================
 // memory_add Edge Cases
 // ============================================================
@@ -302,6 +349,36 @@ async fn test_memory_add_with_edge_to_nonexistent_node() {
     assert!(result.is_err());
 }
 
+#[tokio::test]
+async fn test_memory_add_rejects_unknown_edge_types() {
+    let session = create_test_session();
+    let target = {
+        let mut sess = session.write().await;
+        sess.add_event(agentic_memory::EventType::Fact, "target", 0.9, vec![])
+            .unwrap()
+            .0
+    };
+    let err = ToolRegistry::call(
+        "memory_add",
+        Some(json!({
+            "event_type": "fact",
+            "content": "Test",
+            "edges": [
+                {"target_id": target, "edge_type": "causes"},
+                {"target_id": target, "edge_type": "supports"},
+                {"target_id": target, "edge_type": "relates"}
+            ]
+        })),
+        &session,
+    )
+    .await
+    .unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("causes, relates"), "{message}");
+    assert!(message.contains("temporal_next"), "{message}");
+    assert_eq!(session.read().await.graph().node_count(), 1);
+}
+
 #[tokio::test]
 async fn test_memory_add_with_null_params() {
     let session = create_test_session();
@@ -355,6 +432,21 @@ async fn test_memory_query_with_nonexistent_type() {
     assert_eq!(parsed["count"], 0);
 }
 
+#[tokio::test]
+async fn test_memory_query_misspelled_event_type() {
+    let session = create_test_session();
+    let result = ToolRegistry::call(
+        "memory_query",
+        Some(json!({"event_types": ["facts", "decision"]})),
+        &session,
+    )
+    .await;
+    assert!(
+        matches!(&result, Err(McpError::InvalidParams(m)) if m.contains("facts") && m.contains("episode")),
+        "{result:?}"
+    );
+}
+
 #[tokio::test]
 async fn test_memory_query_null_params() {
     let session = create_test_session();
@@ -454,6 +546,388 @@ async fn test_memory_resolve_node_without_corrections() {
     assert_eq!(resolved["is_latest"], true);
 }
 
+#[tokio::test]
+async fn test_memory_history() {
+    use agentic_memory::EventType;
+
+    let session = create_test_session();
+    let (v1, v2, v3) = {
+        let mut sess = session.write().await;
+        let (v1, _) = sess
+            .add_event(EventType::Fact, "cache TTL is 60s", 0.6, vec![])
+            .unwrap();
+        let v2 = sess.correct_node(v1, "cache TTL is 300s", 0.8).unwrap();
+        let v3 = sess.correct_node(v2, "cache TTL is 900s", 0.75).unwrap();
+        (v1, v2, v3)
+    };
+
+    let forward = ToolRegistry::call("memory_history", Some(json!({"node_id": v2})), &session)
+        .await
+        .unwrap();
+    let text = match &forward.content[0] {
+        ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["count"], 2);
+    assert_eq!(parsed["first_id"], v2);
+    assert_eq!(parsed["latest_id"], v3);
+    assert_eq!(parsed["is_latest"], false);
+    assert_eq!(parsed["versions"][1]["content"], "cache TTL is 900s");
+    assert_eq!(parsed["versions"][1]["confidence"], 0.75);
+    assert!(parsed["versions"][1]["session_id"].is_u64());
+    assert!(parsed["versions"][1]["created_at"].is_u64());
+
+    let full = ToolRegistry::call(
+        "memory_history",
+        Some(json!({"node_id": v2, "include_earlier": true})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &full.content[0] {
+        ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    let ids: Vec<u64> = parsed["versions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v["id"].as_u64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![v1, v2, v3]);
+    assert_eq!(parsed["versions"][0]["version"], 1);
+    assert_eq!(parsed["versions"][0]["content"], "cache TTL is 60s");
+
+    let missing =
+        ToolRegistry::call("memory_history", Some(json!({"node_id": 99999})), &session).await;
+    assert!(missing.is_err());
+}
+
+#[tokio::test]
+async fn test_expected_version_conflict() {
+    use agentic_memory::EventType;
+
+    let session = create_test_session();
+    let (original, version) = {
+        let mut sess = session.write().await;
+        let (id, _) = sess
+            .add_event(EventType::Fact, "deploys run at 09:00", 0.7, vec![])
+            .unwrap();
+        (id, sess.graph_version())
+    };
+
+    let first = ToolRegistry::call(
+        "memory_correct",
+        Some(json!({
+            "old_node_id": original,
+            "new_content": "deploys run at 10:00",
+            "expected_version": version,
+        })),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &first.content[0] {
+        ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    let bumped = parsed["graph_version"].as_u64().unwrap();
+    assert!(bumped > version);
+
+    let second = ToolRegistry::call(
+        "memory_correct",
+        Some(json!({
+            "old_node_id": original,
+            "new_content": "deploys run at 11:00",
+            "expected_version": version,
+        })),
+        &session,
+    )
+    .await;
+    assert!(matches!(
+        second,
+        Err(McpError::VersionConflict { expected, actual }) if expected == version && actual == bumped
+    ));
+    assert_eq!(session.read().await.graph().node_count(), 2);
+
+    // Without expected_version the write is unconditional.
+    ToolRegistry::call(
+        "memory_tag",
+        Some(json!({"node_id": original, "tags": {"owner": "ops"}})),
+        &session,
+    )
+    .await
+    .unwrap();
+    assert!(session.read().await.graph_version() > bumped);
+}
+
+#[tokio::test]
+async fn test_memory_reweight() {
+    use agentic_memory::{EdgeType, EventType};
+
+    let session = create_test_session();
+    let (belief, evidence) = {
+        let mut sess = session.write().await;
+        let (belief, _) = sess
+            .add_event(EventType::Fact, "the cache is warm at 09:00", 0.8, vec![])
+            .unwrap();
+        let (evidence, _) = sess
+            .add_event(
+                EventType::Fact,
+                "hit rate was 98% at 09:05",
+                0.9,
+                vec![(belief, EdgeType::Supports, 0.5)],
+            )
+            .unwrap();
+        (belief, evidence)
+    };
+    let edge = |args: serde_json::Value| {
+        let mut base = json!({"source_id": evidence, "target_id": belief, "edge_type": "supports"});
+        base.as_object_mut()
+            .unwrap()
+            .extend(args.as_object().unwrap().clone());
+        Some(base)
+    };
+    let weight_of = |session: &agentic_memory_mcp::session::SessionManager| {
+        session.graph().edges_from(evidence)[0].weight
+    };
+
+    let result = ToolRegistry::call("memory_reweight", edge(json!({"delta": 0.25})), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["previous_weight"], 0.5);
+    assert_eq!(parsed["weight"], 0.75);
+    assert_eq!(parsed["clamped"], false);
+    assert_eq!(weight_of(&*session.read().await), 0.75);
+
+    ToolRegistry::call("memory_reweight", edge(json!({"weight": 3.0})), &session)
+        .await
+        .unwrap();
+    assert_eq!(weight_of(&*session.read().await), 1.0);
+
+    ToolRegistry::call("memory_undo", None, &session)
+        .await
+        .unwrap();
+    assert_eq!(weight_of(&*session.read().await), 0.75);
+
+    let both = ToolRegistry::call(
+        "memory_reweight",
+        edge(json!({"weight": 0.5, "delta": 0.1})),
+        &session,
+    )
+    .await;
+    assert!(both.is_err());
+
+    let missing = ToolRegistry::call(
+        "memory_reweight",
+        Some(json!({"source_id": belief, "target_id": evidence, "edge_type": "supports", "weight": 0.5})),
+        &session,
+    )
+    .await;
+    assert!(matches!(missing, Err(McpError::InvalidGraphOp(_))));
+}
+
+#[tokio::test]
+async fn test_query_cache() {
+    let session = create_test_session();
+    session.write().await.set_query_cache_size(16);
+
+    let query = || async {
+        let result = ToolRegistry::call(
+            "memory_query",
+            Some(json!({"event_types": ["fact"]})),
+            &session,
+        )
+        .await
+        .unwrap();
+        let text = match &result.content[0] {
+            ToolContent::Text { text } => text.clone(),
+            _ => panic!("Expected text"),
+        };
+        serde_json::from_str::<serde_json::Value>(&text).unwrap()
+    };
+
+    let first = query().await;
+    assert_eq!(first["cached"], false);
+    let second = query().await;
+    assert_eq!(second["cached"], true);
+    assert_eq!(second["count"], first["count"]);
+
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "cache busting fact"})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let third = query().await;
+    assert_eq!(third["cached"], false);
+    assert_eq!(third["count"], 1);
+
+    let stats = session.read().await.query_cache_stats().unwrap();
+    assert_eq!(stats["hits"], 1);
+
+    session.write().await.set_query_cache_size(0);
+    assert!(query().await.get("cached").is_none());
+}
+
+#[tokio::test]
+async fn test_memory_add_blob() {
+    let session = create_test_session();
+
+    let result = ToolRegistry::call(
+        "memory_add",
+        Some(json!({
+            "event_type": "fact",
+            "content": "logo thumbnail",
+            "blob": "89504e47",
+            "blob_encoding": "hex",
+            "blob_mime_type": "image/png"
+        })),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["blob_bytes"], 4);
+    let node_id = parsed["node_id"].as_u64().unwrap();
+
+    let read = ResourceRegistry::read(&format!("amem://node/{node_id}"), &session)
+        .await
+        .unwrap();
+    assert_eq!(read.contents.len(), 2);
+    let meta: serde_json::Value =
+        serde_json::from_str(read.contents[0].text.as_deref().unwrap()).unwrap();
+    assert_eq!(meta["blob"]["size"], 4);
+    assert_eq!(read.contents[1].mime_type.as_deref(), Some("image/png"));
+    assert_eq!(read.contents[1].blob.as_deref(), Some("iVBORw=="));
+
+    session.write().await.set_max_blob_bytes(2);
+    let oversized = ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "too big", "blob": "AAAA"})),
+        &session,
+    )
+    .await;
+    assert!(matches!(oversized, Err(McpError::InvalidParams(_))));
+    assert_eq!(session.read().await.graph().node_count(), 1);
+
+    let malformed = ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "bad", "blob": "not base64!"})),
+        &session,
+    )
+    .await;
+    assert!(matches!(malformed, Err(McpError::InvalidParams(_))));
+}
+
+#[tokio::test]
+async fn test_memory_compact_chain() {
+    use agentic_memory::{EdgeType, EventType};
+
+    let session = create_test_session();
+    let (v1, v2, v3, evidence) = {
+        let mut sess = session.write().await;
+        let (v1, _) = sess
+            .add_event(EventType::Fact, "deploys run on Fridays", 0.9, vec![])
+            .unwrap();
+        let (evidence, _) = sess
+            .add_event(
+                EventType::Fact,
+                "release calendar",
+                0.8,
+                vec![(v1, EdgeType::Supports, 0.7)],
+            )
+            .unwrap();
+        let v2 = sess
+            .correct_node(v1, "deploys run on Thursdays", 0.9)
+            .unwrap();
+        let v3 = sess
+            .correct_node(v2, "deploys run on Tuesdays", 0.9)
+            .unwrap();
+        (v1, v2, v3, evidence)
+    };
+
+    let archived = ToolRegistry::call(
+        "memory_compact_chain",
+        Some(json!({"node_id": v1})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &archived.content[0] {
+        ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["latest_id"], v3);
+    assert_eq!(parsed["collapsed_count"], 2);
+    assert_eq!(parsed["rewired_edges"], 1);
+    {
+        let sess = session.read().await;
+        let graph = sess.graph();
+        let supersedes: Vec<u64> = graph
+            .edges_from(v3)
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::Supersedes)
+            .map(|e| e.target_id)
+            .collect();
+        assert_eq!(supersedes, vec![v1, v2]);
+        assert!(graph
+            .edges_from(evidence)
+            .iter()
+            .any(|e| e.target_id == v3 && e.edge_type == EdgeType::Supports));
+        assert!(graph.edges_from(v2).is_empty());
+    }
+
+    let deleted = ToolRegistry::call(
+        "memory_compact_chain",
+        Some(json!({"node_id": v2, "stale": "delete"})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &deleted.content[0] {
+        ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["collapsed_ids"], json!([v1, v2]));
+    {
+        let sess = session.read().await;
+        assert!(sess.graph().get_node(v1).is_none());
+        assert!(sess.graph().get_node(v2).is_none());
+        assert!(sess.graph().get_node(v3).is_some());
+    }
+
+    let bad = ToolRegistry::call(
+        "memory_compact_chain",
+        Some(json!({"node_id": v3, "stale": "hide"})),
+        &session,
+    )
+    .await;
+    assert!(bad.is_err());
+    let missing = ToolRegistry::call(
+        "memory_compact_chain",
+        Some(json!({"node_id": 99999})),
+        &session,
+    )
+    .await;
+    assert!(missing.is_err());
+}
+
 // ============================================================
 // memory_traverse Edge Cases
 // ============================================================
@@ -470,6 +944,24 @@ async fn test_memory_traverse_nonexistent_start() {
     assert!(result.is_err());
 }
 
+#[tokio::test]
+async fn test_memory_traverse_unknown_edge_type() {
+    let session = create_test_session();
+    let start = {
+        let mut sess = session.write().await;
+        sess.add_event(agentic_memory::EventType::Fact, "start", 0.9, vec![])
+            .unwrap()
+            .0
+    };
+    let result = ToolRegistry::call(
+        "memory_traverse",
+        Some(json!({"start_id": start, "edge_types": ["supports", "causedbyy"]})),
+        &session,
+    )
+    .await;
+    assert!(matches!(result, Err(McpError::InvalidParams(m)) if m.contains("causedbyy")));
+}
+
 #[tokio::test]
 async fn test_memory_traverse_single_node_no_edges() {
     let session = create_test_session();
@@ -505,6 +997,400 @@ async fn test_memory_traverse_single_node_no_edges() {
     assert!(!traversed["visited"].as_array().unwrap().is_empty());
 }
 
+#[tokio::test]
+async fn test_memory_traverse_reports_cycle() {
+    let session = create_test_session();
+    let (a, b) = {
+        let mut s = session.write().await;
+        let (a, _) = s
+            .add_event(agentic_memory::EventType::Fact, "cycle A", 0.9, vec![])
+            .unwrap();
+        let (b, _) = s
+            .add_event(
+                agentic_memory::EventType::Inference,
+                "cycle B",
+                0.9,
+                vec![(a, agentic_memory::EdgeType::CausedBy, 1.0)],
+            )
+            .unwrap();
+        s.link_temporal(a, b).unwrap();
+        (a, b)
+    };
+
+    let result = ToolRegistry::call(
+        "memory_traverse",
+        Some(json!({
+            "start_id": a,
+            "edge_types": ["caused_by", "temporal_next"],
+            "max_depth": 50,
+            "max_results": 50
+        })),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+
+    assert_eq!(parsed["visited_count"], 2);
+    let ids: Vec<u64> = parsed["visited"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v["id"].as_u64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![a, b]);
+    assert_eq!(parsed["cycles_detected"], true);
+    let back = parsed["back_edges"].as_array().unwrap();
+    assert_eq!(back.len(), 1);
+    assert_eq!(back[0]["source_id"], b);
+    assert_eq!(back[0]["target_id"], a);
+}
+
+#[tokio::test]
+async fn test_memory_traverse_tree_format() {
+    let session = create_test_session();
+    let (root, left, right, leaf) = {
+        let mut s = session.write().await;
+        let (root, _) = s
+            .add_event(agentic_memory::EventType::Fact, "root", 0.9, vec![])
+            .unwrap();
+        let child = |s: &mut agentic_memory_mcp::session::SessionManager, content, parent| {
+            s.add_event(
+                agentic_memory::EventType::Inference,
+                content,
+                0.9,
+                vec![(parent, agentic_memory::EdgeType::Supports, 1.0)],
+            )
+            .unwrap()
+            .0
+        };
+        let left = child(&mut s, "left", root);
+        let right = child(&mut s, "right", root);
+        let leaf = child(&mut s, "leaf", left);
+        (root, left, right, leaf)
+    };
+
+    let result = ToolRegistry::call(
+        "memory_traverse",
+        Some(json!({
+            "start_id": root,
+            "edge_types": ["supports"],
+            "direction": "backward",
+            "format": "tree"
+        })),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+
+    assert_eq!(parsed["visited_count"], 4);
+    let tree = &parsed["tree"];
+    assert_eq!(tree["id"], root);
+    let kids = tree["children"].as_array().unwrap();
+    assert_eq!(kids.len(), 2);
+    assert_eq!(kids[0]["id"], left);
+    assert_eq!(kids[0]["edge"]["source_id"], left);
+    assert_eq!(kids[0]["edge"]["target_id"], root);
+    assert_eq!(kids[1]["id"], right);
+    assert!(kids[1]["children"].as_array().unwrap().is_empty());
+    assert_eq!(kids[0]["children"][0]["id"], leaf);
+    assert_eq!(kids[0]["children"][0]["depth"], 2);
+}
+
+#[tokio::test]
+async fn test_memory_traverse_unknown_format() {
+    let session = create_test_session();
+    let result = ToolRegistry::call(
+        "memory_traverse",
+        Some(json!({"start_id": 0, "format": "graphviz"})),
+        &session,
+    )
+    .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_memory_traverse_fields() {
+    use agentic_memory::{EdgeType, EventType};
+
+    let session = create_test_session();
+    let (root, child) = {
+        let mut s = session.write().await;
+        let (root, _) = s.add_event(EventType::Fact, "root", 0.9, vec![]).unwrap();
+        let (child, _) = s
+            .add_event(
+                EventType::Inference,
+                "child",
+                0.9,
+                vec![(root, EdgeType::Supports, 1.0)],
+            )
+            .unwrap();
+        (root, child)
+    };
+
+    let traverse = |fields: &'static str| {
+        let session = session.clone();
+        async move {
+            let result = ToolRegistry::call(
+                "memory_traverse",
+                Some(json!({"start_id": root, "direction": "backward", "fields": fields})),
+                &session,
+            )
+            .await
+            .unwrap();
+            let text = match &result.content[0] {
+                ToolContent::Text { text } => text.clone(),
+                _ => panic!("Expected text"),
+            };
+            serde_json::from_str::<serde_json::Value>(&text).unwrap()
+        }
+    };
+
+    let ids = traverse("ids_only").await;
+    assert_eq!(ids["visited"], json!([root, child]));
+    assert_eq!(ids["visited_count"], 2);
+
+    let typed = traverse("id_and_type").await;
+    assert_eq!(typed["visited"][1]["event_type"], "inference");
+    assert_eq!(typed["visited"][1]["depth"], 1);
+    assert!(typed["visited"][1].get("content").is_none());
+
+    let full = traverse("full").await;
+    assert_eq!(full["visited"][1]["content"], "child");
+
+    let unknown = ToolRegistry::call(
+        "memory_traverse",
+        Some(json!({"start_id": root, "fields": "everything"})),
+        &session,
+    )
+    .await;
+    assert!(unknown.is_err());
+}
+
+#[tokio::test]
+async fn test_node_field_selection() {
+    use agentic_memory::{EdgeType, EventType};
+
+    let session = create_test_session();
+    let root = {
+        let mut s = session.write().await;
+        let (root, _) = s
+            .add_event(EventType::Fact, "Postgres handles the load", 0.9, vec![])
+            .unwrap();
+        s.add_event(
+            EventType::Decision,
+            "Keep Postgres",
+            0.8,
+            vec![(root, EdgeType::Supports, 1.0)],
+        )
+        .unwrap();
+        root
+    };
+
+    let keys = |node: &serde_json::Value| -> Vec<String> {
+        let mut keys: Vec<String> = node.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    };
+
+    let query = call_tool_json("memory_query", json!({"fields": ["content"]}), &session).await;
+    assert_eq!(query["count"], 2);
+    assert_eq!(
+        keys(&query["nodes"][0]),
+        ["content", "event_type", "id", "tags"]
+    );
+
+    // Selected properties the tool does not return by default are added.
+    let similar = call_tool_json(
+        "memory_similar",
+        json!({"query_text": "postgres", "fields": ["session_id", "created_at"]}),
+        &session,
+    )
+    .await;
+    let top = &similar["matches"][0];
+    assert!(top.get("content").is_none());
+    assert!(top.get("confidence").is_none());
+    assert!(top["created_at"].as_u64().is_some());
+    assert!(top["similarity"].as_f64().is_some());
+
+    let traverse = call_tool_json(
+        "memory_traverse",
+        json!({"start_id": root, "direction": "backward", "fields": ["decay_score"]}),
+        &session,
+    )
+    .await;
+    assert_eq!(
+        keys(&traverse["visited"][1]),
+        ["decay_score", "depth", "event_type", "id"]
+    );
+
+    let args = json!({"fields": ["content", "embedding"]});
+    let unknown = ToolRegistry::call("memory_query", Some(args), &session).await;
+    assert!(unknown.unwrap_err().to_string().contains("embedding"));
+}
+
+#[tokio::test]
+async fn test_memory_traverse_multiple_starts() {
+    let session = create_test_session();
+    // a -> shared <- b, and b -> only_b; shared is one hop from both sources.
+    let (a, b, shared, only_b) = {
+        let mut s = session.write().await;
+        let (a, _) = s
+            .add_event(
+                agentic_memory::EventType::Decision,
+                "decision A",
+                0.9,
+                vec![],
+            )
+            .unwrap();
+        let (b, _) = s
+            .add_event(
+                agentic_memory::EventType::Decision,
+                "decision B",
+                0.9,
+                vec![],
+            )
+            .unwrap();
+        let (shared, _) = s
+            .add_event(
+                agentic_memory::EventType::Fact,
+                "shared fact",
+                0.9,
+                vec![
+                    (a, agentic_memory::EdgeType::Supports, 1.0),
+                    (b, agentic_memory::EdgeType::Supports, 1.0),
+                ],
+            )
+            .unwrap();
+        let (only_b, _) = s
+            .add_event(
+                agentic_memory::EventType::Fact,
+                "fact for B",
+                0.9,
+                vec![(b, agentic_memory::EdgeType::Supports, 1.0)],
+            )
+            .unwrap();
+        (a, b, shared, only_b)
+    };
+
+    let result = ToolRegistry::call(
+        "memory_traverse",
+        Some(json!({
+            "start_ids": [a, b],
+            "edge_types": ["supports"],
+            "direction": "backward"
+        })),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+
+    assert_eq!(parsed["start_ids"], json!([a, b]));
+    assert_eq!(parsed["visited_count"], 4);
+    let visited = parsed["visited"].as_array().unwrap();
+    let find = |id: u64| visited.iter().find(|v| v["id"] == id).unwrap();
+    assert_eq!(visited.iter().filter(|v| v["id"] == shared).count(), 1);
+    assert_eq!(find(shared)["depth"], 1);
+    assert_eq!(find(shared)["source_id"], a);
+    assert_eq!(find(only_b)["source_id"], b);
+    assert_eq!(find(b)["depth"], 0);
+
+    let missing = ToolRegistry::call("memory_traverse", Some(json!({})), &session).await;
+    assert!(missing.is_err());
+}
+
+#[tokio::test]
+async fn test_traverse_and_causal_report_truncation() {
+    let session = create_test_session();
+    // root <- n1 <- n2 <- n3, each caused by the previous one.
+    let root = {
+        let mut s = session.write().await;
+        let (root, _) = s
+            .add_event(agentic_memory::EventType::Fact, "root", 0.9, vec![])
+            .unwrap();
+        let mut prev = root;
+        for i in 1..=3 {
+            let (id, _) = s
+                .add_event(
+                    agentic_memory::EventType::Inference,
+                    &format!("step {i}"),
+                    0.9,
+                    vec![(prev, agentic_memory::EdgeType::CausedBy, 1.0)],
+                )
+                .unwrap();
+            prev = id;
+        }
+        root
+    };
+
+    let call = |name: &'static str, args: serde_json::Value| {
+        let session = session.clone();
+        async move {
+            let result = ToolRegistry::call(name, Some(args), &session)
+                .await
+                .unwrap();
+            match &result.content[0] {
+                ToolContent::Text { text } => {
+                    serde_json::from_str::<serde_json::Value>(text).unwrap()
+                }
+                _ => panic!("Expected text"),
+            }
+        }
+    };
+
+    let shallow = call(
+        "memory_traverse",
+        json!({"start_id": root, "direction": "backward", "max_depth": 1}),
+    )
+    .await;
+    assert_eq!(shallow["visited_count"], 2);
+    assert_eq!(shallow["complete"], false);
+    assert_eq!(shallow["truncated_at_depth"], 1);
+    assert_eq!(shallow["frontier_size"], 1);
+
+    let capped = call(
+        "memory_traverse",
+        json!({"start_id": root, "direction": "backward", "max_results": 3}),
+    )
+    .await;
+    assert_eq!(capped["visited_count"], 3);
+    assert_eq!(capped["complete"], false);
+    assert_eq!(capped["truncated_at_depth"], 2);
+
+    let full = call(
+        "memory_traverse",
+        json!({"start_id": root, "direction": "backward"}),
+    )
+    .await;
+    assert_eq!(full["visited_count"], 4);
+    assert_eq!(full["complete"], true);
+    assert!(full["truncated_at_depth"].is_null());
+    assert_eq!(full["frontier_size"], 0);
+
+    let causal = call("memory_causal", json!({"node_id": root, "max_depth": 2})).await;
+    assert_eq!(causal["dependent_count"], 2);
+    assert_eq!(causal["complete"], false);
+    assert_eq!(causal["truncated_at_depth"], 2);
+    assert_eq!(causal["frontier_size"], 1);
+
+    let causal = call("memory_causal", json!({"node_id": root})).await;
+    assert_eq!(causal["complete"], true);
+}
+
 // ============================================================
 // memory_context Edge Cases
 // ============================================================
@@ -517,65 +1403,267 @@ async fn test_memory_context_nonexistent_node() {
     assert!(result.is_err());
 }
 
-// ============================================================
-// memory_similar Edge Cases
-// ============================================================
+// ============================================================
+// memory_similar Edge Cases
+// ============================================================
+
+#[tokio::test]
+async fn test_memory_similar_missing_query() {
+    let session = create_test_session();
+    // Neither query_vec nor query_text provided — should error
+    let result = ToolRegistry::call("memory_similar", Some(json!({})), &session).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_memory_similar_with_query_text() {
+    let session = create_test_session();
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "test memory"})),
+        &session,
+    )
+    .await
+    .unwrap();
+
+    let result = ToolRegistry::call(
+        "memory_similar",
+        Some(json!({"query_text": "test"})),
+        &session,
+    )
+    .await;
+    assert!(result.is_ok());
+    let text = match &result.unwrap().content[0] {
+        ToolContent::Text { text } => text.clone(),
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(parsed["mode"], "text_fallback");
+    assert!(parsed["matches"].is_array());
+}
+
+#[tokio::test]
+async fn test_memory_similar_misspelled_event_type() {
+    let session = create_test_session();
+    let result = ToolRegistry::call(
+        "memory_similar",
+        Some(json!({"query_text": "test", "event_types": ["fact", "skils"]})),
+        &session,
+    )
+    .await;
+    assert!(matches!(result, Err(McpError::InvalidParams(m)) if m.contains("skils")));
+}
+
+#[tokio::test]
+async fn test_memory_similar_with_zero_vector() {
+    let session = create_test_session();
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "cats are fluffy"})),
+        &session,
+    )
+    .await
+    .unwrap();
+
+    // Use a zero vector — should return no matches with skip_zero_vectors
+    let dimension = {
+        let sess = session.read().await;
+        sess.graph().dimension()
+    };
+    let zero_vec: Vec<f32> = vec![0.0; dimension];
+    let result = ToolRegistry::call(
+        "memory_similar",
+        Some(json!({"query_vec": zero_vec})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert!(parsed["matches"].is_array());
+}
+
+#[tokio::test]
+async fn test_memory_compare_two_nodes() {
+    let session = create_test_session();
+    let dimension = session.read().await.graph().dimension();
+    let mut x = vec![0.0f32; dimension];
+    x[0] = 1.0;
+    let mut xy = x.clone();
+    xy[1] = 1.0;
+
+    let mut ids = Vec::new();
+    for (content, vec) in [("axis", x), ("diagonal", xy), ("no vector", Vec::new())] {
+        let result = ToolRegistry::call(
+            "memory_add",
+            Some(json!({"event_type": "fact", "content": content, "feature_vec": vec})),
+            &session,
+        )
+        .await
+        .unwrap();
+        let text = match &result.content[0] {
+            ToolContent::Text { text } => text,
+            _ => panic!("Expected text"),
+        };
+        let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+        ids.push(parsed["node_id"].as_u64().unwrap());
+    }
+
+    let compare = |a: u64, b: u64| {
+        let session = session.clone();
+        async move {
+            let result = ToolRegistry::call(
+                "memory_compare",
+                Some(json!({"node_a": a, "node_b": b})),
+                &session,
+            )
+            .await
+            .unwrap();
+            match &result.content[0] {
+                ToolContent::Text { text } => {
+                    serde_json::from_str::<serde_json::Value>(text).unwrap()
+                }
+                _ => panic!("Expected text"),
+            }
+        }
+    };
+
+    let scored = compare(ids[0], ids[1]).await;
+    let similarity = scored["similarity"].as_f64().unwrap();
+    assert!((similarity - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-4);
+    assert_eq!(scored["missing_vector"], false);
+
+    let unscored = compare(ids[0], ids[2]).await;
+    assert!(unscored["similarity"].is_null());
+    assert_eq!(unscored["missing_vector"], true);
+    assert_eq!(unscored["node_b_has_vector"], false);
+    assert_eq!(unscored["reason"], "missing_vector");
 
-#[tokio::test]
-async fn test_memory_similar_missing_query() {
-    let session = create_test_session();
-    // Neither query_vec nor query_text provided — should error
-    let result = ToolRegistry::call("memory_similar", Some(json!({})), &session).await;
-    assert!(result.is_err());
+    let missing = ToolRegistry::call(
+        "memory_compare",
+        Some(json!({"node_a": ids[0], "node_b": 99999})),
+        &session,
+    )
+    .await;
+    assert!(missing.is_err());
 }
 
 #[tokio::test]
-async fn test_memory_similar_with_query_text() {
+async fn test_memory_session_diff() {
+    use agentic_memory::{EdgeType, EventType};
+
     let session = create_test_session();
-    ToolRegistry::call(
-        "memory_add",
-        Some(json!({"event_type": "fact", "content": "test memory"})),
+    {
+        let mut sess = session.write().await;
+        let dimension = sess.graph().dimension();
+        let unit = |i: usize| {
+            let mut v = vec![0.0f32; dimension];
+            v[i] = 1.0;
+            v
+        };
+
+        sess.start_session(Some(1)).unwrap();
+        sess.add_event_with_vector(EventType::Fact, "rust is fast", 0.9, vec![], unit(0))
+            .unwrap();
+        sess.add_event(EventType::Fact, "uses tokio", 0.9, vec![])
+            .unwrap();
+        let (old, _) = sess
+            .add_event_with_vector(EventType::Fact, "deploys on fridays", 0.9, vec![], unit(1))
+            .unwrap();
+
+        sess.start_session(Some(2)).unwrap();
+        sess.add_event_with_vector(EventType::Fact, "rust is quick", 0.9, vec![], unit(0))
+            .unwrap();
+        sess.add_event(EventType::Fact, "Uses Tokio", 0.9, vec![])
+            .unwrap();
+        sess.add_event_with_vector(EventType::Fact, "ci runs nightly", 0.9, vec![], unit(2))
+            .unwrap();
+        sess.add_event_with_vector(
+            EventType::Fact,
+            "never deploy on fridays",
+            0.9,
+            vec![(old, EdgeType::Contradicts, 1.0)],
+            unit(3),
+        )
+        .unwrap();
+    }
+
+    let result = ToolRegistry::call(
+        "memory_session_diff",
+        Some(json!({"session_a": 1, "session_b": 2})),
         &session,
     )
     .await
     .unwrap();
+    let text = match &result.content[0] {
+        ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
 
-    let result = ToolRegistry::call(
-        "memory_similar",
-        Some(json!({"query_text": "test"})),
+    assert_eq!(parsed["shared_topics"]["count"], 2);
+    assert_eq!(parsed["only_in_a"]["count"], 1);
+    assert_eq!(
+        parsed["only_in_a"]["nodes"][0]["content"],
+        "deploys on fridays"
+    );
+    assert_eq!(parsed["only_in_b"]["count"], 2);
+    assert_eq!(parsed["contradictions"]["count"], 1);
+    assert_eq!(
+        parsed["contradictions"]["edges"][0]["edge_type"],
+        "contradicts"
+    );
+
+    let missing = ToolRegistry::call(
+        "memory_session_diff",
+        Some(json!({"session_a": 1, "session_b": 77})),
         &session,
     )
     .await;
-    assert!(result.is_ok());
-    let text = match &result.unwrap().content[0] {
-        ToolContent::Text { text } => text.clone(),
-        _ => panic!("Expected text"),
-    };
-    let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
-    assert_eq!(parsed["mode"], "text_fallback");
-    assert!(parsed["matches"].is_array());
+    assert!(missing.is_err());
 }
 
 #[tokio::test]
-async fn test_memory_similar_with_zero_vector() {
+async fn test_memory_similar_within_anchor_neighborhood() {
     let session = create_test_session();
-    ToolRegistry::call(
-        "memory_add",
-        Some(json!({"event_type": "fact", "content": "cats are fluffy"})),
-        &session,
-    )
-    .await
-    .unwrap();
-
-    // Use a zero vector — should return no matches with skip_zero_vectors
-    let dimension = {
-        let sess = session.lock().await;
-        sess.graph().dimension()
+    let (anchor, near, far) = {
+        let mut sess = session.write().await;
+        let (anchor, _) = sess
+            .add_event(
+                agentic_memory::EventType::Decision,
+                "use rust for the parser",
+                0.9,
+                vec![],
+            )
+            .unwrap();
+        let (near, _) = sess
+            .add_event(
+                agentic_memory::EventType::Fact,
+                "rust has a strict borrow checker",
+                0.9,
+                vec![(anchor, agentic_memory::EdgeType::Supports, 1.0)],
+            )
+            .unwrap();
+        let (far, _) = sess
+            .add_event(
+                agentic_memory::EventType::Fact,
+                "rust macros are hygienic",
+                0.9,
+                vec![],
+            )
+            .unwrap();
+        (anchor, near, far)
     };
-    let zero_vec: Vec<f32> = vec![0.0; dimension];
+
     let result = ToolRegistry::call(
         "memory_similar",
-        Some(json!({"query_vec": zero_vec})),
+        Some(json!({
+            "query_text": "rust",
+            "within": {"anchor_id": anchor, "max_hops": 1}
+        })),
         &session,
     )
     .await
@@ -585,7 +1673,31 @@ async fn test_memory_similar_with_zero_vector() {
         _ => panic!("Expected text"),
     };
     let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
-    assert!(parsed["matches"].is_array());
+    let matches = parsed["matches"].as_array().unwrap();
+    let ids: Vec<u64> = matches
+        .iter()
+        .map(|m| m["node_id"].as_u64().unwrap())
+        .collect();
+    assert!(ids.contains(&anchor));
+    assert!(ids.contains(&near));
+    assert!(!ids.contains(&far));
+    for m in matches {
+        assert_eq!(m["anchor_id"], anchor);
+        let expected_hops = if m["node_id"] == anchor { 0 } else { 1 };
+        assert_eq!(m["hops"], expected_hops);
+    }
+}
+
+#[tokio::test]
+async fn test_memory_similar_within_missing_anchor() {
+    let session = create_test_session();
+    let result = ToolRegistry::call(
+        "memory_similar",
+        Some(json!({"query_text": "rust", "within": {"anchor_id": 99999}})),
+        &session,
+    )
+    .await;
+    assert!(result.is_err());
 }
 
 // ============================================================
@@ -631,6 +1743,279 @@ async fn test_memory_causal_node_without_causal_edges() {
     assert!(causal["dependents"].as_array().unwrap().is_empty());
 }
 
+#[tokio::test]
+async fn test_memory_causal_custom_dependency_types() {
+    use agentic_memory::{EdgeType, EventType};
+
+    let session = create_test_session();
+    let (episode, member) = {
+        let mut sess = session.write().await;
+        let (episode, _) = sess
+            .add_event(EventType::Episode, "release week", 0.9, vec![])
+            .unwrap();
+        let (member, _) = sess
+            .add_event(
+                EventType::Decision,
+                "ship on thursday",
+                0.9,
+                vec![(episode, EdgeType::PartOf, 1.0)],
+            )
+            .unwrap();
+        (episode, member)
+    };
+
+    let call = |args| call_tool_json("memory_causal", args, &session);
+
+    let default = call(json!({"node_id": episode})).await;
+    assert_eq!(default["dependent_count"], 0);
+    assert_eq!(
+        default["dependency_types"],
+        json!(["caused_by", "supports"])
+    );
+
+    let part_of = call(json!({"node_id": episode, "dependency_types": ["part_of"]})).await;
+    assert_eq!(part_of["dependent_count"], 1);
+    assert_eq!(part_of["dependents"][0]["id"], member);
+    assert_eq!(part_of["affected_decisions"], 1);
+
+    let unknown = ToolRegistry::call(
+        "memory_causal",
+        Some(json!({"node_id": episode, "dependency_types": ["depends_on"]})),
+        &session,
+    )
+    .await;
+    assert!(unknown.is_err());
+}
+
+#[tokio::test]
+async fn test_memory_session_window() {
+    use agentic_memory::EventType;
+
+    let session = create_test_session();
+    {
+        let mut sess = session.write().await;
+        sess.start_session(Some(3)).unwrap();
+        // Added out of order: the window sorts by creation time.
+        for (content, created_at) in [
+            ("Deploy plan approved", 300),
+            ("Design review held", 100),
+            ("Deploy rollback tested", 200),
+        ] {
+            sess.add_event_at(
+                EventType::Fact,
+                content,
+                0.9,
+                vec![],
+                vec![],
+                Some(created_at),
+            )
+            .unwrap();
+        }
+        sess.start_session(Some(4)).unwrap();
+        sess.add_event(EventType::Fact, "Deploy elsewhere", 0.9, vec![])
+            .unwrap();
+    }
+
+    let window = |args| call_tool_json("memory_session_window", args, &session);
+    let contents = |parsed: &serde_json::Value| -> Vec<String> {
+        parsed["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["content"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let all = window(json!({"session_id": 3})).await;
+    assert_eq!(all["total"], 3);
+    assert_eq!(
+        contents(&all),
+        [
+            "Design review held",
+            "Deploy rollback tested",
+            "Deploy plan approved"
+        ]
+    );
+    assert_eq!(all["nodes"][0]["created_at"], 100);
+
+    let latest_deploy = window(json!({"session_id": 3, "contains": "DEPLOY", "last": 1})).await;
+    assert_eq!(latest_deploy["matched"], 2);
+    assert_eq!(latest_deploy["count"], 1);
+    assert_eq!(contents(&latest_deploy), ["Deploy plan approved"]);
+
+    let first_two = window(json!({"session_id": 3, "first": 2})).await;
+    assert_eq!(
+        contents(&first_two),
+        ["Design review held", "Deploy rollback tested"]
+    );
+
+    // Without session_id, the current session is listed.
+    let current = window(json!({})).await;
+    assert_eq!(current["session_id"], 4);
+    assert_eq!(contents(&current), ["Deploy elsewhere"]);
+
+    for args in [
+        json!({"session_id": 3, "first": 1, "last": 1}),
+        json!({"session_id": 77}),
+    ] {
+        assert!(
+            ToolRegistry::call("memory_session_window", Some(args), &session)
+                .await
+                .is_err()
+        );
+    }
+
+    // A session that has just started is empty, not missing.
+    session.write().await.start_session(Some(5)).unwrap();
+    for args in [json!({}), json!({"session_id": 5})] {
+        let empty = window(args).await;
+        assert_eq!(empty["session_id"], 5);
+        assert_eq!(empty["total"], 0);
+        assert_eq!(empty["nodes"], json!([]));
+    }
+}
+
+#[tokio::test]
+async fn test_memory_explain_causal() {
+    use agentic_memory::{EdgeType, EventType};
+
+    let session = create_test_session();
+    let (fact_a, fact_b, inference, decision) = {
+        let mut sess = session.write().await;
+        let (inference, _) = sess
+            .add_event(EventType::Inference, "Need ACID transactions", 0.8, vec![])
+            .unwrap();
+        let (fact_a, _) = sess
+            .add_event(
+                EventType::Fact,
+                "Orders span several tables",
+                0.9,
+                vec![(inference, EdgeType::Supports, 1.0)],
+            )
+            .unwrap();
+        let (fact_b, _) = sess
+            .add_event(
+                EventType::Fact,
+                "Payments must not double-post",
+                0.9,
+                vec![(inference, EdgeType::Supports, 1.0)],
+            )
+            .unwrap();
+        let (decision, _) = sess
+            .add_event(
+                EventType::Decision,
+                "Use PostgreSQL",
+                0.9,
+                vec![(inference, EdgeType::CausedBy, 1.0)],
+            )
+            .unwrap();
+        (fact_a, fact_b, inference, decision)
+    };
+
+    let call = |args| call_tool_json("memory_explain_causal", args, &session);
+
+    let explained = call(json!({"node_id": decision, "include_content": false})).await;
+    assert_eq!(
+        explained["explanation"],
+        format!(
+            "Decision #{decision} was caused by Inference #{inference}, \
+             which was supported by Facts #{fact_a} and #{fact_b}."
+        )
+    );
+    let chain: Vec<u64> = explained["chain"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|step| step["id"].as_u64().unwrap())
+        .collect();
+    assert_eq!(chain, vec![decision, inference, fact_a, fact_b]);
+    assert_eq!(
+        explained["chain"][1]["supported_by"],
+        json!([fact_a, fact_b])
+    );
+    assert_eq!(explained["complete"], true);
+
+    let quoted = call(json!({"node_id": decision})).await;
+    assert!(quoted["explanation"]
+        .as_str()
+        .unwrap()
+        .starts_with(&format!(
+            "Decision #{decision} (\"Use PostgreSQL\") was caused by"
+        )));
+
+    let shallow = call(json!({"node_id": decision, "max_depth": 1})).await;
+    assert_eq!(shallow["complete"], false);
+    assert_eq!(shallow["frontier_size"], 2);
+
+    let leaf = call(json!({"node_id": fact_a, "include_content": false})).await;
+    assert_eq!(
+        leaf["explanation"],
+        format!("Fact #{fact_a} has no recorded causes or support.")
+    );
+
+    let missing = ToolRegistry::call(
+        "memory_explain_causal",
+        Some(json!({"node_id": 99999})),
+        &session,
+    )
+    .await;
+    assert!(missing.is_err());
+}
+
+#[tokio::test]
+async fn test_memory_support_score() {
+    use agentic_memory::{EdgeType, EventType};
+
+    let session = create_test_session();
+    let claim = {
+        let mut sess = session.write().await;
+        let (claim, _) = sess
+            .add_event(EventType::Inference, "cache is the bottleneck", 0.6, vec![])
+            .unwrap();
+        sess.add_event(
+            EventType::Fact,
+            "profiler shows 70% in cache lookups",
+            0.8,
+            vec![(claim, EdgeType::Supports, 1.0)],
+        )
+        .unwrap();
+        sess.add_event(
+            EventType::Fact,
+            "disabling the cache did not help",
+            0.5,
+            vec![(claim, EdgeType::Contradicts, 1.0)],
+        )
+        .unwrap();
+        claim
+    };
+
+    let result = ToolRegistry::call(
+        "memory_support_score",
+        Some(json!({"node_id": claim})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert!((parsed["support"].as_f64().unwrap() - 0.8).abs() < 1e-5);
+    assert!((parsed["opposition"].as_f64().unwrap() - 0.5).abs() < 1e-5);
+    assert!((parsed["net"].as_f64().unwrap() - 0.3).abs() < 1e-5);
+    assert_eq!(parsed["supporter_count"], 1);
+    assert_eq!(parsed["opposer_count"], 1);
+
+    let missing = ToolRegistry::call(
+        "memory_support_score",
+        Some(json!({"node_id": 99999})),
+        &session,
+    )
+    .await;
+    assert!(missing.is_err());
+}
+
 // ============================================================
 // memory_temporal Edge Cases
 // ============================================================
@@ -713,6 +2098,103 @@ async fn test_memory_stats_after_many_operations() {
     assert_eq!(parsed["node_count"], 10);
 }
 
+#[tokio::test]
+async fn test_memory_stats_per_session() {
+    let session = create_test_session();
+
+    let sessions = [
+        (1, vec!["fact", "episode"]),
+        (2, vec!["fact", "fact"]),
+        (3, vec!["decision"]),
+    ];
+    for (session_id, event_types) in sessions {
+        ToolRegistry::call(
+            "session_start",
+            Some(json!({"session_id": session_id})),
+            &session,
+        )
+        .await
+        .unwrap();
+        for event_type in event_types {
+            ToolRegistry::call(
+                "memory_add",
+                Some(json!({"event_type": event_type, "content": format!("{event_type} in {session_id}")})),
+                &session,
+            )
+            .await
+            .unwrap();
+        }
+    }
+
+    let stats = |args| call_tool_json("memory_stats", args, &session);
+
+    assert!(stats(json!({})).await.get("sessions").is_none());
+
+    let first = stats(json!({"per_session": true, "max_sessions": 2})).await;
+    let sessions = &first["sessions"];
+    assert_eq!(sessions["total"], 3);
+    assert_eq!(sessions["items"].as_array().unwrap().len(), 2);
+    assert_eq!(sessions["items"][0]["session_id"], 1);
+    assert_eq!(sessions["items"][0]["node_count"], 2);
+    assert_eq!(sessions["items"][0]["has_episode"], true);
+    assert_eq!(sessions["items"][1]["has_episode"], false);
+    assert_eq!(sessions["next_after_session"], 2);
+
+    let second = stats(json!({"per_session": true, "max_sessions": 2, "after_session": 2})).await;
+    let sessions = &second["sessions"];
+    assert_eq!(sessions["items"].as_array().unwrap().len(), 1);
+    assert_eq!(sessions["items"][0]["session_id"], 3);
+    assert_eq!(sessions["items"][0]["node_count"], 1);
+    assert!(sessions["next_after_session"].is_null());
+}
+
+#[tokio::test]
+async fn test_memory_stats_cache_invalidated_by_mutation() {
+    let session = create_test_session();
+    let tool_stats = || call_tool_json("memory_stats", json!({}), &session);
+    let resource_stats = || {
+        let session = session.clone();
+        async move {
+            let result = ResourceRegistry::read("amem://graph/stats", &session)
+                .await
+                .unwrap();
+            serde_json::from_str::<serde_json::Value>(result.contents[0].text.as_ref().unwrap())
+                .unwrap()
+        }
+    };
+
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "First fact"})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let before = tool_stats().await;
+    assert_eq!(before["node_count"], 1);
+    assert_eq!(before["type_counts"]["fact"], 1);
+    // A second read at the same version is served from the cache unchanged.
+    assert_eq!(tool_stats().await["type_counts"], before["type_counts"]);
+    assert_eq!(resource_stats().await["node_count"], 1);
+
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "decision", "content": "Use the cache"})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let after = tool_stats().await;
+    assert_eq!(after["node_count"], 2);
+    assert_eq!(after["type_counts"]["fact"], 1);
+    assert_eq!(after["type_counts"]["decision"], 1);
+    assert_ne!(after["graph_version"], before["graph_version"]);
+
+    let resource = resource_stats().await;
+    assert_eq!(resource["node_count"], 2);
+    assert_eq!(resource["type_counts"]["decision"], 1);
+}
+
 // ============================================================
 // session_start / session_end Edge Cases
 // ============================================================
@@ -874,6 +2356,62 @@ async fn test_resource_graph_important_empty() {
     assert_eq!(parsed["count"], 0);
 }
 
+#[tokio::test]
+async fn test_pinned_nodes_lead_graph_important_and_resist_deletion() {
+    let session = create_test_session();
+    for i in 0..21 {
+        ToolRegistry::call(
+            "memory_add",
+            Some(json!({"event_type": "fact", "content": format!("fact {i}")})),
+            &session,
+        )
+        .await
+        .unwrap();
+    }
+
+    let important = || {
+        let session = session.clone();
+        async move {
+            let result = ResourceRegistry::read("amem://graph/important", &session)
+                .await
+                .unwrap();
+            let text = result.contents[0].text.as_ref().unwrap();
+            serde_json::from_str::<serde_json::Value>(text).unwrap()
+        }
+    };
+    let pin = |tool: &'static str| {
+        let session = session.clone();
+        async move {
+            let result = ToolRegistry::call(tool, Some(json!({"node_id": 0})), &session)
+                .await
+                .unwrap();
+            let text = match &result.content[0] {
+                ToolContent::Text { text } => text.clone(),
+                _ => panic!("Expected text"),
+            };
+            serde_json::from_str::<serde_json::Value>(&text).unwrap()
+        }
+    };
+
+    let pinned = pin("memory_pin").await;
+    assert_eq!(pinned["pinned"], true);
+    assert_eq!(pinned["changed"], true);
+    assert_eq!(pin("memory_pin").await["changed"], false);
+
+    let listed = important().await;
+    assert_eq!(listed["pinned"], 1);
+    assert_eq!(listed["count"], 21);
+    assert_eq!(listed["nodes"][0]["id"], 0);
+    assert_eq!(listed["nodes"][0]["pinned"], true);
+
+    let err = session.write().await.delete_node(0).unwrap_err();
+    assert!(err.to_string().contains("pinned"));
+
+    assert_eq!(pin("memory_unpin").await["pinned"], false);
+    assert_eq!(important().await["count"], 20);
+    session.write().await.delete_node(0).unwrap();
+}
+
 // ============================================================
 // Prompt Edge Cases
 // ============================================================
@@ -963,6 +2501,50 @@ async fn test_prompt_summarize_explicit_session_id() {
     assert!(msg_text.contains("session 999"));
 }
 
+#[tokio::test]
+async fn test_prompt_locale() {
+    use agentic_memory_mcp::prompts::{PromptLocale, PromptRegistry};
+
+    assert_eq!(PromptLocale::from_tag("de-AT"), Some(PromptLocale::De));
+    assert_eq!(
+        PromptLocale::from_tag("es_ES.UTF-8"),
+        Some(PromptLocale::Es)
+    );
+    assert_eq!(PromptLocale::from_tag("fr"), None);
+
+    let session = create_test_session();
+    session.write().await.set_prompt_locale(PromptLocale::Es);
+    let result = PromptRegistry::get(
+        "correct",
+        Some(json!({"old_belief": "A", "new_information": "B", "reason": "typo"})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let msg_text = match &result.messages[0].content {
+        ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    assert!(msg_text.contains("Creencia anterior: A"));
+    assert!(msg_text.contains("Motivo: typo"));
+    assert!(msg_text.contains("memory_correct"));
+    assert_eq!(
+        result.description.as_deref(),
+        Some("Guía para actualizar creencias")
+    );
+
+    session.write().await.set_prompt_locale(PromptLocale::De);
+    let result = PromptRegistry::get("summarize", Some(json!({"session_id": 7})), &session)
+        .await
+        .unwrap();
+    let msg_text = match &result.messages[0].content {
+        ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    assert!(msg_text.contains("Sitzung 7"));
+    assert!(msg_text.contains("session_end"));
+}
+
 // ============================================================
 // Integration: Multi-step workflows
 // ============================================================
@@ -1121,3 +2703,164 @@ async fn test_concurrent_tool_calls() {
     let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
     assert_eq!(parsed["node_count"], 10);
 }
+
+#[tokio::test]
+async fn test_read_tools_share_the_session_lock() {
+    use std::time::Duration;
+
+    let session = create_test_session();
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "Readers do not queue"})),
+        &session,
+    )
+    .await
+    .unwrap();
+
+    // Another reader holds the lock; read tools still run, writes wait.
+    let reader = session.read().await;
+    for tool in ["memory_query", "memory_stats", "memory_similar"] {
+        let args = json!({"query_text": "readers", "min_similarity": 0.0});
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            ToolRegistry::call(tool, Some(args), &session),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("{tool} waited behind a reader"));
+        assert!(result.is_ok(), "{tool} failed: {result:?}");
+    }
+    let write = ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "Writers do"})),
+        &session,
+    );
+    assert!(tokio::time::timeout(Duration::from_millis(100), write)
+        .await
+        .is_err());
+    drop(reader);
+}
+
+#[tokio::test]
+async fn test_handler_runs_concurrent_queries_under_a_reader() {
+    use std::time::Duration;
+
+    let session = create_test_session();
+    let handler = ProtocolHandler::new(session.clone());
+    let call = |id: i64| {
+        JsonRpcMessage::Request(JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: RequestId::Number(id),
+            method: "tools/call".to_string(),
+            params: Some(json!({
+                "name": "memory_query",
+                "arguments": {"max_results": 5}
+            })),
+        })
+    };
+    // Prime the resume hook, which runs once per session.
+    handler.handle_message(call(1)).await.unwrap();
+
+    // With a reader holding the lock, neither call may need the write lock.
+    let reader = session.read().await;
+    let (first, second) = tokio::time::timeout(Duration::from_secs(5), async {
+        tokio::join!(
+            handler.handle_message(call(2)),
+            handler.handle_message(call(3))
+        )
+    })
+    .await
+    .expect("memory_query waited for the write lock");
+    drop(reader);
+
+    for response in [first, second] {
+        let response = response.unwrap();
+        assert!(response.get("error").is_none(), "{response}");
+        assert_ne!(response["result"]["isError"], true, "{response}");
+    }
+}
+
+#[test]
+fn test_memory_path_template_expansion() {
+    use agentic_memory_mcp::config::{expand_path_template, resolve_memory_path};
+
+    std::env::set_var("AMEM_TEST_TENANT", "acme");
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+
+    assert_eq!(
+        expand_path_template("/srv/${AMEM_TEST_TENANT}/{date}.amem"),
+        format!("/srv/acme/{today}.amem")
+    );
+    assert_eq!(
+        expand_path_template("/srv/{unknown}/${AMEM_TEST_UNSET_VAR}.amem"),
+        "/srv/{unknown}/${AMEM_TEST_UNSET_VAR}.amem"
+    );
+    assert_eq!(expand_path_template("/srv/plain.amem"), "/srv/plain.amem");
+    assert_eq!(
+        resolve_memory_path(Some("/srv/${AMEM_TEST_TENANT}/brain.amem")),
+        "/srv/acme/brain.amem"
+    );
+}
+
+#[tokio::test]
+async fn test_tool_call_timeout_releases_the_session() {
+    use agentic_memory_mcp::tools::timeout::ToolTimeouts;
+    use agentic_memory_mcp::tools::CustomTools;
+    use agentic_memory_mcp::SessionManager;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+    use tokio::sync::RwLock;
+
+    let definition = ToolDefinition {
+        name: "slow_lookup".to_string(),
+        description: None,
+        input_schema: json!({ "type": "object" }),
+    };
+    let custom_tools = CustomTools::default();
+    custom_tools
+        .register(
+            definition,
+            |_: serde_json::Value, session: Arc<RwLock<SessionManager>>| async move {
+                let _guard = session.write().await;
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                Ok(ToolCallResult::text("done".to_string()))
+            },
+        )
+        .unwrap();
+    let mut timeouts = ToolTimeouts::default();
+    timeouts
+        .tools
+        .insert("slow_lookup".to_string(), Some(Duration::from_millis(50)));
+    assert_eq!(
+        timeouts.limit_for("memory_query"),
+        Some(Duration::from_secs(30))
+    );
+    assert_eq!(
+        timeouts.limit_for("memory_reembed"),
+        Some(Duration::from_secs(600))
+    );
+
+    let mut client = MockClient::new(ProtocolHandler::new(create_test_session()).with_settings(
+        HandlerSettings {
+            tool_timeouts: timeouts,
+            custom_tools,
+            ..Default::default()
+        },
+    ));
+    client.initialize().await;
+    let started = Instant::now();
+    let response = client.call_tool("slow_lookup", None).await;
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(
+        response["error"]["code"],
+        mcp_error_codes::TOOL_TIMEOUT,
+        "expected a timeout error: {response}"
+    );
+    assert!(response["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("slow_lookup"));
+
+    // The abandoned call dropped its lock, so the next call runs.
+    let stats = client.call_tool("memory_stats", None).await;
+    assert!(stats["result"].is_object(), "stats failed: {stats}");
+}
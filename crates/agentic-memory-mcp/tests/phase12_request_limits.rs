@@ -0,0 +1,129 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use serde_json::Value;
+use tempfile::tempdir;
+
+#[test]
+fn oversized_stdin_line_is_rejected_and_server_keeps_serving() {
+    let dir = tempdir().expect("temp dir");
+    let memory = dir.path().join("limits.amem");
+    let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .canonicalize()
+        .expect("workspace root");
+
+    let mut child = Command::new("cargo")
+        .arg("run")
+        .arg("-p")
+        .arg("agentic-memory-mcp")
+        .arg("--quiet")
+        .arg("--")
+        .arg("serve")
+        .arg("--memory")
+        .arg(&memory)
+        .env("AMEM_MAX_REQUEST_BYTES", "256")
+        .env("HOME", dir.path())
+        .current_dir(workspace_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn server");
+
+    let oversized = format!(
+        r#"{{"jsonrpc":"2.0","id":1,"method":"ping","params":{{"pad":"{}"}}}}"#,
+        "x".repeat(4096)
+    );
+    {
+        let mut stdin = child.stdin.take().expect("stdin");
+        writeln!(stdin, "{oversized}").expect("write oversized");
+        writeln!(stdin, r#"{{"jsonrpc":"2.0","id":2,"method":"ping"}}"#).expect("write ping");
+    }
+
+    let out = child.wait_with_output().expect("server output");
+    let responses: Vec<Value> = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+
+    assert_eq!(responses.len(), 2, "responses: {responses:?}");
+    assert_eq!(responses[0]["error"]["code"], -32801);
+    assert!(responses[0]["id"].is_null());
+    assert_eq!(responses[1]["id"], 2);
+    assert!(responses[1]["result"].is_object());
+}
+
+#[test]
+fn oversized_framed_body_is_skipped_and_server_keeps_serving() {
+    let dir = tempdir().expect("temp dir");
+    let memory = dir.path().join("limits-framed.amem");
+    let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .canonicalize()
+        .expect("workspace root");
+
+    let mut child = Command::new("cargo")
+        .arg("run")
+        .arg("-p")
+        .arg("agentic-memory-mcp")
+        .arg("--quiet")
+        .arg("--")
+        .arg("serve")
+        .arg("--memory")
+        .arg(&memory)
+        .env("AMEM_MAX_REQUEST_BYTES", "256")
+        .env("HOME", dir.path())
+        .current_dir(workspace_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn server");
+
+    // The oversized body contains a newline and a valid-looking request, so
+    // a reader that failed to skip the whole body would answer it.
+    let oversized = format!(
+        "{{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"ping\",\"params\":{{\"pad\":\"{}\"}}}}\n\
+         {{\"jsonrpc\":\"2.0\",\"id\":99,\"method\":\"ping\"}}",
+        "x".repeat(4096)
+    );
+    let valid = r#"{"jsonrpc":"2.0","id":2,"method":"ping"}"#;
+    {
+        let mut stdin = child.stdin.take().expect("stdin");
+        for body in [oversized.as_str(), valid] {
+            write!(stdin, "Content-Length: {}\r\n\r\n{body}", body.len()).expect("write frame");
+        }
+    }
+
+    let out = child.wait_with_output().expect("server output");
+    let responses = parse_framed(&out.stdout);
+
+    assert_eq!(responses.len(), 2, "responses: {responses:?}");
+    assert_eq!(responses[0]["error"]["code"], -32801);
+    assert!(responses[0]["id"].is_null());
+    assert_eq!(responses[1]["id"], 2);
+    assert!(responses[1]["result"].is_object());
+}
+
+/// Split `Content-Length`-framed output into JSON messages.
+fn parse_framed(mut out: &[u8]) -> Vec<Value> {
+    let mut messages = Vec::new();
+    while let Some(end) = out.windows(4).position(|w| w == b"\r\n\r\n") {
+        let header = String::from_utf8_lossy(&out[..end]);
+        let len: usize = header
+            .trim()
+            .strip_prefix("Content-Length:")
+            .expect("Content-Length header")
+            .trim()
+            .parse()
+            .expect("body length");
+        let body = &out[end + 4..end + 4 + len];
+        messages.push(serde_json::from_slice(body).expect("json body"));
+        out = &out[end + 4 + len..];
+    }
+    messages
+}
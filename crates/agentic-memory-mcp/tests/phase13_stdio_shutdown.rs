@@ -0,0 +1,76 @@
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use agentic_memory::AmemReader;
+use serde_json::{json, Value};
+use tempfile::tempdir;
+
+#[test]
+fn client_hangup_saves_and_exits_cleanly() {
+    let dir = tempdir().expect("temp dir");
+    let memory = dir.path().join("hangup.amem");
+    let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .canonicalize()
+        .expect("workspace root");
+
+    let mut child = Command::new("cargo")
+        .arg("run")
+        .arg("-p")
+        .arg("agentic-memory-mcp")
+        .arg("--quiet")
+        .arg("--")
+        .arg("serve")
+        .arg("--memory")
+        .arg(&memory)
+        .env("HOME", dir.path())
+        .current_dir(workspace_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn server");
+
+    let mut stdin = child.stdin.take().expect("stdin");
+    let messages = [
+        json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {
+            "protocolVersion": "2024-11-05",
+            "capabilities": {},
+            "clientInfo": {"name": "hangup-test", "version": "1.0"}
+        }}),
+        json!({"jsonrpc": "2.0", "method": "initialized"}),
+        json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {
+            "name": "memory_add",
+            "arguments": {"event_type": "fact", "content": "survives the hangup"}
+        }}),
+    ];
+    for message in &messages {
+        writeln!(stdin, "{message}").expect("write request");
+    }
+
+    // Wait for the add to be acknowledged, then drop both ends of the pipe.
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout"));
+    let mut line = String::new();
+    loop {
+        line.clear();
+        assert!(stdout.read_line(&mut line).expect("read response") > 0);
+        let response: Value = serde_json::from_str(&line).expect("json response");
+        if response["id"] == 2 {
+            assert!(response["result"].is_object(), "add failed: {response}");
+            break;
+        }
+    }
+    drop(stdin);
+    drop(stdout);
+
+    let status = child.wait().expect("server exit");
+    assert!(status.success(), "server exited with {status}");
+
+    let graph = AmemReader::read_from_file(&memory).expect("saved memory file");
+    assert!(graph
+        .nodes()
+        .iter()
+        .any(|n| n.content == "survives the hangup"));
+}
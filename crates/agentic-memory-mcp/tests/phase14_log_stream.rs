@@ -0,0 +1,65 @@
+//! Phase 14: tracing events mirrored to the client as notifications/message.
+
+mod common;
+
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+use tracing_subscriber::layer::SubscriberExt;
+
+use agentic_memory_mcp::protocol::{HandlerSettings, ProtocolHandler};
+use agentic_memory_mcp::streaming::McpLogLayer;
+use agentic_memory_mcp::types::JsonRpcNotification;
+
+use common::fixtures::create_test_session;
+use common::mock_client::MockClient;
+
+fn drain(rx: &mut mpsc::Receiver<JsonRpcNotification>) -> Vec<Value> {
+    let mut out = Vec::new();
+    while let Ok(notification) = rx.try_recv() {
+        assert_eq!(notification.method, "notifications/message");
+        out.push(notification.params.expect("params"));
+    }
+    out
+}
+
+#[tokio::test]
+async fn log_records_follow_client_level() {
+    let layer = McpLogLayer::new();
+    let handler = ProtocolHandler::new(create_test_session()).with_settings(HandlerSettings {
+        log_sinks: layer.sinks(),
+        ..Default::default()
+    });
+    let subscriber = tracing_subscriber::registry().with(layer);
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let (tx, mut rx) = mpsc::channel(64);
+    handler.attach_notifications(tx).await;
+    let mut client = MockClient::new(handler);
+
+    tracing::warn!("before set level");
+    assert!(drain(&mut rx).is_empty());
+
+    let response = client
+        .request("logging/setLevel", Some(json!({"level": "warning"})))
+        .await;
+    assert_eq!(response["result"], json!({}));
+
+    tracing::info!("too quiet");
+    tracing::warn!(node_id = 7, "save slow");
+    let records = drain(&mut rx);
+    assert_eq!(records.len(), 1, "records: {records:?}");
+    assert_eq!(records[0]["level"], "warning");
+    assert_eq!(records[0]["data"]["message"], "save slow");
+    assert_eq!(records[0]["data"]["node_id"], 7);
+    assert_eq!(records[0]["logger"], "phase14_log_stream");
+
+    let bad = client
+        .request("logging/setLevel", Some(json!({"level": "verbose"})))
+        .await;
+    assert_eq!(bad["error"]["code"], -32602);
+
+    // Dropping the handler ends its subscription.
+    drop(client);
+    tracing::error!("after disconnect");
+    assert!(drain(&mut rx).is_empty());
+}
@@ -0,0 +1,87 @@
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use serde_json::{json, Value};
+use tempfile::tempdir;
+
+#[test]
+fn progress_notifications_precede_the_tool_response() {
+    let dir = tempdir().expect("temp dir");
+    let memory = dir.path().join("progress.amem");
+    let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .canonicalize()
+        .expect("workspace root");
+
+    let mut child = Command::new("cargo")
+        .arg("run")
+        .arg("-p")
+        .arg("agentic-memory-mcp")
+        .arg("--quiet")
+        .arg("--")
+        .arg("serve")
+        .arg("--memory")
+        .arg(&memory)
+        .env("HOME", dir.path())
+        .current_dir(workspace_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn server");
+
+    let mut stdin = child.stdin.take().expect("stdin");
+    let mut messages = vec![
+        json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {
+            "protocolVersion": "2024-11-05",
+            "capabilities": {},
+            "clientInfo": {"name": "progress-test", "version": "1.0"}
+        }}),
+        json!({"jsonrpc": "2.0", "method": "initialized"}),
+    ];
+    for i in 0..3 {
+        messages.push(
+            json!({"jsonrpc": "2.0", "id": 10 + i, "method": "tools/call", "params": {
+                "name": "memory_add",
+                "arguments": {"event_type": "fact", "content": format!("fact {i}")}
+            }}),
+        );
+    }
+    messages.push(
+        json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {
+            "name": "memory_reembed",
+            "arguments": {"only_mismatched": false},
+            "_meta": {"progressToken": "reembed-1"}
+        }}),
+    );
+    for message in &messages {
+        writeln!(stdin, "{message}").expect("write request");
+    }
+
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout"));
+    let mut line = String::new();
+    let mut progress = Vec::new();
+    loop {
+        line.clear();
+        assert!(stdout.read_line(&mut line).expect("read output") > 0);
+        let message: Value = serde_json::from_str(&line).expect("json output");
+        if message["method"] == "notifications/progress" {
+            progress.push(message["params"].clone());
+        }
+        if message["id"] == 2 {
+            assert!(message["result"].is_object(), "reembed failed: {message}");
+            break;
+        }
+    }
+    drop(stdin);
+    drop(stdout);
+    child.wait().expect("server exit");
+
+    let last = progress.last().expect("progress before the response");
+    assert!(progress.iter().all(|p| p["progressToken"] == "reembed-1"));
+    // Auto-capture may have added nodes of its own next to the three facts.
+    assert!(last["total"].as_f64().expect("total") >= 3.0);
+    assert_eq!(last["progress"], last["total"]);
+}
@@ -97,6 +97,7 @@ fn test_server_capabilities() {
     assert!(caps.resources.is_some());
     assert!(caps.prompts.is_some());
     assert!(caps.logging.is_some());
+    assert!(caps.completions.is_some());
 }
 
 #[test]
@@ -4,7 +4,8 @@ mod common;
 
 use serde_json::json;
 
-use agentic_memory_mcp::protocol::ProtocolHandler;
+use agentic_memory_mcp::protocol::negotiation::NegotiatedCapabilities;
+use agentic_memory_mcp::protocol::{HandlerSettings, ProtocolHandler};
 use agentic_memory_mcp::types::*;
 
 use common::fixtures::create_test_session;
@@ -129,6 +130,54 @@ async fn test_invalid_jsonrpc_version() {
     assert_eq!(response["error"]["code"], error_codes::INVALID_REQUEST);
 }
 
+#[tokio::test]
+async fn test_initialize_unsupported_protocol_version() {
+    let params = |version: &str| -> InitializeParams {
+        serde_json::from_value(json!({
+            "protocolVersion": version,
+            "capabilities": {},
+            "clientInfo": { "name": "test-client", "version": "1.0" }
+        }))
+        .unwrap()
+    };
+
+    // Lenient (the default): warn and answer with the server's version.
+    let mut lenient = NegotiatedCapabilities::default().with_strict_protocol_version(false);
+    let result = lenient.negotiate(params("1999-01-01")).unwrap();
+    assert_eq!(result.protocol_version, MCP_VERSION);
+
+    let initialize = || {
+        make_request(
+            1,
+            "initialize",
+            Some(json!({
+                "protocolVersion": "1999-01-01",
+                "capabilities": {},
+                "clientInfo": { "name": "test-client", "version": "1.0" }
+            })),
+        )
+    };
+    let handler = ProtocolHandler::new(create_test_session());
+    let response = handler.handle_message(initialize()).await.unwrap();
+    assert_eq!(response["result"]["protocolVersion"], MCP_VERSION);
+
+    // Strict: unsupported versions fail, supported ones still succeed.
+    let mut strict = NegotiatedCapabilities::default().with_strict_protocol_version(true);
+    let err = strict.negotiate(params("1999-01-01")).unwrap_err();
+    assert!(matches!(err, McpError::InvalidParams(_)));
+    assert!(err.to_string().contains("1999-01-01"));
+    assert!(err.to_string().contains(MCP_VERSION));
+    assert!(strict.negotiate(params(MCP_VERSION)).is_ok());
+
+    // The handler passes its setting on to negotiation.
+    let handler = ProtocolHandler::new(create_test_session()).with_settings(HandlerSettings {
+        strict_protocol_version: true,
+        ..Default::default()
+    });
+    let response = handler.handle_message(initialize()).await.unwrap();
+    assert_eq!(response["error"]["code"], -32602);
+}
+
 #[tokio::test]
 async fn test_ping() {
     let session = create_test_session();
@@ -161,3 +210,185 @@ async fn test_shutdown() {
     assert!(response.get("result").is_some());
     assert!(handler.shutdown_requested());
 }
+
+#[tokio::test]
+async fn test_session_end_samples_summary_from_client() {
+    let session = create_test_session();
+    let handler = ProtocolHandler::new(session);
+    let (tx, mut client_requests) = tokio::sync::mpsc::channel(4);
+    handler.attach_client_requests(tx);
+
+    handler
+        .handle_message(make_request(
+            1,
+            "initialize",
+            Some(json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": { "sampling": {} },
+                "clientInfo": { "name": "test-client", "version": "1.0" }
+            })),
+        ))
+        .await
+        .unwrap();
+    handler
+        .handle_message(make_request(
+            2,
+            "tools/call",
+            Some(json!({
+                "name": "memory_add",
+                "arguments": { "event_type": "decision", "content": "Ship the sampling bridge" }
+            })),
+        ))
+        .await
+        .unwrap();
+
+    let end = handler.handle_message(make_request(
+        3,
+        "tools/call",
+        Some(json!({ "name": "session_end", "arguments": {} })),
+    ));
+    let client = async {
+        let request = client_requests.recv().await.unwrap();
+        assert_eq!(request.method, "sampling/createMessage");
+        let prompt = request.params.as_ref().unwrap()["messages"][0]["content"]["text"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert!(prompt.contains("Ship the sampling bridge"));
+        handler
+            .handle_message(JsonRpcMessage::Response(JsonRpcResponse::new(
+                request.id,
+                json!({
+                    "role": "assistant",
+                    "content": { "type": "text", "text": "Decided to ship the sampling bridge." },
+                    "model": "test-model"
+                }),
+            )))
+            .await
+    };
+    let (response, reply) = tokio::join!(end, client);
+    assert!(reply.is_none());
+
+    let text = response.unwrap()["result"]["content"][0]["text"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(parsed["summary_source"], "sampling");
+    assert_eq!(parsed["summary"], "Decided to ship the sampling bridge.");
+}
+
+#[tokio::test]
+async fn test_session_end_sampling_prompt_is_redacted() {
+    let handler = ProtocolHandler::new(create_test_session()).with_settings(HandlerSettings {
+        redactor: Redactor::new(&[r"sk-[a-z0-9]+".to_string()]).unwrap(),
+        ..Default::default()
+    });
+    let (tx, mut client_requests) = tokio::sync::mpsc::channel(4);
+    handler.attach_client_requests(tx);
+
+    handler
+        .handle_message(make_request(
+            1,
+            "initialize",
+            Some(json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": { "sampling": {} },
+                "clientInfo": { "name": "test-client", "version": "1.0" }
+            })),
+        ))
+        .await
+        .unwrap();
+    handler
+        .handle_message(make_request(
+            2,
+            "tools/call",
+            Some(json!({
+                "name": "memory_add",
+                "arguments": { "event_type": "fact", "content": "Deploy key is sk-abc123" }
+            })),
+        ))
+        .await
+        .unwrap();
+
+    let end = handler.handle_message(make_request(
+        3,
+        "tools/call",
+        Some(json!({ "name": "session_end", "arguments": {} })),
+    ));
+    let client = async {
+        let request = client_requests.recv().await.unwrap();
+        let prompt = request.params.as_ref().unwrap()["messages"][0]["content"]["text"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        handler
+            .handle_message(JsonRpcMessage::Response(JsonRpcResponse::new(
+                request.id,
+                json!({
+                    "role": "assistant",
+                    "content": { "type": "text", "text": "Recorded the deploy key." },
+                    "model": "test-model"
+                }),
+            )))
+            .await;
+        prompt
+    };
+    let (_, prompt) = tokio::join!(end, client);
+    assert!(!prompt.contains("sk-abc123"), "secret sent: {prompt}");
+    assert!(prompt.contains("Deploy key is [REDACTED]"));
+}
+
+#[tokio::test]
+async fn test_dropped_client_request_is_unregistered() {
+    use agentic_memory_mcp::protocol::ClientRequester;
+
+    let (tx, mut outbound) = tokio::sync::mpsc::channel(4);
+    let requester = ClientRequester::new(tx);
+
+    // The caller gives up (as a tool timeout or cancellation does) before
+    // the client answers.
+    let waited = tokio::time::timeout(
+        std::time::Duration::from_millis(20),
+        requester.request("ping", json!({}), std::time::Duration::from_secs(60)),
+    )
+    .await;
+    assert!(waited.is_err());
+    assert!(outbound.recv().await.is_some());
+    assert_eq!(requester.pending_count(), 0);
+}
+
+#[tokio::test]
+async fn test_tool_calls_notify_resource_list_changes() {
+    let handler = ProtocolHandler::new(create_test_session());
+    let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+    handler.attach_notifications(tx).await;
+    let mut list_changes = || {
+        let mut count = 0;
+        while let Ok(notification) = rx.try_recv() {
+            if notification.method == "notifications/resources/list_changed" {
+                count += 1;
+            }
+        }
+        count
+    };
+    let call = |id: i64, name: &str, arguments: serde_json::Value| {
+        make_request(
+            id,
+            "tools/call",
+            Some(json!({ "name": name, "arguments": arguments })),
+        )
+    };
+
+    // The first fact adds a session and a type.
+    let args = json!({ "event_type": "fact", "content": "Caches are per tenant" });
+    handler.handle_message(call(1, "memory_add", args)).await;
+    assert_eq!(list_changes(), 1);
+
+    // Neither a read nor another fact in the same session changes the list.
+    let args = json!({ "max_results": 5 });
+    handler.handle_message(call(2, "memory_query", args)).await;
+    let args = json!({ "event_type": "fact", "content": "Keys rotate weekly" });
+    handler.handle_message(call(3, "memory_add", args)).await;
+    assert_eq!(list_changes(), 0);
+}
@@ -6,6 +6,7 @@ use serde_json::json;
 
 use agentic_memory_mcp::resources::ResourceRegistry;
 use agentic_memory_mcp::tools::ToolRegistry;
+use agentic_memory_mcp::types::McpError;
 
 use common::fixtures::create_test_session;
 
@@ -43,6 +44,59 @@ async fn test_resource_stats() {
     assert_eq!(parsed["node_count"], 1);
 }
 
+#[tokio::test]
+async fn test_resource_graph_digest() {
+    use agentic_memory::{EdgeType, EventType};
+
+    let session = create_test_session();
+    let (early, late) = {
+        let mut sess = session.write().await;
+        let (early, _) = sess
+            .add_event(EventType::Fact, "deploys run at 09:00", 0.7, vec![])
+            .unwrap();
+        let (late, _) = sess
+            .add_event(
+                EventType::Fact,
+                "deploys run at 10:00",
+                0.8,
+                vec![(early, EdgeType::Contradicts, 1.0)],
+            )
+            .unwrap();
+        let current = sess.current_session_id();
+        sess.end_session_with_episode(current, "Compared deploy schedules")
+            .unwrap();
+        (early, late)
+    };
+
+    let result = ResourceRegistry::read("amem://graph/digest", &session)
+        .await
+        .unwrap();
+    let text = result.contents[0].text.as_ref().unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["node_count"], 3);
+    assert!(parsed["important_nodes"].as_array().unwrap().len() <= 5);
+    assert_eq!(
+        parsed["latest_episode"]["summary"],
+        "Compared deploy schedules"
+    );
+    assert_eq!(parsed["contradiction_count"], 1);
+    assert_eq!(parsed["contradictions"][0]["source_id"], late);
+    assert_eq!(parsed["contradictions"][0]["target_id"], early);
+
+    // Superseding one side resolves the contradiction.
+    session
+        .write()
+        .await
+        .correct_node(early, "deploys run at 10:00", 0.9)
+        .unwrap();
+    let result = ResourceRegistry::read("amem://graph/digest", &session)
+        .await
+        .unwrap();
+    let parsed: serde_json::Value =
+        serde_json::from_str(result.contents[0].text.as_ref().unwrap()).unwrap();
+    assert_eq!(parsed["contradiction_count"], 0);
+}
+
 #[tokio::test]
 async fn test_resource_node() {
     let session = create_test_session();
@@ -97,6 +151,39 @@ async fn test_resource_recent() {
     assert!(parsed["count"].as_u64().unwrap() >= 1);
 }
 
+#[tokio::test]
+async fn test_resource_tools_schema() {
+    let session = create_test_session();
+    let result = ResourceRegistry::read("amem://tools/schema", &session)
+        .await
+        .unwrap();
+
+    let text = result.contents[0].text.as_ref().unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    let schemas = parsed.as_object().unwrap();
+    assert_eq!(schemas.len(), ToolRegistry::list_tools().len());
+    assert_eq!(schemas["memory_resolve"]["required"], json!(["node_id"]));
+}
+
+#[tokio::test]
+async fn test_resource_schema_types() {
+    let session = create_test_session();
+    let result = ResourceRegistry::read("amem://schema/types", &session)
+        .await
+        .unwrap();
+
+    let text = result.contents[0].text.as_ref().unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    let event_types = parsed["event_types"].as_array().unwrap();
+    let edge_types = parsed["edge_types"].as_array().unwrap();
+    assert_eq!(event_types.len(), agentic_memory::EventType::all().len());
+    assert_eq!(edge_types.len(), agentic_memory::EdgeType::all().len());
+    assert_eq!(event_types[0]["name"], "fact");
+    assert!(edge_types
+        .iter()
+        .any(|t| t["name"] == "supersedes" && t["description"].as_str().is_some()));
+}
+
 #[tokio::test]
 async fn test_resource_not_found() {
     let session = create_test_session();
@@ -110,3 +197,201 @@ async fn test_resource_node_not_found() {
     let result = ResourceRegistry::read("amem://node/99999", &session).await;
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn test_resource_node_vector() {
+    let session = create_test_session();
+
+    let add_result = ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "Vector resource test"})),
+        &session,
+    )
+    .await
+    .unwrap();
+
+    let text = match &add_result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    let id = parsed["node_id"].as_u64().unwrap();
+
+    let result = ResourceRegistry::read(&format!("amem://node/{id}/vector"), &session)
+        .await
+        .unwrap();
+    let text = result.contents[0].text.as_ref().unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["id"], id);
+    assert_eq!(
+        parsed["vector"].as_array().unwrap().len() as u64,
+        parsed["dimension"].as_u64().unwrap()
+    );
+
+    // The plain node resource still omits the vector.
+    let result = ResourceRegistry::read(&format!("amem://node/{id}"), &session)
+        .await
+        .unwrap();
+    let text = result.contents[0].text.as_ref().unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert!(parsed.get("vector").is_none());
+}
+
+#[tokio::test]
+async fn test_resource_graph_export_and_pages() {
+    let session = create_test_session();
+
+    for content in ["Export A", "Export B", "Export C"] {
+        ToolRegistry::call(
+            "memory_add",
+            Some(json!({"event_type": "fact", "content": content})),
+            &session,
+        )
+        .await
+        .unwrap();
+    }
+
+    let result = ResourceRegistry::read("amem://graph/export", &session)
+        .await
+        .unwrap();
+    let text = result.contents[0].text.as_ref().unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["nodes"].as_array().unwrap().len(), 3);
+    assert!(parsed["edges"].is_array());
+    assert!(parsed["dimension"].as_u64().unwrap() > 0);
+
+    let result = ResourceRegistry::read("amem://graph/export/nodes?cursor=0&limit=2", &session)
+        .await
+        .unwrap();
+    let text = result.contents[0].text.as_ref().unwrap();
+    let page: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(page["nodes"].as_array().unwrap().len(), 2);
+    assert_eq!(page["next_cursor"], 2);
+
+    let result = ResourceRegistry::read("amem://graph/export/nodes?cursor=2&limit=2", &session)
+        .await
+        .unwrap();
+    let text = result.contents[0].text.as_ref().unwrap();
+    let page: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(page["nodes"].as_array().unwrap().len(), 1);
+    assert!(page["next_cursor"].is_null());
+
+    assert!(
+        ResourceRegistry::read("amem://graph/export/nodes?cursor=abc", &session)
+            .await
+            .is_err()
+    );
+    for uri in [
+        "amem://graph/export/nodesXYZ",
+        "amem://graph/export/edges/extra",
+    ] {
+        assert!(matches!(
+            ResourceRegistry::read(uri, &session).await,
+            Err(McpError::ResourceNotFound(_))
+        ));
+    }
+}
+
+#[tokio::test]
+async fn test_resource_listings_page() {
+    let session = create_test_session();
+
+    for i in 0..5 {
+        ToolRegistry::call(
+            "memory_add",
+            Some(json!({"event_type": "fact", "content": format!("Paged fact {i}")})),
+            &session,
+        )
+        .await
+        .unwrap();
+    }
+    let sid = session.read().await.current_session_id();
+
+    let read_page = |uri: String| {
+        let session = session.clone();
+        async move {
+            let result = ResourceRegistry::read(&uri, &session).await.unwrap();
+            let text = result.contents[0].text.clone().unwrap();
+            serde_json::from_str::<serde_json::Value>(&text).unwrap()
+        }
+    };
+
+    let page = read_page("amem://types/fact?limit=2".to_string()).await;
+    assert_eq!(page["nodes"].as_array().unwrap().len(), 2);
+    assert_eq!(page["total"], 5);
+    assert_eq!(page["next_cursor"], 2);
+
+    let page = read_page("amem://types/fact?cursor=4&limit=2".to_string()).await;
+    assert_eq!(page["nodes"].as_array().unwrap().len(), 1);
+    assert!(page["next_cursor"].is_null());
+
+    let page = read_page(format!("amem://session/{sid}?cursor=0&limit=3")).await;
+    assert_eq!(page["nodes"].as_array().unwrap().len(), 3);
+    assert_eq!(page["next_cursor"], 3);
+
+    let page = read_page("amem://graph/recent?limit=2".to_string()).await;
+    assert_eq!(page["count"], 2);
+    assert_eq!(page["next_cursor"], 2);
+    let page = read_page("amem://graph/recent?cursor=2&limit=10".to_string()).await;
+    assert_eq!(page["count"], 3);
+    assert!(page["next_cursor"].is_null());
+
+    assert!(
+        ResourceRegistry::read("amem://types/fact?limit=x", &session)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_output_redaction_masks_tools_and_resources() {
+    use agentic_memory_mcp::protocol::{HandlerSettings, ProtocolHandler};
+    use agentic_memory_mcp::types::Redactor;
+    use common::mock_client::MockClient;
+
+    let session = create_test_session();
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "Customer SSN is 321-54-9876"})),
+        &session,
+    )
+    .await
+    .unwrap();
+
+    assert!(Redactor::new(&["(unclosed".to_string()]).is_err());
+    let redactor = Redactor::new(&[r"\b\d{3}-\d{2}-\d{4}\b".to_string()]).unwrap();
+    let mut client = MockClient::new(ProtocolHandler::new(session.clone()).with_settings(
+        HandlerSettings {
+            redactor,
+            ..Default::default()
+        },
+    ));
+    client.initialize().await;
+
+    let result = client.call_tool("memory_query", Some(json!({}))).await;
+    let text = result["result"]["content"][0]["text"].as_str().unwrap();
+    let resource = client.read_resource("amem://node/0").await;
+    let resource_text = resource["result"]["contents"][0]["text"].as_str().unwrap();
+
+    assert!(!text.contains("321-54-9876"));
+    assert!(text.contains("Customer SSN is [REDACTED]"));
+    assert!(!resource_text.contains("321-54-9876"));
+    assert!(resource_text.contains("[REDACTED]"));
+    serde_json::from_str::<serde_json::Value>(resource_text).unwrap();
+
+    // A handler without patterns sends content as stored.
+    let mut plain = MockClient::new(ProtocolHandler::new(session.clone()));
+    plain.initialize().await;
+    let resource = plain.read_resource("amem://node/0").await;
+    assert!(resource["result"]["contents"][0]["text"]
+        .as_str()
+        .unwrap()
+        .contains("321-54-9876"));
+
+    // The stored graph is untouched.
+    let s = session.read().await;
+    assert_eq!(
+        s.graph().get_node(0).unwrap().content,
+        "Customer SSN is 321-54-9876"
+    );
+}
@@ -6,7 +6,7 @@ use serde_json::json;
 
 use agentic_memory_mcp::tools::ToolRegistry;
 
-use common::fixtures::create_test_session;
+use common::fixtures::{call_tool_json, create_test_session};
 
 #[tokio::test]
 async fn test_memory_add_fact() {
@@ -31,6 +31,14 @@ async fn test_memory_add_fact() {
     let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
     assert!(parsed["node_id"].as_u64().is_some());
     assert_eq!(parsed["event_type"], "fact");
+
+    let node = &parsed["node"];
+    assert_eq!(node["id"], parsed["node_id"]);
+    assert_eq!(node["content"], "User prefers Rust");
+    assert!((node["confidence"].as_f64().unwrap() - 0.95).abs() < 1e-6);
+    assert!(node["created_at"].as_u64().unwrap() > 0);
+    assert!(node["decay_score"].is_number());
+    assert!(node["session_id"].is_number());
 }
 
 #[tokio::test]
@@ -93,6 +101,60 @@ async fn test_memory_query_empty() {
     assert_eq!(parsed["count"], 0);
 }
 
+#[tokio::test]
+async fn test_memory_query_cursor_pages_without_gaps() {
+    let session = create_test_session();
+    {
+        let mut s = session.write().await;
+        // Groups of five share a timestamp, so pages must break ties by id.
+        for i in 0..23u64 {
+            s.add_event_at(
+                agentic_memory::EventType::Fact,
+                &format!("fact {i}"),
+                0.9,
+                vec![],
+                Vec::new(),
+                Some(1_700_000_000_000_000 + i / 5),
+            )
+            .unwrap();
+        }
+    }
+
+    let query = |args| call_tool_json("memory_query", args, &session);
+    let ids = |page: &serde_json::Value| -> Vec<u64> {
+        page["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["id"].as_u64().unwrap())
+            .collect()
+    };
+
+    let all = ids(&query(json!({"max_results": 100})).await);
+    assert_eq!(all.len(), 23);
+
+    let mut paged = Vec::new();
+    let mut cursor = serde_json::Value::Null;
+    loop {
+        let page = query(json!({"max_results": 7, "cursor": cursor})).await;
+        paged.extend(ids(&page));
+        cursor = page["next_cursor"].clone();
+        if cursor.is_null() {
+            break;
+        }
+    }
+    assert_eq!(paged, all);
+
+    let first = query(json!({"max_results": 7})).await;
+    let mismatched = ToolRegistry::call(
+        "memory_query",
+        Some(json!({"sort_by": "highest_confidence", "cursor": first["next_cursor"]})),
+        &session,
+    )
+    .await;
+    assert!(mismatched.is_err());
+}
+
 #[tokio::test]
 async fn test_memory_query_with_results() {
     let session = create_test_session();
@@ -143,6 +205,45 @@ async fn test_memory_query_with_results() {
     assert_eq!(parsed["count"], 1);
 }
 
+#[tokio::test]
+async fn test_memory_query_decay_score_range() {
+    let session = create_test_session();
+    for (i, decay_score) in [0.1f32, 0.5, 0.9].into_iter().enumerate() {
+        ToolRegistry::call(
+            "memory_add",
+            Some(json!({"event_type": "fact", "content": format!("Fact {i}")})),
+            &session,
+        )
+        .await
+        .unwrap();
+        let mut s = session.write().await;
+        s.graph_mut().get_node_mut(i as u64).unwrap().decay_score = decay_score;
+    }
+
+    let query = |args: serde_json::Value| {
+        let session = session.clone();
+        async move {
+            let parsed = call_tool_json("memory_query", args, &session).await;
+            parsed["nodes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|n| n["id"].as_u64().unwrap())
+                .collect::<Vec<_>>()
+        }
+    };
+
+    assert_eq!(query(json!({"max_decay_score": 0.2})).await, vec![0]);
+    assert_eq!(
+        query(json!({"min_decay_score": 0.5, "sort_by": "most_important"})).await,
+        vec![2, 1]
+    );
+    assert_eq!(
+        query(json!({"min_decay_score": 0.3, "max_decay_score": 0.6})).await,
+        vec![1]
+    );
+}
+
 #[tokio::test]
 async fn test_memory_correct() {
     let session = create_test_session();
@@ -186,6 +287,104 @@ async fn test_memory_correct() {
     assert_eq!(parsed["supersedes"], true);
 }
 
+#[tokio::test]
+async fn test_dry_run_correct_and_retype_change_nothing() {
+    let session = create_test_session();
+    let old_id = {
+        let mut s = session.write().await;
+        let (id, _) = s
+            .add_event(
+                agentic_memory::EventType::Inference,
+                "Cache is cold",
+                0.8,
+                vec![],
+            )
+            .unwrap();
+        s.save().unwrap();
+        id
+    };
+
+    let call = |name: &'static str, args: serde_json::Value| {
+        let session = session.clone();
+        async move {
+            let result = ToolRegistry::call(name, Some(args), &session)
+                .await
+                .unwrap();
+            match &result.content[0] {
+                agentic_memory_mcp::types::ToolContent::Text { text } => {
+                    serde_json::from_str::<serde_json::Value>(text).unwrap()
+                }
+                _ => panic!("Expected text"),
+            }
+        }
+    };
+
+    let preview = call(
+        "memory_correct",
+        json!({"old_node_id": old_id, "new_content": "Cache is warm", "dry_run": true}),
+    )
+    .await;
+    assert_eq!(preview["dry_run"], true);
+    assert_eq!(preview["old_node_id"], old_id);
+    assert!(preview["new_node_id"].is_null());
+    assert_eq!(preview["old_confidence"]["after"], 0.0);
+
+    let preview = call(
+        "memory_retype",
+        json!({"node_ids": [old_id], "event_type": "decision", "dry_run": true}),
+    )
+    .await;
+    assert_eq!(preview["dry_run"], true);
+    assert_eq!(preview["node_ids"], json!([old_id]));
+
+    let s = session.read().await;
+    assert_eq!(s.graph().node_count(), 1);
+    let node = s.graph().get_node(old_id).unwrap();
+    assert_eq!(node.event_type, agentic_memory::EventType::Inference);
+    assert!((node.confidence - 0.8).abs() < 1e-6);
+    assert!(!s.is_dirty());
+    assert_eq!(s.undo_available(), 1);
+}
+
+#[tokio::test]
+async fn test_memory_correct_by_content_query() {
+    let session = create_test_session();
+    for content in ["User prefers tabs", "User writes Rust daily"] {
+        ToolRegistry::call(
+            "memory_add",
+            Some(json!({"event_type": "fact", "content": content})),
+            &session,
+        )
+        .await
+        .unwrap();
+    }
+
+    let result = ToolRegistry::call(
+        "memory_correct",
+        Some(json!({"old_content_query": "prefers TABS", "new_content": "User prefers spaces"})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["old_node_id"], 0);
+    assert_eq!(parsed["matched_by"], "substring");
+
+    // "User" now matches two live nodes; the superseded one is not counted.
+    let err = ToolRegistry::call(
+        "memory_correct",
+        Some(json!({"old_content_query": "User", "new_content": "ambiguous"})),
+        &session,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("matches 2 live memories"));
+}
+
 #[tokio::test]
 async fn test_memory_resolve() {
     let session = create_test_session();
@@ -254,6 +453,527 @@ async fn test_memory_stats() {
     assert!(parsed["type_counts"]["fact"].as_u64().unwrap() >= 1);
 }
 
+#[tokio::test]
+async fn test_memory_stats_reports_dirty_and_last_saved() {
+    let session = create_test_session();
+
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "Unflushed fact"})),
+        &session,
+    )
+    .await
+    .unwrap();
+
+    let stats = |result: agentic_memory_mcp::types::ToolCallResult| {
+        let text = match &result.content[0] {
+            agentic_memory_mcp::types::ToolContent::Text { text } => text.clone(),
+            _ => panic!("Expected text"),
+        };
+        serde_json::from_str::<serde_json::Value>(&text).unwrap()
+    };
+
+    let before = stats(
+        ToolRegistry::call("memory_stats", Some(json!({})), &session)
+            .await
+            .unwrap(),
+    );
+    assert_eq!(before["dirty"], true);
+
+    session.write().await.save().unwrap();
+
+    let after = stats(
+        ToolRegistry::call("memory_stats", Some(json!({})), &session)
+            .await
+            .unwrap(),
+    );
+    assert_eq!(after["dirty"], false);
+    assert!(after["last_saved_unix"].as_u64().unwrap() > 0);
+}
+
+#[tokio::test]
+async fn test_memory_health_reports_readiness() {
+    let session = create_test_session();
+
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "Health fact"})),
+        &session,
+    )
+    .await
+    .unwrap();
+
+    let result = ToolRegistry::call("memory_health", Some(json!({})), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["status"], "ok");
+    assert_eq!(parsed["can_write"], true);
+    assert!(parsed["file_exists"].is_boolean());
+    assert_eq!(parsed["node_count"], 1);
+    assert!(parsed["last_error"].is_null());
+    assert!(parsed["last_error_unix"].is_null());
+    assert_eq!(parsed["consecutive_save_failures"], 0);
+}
+
+#[tokio::test]
+async fn test_memory_save_writes_pending_changes() {
+    let session = create_test_session();
+
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "Save me"})),
+        &session,
+    )
+    .await
+    .unwrap();
+
+    let save = || async {
+        let result = ToolRegistry::call("memory_save", Some(json!({})), &session)
+            .await
+            .unwrap();
+        let text = match &result.content[0] {
+            agentic_memory_mcp::types::ToolContent::Text { text } => text.clone(),
+            _ => panic!("Expected text"),
+        };
+        serde_json::from_str::<serde_json::Value>(&text).unwrap()
+    };
+
+    let first = save().await;
+    assert_eq!(first["success"], true);
+    assert_eq!(first["saved"], true);
+    assert!(first["error"].is_null());
+    assert_eq!(first["dirty"], false);
+    assert_eq!(first["consecutive_save_failures"], 0);
+    assert!(first["last_saved_unix"].as_u64().unwrap() > 0);
+
+    let second = save().await;
+    assert_eq!(second["success"], true);
+    assert_eq!(second["saved"], false);
+}
+
+#[tokio::test]
+async fn test_memory_undo_reverts_recent_mutations() {
+    let session = create_test_session();
+    let (a, b) = {
+        let mut s = session.write().await;
+        let (a, _) = s
+            .add_event(
+                agentic_memory::EventType::Fact,
+                "Uses Postgres",
+                0.9,
+                vec![],
+            )
+            .unwrap();
+        let (b, _) = s
+            .add_event(
+                agentic_memory::EventType::Decision,
+                "Keep Postgres",
+                0.8,
+                vec![(a, agentic_memory::EdgeType::CausedBy, 1.0)],
+            )
+            .unwrap();
+        (a, b)
+    };
+    ToolRegistry::call(
+        "memory_correct",
+        Some(json!({"old_node_id": a, "new_content": "Uses MySQL"})),
+        &session,
+    )
+    .await
+    .unwrap();
+
+    let result = ToolRegistry::call("memory_undo", Some(json!({"steps": 1})), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["undone_count"], 1);
+    assert_eq!(parsed["undone"][0]["op"], "correct");
+    assert_eq!(parsed["remaining"], 2);
+    {
+        let s = session.read().await;
+        assert_eq!(s.graph().node_count(), 2);
+        assert!((s.graph().get_node(a).unwrap().confidence - 0.9).abs() < 1e-6);
+    }
+
+    // Deleting and undoing restores the node under its ID, with its edge.
+    {
+        let mut s = session.write().await;
+        s.delete_node(a).unwrap();
+        assert!(s.graph().get_node(a).is_none());
+    }
+    ToolRegistry::call("memory_undo", Some(json!({})), &session)
+        .await
+        .unwrap();
+    {
+        let s = session.read().await;
+        assert_eq!(s.graph().get_node(a).unwrap().content, "Uses Postgres");
+        assert!(s.graph().edges_from(b).iter().any(|e| e.target_id == a));
+    }
+
+    // A memory_add is undone in one step, temporal link included.
+    let mut chained = Vec::new();
+    for content in ["First chained fact", "Second chained fact"] {
+        let result = ToolRegistry::call(
+            "memory_add",
+            Some(json!({"event_type": "fact", "content": content})),
+            &session,
+        )
+        .await
+        .unwrap();
+        let text = match &result.content[0] {
+            agentic_memory_mcp::types::ToolContent::Text { text } => text,
+            _ => panic!("Expected text"),
+        };
+        let added: serde_json::Value = serde_json::from_str(text).unwrap();
+        chained.push(added["node_id"].as_u64().unwrap());
+    }
+    assert!(!session
+        .read()
+        .await
+        .graph()
+        .edges_from(chained[0])
+        .is_empty());
+    ToolRegistry::call("memory_undo", Some(json!({})), &session)
+        .await
+        .unwrap();
+    {
+        let s = session.read().await;
+        assert!(s.graph().get_node(chained[1]).is_none());
+        assert!(s.graph().get_node(chained[0]).is_some());
+    }
+
+    // More steps than recorded undoes what is left.
+    let result = ToolRegistry::call("memory_undo", Some(json!({"steps": 5})), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["undone_count"], 3);
+    assert_eq!(parsed["remaining"], 0);
+    let s = session.read().await;
+    assert_eq!(s.graph().node_count(), 0);
+    assert!(s.is_dirty());
+}
+
+#[tokio::test]
+async fn test_memory_add_created_at_orders_queries() {
+    let session = create_test_session();
+    {
+        let mut s = session.write().await;
+        s.set_clock(|| 5_000_000);
+    }
+
+    // Imported out of order: the older memory arrives second.
+    for (content, created_at) in [("Newer import", 2_000_000u64), ("Older import", 1_000_000)] {
+        ToolRegistry::call(
+            "memory_add",
+            Some(json!({"event_type": "fact", "content": content, "created_at": created_at})),
+            &session,
+        )
+        .await
+        .unwrap();
+    }
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "Clock stamped"})),
+        &session,
+    )
+    .await
+    .unwrap();
+
+    let result = ToolRegistry::call(
+        "memory_query",
+        Some(json!({"sort_by": "most_recent"})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    let contents: Vec<&str> = parsed["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n["content"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        contents,
+        vec!["Clock stamped", "Newer import", "Older import"]
+    );
+
+    let s = session.read().await;
+    assert_eq!(s.graph().get_node(1).unwrap().created_at, 1_000_000);
+    assert_eq!(s.graph().get_node(2).unwrap().created_at, 5_000_000);
+}
+
+#[tokio::test]
+async fn test_memory_edges_filters_by_weight() {
+    let session = create_test_session();
+    let (a, b, c) = {
+        let mut s = session.write().await;
+        let (a, _) = s
+            .add_event(agentic_memory::EventType::Fact, "A", 0.9, vec![])
+            .unwrap();
+        let (b, _) = s
+            .add_event(
+                agentic_memory::EventType::Fact,
+                "B",
+                0.9,
+                vec![(a, agentic_memory::EdgeType::Supports, 0.8)],
+            )
+            .unwrap();
+        let (c, _) = s
+            .add_event(
+                agentic_memory::EventType::Fact,
+                "C",
+                0.9,
+                vec![
+                    (a, agentic_memory::EdgeType::Supports, 0.3),
+                    (b, agentic_memory::EdgeType::RelatedTo, 0.95),
+                ],
+            )
+            .unwrap();
+        (a, b, c)
+    };
+
+    let result = ToolRegistry::call(
+        "memory_edges",
+        Some(json!({"edge_types": ["supports"], "min_weight": 0.5})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["total"], 1);
+    assert_eq!(parsed["edges"][0]["source_id"], b);
+    assert_eq!(parsed["edges"][0]["target_id"], a);
+    assert_eq!(parsed["edges"][0]["weight"], json!(0.8));
+
+    let result = ToolRegistry::call("memory_edges", Some(json!({"node_id": c})), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    let weights: Vec<f64> = parsed["edges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["weight"].as_f64().unwrap())
+        .collect();
+    assert_eq!(weights, vec![0.95, 0.3]);
+
+    let inverted = ToolRegistry::call(
+        "memory_edges",
+        Some(json!({"min_weight": 0.9, "max_weight": 0.1})),
+        &session,
+    )
+    .await;
+    assert!(inverted.is_err());
+}
+
+#[tokio::test]
+async fn test_compact_output_switch() {
+    use agentic_memory_mcp::types::{OutputFormat, ToolCallResult, ToolContent};
+
+    let value = json!({"a": 1, "b": [1, 2]});
+    let text_of = |r: ToolCallResult| match &r.content[0] {
+        ToolContent::Text { text } => text.clone(),
+        _ => panic!("Expected text"),
+    };
+
+    let compact = text_of(ToolCallResult::json(&value).render(OutputFormat {
+        compact: true,
+        ..Default::default()
+    }));
+    let pretty = text_of(ToolCallResult::json(&value).render(OutputFormat::default()));
+
+    assert_eq!(compact, r#"{"a":1,"b":[1,2]}"#);
+    assert!(pretty.contains('\n'));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&pretty).unwrap(),
+        value
+    );
+}
+
+#[tokio::test]
+async fn test_structured_output_switch() {
+    use agentic_memory_mcp::types::OutputFormat;
+
+    let session = create_test_session();
+    let args = json!({"event_type": "fact", "content": "structured"});
+
+    let result = ToolRegistry::call("memory_add", Some(args), &session)
+        .await
+        .unwrap();
+    let structured = result.clone().render(OutputFormat {
+        structured: true,
+        ..Default::default()
+    });
+    let plain = result.render(OutputFormat::default());
+
+    let wire = serde_json::to_value(&structured).unwrap();
+    assert!(wire["structuredContent"]["node_id"].is_u64());
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(wire["content"][0]["text"].as_str().unwrap())
+            .unwrap(),
+        wire["structuredContent"]
+    );
+    let wire = serde_json::to_value(&plain).unwrap();
+    assert!(wire.get("structuredContent").is_none());
+}
+
+#[tokio::test]
+async fn test_memory_add_configured_defaults() {
+    use agentic_memory::EventType;
+    use agentic_memory_mcp::session::ToolDefaults;
+    use agentic_memory_mcp::tools::memory_add;
+    use agentic_memory_mcp::types::{McpError, ToolContent};
+
+    let session = create_test_session();
+    let minimal = json!({"content": "remember this string"});
+
+    let err = ToolRegistry::call("memory_add", Some(minimal.clone()), &session).await;
+    assert!(matches!(err, Err(McpError::InvalidParams(_))));
+
+    let defaults = ToolDefaults {
+        event_type: Some(EventType::Inference),
+        confidence: 0.6,
+        ..Default::default()
+    };
+    session.write().await.set_tool_defaults(defaults.clone());
+    let schema = memory_add::definition_with(&defaults).input_schema;
+    let result = ToolRegistry::call("memory_add", Some(minimal), &session).await;
+
+    assert_eq!(schema["required"], json!(["content"]));
+    assert_eq!(schema["properties"]["confidence"]["default"], json!(0.6f32));
+    let text = match &result.unwrap().content[0] {
+        ToolContent::Text { text } => text.clone(),
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(parsed["event_type"], "inference");
+    assert!((parsed["confidence"].as_f64().unwrap() - 0.6).abs() < 1e-6);
+    assert_eq!(
+        memory_add::definition().input_schema["required"],
+        json!(["event_type", "content"])
+    );
+}
+
+#[tokio::test]
+async fn test_memory_traverse_configured_default_edge_types() {
+    use agentic_memory::{EdgeType, EventType};
+    use agentic_memory_mcp::session::ToolDefaults;
+
+    let session = create_test_session();
+    let (start, related, cause) = {
+        let mut s = session.write().await;
+        let (related, _) = s
+            .add_event(EventType::Fact, "related", 0.9, vec![])
+            .unwrap();
+        let (cause, _) = s.add_event(EventType::Fact, "cause", 0.9, vec![]).unwrap();
+        let edges = vec![
+            (related, EdgeType::RelatedTo, 1.0),
+            (cause, EdgeType::CausedBy, 1.0),
+        ];
+        let (start, _) = s
+            .add_event(EventType::Decision, "start", 0.9, edges)
+            .unwrap();
+        (start, related, cause)
+    };
+
+    let visited = |args: serde_json::Value| {
+        let session = session.clone();
+        async move {
+            let parsed = call_tool_json("memory_traverse", args, &session).await;
+            let mut ids: Vec<u64> = parsed["visited"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|n| n["id"].as_u64().unwrap())
+                .collect();
+            ids.sort_unstable();
+            ids
+        }
+    };
+    let mut all = vec![start, related, cause];
+    all.sort_unstable();
+
+    assert_eq!(visited(json!({"start_id": start})).await, all);
+
+    session.write().await.set_tool_defaults(ToolDefaults {
+        traversal_edge_types: vec![EdgeType::CausedBy],
+        ..Default::default()
+    });
+    let defaulted = visited(json!({"start_id": start})).await;
+    let explicit = visited(json!({"start_id": start, "edge_types": ["related_to"]})).await;
+    session
+        .write()
+        .await
+        .set_tool_defaults(ToolDefaults::default());
+
+    let mut reasoning = vec![start, cause];
+    reasoning.sort_unstable();
+    assert_eq!(defaulted, reasoning);
+    let mut related_only = vec![start, related];
+    related_only.sort_unstable();
+    assert_eq!(explicit, related_only);
+    assert_eq!(visited(json!({"start_id": start})).await, all);
+}
+
+#[tokio::test]
+async fn test_memory_stats_embedding_coverage() {
+    let session = create_test_session();
+    for content in ["no embedding", "has embedding"] {
+        ToolRegistry::call(
+            "memory_add",
+            Some(json!({"event_type": "fact", "content": content})),
+            &session,
+        )
+        .await
+        .unwrap();
+    }
+    {
+        let mut s = session.write().await;
+        let node = s.graph_mut().get_node_mut(1).unwrap();
+        node.feature_vec[0] = 1.0;
+    }
+
+    let result = ToolRegistry::call("memory_stats", Some(json!({})), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    let coverage = &parsed["embedding_coverage"];
+    assert_eq!(coverage["nodes_with_vectors"], 1);
+    assert_eq!(coverage["nodes_without_vectors"], 1);
+    assert_eq!(coverage["coverage"], 0.5);
+}
+
 #[tokio::test]
 async fn test_session_lifecycle() {
     let session = create_test_session();
@@ -342,3 +1062,1034 @@ async fn test_memory_context() {
     assert_eq!(parsed["center_id"], id);
     assert!(parsed["node_count"].as_u64().unwrap() >= 1);
 }
+
+#[tokio::test]
+async fn test_memory_ego() {
+    let session = create_test_session();
+    // A chain a <- b <- c <- d of caused_by edges.
+    let ids = {
+        let mut s = session.write().await;
+        let mut ids: Vec<u64> = Vec::new();
+        for content in ["a", "b", "c", "d"] {
+            let edges = ids
+                .last()
+                .map(|&prev| vec![(prev, agentic_memory::EdgeType::CausedBy, 1.0)])
+                .unwrap_or_default();
+            let (id, _) = s
+                .add_event(agentic_memory::EventType::Fact, content, 0.9, edges)
+                .unwrap();
+            ids.push(id);
+        }
+        ids
+    };
+
+    let near = call_tool_json("memory_ego", json!({"center_id": ids[1]}), &session).await;
+    assert_eq!(near["node_count"], 3);
+    assert_eq!(near["edge_count"], 2);
+    assert_eq!(near["nodes"][0]["id"], ids[1]);
+    assert_eq!(near["nodes"][0]["hop"], 0);
+
+    let wide = call_tool_json(
+        "memory_ego",
+        json!({"center_id": ids[1], "radius": 2}),
+        &session,
+    )
+    .await;
+    assert_eq!(wide["node_count"], 4);
+    assert_eq!(wide["edge_count"], 3);
+    let nodes = wide["nodes"].as_array().unwrap();
+    let hop_of = |id: u64| nodes.iter().find(|n| n["id"] == id).unwrap()["hop"].clone();
+    assert_eq!(hop_of(ids[3]), 2);
+    // Edges reference nodes by position in the node list.
+    for edge in wide["edges"].as_array().unwrap() {
+        let source = nodes[edge["source"].as_u64().unwrap() as usize]["id"]
+            .as_u64()
+            .unwrap();
+        let target = nodes[edge["target"].as_u64().unwrap() as usize]["id"]
+            .as_u64()
+            .unwrap();
+        assert!(ids.windows(2).any(|w| (w[1], w[0]) == (source, target)));
+        assert_eq!(edge["edge_type"], "caused_by");
+    }
+
+    for args in [
+        json!({"center_id": 9999}),
+        json!({"center_id": ids[0], "radius": 6}),
+    ] {
+        assert!(ToolRegistry::call("memory_ego", Some(args), &session)
+            .await
+            .is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_memory_query_scoped_to_current_session() {
+    let session = create_test_session();
+
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "Earlier session fact"})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let earlier = session.read().await.current_session_id();
+
+    let current = {
+        let mut s = session.write().await;
+        let sid = s.start_session(None).unwrap();
+        s.set_scope_to_current_session(true);
+        sid
+    };
+
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "Current session fact"})),
+        &session,
+    )
+    .await
+    .unwrap();
+
+    // Implicitly scoped to the current session.
+    let result = ToolRegistry::call("memory_query", Some(json!({})), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["count"], 1);
+    assert_eq!(parsed["nodes"][0]["session_id"], current);
+
+    // Explicit session_ids broaden the filter.
+    let result = ToolRegistry::call(
+        "memory_query",
+        Some(json!({"session_ids": [earlier, current]})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["count"], 2);
+
+    // memory_stats reports that scoping is active.
+    let result = ToolRegistry::call("memory_stats", Some(json!({})), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["session_scope"]["scope_to_current_session"], true);
+    assert_eq!(parsed["session_scope"]["session_ids"], json!([current]));
+}
+
+#[tokio::test]
+async fn test_memory_add_dedupe_reuses_existing_node() {
+    let session = create_test_session();
+
+    let args = json!({
+        "event_type": "fact",
+        "content": "User prefers Rust",
+        "dedupe": true
+    });
+
+    let result = ToolRegistry::call("memory_add", Some(args.clone()), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let first: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(first["deduped"], false);
+
+    let result = ToolRegistry::call("memory_add", Some(args), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let second: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(second["deduped"], true);
+    assert_eq!(second["node_id"], first["node_id"]);
+
+    let s = session.read().await;
+    let id = first["node_id"].as_u64().unwrap();
+    assert_eq!(s.graph().node_count(), 1);
+    assert_eq!(s.graph().get_node(id).unwrap().access_count, 1);
+}
+
+#[tokio::test]
+async fn test_memory_add_dedupe_applies_edges_and_blob() {
+    let session = create_test_session();
+    let call = |args| call_tool_json("memory_add", args, &session);
+
+    let target = call(json!({"event_type": "fact", "content": "Rust is fast"})).await;
+    let target = target["node_id"].as_u64().unwrap();
+    let first = call(json!({"event_type": "fact", "content": "User prefers Rust"})).await;
+    let id = first["node_id"].as_u64().unwrap();
+
+    let args = json!({
+        "event_type": "fact",
+        "content": "User prefers Rust",
+        "dedupe": true,
+        "edges": [{"target_id": target, "edge_type": "supports"}],
+        "blob": "AAEC"
+    });
+    let second = call(args.clone()).await;
+    assert_eq!(second["deduped"], true);
+    assert_eq!(second["node_id"], id);
+    assert_eq!(second["edges_created"], 1);
+    assert_eq!(second["blob_bytes"], 3);
+
+    // Repeating the call finds the edge already there.
+    let third = call(args).await;
+    assert_eq!(third["edges_created"], 0);
+
+    let s = session.read().await;
+    assert_eq!(s.graph().node_count(), 2);
+    assert!(s
+        .graph()
+        .edges_from(id)
+        .iter()
+        .any(|e| e.target_id == target && e.edge_type == agentic_memory::EdgeType::Supports));
+    assert_eq!(s.node_blob(id).unwrap().size, 3);
+}
+
+#[tokio::test]
+async fn test_linking_the_same_pair_twice_creates_one_edge() {
+    use agentic_memory::EdgeType;
+
+    let session = create_test_session();
+    let target = session
+        .write()
+        .await
+        .add_event(agentic_memory::EventType::Fact, "Rust is fast", 0.9, vec![])
+        .unwrap()
+        .0;
+
+    let result = ToolRegistry::call(
+        "memory_add",
+        Some(json!({
+            "event_type": "fact",
+            "content": "User prefers Rust",
+            "edges": [
+                {"target_id": target, "edge_type": "supports"},
+                {"target_id": target, "edge_type": "supports", "weight": 0.5}
+            ]
+        })),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    let id = parsed["node_id"].as_u64().unwrap();
+
+    // Linking the pair again is a no-op rather than an error.
+    let mut s = session.write().await;
+    assert_eq!(
+        s.add_edges(id, &[(target, EdgeType::Supports, 1.0)])
+            .unwrap(),
+        0
+    );
+    let supports = s
+        .graph()
+        .edges_from(id)
+        .iter()
+        .filter(|e| e.target_id == target && e.edge_type == EdgeType::Supports)
+        .count();
+    assert_eq!(supports, 1);
+}
+
+#[tokio::test]
+async fn test_memory_add_without_dedupe_keeps_repeats() {
+    let session = create_test_session();
+
+    for _ in 0..2 {
+        ToolRegistry::call(
+            "memory_add",
+            Some(json!({"event_type": "correction", "content": "Same text"})),
+            &session,
+        )
+        .await
+        .unwrap();
+    }
+
+    assert_eq!(session.read().await.graph().node_count(), 2);
+}
+
+#[tokio::test]
+async fn test_memory_retype_keeps_type_counts_consistent() {
+    let session = create_test_session();
+
+    for content in ["Use Postgres", "Cache sessions in Redis"] {
+        ToolRegistry::call(
+            "memory_add",
+            Some(json!({"event_type": "inference", "content": content})),
+            &session,
+        )
+        .await
+        .unwrap();
+    }
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "Team knows SQL"})),
+        &session,
+    )
+    .await
+    .unwrap();
+
+    let result = ToolRegistry::call(
+        "memory_retype",
+        Some(json!({"from_event_type": "inference", "event_type": "decision"})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["retyped_count"], 2);
+
+    let result = ToolRegistry::call("memory_stats", Some(json!({})), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["type_counts"]["inference"], 0);
+    assert_eq!(parsed["type_counts"]["decision"], 2);
+    assert_eq!(parsed["type_counts"]["fact"], 1);
+}
+
+#[tokio::test]
+async fn test_memory_add_idempotency_key_replays_original() {
+    let session = create_test_session();
+
+    let args = json!({
+        "event_type": "decision",
+        "content": "Ship on Friday",
+        "idempotency_key": "req-42"
+    });
+
+    let result = ToolRegistry::call("memory_add", Some(args.clone()), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let first: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert!(first.get("idempotent_replay").is_none());
+
+    let result = ToolRegistry::call("memory_add", Some(args), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let retry: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(retry["idempotent_replay"], true);
+    assert_eq!(retry["node_id"], first["node_id"]);
+    assert_eq!(session.read().await.graph().node_count(), 1);
+}
+
+#[tokio::test]
+async fn test_memory_tag_and_query_by_tags() {
+    let session = create_test_session();
+    for content in ["Invoice totals are rounded", "Deploys happen on Tuesdays"] {
+        ToolRegistry::call(
+            "memory_add",
+            Some(json!({"event_type": "fact", "content": content})),
+            &session,
+        )
+        .await
+        .unwrap();
+    }
+
+    let result = ToolRegistry::call(
+        "memory_tag",
+        Some(json!({"node_id": 0, "tags": {"topic": "billing", "source": "user"}})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let tagged: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(
+        tagged["tags"],
+        json!({"source": "user", "topic": "billing"})
+    );
+
+    let result = ToolRegistry::call(
+        "memory_query",
+        Some(json!({"tags": {"topic": "billing"}})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["count"], 1);
+    assert_eq!(parsed["nodes"][0]["id"], 0);
+    assert_eq!(parsed["nodes"][0]["tags"]["source"], "user");
+
+    let result = ToolRegistry::call(
+        "memory_tag",
+        Some(json!({"node_id": 0, "remove": ["topic"]})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let tagged: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(tagged["tags"], json!({"source": "user"}));
+
+    let missing = ToolRegistry::call(
+        "memory_tag",
+        Some(json!({"node_id": 999, "tags": {"a": "b"}})),
+        &session,
+    )
+    .await;
+    assert!(missing.is_err());
+}
+
+#[tokio::test]
+async fn test_memory_add_rejects_wrong_vector_dimension() {
+    let session = create_test_session();
+    let dimension = session.read().await.graph().dimension();
+
+    let err = ToolRegistry::call(
+        "memory_add",
+        Some(json!({
+            "event_type": "fact",
+            "content": "Embedded by the wrong model",
+            "feature_vec": vec![0.5; dimension + 1]
+        })),
+        &session,
+    )
+    .await
+    .unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains(&(dimension + 1).to_string()));
+    assert!(message.contains(&dimension.to_string()));
+    assert_eq!(session.read().await.graph().node_count(), 0);
+
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({
+            "event_type": "fact",
+            "content": "Embedded by the right model",
+            "feature_vec": vec![0.5; dimension]
+        })),
+        &session,
+    )
+    .await
+    .unwrap();
+    let s = session.read().await;
+    assert_eq!(s.graph().get_node(0).unwrap().feature_vec[0], 0.5);
+}
+
+#[tokio::test]
+async fn test_memory_reembed_repairs_mismatched_vectors() {
+    let session = create_test_session();
+    for content in ["first", "second"] {
+        ToolRegistry::call(
+            "memory_add",
+            Some(json!({"event_type": "fact", "content": content})),
+            &session,
+        )
+        .await
+        .unwrap();
+    }
+    let dimension = {
+        let mut s = session.write().await;
+        s.graph_mut().get_node_mut(1).unwrap().feature_vec = vec![1.0; 3];
+        s.graph().dimension()
+    };
+
+    let result = ToolRegistry::call(
+        "memory_reembed",
+        Some(json!({"only_mismatched": true})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["mismatched_before"], 1);
+    assert_eq!(parsed["reembedded_count"], 1);
+
+    let s = session.read().await;
+    assert!(s
+        .graph()
+        .nodes()
+        .iter()
+        .all(|n| n.feature_vec.len() == dimension));
+}
+
+#[tokio::test]
+async fn test_memory_reembed_without_arguments_keeps_valid_vectors() {
+    let session = create_test_session();
+    let dimension = session.read().await.graph().dimension();
+    let mut vector = vec![0.0; dimension];
+    vector[0] = 1.0;
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "embedded", "feature_vec": vector})),
+        &session,
+    )
+    .await
+    .unwrap();
+
+    let result = ToolRegistry::call("memory_reembed", Some(json!({})), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["reembedded_count"], 0);
+    let s = session.read().await;
+    assert_eq!(s.graph().get_node(0).unwrap().feature_vec, vector);
+}
+
+#[tokio::test]
+async fn test_memory_retrieve_ranks_filtered_set() {
+    let session = create_test_session();
+    let dimension = session.read().await.graph().dimension();
+    let vector = |lead: f32| {
+        let mut v = vec![0.0; dimension];
+        v[0] = lead;
+        v[1] = 1.0 - lead;
+        v
+    };
+    for (event_type, content, lead) in [
+        ("fact", "loosely related fact", 0.2),
+        ("decision", "closest but a decision", 1.0),
+        ("fact", "closely related fact", 0.9),
+    ] {
+        ToolRegistry::call(
+            "memory_add",
+            Some(json!({
+                "event_type": event_type,
+                "content": content,
+                "feature_vec": vector(lead)
+            })),
+            &session,
+        )
+        .await
+        .unwrap();
+    }
+
+    let result = ToolRegistry::call(
+        "memory_retrieve",
+        Some(json!({"event_types": ["fact"], "query_vec": vector(1.0)})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["mode"], "vector");
+    assert_eq!(parsed["filtered_count"], 2);
+    let ids: Vec<u64> = parsed["matches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["node_id"].as_u64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![2, 0]);
+
+    let missing = ToolRegistry::call("memory_retrieve", Some(json!({})), &session).await;
+    assert!(missing.is_err());
+}
+
+#[tokio::test]
+async fn test_memory_similar_explain() {
+    let session = create_test_session();
+    let dimension = session.read().await.graph().dimension();
+    let vector = |lead: f32| {
+        let mut v = vec![0.0; dimension];
+        v[0] = lead;
+        v[1] = 1.0 - lead;
+        v
+    };
+    for args in [
+        json!({"event_type": "fact", "content": "close fact", "feature_vec": vector(0.9)}),
+        json!({"event_type": "decision", "content": "closest decision", "feature_vec": vector(1.0)}),
+        json!({"event_type": "fact", "content": "distant fact", "feature_vec": vector(0.2)}),
+        json!({"event_type": "fact", "content": "fact without embedding"}),
+    ] {
+        ToolRegistry::call("memory_add", Some(args), &session)
+            .await
+            .unwrap();
+    }
+
+    let result = ToolRegistry::call(
+        "memory_similar",
+        Some(json!({"query_vec": vector(1.0), "event_types": ["fact"], "explain": true})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["count"], 1);
+    let matched = &parsed["matches"][0];
+    assert_eq!(matched["node_id"], 0);
+    assert_eq!(matched["explain"]["contributing_dims"], 1);
+    assert_eq!(matched["explain"]["top_dims"][0]["dim"], 0);
+
+    let explain = &parsed["explain"];
+    assert_eq!(explain["skipped_zero_vectors"], 1);
+    assert_eq!(explain["filtered_event_types"], 1);
+    assert_eq!(explain["filtered_min_similarity"], 1);
+    assert_eq!(explain["near_misses"][0]["node_id"], 1);
+    assert_eq!(explain["near_misses"][0]["filtered_by"], "event_types");
+}
+
+#[tokio::test]
+async fn test_memory_similar_cancelled_scan() {
+    use agentic_memory_mcp::streaming::CancellationRegistry;
+    use agentic_memory_mcp::types::RequestId;
+
+    let session = create_test_session();
+    let dimension = session.read().await.graph().dimension();
+    let vector = vec![1.0f32; dimension];
+    for content in ["first match", "second match"] {
+        ToolRegistry::call(
+            "memory_add",
+            Some(json!({"event_type": "fact", "content": content, "feature_vec": vector})),
+            &session,
+        )
+        .await
+        .unwrap();
+    }
+
+    let cancellations = CancellationRegistry::new();
+    let id = RequestId::Number(7);
+    let result = cancellations
+        .run(&id, async {
+            assert!(cancellations.cancel(&id));
+            ToolRegistry::call(
+                "memory_similar",
+                Some(json!({"query_vec": vector})),
+                &session,
+            )
+            .await
+            .unwrap()
+        })
+        .await;
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["cancelled"], true);
+    assert_eq!(parsed["count"], 0);
+    assert!(!cancellations.cancel(&id));
+
+    let result = ToolRegistry::call(
+        "memory_similar",
+        Some(json!({"query_vec": vector})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert!(parsed.get("cancelled").is_none());
+    assert_eq!(parsed["count"], 2);
+}
+
+#[tokio::test]
+async fn test_memory_similar_metric() {
+    let session = create_test_session();
+    let dimension = session.read().await.graph().dimension();
+    let unit = vec![1.0f32; dimension];
+    let long = vec![2.0f32; dimension];
+    let mut ids = Vec::new();
+    for (content, vector) in [("unit", &unit), ("long", &long)] {
+        let result = ToolRegistry::call(
+            "memory_add",
+            Some(json!({"event_type": "fact", "content": content, "feature_vec": vector})),
+            &session,
+        )
+        .await
+        .unwrap();
+        let text = match &result.content[0] {
+            agentic_memory_mcp::types::ToolContent::Text { text } => text,
+            _ => panic!("Expected text"),
+        };
+        let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+        ids.push(parsed["node_id"].as_u64().unwrap());
+    }
+
+    let similar = |args| call_tool_json("memory_similar", args, &session);
+
+    let cosine = similar(json!({"query_vec": unit})).await;
+    assert_eq!(cosine["metric"], "cosine");
+    assert_eq!(cosine["count"], 2);
+
+    let dot = similar(json!({"query_vec": unit, "metric": "dot"})).await;
+    assert_eq!(dot["metric"], "dot");
+    assert_eq!(dot["matches"][0]["node_id"], ids[1]);
+
+    let euclidean = similar(json!({"query_vec": unit, "metric": "euclidean"})).await;
+    assert_eq!(euclidean["matches"][0]["node_id"], ids[0]);
+    assert!(euclidean["matches"][1]["similarity"].as_f64().unwrap() < 0.0);
+
+    // An explicit threshold still applies to the unbounded metrics.
+    let thresholded = similar(json!({
+        "query_vec": unit, "metric": "euclidean", "min_similarity": -0.5
+    }))
+    .await;
+    assert_eq!(thresholded["count"], 1);
+
+    let err = ToolRegistry::call(
+        "memory_similar",
+        Some(json!({"query_vec": unit, "metric": "manhattan"})),
+        &session,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("manhattan"));
+}
+
+#[tokio::test]
+async fn test_memory_similar_recency_boost() {
+    const DAY: u64 = 86_400_000_000;
+    let session = create_test_session();
+    let dimension = {
+        let mut s = session.write().await;
+        s.set_clock(|| 100 * DAY);
+        s.graph().dimension()
+    };
+    let query = vec![1.0f32; dimension];
+    let mut close = query.clone();
+    close[0] = 0.0;
+    // An exact match from 90 days ago and a slightly weaker one from today.
+    for (content, vector, created_at) in [("stale", &query, 10 * DAY), ("fresh", &close, 100 * DAY)]
+    {
+        ToolRegistry::call(
+            "memory_add",
+            Some(json!({
+                "event_type": "fact", "content": content,
+                "feature_vec": vector, "created_at": created_at
+            })),
+            &session,
+        )
+        .await
+        .unwrap();
+    }
+
+    let plain = call_tool_json("memory_similar", json!({"query_vec": query}), &session).await;
+    assert_eq!(plain["matches"][0]["content"], "stale");
+    assert!(plain["matches"][0].get("recency").is_none());
+
+    let boosted = call_tool_json(
+        "memory_similar",
+        json!({"query_vec": query, "recency_boost": 0.5}),
+        &session,
+    )
+    .await;
+    assert_eq!(boosted["recency_boost"], 0.5);
+    let top = &boosted["matches"][0];
+    assert_eq!(top["content"], "fresh");
+    assert!((top["recency"].as_f64().unwrap() - 1.0).abs() < 1e-6);
+    let expected = top["similarity"].as_f64().unwrap() + 0.5;
+    assert!((top["score"].as_f64().unwrap() - expected).abs() < 1e-5);
+    // Three half-lives old.
+    let stale = &boosted["matches"][1];
+    assert!((stale["recency"].as_f64().unwrap() - 0.125).abs() < 1e-6);
+
+    let args = json!({"query_vec": query, "recency_boost": -1.0});
+    let err = ToolRegistry::call("memory_similar", Some(args), &session)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("recency_boost"));
+}
+
+#[tokio::test]
+async fn test_memory_clusters_groups_connected_nodes() {
+    let session = create_test_session();
+    let adds = [
+        json!({"event_type": "decision", "content": "Adopt Rust"}),
+        json!({"event_type": "fact", "content": "Rust is memory safe",
+               "edges": [{"target_id": 0, "edge_type": "supports"}]}),
+        json!({"event_type": "fact", "content": "Rust has no data races",
+               "edges": [{"target_id": 1, "edge_type": "supports"}]}),
+        json!({"event_type": "fact", "content": "Postgres handles our load"}),
+        json!({"event_type": "decision", "content": "Keep Postgres",
+               "edges": [{"target_id": 3, "edge_type": "related_to"}]}),
+        json!({"event_type": "fact", "content": "Lunch is at noon"}),
+    ];
+    for args in adds {
+        ToolRegistry::call("memory_add", Some(args), &session)
+            .await
+            .unwrap();
+    }
+
+    let clusters = |args| call_tool_json("memory_clusters", args, &session);
+
+    let parsed = clusters(json!({})).await;
+    assert_eq!(parsed["node_count"], 6);
+    assert_eq!(parsed["total_clusters"], 2);
+    assert_eq!(parsed["singletons"], 1);
+    assert_eq!(parsed["clusters"][0]["size"], 3);
+    assert_eq!(parsed["clusters"][0]["representative"]["node_id"], 1);
+    assert_eq!(parsed["clusters"][0]["representative"]["degree"], 2);
+    assert_eq!(parsed["clusters"][0]["type_counts"]["fact"], 2);
+    assert_eq!(parsed["clusters"][1]["size"], 2);
+
+    let parsed = clusters(json!({"edge_types": ["supports"], "min_size": 1})).await;
+    assert_eq!(parsed["total_clusters"], 4);
+    assert_eq!(parsed["clusters"][0]["size"], 3);
+
+    let parsed = clusters(json!({"event_types": ["fact"]})).await;
+    assert_eq!(parsed["node_count"], 4);
+    assert_eq!(parsed["total_clusters"], 1);
+    assert_eq!(parsed["clusters"][0]["size"], 2);
+
+    let err = ToolRegistry::call(
+        "memory_clusters",
+        Some(json!({"representative": "centrality"})),
+        &session,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        agentic_memory_mcp::types::McpError::InvalidParams(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_memory_find_connected_nearest_decision() {
+    let session = create_test_session();
+    let adds = [
+        json!({"event_type": "decision", "content": "Adopt Rust"}),
+        json!({"event_type": "inference", "content": "Rust fits our safety goals",
+               "edges": [{"target_id": 0, "edge_type": "supports"}]}),
+        json!({"event_type": "fact", "content": "Rust has no data races",
+               "edges": [{"target_id": 1, "edge_type": "supports"}]}),
+        json!({"event_type": "decision", "content": "Unrelated decision"}),
+    ];
+    for args in adds {
+        ToolRegistry::call("memory_add", Some(args), &session)
+            .await
+            .unwrap();
+    }
+
+    let result = ToolRegistry::call(
+        "memory_find_connected",
+        Some(json!({
+            "start_id": 2,
+            "event_types": ["decision"],
+            "edge_types": ["supports"]
+        })),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["found"], true);
+    assert_eq!(parsed["count"], 1);
+    assert_eq!(parsed["matches"][0]["id"], 0);
+    assert_eq!(parsed["matches"][0]["depth"], 2);
+    assert_eq!(parsed["matches"][0]["path"], json!([2, 1, 0]));
+
+    let none = ToolRegistry::call(
+        "memory_find_connected",
+        Some(json!({
+            "start_id": 2,
+            "event_types": ["decision"],
+            "edge_types": ["supports"],
+            "max_depth": 1
+        })),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &none.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["found"], false);
+}
+
+#[tokio::test]
+async fn test_register_custom_tool() {
+    use agentic_memory_mcp::tools::CustomTools;
+    use agentic_memory_mcp::types::{ToolCallResult, ToolDefinition};
+    use agentic_memory_mcp::SessionManager;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    type Session = Arc<RwLock<SessionManager>>;
+
+    let custom = CustomTools::default();
+    let definition = ToolDefinition {
+        name: " ticket_lookup ".to_string(),
+        description: Some("Look up a ticket by key".to_string()),
+        input_schema: json!({
+            "type": "object",
+            "properties": { "key": { "type": "string" } }
+        }),
+    };
+    custom
+        .register(
+            definition.clone(),
+            |args: serde_json::Value, session: Session| async move {
+                let nodes = session.read().await.graph().node_count();
+                Ok(ToolCallResult::json(&json!({
+                    "key": args["key"],
+                    "node_count": nodes,
+                })))
+            },
+        )
+        .unwrap();
+
+    assert_eq!(custom.definitions()[0].name, "ticket_lookup");
+    assert!(!ToolRegistry::list_tools()
+        .iter()
+        .any(|t| t.name == "ticket_lookup"));
+
+    let session = create_test_session();
+    let result = ToolRegistry::call_with(
+        &custom,
+        "ticket_lookup",
+        Some(json!({"key": "OPS-7"})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["key"], "OPS-7");
+
+    // Duplicates and built-in names are refused.
+    assert!(custom
+        .register(definition, |_: serde_json::Value, _: Session| async {
+            Ok(ToolCallResult::text("again".to_string()))
+        })
+        .is_err());
+    let shadow = ToolDefinition {
+        name: "memory_add".to_string(),
+        description: None,
+        input_schema: json!({ "type": "object" }),
+    };
+    assert!(custom
+        .register(shadow, |_: serde_json::Value, _: Session| async {
+            Ok(ToolCallResult::text("shadow".to_string()))
+        })
+        .is_err());
+
+    assert!(custom.unregister("ticket_lookup"));
+    assert!(
+        ToolRegistry::call_with(&custom, "ticket_lookup", None, &session)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_memory_import_notes() {
+    let session = create_test_session();
+    let notes = "Intro line.\n\n# Setup\nInstall Rust.\n\nRun cargo build.\n\n\
+                 ```sh\n# not a heading\n```\n\n## Usage\nCall amem.\n";
+
+    let import = |args| call_tool_json("memory_import_notes", args, &session);
+
+    let sections = import(json!({"text": notes, "split": "heading"})).await;
+    assert_eq!(sections["count"], 3);
+    assert_eq!(sections["edges_created"], 2);
+    let chunks = sections["chunks"].as_array().unwrap();
+    assert!(chunks[0]["heading"].is_null());
+    assert_eq!(chunks[1]["heading"], "Setup");
+    assert_eq!(chunks[1]["level"], 1);
+    assert_eq!(chunks[2]["heading"], "Usage");
+    assert_eq!(chunks[2]["level"], 2);
+
+    let ids: Vec<u64> = sections["node_ids"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|id| id.as_u64().unwrap())
+        .collect();
+    {
+        let s = session.read().await;
+        let setup = s.graph().get_node(ids[1]).unwrap();
+        assert_eq!(setup.event_type, agentic_memory::EventType::Fact);
+        assert!(setup.content.starts_with("# Setup\nInstall Rust."));
+        assert!(setup.content.contains("# not a heading"));
+        for pair in ids.windows(2) {
+            assert!(s.graph().edges().iter().any(|e| e.source_id == pair[0]
+                && e.target_id == pair[1]
+                && e.edge_type == agentic_memory::EdgeType::TemporalNext));
+        }
+    }
+
+    let paragraphs = import(json!({"text": notes, "link": false})).await;
+    assert_eq!(paragraphs["count"], 5);
+    assert_eq!(paragraphs["edges_created"], 0);
+
+    for args in [
+        json!({"text": "\n\n  \n"}),
+        json!({"text": notes, "split": "sentence"}),
+    ] {
+        assert!(
+            ToolRegistry::call("memory_import_notes", Some(args), &session)
+                .await
+                .is_err()
+        );
+    }
+}
@@ -7,7 +7,7 @@ use serde_json::json;
 use agentic_memory_mcp::session::Transaction;
 use agentic_memory_mcp::tools::ToolRegistry;
 
-use common::fixtures::create_test_session;
+use common::fixtures::{call_tool_json, create_test_session};
 
 #[tokio::test]
 async fn test_session_start_and_end() {
@@ -55,6 +55,143 @@ async fn test_session_start_and_end() {
     assert!(parsed["episode_node_id"].as_u64().is_some());
 }
 
+#[tokio::test]
+async fn test_structured_episode() {
+    use agentic_memory::EventType;
+    use agentic_memory_mcp::resources::ResourceRegistry;
+
+    let session = create_test_session();
+    let (session_id, fact, decision) = {
+        let mut sess = session.write().await;
+        let (fact, _) = sess
+            .add_event(EventType::Fact, "API uses JWT", 0.9, vec![])
+            .unwrap();
+        let (decision, _) = sess
+            .add_event(EventType::Decision, "Rotate keys weekly", 0.8, vec![])
+            .unwrap();
+        (sess.current_session_id(), fact, decision)
+    };
+
+    let result = ToolRegistry::call(
+        "session_end",
+        Some(json!({"summary": "Auth review", "format": "structured"})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text content"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["format"], "structured");
+    let episode_id = parsed["episode_node_id"].as_u64().unwrap();
+
+    let stored: serde_json::Value = {
+        let sess = session.read().await;
+        serde_json::from_str(&sess.graph().get_node(episode_id).unwrap().content).unwrap()
+    };
+    assert_eq!(stored["session_id"], session_id);
+    assert_eq!(stored["summary"], "Auth review");
+    assert_eq!(stored["node_ids"], json!([fact, decision]));
+    assert_eq!(stored["type_counts"], json!({"decision": 1, "fact": 1}));
+    assert_eq!(stored["key_nodes"][0]["id"], decision);
+
+    let resource = ResourceRegistry::read(&format!("amem://episode/{episode_id}"), &session)
+        .await
+        .unwrap();
+    let episode: serde_json::Value =
+        serde_json::from_str(resource.contents[0].text.as_ref().unwrap()).unwrap();
+    assert_eq!(episode["format"], "structured");
+    assert_eq!(episode["summary"], "Auth review");
+    assert_eq!(episode["member_ids"], json!([fact, decision]));
+
+    let not_episode = ResourceRegistry::read(&format!("amem://episode/{fact}"), &session).await;
+    assert!(not_episode.is_err());
+}
+
+#[tokio::test]
+async fn test_episode_links_members() {
+    use agentic_memory::{EdgeType, EventType};
+    use agentic_memory_mcp::session::EpisodeLinks;
+
+    let session = create_test_session();
+    let add_members = |sess: &mut agentic_memory_mcp::session::SessionManager| {
+        let (a, _) = sess
+            .add_event(EventType::Fact, "Cache is per tenant", 0.9, vec![])
+            .unwrap();
+        let (b, _) = sess
+            .add_event(EventType::Decision, "Evict after an hour", 0.8, vec![])
+            .unwrap();
+        vec![a, b]
+    };
+    let part_of_sources = |sess: &agentic_memory_mcp::session::SessionManager, episode: u64| {
+        let mut ids: Vec<u64> = sess
+            .graph()
+            .edges_to(episode)
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::PartOf)
+            .map(|e| e.source_id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    };
+
+    let members = {
+        let mut sess = session.write().await;
+        add_members(&mut sess)
+    };
+    let result = ToolRegistry::call(
+        "session_end",
+        Some(json!({"summary": "Cache design"})),
+        &session,
+    )
+    .await
+    .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text content"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    let episode_id = parsed["episode_node_id"].as_u64().unwrap();
+    {
+        let sess = session.read().await;
+        assert_eq!(part_of_sources(&sess, episode_id), members);
+    }
+
+    // In content mode the members are listed in the episode instead.
+    let (members, episode_id) = {
+        let mut sess = session.write().await;
+        sess.set_episode_links(EpisodeLinks::Content);
+        sess.start_session(None).unwrap();
+        let members = add_members(&mut sess);
+        let session_id = sess.current_session_id();
+        let episode_id = sess
+            .end_session_with_episode(session_id, "Cache follow-up")
+            .unwrap();
+        assert!(part_of_sources(&sess, episode_id).is_empty());
+        let content = &sess.graph().get_node(episode_id).unwrap().content;
+        assert_eq!(
+            *content,
+            format!(
+                "Cache follow-up\n\nMembers: #{}, #{}",
+                members[0], members[1]
+            )
+        );
+        (members, episode_id)
+    };
+
+    let resource = agentic_memory_mcp::resources::ResourceRegistry::read(
+        &format!("amem://episode/{episode_id}"),
+        &session,
+    )
+    .await
+    .unwrap();
+    let episode: serde_json::Value =
+        serde_json::from_str(resource.contents[0].text.as_ref().unwrap()).unwrap();
+    assert_eq!(episode["member_ids"], json!(members));
+}
+
 #[tokio::test]
 async fn test_auto_save_triggers() {
     let session = create_test_session();
@@ -70,7 +207,7 @@ async fn test_auto_save_triggers() {
 
     // Manually trigger maybe_auto_save
     {
-        let mut sess = session.lock().await;
+        let mut sess = session.write().await;
         sess.mark_dirty();
         // The interval won't have elapsed yet, so this should be a no-op
         sess.maybe_auto_save().unwrap();
@@ -94,7 +231,7 @@ async fn test_transaction_batching() {
     let session = create_test_session();
 
     {
-        let mut sess = session.lock().await;
+        let mut sess = session.write().await;
 
         let event1 = agentic_memory::CognitiveEventBuilder::new(
             agentic_memory::EventType::Fact,
@@ -146,13 +283,13 @@ async fn test_explicit_save() {
 
     // Explicitly save
     {
-        let mut sess = session.lock().await;
+        let mut sess = session.write().await;
         sess.save().unwrap();
     }
 
     // Verify file path is accessible
     {
-        let sess = session.lock().await;
+        let sess = session.read().await;
         assert!(sess.file_path().exists());
     }
 }
@@ -163,13 +300,13 @@ async fn test_session_id_continuity() {
 
     // Get initial session ID
     let initial_id = {
-        let sess = session.lock().await;
+        let sess = session.read().await;
         sess.current_session_id()
     };
 
     // Start a new session
     {
-        let mut sess = session.lock().await;
+        let mut sess = session.write().await;
         let new_id = sess.start_session(None).unwrap();
         assert!(new_id >= initial_id);
     }
@@ -199,3 +336,291 @@ async fn test_drop_saves_dirty() {
     let session = agentic_memory_mcp::session::SessionManager::open(&path_str).unwrap();
     assert_eq!(session.graph().node_count(), 1);
 }
+
+#[tokio::test]
+async fn test_undo_after_merge_with_disk() {
+    use agentic_memory::EventType;
+    use agentic_memory_mcp::session::SessionManager;
+
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path_str = dir.path().join("shared.amem").display().to_string();
+
+    let mut ours = SessionManager::open(&path_str).unwrap();
+    ours.add_event(EventType::Fact, "Ours, saved", 0.9, vec![])
+        .unwrap();
+    ours.save().unwrap();
+
+    // Another instance writes the file after our last save.
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    {
+        let mut other = SessionManager::open(&path_str).unwrap();
+        other
+            .add_event(EventType::Fact, "Theirs", 0.9, vec![])
+            .unwrap();
+        other.save().unwrap();
+    }
+
+    ours.add_event(EventType::Fact, "Ours, unsaved", 0.9, vec![])
+        .unwrap();
+    assert_eq!(ours.undo_available(), 2);
+    ours.save().unwrap();
+    assert!(ours.graph().nodes().iter().any(|n| n.content == "Theirs"));
+
+    // The merge re-added our nodes under new IDs, so the recorded
+    // operations no longer apply and undo leaves the merged graph alone.
+    assert_eq!(ours.undo_available(), 0);
+    let node_count = ours.graph().node_count();
+    let session = std::sync::Arc::new(tokio::sync::RwLock::new(ours));
+    let result = ToolRegistry::call("memory_undo", Some(json!({})), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["undone_count"], 0);
+    let session = session.read().await;
+    assert_eq!(session.graph().node_count(), node_count);
+    assert!(session
+        .graph()
+        .nodes()
+        .iter()
+        .any(|n| n.content == "Ours, unsaved"));
+}
+
+#[tokio::test]
+async fn test_open_dedups_edges_when_enabled() {
+    use agentic_memory::{
+        AmemWriter, CognitiveEventBuilder, Edge, EdgeType, EventType, MemoryGraph,
+    };
+    use agentic_memory_mcp::session::SessionManager;
+
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = dir.path().join("duplicates.amem");
+    let dimension = agentic_memory::DEFAULT_DIMENSION;
+
+    let events = (0..2)
+        .map(|id| {
+            let mut event =
+                CognitiveEventBuilder::new(EventType::Fact, format!("node {id}")).build();
+            event.id = id;
+            event.feature_vec = vec![0.0; dimension];
+            event
+        })
+        .collect();
+    let edges = vec![
+        Edge::new(1, 0, EdgeType::Supports, 1.0),
+        Edge::new(1, 0, EdgeType::Supports, 0.5),
+    ];
+    let graph = MemoryGraph::from_parts(events, edges, dimension).unwrap();
+    AmemWriter::new(dimension)
+        .write_to_file(&graph, &path)
+        .unwrap();
+
+    std::env::set_var("AMEM_DEDUP_EDGES", "true");
+    let session = SessionManager::open(&path.display().to_string());
+    std::env::remove_var("AMEM_DEDUP_EDGES");
+    let session = session.unwrap();
+
+    assert_eq!(session.graph().edge_count(), 1);
+    assert!(session.is_dirty());
+}
+
+#[tokio::test]
+async fn test_ephemeral_session_never_touches_disk() {
+    use agentic_memory_mcp::session::manager::EPHEMERAL_MEMORY_PATH;
+    use agentic_memory_mcp::session::SessionManager;
+
+    {
+        let mut session = SessionManager::open(EPHEMERAL_MEMORY_PATH).unwrap();
+        assert!(session.is_ephemeral());
+        assert!(session.can_write());
+        session
+            .add_event(agentic_memory::EventType::Fact, "Scratch fact", 0.9, vec![])
+            .unwrap();
+        session.save().unwrap();
+        session.maybe_auto_save().unwrap();
+        assert!(!session.is_dirty());
+        assert_eq!(session.graph().node_count(), 1);
+        // Session drops here without saving.
+    }
+
+    assert!(!std::path::Path::new(EPHEMERAL_MEMORY_PATH).exists());
+    let session = SessionManager::open(EPHEMERAL_MEMORY_PATH).unwrap();
+    assert_eq!(session.graph().node_count(), 0);
+}
+
+#[tokio::test]
+async fn test_text_index_built_lazily_and_kept_current() {
+    use agentic_memory::EventType;
+
+    let session = create_test_session();
+    let postgres = {
+        let mut sess = session.write().await;
+        sess.set_text_index_enabled(true);
+        let (id, _) = sess
+            .add_event(EventType::Fact, "Postgres listens on 5432", 0.9, vec![])
+            .unwrap();
+        assert!(sess.graph().term_index().is_none());
+        id
+    };
+
+    // With a single indexed node BM25 scores stay below the default
+    // threshold, so accept any match.
+    let claim = |claim: &str| json!({"claim": claim, "threshold": 0.01});
+    let grounded = call_tool_json("memory_ground", claim("postgres"), &session).await;
+    assert_eq!(grounded["status"], "verified");
+    assert!(session.read().await.graph().term_index().is_some());
+
+    // Nodes added and deleted after the build are reflected without a rebuild.
+    let redis = {
+        let mut sess = session.write().await;
+        let (id, _) = sess
+            .add_event(EventType::Fact, "Redis listens on 6379", 0.9, vec![])
+            .unwrap();
+        sess.delete_node(postgres).unwrap();
+        id
+    };
+    let grounded = call_tool_json("memory_ground", claim("redis"), &session).await;
+    assert_eq!(grounded["evidence"][0]["node_id"], redis);
+    let gone = call_tool_json("memory_ground", claim("postgres"), &session).await;
+    assert_eq!(gone["status"], "ungrounded");
+
+    let stats = call_tool_json("memory_stats", json!({}), &session).await;
+    assert_eq!(stats["text_index"]["enabled"], true);
+    assert_eq!(stats["text_index"]["built"], true);
+    assert_eq!(stats["text_index"]["indexed_nodes"], 1);
+    assert_eq!(stats["text_index"]["coverage"], 1.0);
+
+    session.write().await.set_text_index_enabled(false);
+    let stats = call_tool_json("memory_stats", json!({}), &session).await;
+    assert_eq!(stats["text_index"]["built"], false);
+}
+
+#[tokio::test]
+async fn test_prebuild_text_index_up_front() {
+    use agentic_memory::EventType;
+
+    let session = create_test_session();
+    let mut sess = session.write().await;
+    sess.add_event(EventType::Fact, "Postgres listens on 5432", 0.9, vec![])
+        .unwrap();
+
+    // Nothing to build while the text index is disabled.
+    assert!(sess.prebuild_text_index().is_none());
+    assert!(sess.graph().term_index().is_none());
+
+    sess.set_text_index_enabled(true);
+    assert!(sess.prebuild_text_index().is_some());
+    assert_eq!(sess.graph().term_index().unwrap().doc_count(), 1);
+    // Already built: a second call is a no-op.
+    assert!(sess.prebuild_text_index().is_none());
+}
+
+#[tokio::test]
+async fn test_memory_dir_namespaces() {
+    use agentic_memory_mcp::session::SessionManager;
+
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let dir_str = dir.path().display().to_string();
+
+    // Seed a second namespace before opening the directory.
+    {
+        let mut work =
+            SessionManager::open(&dir.path().join("work.amem").display().to_string()).unwrap();
+        work.add_event(agentic_memory::EventType::Fact, "Work fact", 0.9, vec![])
+            .unwrap();
+    }
+
+    let session = SessionManager::open_dir(&dir_str, "personal").unwrap();
+    assert_eq!(session.namespace(), "personal");
+    assert!(session.namespaces().contains_key("work"));
+    let session = std::sync::Arc::new(tokio::sync::RwLock::new(session));
+
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "Personal fact"})),
+        &session,
+    )
+    .await
+    .unwrap();
+    ToolRegistry::call(
+        "memory_add",
+        Some(json!({"event_type": "fact", "content": "Another work fact", "namespace": "work"})),
+        &session,
+    )
+    .await
+    .unwrap();
+
+    let result = ToolRegistry::call("memory_stats", Some(json!({})), &session)
+        .await
+        .unwrap();
+    let text = match &result.content[0] {
+        agentic_memory_mcp::types::ToolContent::Text { text } => text,
+        _ => panic!("Expected text"),
+    };
+    let parsed: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed["namespace"], "personal");
+    assert_eq!(parsed["node_count"], 1);
+    assert_eq!(parsed["namespaces"]["personal"]["node_count"], 1);
+    assert_eq!(parsed["namespaces"]["work"]["node_count"], 2);
+
+    let err = ToolRegistry::call(
+        "memory_stats",
+        Some(json!({"namespace": "missing"})),
+        &session,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("Unknown namespace"));
+}
+
+#[tokio::test]
+async fn test_continue_session_after_restart() {
+    use agentic_memory_mcp::session::SessionManager;
+
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path_str = dir.path().join("resume.amem").display().to_string();
+
+    let (session_id, node_id) = {
+        let mut session = SessionManager::open(&path_str).unwrap();
+        let session_id = session.start_session(None).unwrap();
+        let (node_id, _) = session
+            .add_event(
+                agentic_memory::EventType::Fact,
+                "Before restart",
+                0.9,
+                vec![],
+            )
+            .unwrap();
+        (session_id, node_id)
+    };
+
+    let mut session = SessionManager::open(&path_str).unwrap();
+    assert_ne!(session.current_session_id(), session_id);
+    assert_eq!(session.resume_last_session(), Some(session_id));
+    assert_eq!(session.current_session_id(), session_id);
+    assert_eq!(session.last_temporal_node_id(), Some(node_id));
+
+    let (next_id, _) = session
+        .add_event(
+            agentic_memory::EventType::Fact,
+            "After restart",
+            0.9,
+            vec![],
+        )
+        .unwrap();
+    assert_eq!(
+        session.graph().get_node(next_id).unwrap().session_id,
+        session_id
+    );
+
+    // An ended session is not resumed.
+    session
+        .end_session_with_episode(session_id, "Done")
+        .unwrap();
+    drop(session);
+    let mut session = SessionManager::open(&path_str).unwrap();
+    assert_eq!(session.resume_last_session(), None);
+}
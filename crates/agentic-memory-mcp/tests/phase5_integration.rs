@@ -172,6 +172,99 @@ async fn test_session_lifecycle_integration() {
     client.shutdown().await;
 }
 
+#[tokio::test]
+async fn test_resource_template_completion() {
+    let mut client = create_client();
+    let init = client.initialize().await;
+    assert!(init["result"]["capabilities"]["completions"].is_object());
+
+    let mut node_ids = Vec::new();
+    for content in ["Completion fact one", "Completion fact two"] {
+        let add = client
+            .call_tool(
+                "memory_add",
+                Some(json!({"event_type": "fact", "content": content})),
+            )
+            .await;
+        let add_text = add["result"]["content"][0]["text"].as_str().unwrap();
+        let add_parsed: serde_json::Value = serde_json::from_str(add_text).unwrap();
+        node_ids.push(add_parsed["node_id"].as_u64().unwrap().to_string());
+    }
+
+    let complete = |uri: &str, name: &str, value: &str| {
+        json!({
+            "ref": {"type": "ref/resource", "uri": uri},
+            "argument": {"name": name, "value": value}
+        })
+    };
+
+    // Node ids come back newest first
+    let nodes = client
+        .request(
+            "completion/complete",
+            Some(complete("amem://node/{id}", "id", "")),
+        )
+        .await;
+    let completion = &nodes["result"]["completion"];
+    let values: Vec<String> = completion["values"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(values, vec![node_ids[1].clone(), node_ids[0].clone()]);
+    assert_eq!(completion["total"], 2);
+    assert_eq!(completion["hasMore"], false);
+
+    // Event type names are filtered by the typed prefix
+    let types = client
+        .request(
+            "completion/complete",
+            Some(complete("amem://types/{type}", "type", "de")),
+        )
+        .await;
+    assert_eq!(types["result"]["completion"]["values"], json!(["decision"]));
+
+    // The current session is offered for the session template
+    let sessions = client
+        .request(
+            "completion/complete",
+            Some(complete("amem://session/{id}", "id", "")),
+        )
+        .await;
+    assert!(!sessions["result"]["completion"]["values"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+
+    // No episodes yet
+    let episodes = client
+        .request(
+            "completion/complete",
+            Some(complete("amem://episode/{id}", "id", "")),
+        )
+        .await;
+    assert_eq!(episodes["result"]["completion"]["values"], json!([]));
+
+    let bad_arg = client
+        .request(
+            "completion/complete",
+            Some(complete("amem://node/{id}", "type", "")),
+        )
+        .await;
+    assert_eq!(bad_arg["error"]["code"], -32602);
+
+    let bad_template = client
+        .request(
+            "completion/complete",
+            Some(complete("amem://nowhere/{id}", "id", "")),
+        )
+        .await;
+    assert!(bad_template["error"].is_object());
+
+    client.shutdown().await;
+}
+
 #[tokio::test]
 async fn test_resource_templates_and_reads() {
     let mut client = create_client();
@@ -291,3 +384,49 @@ async fn test_ping() {
     let result = client.request("ping", None).await;
     assert!(result["result"].is_object());
 }
+
+#[tokio::test]
+async fn test_batch_resource_read_marks_bad_uris() {
+    let mut client = create_client();
+    client.initialize().await;
+
+    let add = client
+        .call_tool(
+            "memory_add",
+            Some(json!({"event_type": "fact", "content": "Batch read target"})),
+        )
+        .await;
+    let add_text = add["result"]["content"][0]["text"].as_str().unwrap();
+    let node_id = serde_json::from_str::<serde_json::Value>(add_text).unwrap()["node_id"]
+        .as_u64()
+        .unwrap();
+
+    let result = client
+        .request(
+            "resources/read",
+            Some(json!({
+                "uris": [
+                    format!("amem://node/{node_id}"),
+                    "amem://node/99999",
+                    "amem://graph/stats"
+                ]
+            })),
+        )
+        .await;
+    let contents = result["result"]["contents"].as_array().unwrap();
+    assert_eq!(contents.len(), 3);
+
+    let node: serde_json::Value =
+        serde_json::from_str(contents[0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(node["content"], "Batch read target");
+
+    assert_eq!(contents[1]["uri"], "amem://node/99999");
+    assert_eq!(contents[1]["mimeType"], "application/vnd.amem.error+json");
+    let error: serde_json::Value =
+        serde_json::from_str(contents[1]["text"].as_str().unwrap()).unwrap();
+    assert!(error["error"]["message"].is_string());
+
+    let stats: serde_json::Value =
+        serde_json::from_str(contents[2]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(stats["node_count"], 1);
+}
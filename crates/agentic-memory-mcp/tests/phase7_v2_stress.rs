@@ -6,7 +6,7 @@ mod common;
 
 use serde_json::json;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use agentic_memory_mcp::session::SessionManager;
 use agentic_memory_mcp::tools::ToolRegistry;
@@ -34,7 +34,7 @@ async fn create_seeded_amem(filename: &str, memories: &[(&str, &str)]) -> Seeded
     let path = dir.path().join(filename);
     let path_str = path.display().to_string();
     let session = SessionManager::open(&path_str).expect("open session");
-    let session = Arc::new(Mutex::new(session));
+    let session = Arc::new(RwLock::new(session));
     for (event_type, content) in memories {
         ToolRegistry::call(
             "memory_add",
@@ -44,7 +44,7 @@ async fn create_seeded_amem(filename: &str, memories: &[(&str, &str)]) -> Seeded
         .await
         .unwrap();
     }
-    session.lock().await.save().unwrap();
+    session.write().await.save().unwrap();
     SeededFile {
         path: path_str,
         _dir: dir,
@@ -982,13 +982,13 @@ async fn test_ground_then_workspace() {
 
     // Save the session to disk
     {
-        let mut sess = session.lock().await;
+        let mut sess = session.write().await;
         sess.save().unwrap();
     }
 
     // Now create a workspace and add the saved file
     let file_path = {
-        let sess = session.lock().await;
+        let sess = session.read().await;
         sess.file_path().display().to_string()
     };
 
@@ -18,7 +18,7 @@ mod common;
 
 use serde_json::json;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use agentic_memory_mcp::session::SessionManager;
 use agentic_memory_mcp::tools::ToolRegistry;
@@ -40,7 +40,7 @@ fn result_json(result: &agentic_memory_mcp::types::ToolCallResult) -> serde_json
     serde_json::from_str(&result_text(result)).unwrap()
 }
 
-async fn seed_facts(session: &Arc<Mutex<SessionManager>>, count: usize) -> Vec<u64> {
+async fn seed_facts(session: &Arc<RwLock<SessionManager>>, count: usize) -> Vec<u64> {
     let mut ids = Vec::new();
     for i in 0..count {
         let result = ToolRegistry::call(
@@ -64,7 +64,7 @@ async fn create_seeded_amem(filename: &str, memories: &[(&str, &str)]) -> Seeded
     let path = dir.path().join(filename);
     let path_str = path.display().to_string();
     let session = SessionManager::open(&path_str).expect("open session");
-    let session = Arc::new(Mutex::new(session));
+    let session = Arc::new(RwLock::new(session));
     for (event_type, content) in memories {
         ToolRegistry::call(
             "memory_add",
@@ -74,7 +74,7 @@ async fn create_seeded_amem(filename: &str, memories: &[(&str, &str)]) -> Seeded
         .await
         .unwrap();
     }
-    session.lock().await.save().unwrap();
+    session.write().await.save().unwrap();
     SeededFile {
         path: path_str,
         _dir: dir,
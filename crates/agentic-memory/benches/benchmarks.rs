@@ -5,7 +5,8 @@ use rand::Rng;
 use tempfile::NamedTempFile;
 
 use agentic_memory::engine::{
-    PatternParams, PatternSort, QueryEngine, SimilarityParams, TraversalParams, WriteEngine,
+    PatternParams, PatternSort, QueryEngine, SimilarityMetric, SimilarityParams, TraversalParams,
+    WriteEngine,
 };
 use agentic_memory::format::{AmemReader, AmemWriter, MmapReader};
 use agentic_memory::graph::MemoryGraph;
@@ -176,6 +177,7 @@ fn bench_pattern_query(c: &mut Criterion) {
                 created_after: None,
                 created_before: None,
                 min_decay_score: None,
+                max_decay_score: None,
                 max_results: 50,
                 sort_by: PatternSort::MostRecent,
             };
@@ -200,6 +202,7 @@ fn bench_similarity_search_100k(c: &mut Criterion) {
                 min_similarity: 0.0,
                 event_types: vec![],
                 skip_zero_vectors: true,
+                metric: SimilarityMetric::Cosine,
             };
             let _ = query_engine.similarity(&graph, params);
         })
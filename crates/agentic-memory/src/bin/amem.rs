@@ -189,6 +189,20 @@ enum Commands {
         #[arg(long, default_value = "0.1")]
         threshold: f32,
     },
+    /// Repair an .amem file written by an older version and write a clean copy
+    Migrate {
+        /// Path to the .amem file
+        file: PathBuf,
+        /// Where to write the migrated file (default: <name>.migrated.amem)
+        #[arg(long, conflicts_with = "in_place")]
+        output: Option<PathBuf>,
+        /// Replace the original file instead of writing a copy
+        #[arg(long)]
+        in_place: bool,
+        /// Overwrite the output file if it already exists
+        #[arg(long)]
+        force: bool,
+    },
     /// Detailed statistics about the graph
     Stats {
         /// Path to the .amem file
@@ -578,6 +592,12 @@ fn main() {
         }) => commands::cmd_export(&file, nodes_only, session, pretty),
         Some(Commands::Import { file, json_file }) => commands::cmd_import(&file, &json_file),
         Some(Commands::Decay { file, threshold }) => commands::cmd_decay(&file, threshold, json),
+        Some(Commands::Migrate {
+            file,
+            output,
+            in_place,
+            force,
+        }) => commands::cmd_migrate(&file, output.as_deref(), in_place, force, json),
         Some(Commands::Stats { file }) => commands::cmd_stats(&file, json),
         Some(Commands::Quality {
             file,
@@ -10,8 +10,11 @@ use crate::engine::{
 };
 use crate::format::{AmemReader, AmemWriter};
 use crate::graph::traversal::TraversalDirection;
-use crate::graph::MemoryGraph;
-use crate::types::{AmemResult, CognitiveEvent, CognitiveEventBuilder, Edge, EdgeType, EventType};
+use crate::graph::{repair_graph, MemoryGraph};
+use crate::types::{
+    AmemError, AmemResult, CognitiveEvent, CognitiveEventBuilder, Edge, EdgeType, EventType,
+    FileHeader, FORMAT_VERSION,
+};
 
 /// Create a new empty .amem file.
 pub fn cmd_create(path: &Path, dimension: usize) -> AmemResult<()> {
@@ -133,15 +136,34 @@ pub fn cmd_link(
 ) -> AmemResult<()> {
     let mut graph = AmemReader::read_from_file(path)?;
     let edge = Edge::new(source_id, target_id, edge_type, weight);
-    graph.add_edge(edge)?;
+    // Linking an already-linked pair is a no-op, not an error.
+    let already_linked = match graph.add_edge(edge) {
+        Ok(()) => false,
+        Err(AmemError::DuplicateEdge { .. }) => true,
+        Err(e) => return Err(e),
+    };
 
-    let writer = AmemWriter::new(graph.dimension());
-    writer.write_to_file(&graph, path)?;
+    if !already_linked {
+        let writer = AmemWriter::new(graph.dimension());
+        writer.write_to_file(&graph, path)?;
+    }
 
     if json {
         println!(
             "{}",
-            serde_json::json!({"source": source_id, "target": target_id, "type": edge_type.name()})
+            serde_json::json!({
+                "source": source_id,
+                "target": target_id,
+                "type": edge_type.name(),
+                "already_linked": already_linked,
+            })
+        );
+    } else if already_linked {
+        println!(
+            "Already linked {} --{}--> {}",
+            source_id,
+            edge_type.name(),
+            target_id
         );
     } else {
         println!(
@@ -305,6 +327,7 @@ pub fn cmd_search(
             created_after,
             created_before,
             min_decay_score: None,
+            max_decay_score: None,
             max_results: limit,
             sort_by,
         },
@@ -676,6 +699,99 @@ pub fn cmd_decay(path: &Path, threshold: f32, json: bool) -> AmemResult<()> {
     Ok(())
 }
 
+/// Read an .amem file written by an older version, repair it, and write a
+/// clean copy.
+///
+/// The copy goes to `output`, or next to the input as
+/// `<name>.migrated.amem`, unless `in_place` is set. An existing output file
+/// is only replaced with `force`.
+pub fn cmd_migrate(
+    path: &Path,
+    output: Option<&Path>,
+    in_place: bool,
+    force: bool,
+    json: bool,
+) -> AmemResult<()> {
+    let target = match (output, in_place) {
+        (_, true) => path.to_path_buf(),
+        (Some(output), false) => output.to_path_buf(),
+        (None, false) => path.with_extension("migrated.amem"),
+    };
+    if !in_place && target.exists() && !force {
+        return Err(AmemError::Io(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!(
+                "{} already exists; pass --force to replace it",
+                target.display()
+            ),
+        )));
+    }
+
+    let from_version = FileHeader::read_from(&mut std::fs::File::open(path)?)?.version;
+    let (mut graph, contents_replaced) = AmemReader::read_from_file_lossy(path)?;
+    let report = repair_graph(&mut graph, crate::types::now_micros(), contents_replaced);
+
+    // Write beside the target and rename, so a failed write never leaves a
+    // half-written file in place of the original.
+    let tmp = target.with_extension("amem.tmp");
+    AmemWriter::new(graph.dimension()).write_to_file(&graph, &tmp)?;
+    std::fs::rename(&tmp, &target)?;
+
+    if json {
+        let info = serde_json::json!({
+            "input": path.display().to_string(),
+            "output": target.display().to_string(),
+            "from_version": from_version,
+            "to_version": FORMAT_VERSION,
+            "nodes": graph.node_count(),
+            "edges": graph.edge_count(),
+            "duplicate_edges_removed": report.duplicate_edges_removed,
+            "dangling_edges_removed": report.dangling_edges_removed,
+            "decay_scores_updated": report.decay_scores_updated,
+            "vectors_filled": report.vectors_filled,
+            "contents_replaced": report.contents_replaced,
+            "contents_truncated": report.contents_truncated,
+            "text_index_dropped": report.text_index_dropped,
+            "changed": !report.is_clean(),
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&info).unwrap_or_default()
+        );
+    } else {
+        println!("Migrated {} -> {}", path.display(), target.display());
+        println!(
+            "  Format version:    {} -> {}",
+            from_version, FORMAT_VERSION
+        );
+        println!(
+            "  Duplicate edges:   {} removed",
+            report.duplicate_edges_removed
+        );
+        println!(
+            "  Dangling edges:    {} removed",
+            report.dangling_edges_removed
+        );
+        println!(
+            "  Decay scores:      {} recomputed",
+            report.decay_scores_updated
+        );
+        println!("  Vectors filled:    {}", report.vectors_filled);
+        println!(
+            "  Invalid UTF-8:     {} nodes repaired",
+            report.contents_replaced
+        );
+        println!(
+            "  Oversized content: {} nodes truncated",
+            report.contents_truncated
+        );
+        if report.text_index_dropped {
+            println!("  Stored text index dropped; it is rebuilt on the next keyword search");
+        }
+    }
+    Ok(())
+}
+
 /// Detailed statistics.
 pub fn cmd_stats(path: &Path, json: bool) -> AmemResult<()> {
     let graph = AmemReader::read_from_file(path)?;
@@ -11,8 +11,9 @@ pub mod write;
 
 pub use query::{
     CausalParams, CausalResult, MemoryQualityParams, MemoryQualityReport, PatternParams,
-    PatternSort, QueryEngine, SimilarityMatchResult, SimilarityParams, SubGraph, TemporalParams,
-    TemporalResult, TimeRange, TraversalParams, TraversalResult,
+    PatternSort, QueryEngine, SimilarityMatchResult, SimilarityMetric, SimilarityParams, SubGraph,
+    SupportContribution, SupportScore, TemporalParams, TemporalResult, TimeRange, TraversalParams,
+    TraversalResult,
 };
 pub use write::{DecayReport, IngestResult, WriteEngine};
 
@@ -2,7 +2,7 @@
 
 use std::collections::{HashMap, HashSet, VecDeque};
 
-use crate::graph::traversal::{bfs_traverse, TraversalDirection};
+use crate::graph::traversal::{bfs_traverse, bfs_traverse_multi, TraversalDirection};
 use crate::graph::MemoryGraph;
 use crate::index::cosine_similarity;
 use crate::types::{AmemError, AmemResult, CognitiveEvent, Edge, EdgeType, EventType};
@@ -31,6 +31,23 @@ pub struct TraversalResult {
     pub edges_traversed: Vec<Edge>,
     /// Depth at which each node was found.
     pub depths: HashMap<u64, u32>,
+    /// Edges that lead back to an already-visited ancestor (each closes a cycle).
+    pub back_edges: Vec<Edge>,
+    /// Start node each visited node was reached from.
+    pub sources: HashMap<u64, u64>,
+    /// Nodes the traversal would have followed but left unvisited because
+    /// `max_depth` or `max_results` stopped it. Zero means the result is complete.
+    pub frontier: usize,
+    /// Shallowest depth whose node still had unvisited neighbors; every
+    /// level up to and including it is complete. `None` when nothing was cut.
+    pub truncated_at_depth: Option<u32>,
+}
+
+impl TraversalResult {
+    /// Whether the traversal saw everything reachable under its filters.
+    pub fn is_complete(&self) -> bool {
+        self.frontier == 0
+    }
 }
 
 /// Sort order for pattern queries.
@@ -46,6 +63,25 @@ pub enum PatternSort {
     MostImportant,
 }
 
+impl PatternSort {
+    /// The value this order sorts on; larger values come first.
+    pub fn key(self, event: &CognitiveEvent) -> f64 {
+        match self {
+            PatternSort::MostRecent => event.created_at as f64,
+            PatternSort::HighestConfidence => event.confidence as f64,
+            PatternSort::MostAccessed => event.access_count as f64,
+            PatternSort::MostImportant => event.decay_score as f64,
+        }
+    }
+
+    /// Order two `(key, id)` positions: key descending, ties by id ascending.
+    pub fn compare_keys(a: (f64, u64), b: (f64, u64)) -> std::cmp::Ordering {
+        b.0.partial_cmp(&a.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.1.cmp(&b.1))
+    }
+}
+
 /// Parameters for a pattern query.
 pub struct PatternParams {
     /// Filter by event type(s). Empty = all types.
@@ -60,8 +96,10 @@ pub struct PatternParams {
     pub created_after: Option<u64>,
     /// Filter by creation time: before this timestamp.
     pub created_before: Option<u64>,
-    /// Filter by minimum decay score.
+    /// Filter by minimum decay score (inclusive).
     pub min_decay_score: Option<f32>,
+    /// Filter by maximum decay score (inclusive).
+    pub max_decay_score: Option<f32>,
     /// Maximum number of results.
     pub max_results: usize,
     /// Sort order.
@@ -120,6 +158,10 @@ pub struct CausalResult {
     pub affected_decisions: usize,
     /// Total number of inferences that depend on this node.
     pub affected_inferences: usize,
+    /// Dependents beyond `max_depth` that were not explored. Zero means complete.
+    pub frontier: usize,
+    /// The depth limit, when it cut the analysis short.
+    pub truncated_at_depth: Option<u32>,
 }
 
 /// Parameters for a similarity query.
@@ -134,6 +176,58 @@ pub struct SimilarityParams {
     pub event_types: Vec<EventType>,
     /// Exclude nodes with zero vectors.
     pub skip_zero_vectors: bool,
+    /// How vectors are scored against the query.
+    pub metric: SimilarityMetric,
+}
+
+/// Scoring function for similarity queries. Higher scores are more similar
+/// under every metric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SimilarityMetric {
+    /// Cosine of the angle between the vectors, in `[-1, 1]`.
+    #[default]
+    Cosine,
+    /// Dot product; for embeddings whose length carries meaning.
+    Dot,
+    /// Negative Euclidean distance: 0 for identical vectors, lower the
+    /// further apart they are.
+    Euclidean,
+}
+
+impl SimilarityMetric {
+    /// Every metric, in declaration order.
+    pub fn all() -> &'static [SimilarityMetric] {
+        &[Self::Cosine, Self::Dot, Self::Euclidean]
+    }
+
+    /// Name used in parameters and results.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Cosine => "cosine",
+            Self::Dot => "dot",
+            Self::Euclidean => "euclidean",
+        }
+    }
+
+    /// Parse a metric name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|m| m.name() == name)
+    }
+
+    /// Score `b` against `a`. Vectors of different lengths are compared over
+    /// their common prefix.
+    pub fn score(&self, a: &[f32], b: &[f32]) -> f32 {
+        match self {
+            Self::Cosine => cosine_similarity(a, b),
+            Self::Dot => a.iter().zip(b).map(|(x, y)| x * y).sum(),
+            Self::Euclidean => -a
+                .iter()
+                .zip(b)
+                .map(|(x, y)| (x - y) * (x - y))
+                .sum::<f32>()
+                .sqrt(),
+        }
+    }
 }
 
 /// A match from a similarity search.
@@ -181,6 +275,34 @@ pub struct MemoryQualityReport {
     pub unsupported_decision_examples: Vec<u64>,
 }
 
+/// One incoming edge counted by [`QueryEngine::support_score`].
+pub struct SupportContribution {
+    /// The supporting or contradicting node.
+    pub node_id: u64,
+    /// That node's confidence.
+    pub confidence: f32,
+    /// The edge weight.
+    pub weight: f32,
+    /// `weight * confidence`, both clamped to `[0.0, 1.0]`.
+    pub strength: f32,
+}
+
+/// Evidential support for a node, aggregated from incoming edges.
+pub struct SupportScore {
+    /// The node being scored.
+    pub node_id: u64,
+    /// Noisy-OR of incoming `Supports` strengths, in `[0.0, 1.0]`.
+    pub support: f32,
+    /// Noisy-OR of incoming `Contradicts` strengths, in `[0.0, 1.0]`.
+    pub opposition: f32,
+    /// `support - opposition`, in `[-1.0, 1.0]`.
+    pub net: f32,
+    /// Each incoming `Supports` edge, strongest first.
+    pub supporters: Vec<SupportContribution>,
+    /// Each incoming `Contradicts` edge, strongest first.
+    pub opposers: Vec<SupportContribution>,
+}
+
 /// A subgraph extracted around a center node.
 pub struct SubGraph {
     /// All nodes in the subgraph.
@@ -206,9 +328,23 @@ impl QueryEngine {
         graph: &MemoryGraph,
         params: TraversalParams,
     ) -> AmemResult<TraversalResult> {
-        let (visited, edges_traversed, depths) = bfs_traverse(
+        let start_id = params.start_id;
+        self.traverse_multi(graph, &[start_id], params)
+    }
+
+    /// Traverse from several starting nodes at once; `params.start_id` is ignored.
+    ///
+    /// Each reachable node is returned once with its minimum depth from any
+    /// start, and `sources` records which start reached it.
+    pub fn traverse_multi(
+        &self,
+        graph: &MemoryGraph,
+        start_ids: &[u64],
+        params: TraversalParams,
+    ) -> AmemResult<TraversalResult> {
+        let (visited, edges_traversed, depths, back_edges, sources) = bfs_traverse_multi(
             graph,
-            params.start_id,
+            start_ids,
             &params.edge_types,
             params.direction,
             params.max_depth,
@@ -216,10 +352,16 @@ impl QueryEngine {
             params.min_confidence,
         )?;
 
+        let (frontier, truncated_at_depth) = unexplored_frontier(graph, &visited, &depths, &params);
+
         Ok(TraversalResult {
             visited,
             edges_traversed,
             depths,
+            back_edges,
+            sources,
+            frontier,
+            truncated_at_depth,
         })
     }
 
@@ -266,31 +408,15 @@ impl QueryEngine {
         if let Some(min_decay) = params.min_decay_score {
             candidates.retain(|n| n.decay_score >= min_decay);
         }
-
-        // Sort
-        match params.sort_by {
-            PatternSort::MostRecent => {
-                candidates.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-            }
-            PatternSort::HighestConfidence => {
-                candidates.sort_by(|a, b| {
-                    b.confidence
-                        .partial_cmp(&a.confidence)
-                        .unwrap_or(std::cmp::Ordering::Equal)
-                });
-            }
-            PatternSort::MostAccessed => {
-                candidates.sort_by(|a, b| b.access_count.cmp(&a.access_count));
-            }
-            PatternSort::MostImportant => {
-                candidates.sort_by(|a, b| {
-                    b.decay_score
-                        .partial_cmp(&a.decay_score)
-                        .unwrap_or(std::cmp::Ordering::Equal)
-                });
-            }
+        if let Some(max_decay) = params.max_decay_score {
+            candidates.retain(|n| n.decay_score <= max_decay);
         }
 
+        // Sort; ties fall back to node id so repeated queries return the same order.
+        let sort = params.sort_by;
+        candidates
+            .sort_by(|a, b| PatternSort::compare_keys((sort.key(a), a.id), (sort.key(b), b.id)));
+
         candidates.truncate(params.max_results);
         Ok(candidates)
     }
@@ -379,12 +505,22 @@ impl QueryEngine {
         let mut dependency_tree: HashMap<u64, Vec<(u64, EdgeType)>> = HashMap::new();
         let mut visited: HashSet<u64> = HashSet::new();
         let mut queue: VecDeque<(u64, u32)> = VecDeque::new();
+        let mut unexplored: HashSet<u64> = HashSet::new();
 
         visited.insert(params.node_id);
         queue.push_back((params.node_id, 0));
 
         while let Some((current_id, depth)) = queue.pop_front() {
             if depth >= params.max_depth {
+                unexplored.extend(
+                    graph
+                        .edges_to(current_id)
+                        .into_iter()
+                        .filter(|e| {
+                            dep_set.contains(&e.edge_type) && !visited.contains(&e.source_id)
+                        })
+                        .map(|e| e.source_id),
+                );
                 continue;
             }
 
@@ -421,6 +557,8 @@ impl QueryEngine {
             dependency_tree,
             affected_decisions,
             affected_inferences,
+            frontier: unexplored.len(),
+            truncated_at_depth: (!unexplored.is_empty()).then_some(params.max_depth),
         })
     }
 
@@ -430,11 +568,32 @@ impl QueryEngine {
         graph: &MemoryGraph,
         params: SimilarityParams,
     ) -> AmemResult<Vec<SimilarityMatchResult>> {
+        self.similarity_cancellable(graph, params, &|| false)
+            .map(|(matches, _)| matches)
+    }
+
+    /// Like [`Self::similarity`], but polls `cancelled` before each candidate
+    /// and stops scanning once it returns true.
+    ///
+    /// Returns the best matches among the nodes scanned so far, and whether
+    /// the scan was cut short.
+    pub fn similarity_cancellable(
+        &self,
+        graph: &MemoryGraph,
+        params: SimilarityParams,
+        cancelled: &dyn Fn() -> bool,
+    ) -> AmemResult<(Vec<SimilarityMatchResult>, bool)> {
         let type_filter: HashSet<EventType> = params.event_types.iter().copied().collect();
 
         let mut matches: Vec<SimilarityMatchResult> = Vec::new();
+        let mut was_cancelled = false;
 
         for node in graph.nodes() {
+            if cancelled() {
+                was_cancelled = true;
+                break;
+            }
+
             // Type filter
             if !type_filter.is_empty() && !type_filter.contains(&node.event_type) {
                 continue;
@@ -445,7 +604,7 @@ impl QueryEngine {
                 continue;
             }
 
-            let sim = cosine_similarity(&params.query_vec, &node.feature_vec);
+            let sim = params.metric.score(&params.query_vec, &node.feature_vec);
             if sim >= params.min_similarity {
                 matches.push(SimilarityMatchResult {
                     node_id: node.id,
@@ -461,7 +620,63 @@ impl QueryEngine {
         });
         matches.truncate(params.top_k);
 
-        Ok(matches)
+        Ok((matches, was_cancelled))
+    }
+
+    /// Aggregate how well the graph supports a node.
+    ///
+    /// Each incoming edge from node `i` has strength `s_i = w_i * c_i`, its
+    /// weight times the source node's confidence, both clamped to `[0, 1]`.
+    /// Strengths combine by noisy-OR, `1 - prod(1 - s_i)`: independent
+    /// evidence accumulates with diminishing returns and never exceeds 1.
+    /// `support` uses `Supports` edges, `opposition` uses `Contradicts` edges,
+    /// and `net = support - opposition`. No edges scores 0.
+    pub fn support_score(&self, graph: &MemoryGraph, node_id: u64) -> AmemResult<SupportScore> {
+        if graph.get_node(node_id).is_none() {
+            return Err(AmemError::NodeNotFound(node_id));
+        }
+
+        let mut supporters = Vec::new();
+        let mut opposers = Vec::new();
+        for edge in graph.edges_to(node_id) {
+            let bucket = match edge.edge_type {
+                EdgeType::Supports => &mut supporters,
+                EdgeType::Contradicts => &mut opposers,
+                _ => continue,
+            };
+            let Some(source) = graph.get_node(edge.source_id) else {
+                continue;
+            };
+            let weight = edge.weight.clamp(0.0, 1.0);
+            let confidence = source.confidence.clamp(0.0, 1.0);
+            bucket.push(SupportContribution {
+                node_id: source.id,
+                confidence: source.confidence,
+                weight: edge.weight,
+                strength: weight * confidence,
+            });
+        }
+
+        let noisy_or = |items: &mut Vec<SupportContribution>| -> f32 {
+            items.sort_by(|a, b| {
+                b.strength
+                    .partial_cmp(&a.strength)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(a.node_id.cmp(&b.node_id))
+            });
+            1.0 - items.iter().map(|c| 1.0 - c.strength).product::<f32>()
+        };
+        let support = noisy_or(&mut supporters);
+        let opposition = noisy_or(&mut opposers);
+
+        Ok(SupportScore {
+            node_id,
+            support,
+            opposition,
+            net: support - opposition,
+            supporters,
+            opposers,
+        })
     }
 
     /// Evaluate memory quality across confidence, freshness, and graph structure.
@@ -564,10 +779,6 @@ impl QueryEngine {
 
     /// Get the full context for a node: the node itself, all edges, and connected nodes.
     pub fn context(&self, graph: &MemoryGraph, node_id: u64, depth: u32) -> AmemResult<SubGraph> {
-        if graph.get_node(node_id).is_none() {
-            return Err(AmemError::NodeNotFound(node_id));
-        }
-
         // BFS in all directions, following all edge types
         let all_edge_types: Vec<EdgeType> = vec![
             EdgeType::CausedBy,
@@ -578,11 +789,25 @@ impl QueryEngine {
             EdgeType::PartOf,
             EdgeType::TemporalNext,
         ];
+        self.context_with_edge_types(graph, node_id, depth, &all_edge_types)
+    }
+
+    /// Get the context for a node, expanding and returning only edges of `edge_types`.
+    pub fn context_with_edge_types(
+        &self,
+        graph: &MemoryGraph,
+        node_id: u64,
+        depth: u32,
+        edge_types: &[EdgeType],
+    ) -> AmemResult<SubGraph> {
+        if graph.get_node(node_id).is_none() {
+            return Err(AmemError::NodeNotFound(node_id));
+        }
 
-        let (visited, _, _) = bfs_traverse(
+        let (visited, _, _, _) = bfs_traverse(
             graph,
             node_id,
-            &all_edge_types,
+            edge_types,
             TraversalDirection::Both,
             depth,
             usize::MAX,
@@ -601,7 +826,11 @@ impl QueryEngine {
         let edges: Vec<Edge> = graph
             .edges()
             .iter()
-            .filter(|e| visited_set.contains(&e.source_id) && visited_set.contains(&e.target_id))
+            .filter(|e| {
+                edge_types.contains(&e.edge_type)
+                    && visited_set.contains(&e.source_id)
+                    && visited_set.contains(&e.target_id)
+            })
             .copied()
             .collect();
 
@@ -644,6 +873,66 @@ impl QueryEngine {
             .get_node(current_id)
             .ok_or(AmemError::NodeNotFound(current_id))
     }
+
+    /// The versions of a belief along its SUPERSEDES chain, oldest first.
+    ///
+    /// Runs from `node_id` to the latest version, or from the original when
+    /// `include_earlier` is set. Where a node supersedes several older ones
+    /// (as after chain compaction), the most recently created is followed.
+    pub fn history<'a>(
+        &self,
+        graph: &'a MemoryGraph,
+        node_id: u64,
+        include_earlier: bool,
+    ) -> AmemResult<Vec<&'a CognitiveEvent>> {
+        let node = graph
+            .get_node(node_id)
+            .ok_or(AmemError::NodeNotFound(node_id))?;
+        let mut seen = HashSet::from([node_id]);
+
+        let mut earlier = Vec::new();
+        if include_earlier {
+            let mut current = node;
+            for _ in 0..100 {
+                let previous = graph
+                    .edges_from(current.id)
+                    .iter()
+                    .filter(|e| e.edge_type == EdgeType::Supersedes)
+                    .filter_map(|e| graph.get_node(e.target_id))
+                    .filter(|n| !seen.contains(&n.id))
+                    .max_by_key(|n| (n.created_at, n.id));
+                match previous {
+                    Some(previous) => {
+                        seen.insert(previous.id);
+                        earlier.push(previous);
+                        current = previous;
+                    }
+                    None => break,
+                }
+            }
+            earlier.reverse();
+        }
+
+        let mut versions = earlier;
+        versions.push(node);
+        let mut current = node;
+        for _ in 0..100 {
+            let next = graph
+                .edges_to(current.id)
+                .iter()
+                .find(|e| e.edge_type == EdgeType::Supersedes && !seen.contains(&e.source_id))
+                .and_then(|e| graph.get_node(e.source_id));
+            match next {
+                Some(next) => {
+                    seen.insert(next.id);
+                    versions.push(next);
+                    current = next;
+                }
+                None => break,
+            }
+        }
+        Ok(versions)
+    }
 }
 
 impl Default for QueryEngine {
@@ -651,3 +940,68 @@ impl Default for QueryEngine {
         Self::new()
     }
 }
+
+/// Count the nodes one hop beyond a finished traversal that it would have
+/// followed (matching edge type, direction, and confidence) but never visited.
+///
+/// Returns that count and the shallowest depth of a visited node with such a
+/// neighbor.
+fn unexplored_frontier(
+    graph: &MemoryGraph,
+    visited: &[u64],
+    depths: &HashMap<u64, u32>,
+    params: &TraversalParams,
+) -> (usize, Option<u32>) {
+    let edge_set: HashSet<EdgeType> = params.edge_types.iter().copied().collect();
+    let forward = matches!(
+        params.direction,
+        TraversalDirection::Forward | TraversalDirection::Both
+    );
+    let backward = matches!(
+        params.direction,
+        TraversalDirection::Backward | TraversalDirection::Both
+    );
+    let seen: HashSet<u64> = visited.iter().copied().collect();
+    let followable = |id: u64| {
+        !seen.contains(&id)
+            && graph
+                .get_node(id)
+                .is_some_and(|n| n.confidence >= params.min_confidence)
+    };
+
+    let mut unexplored: HashSet<u64> = HashSet::new();
+    let mut shallowest: Option<u32> = None;
+    for &id in visited {
+        let mut neighbors: Vec<u64> = Vec::new();
+        if forward {
+            neighbors.extend(
+                graph
+                    .edges_from(id)
+                    .iter()
+                    .filter(|e| edge_set.contains(&e.edge_type))
+                    .map(|e| e.target_id),
+            );
+        }
+        if backward {
+            neighbors.extend(
+                graph
+                    .edges_to(id)
+                    .into_iter()
+                    .filter(|e| edge_set.contains(&e.edge_type))
+                    .map(|e| e.source_id),
+            );
+        }
+        let mut cut_here = false;
+        for neighbor in neighbors {
+            if followable(neighbor) {
+                unexplored.insert(neighbor);
+                cut_here = true;
+            }
+        }
+        if cut_here {
+            let depth = depths.get(&id).copied().unwrap_or(0);
+            shallowest = Some(shallowest.map_or(depth, |d| d.min(depth)));
+        }
+    }
+    (unexplored.len(), shallowest)
+}
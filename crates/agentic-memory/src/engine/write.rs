@@ -55,11 +55,14 @@ impl WriteEngine {
             new_node_ids.push(id);
         }
 
-        // Step 4-5: Validate and add all edges
+        // Step 4-5: Validate and add all edges; repeats of an existing edge are skipped
         let mut new_edge_count = 0;
         for edge in edges {
-            graph.add_edge(edge)?;
-            new_edge_count += 1;
+            match graph.add_edge(edge) {
+                Ok(()) => new_edge_count += 1,
+                Err(AmemError::DuplicateEdge { .. }) => {}
+                Err(e) => return Err(e),
+            }
         }
 
         // Ensure adjacency is rebuilt after bulk edge insertion
@@ -134,6 +137,18 @@ impl WriteEngine {
         graph: &mut MemoryGraph,
         session_id: u32,
         summary: &str,
+    ) -> AmemResult<u64> {
+        self.compress_session_with_links(graph, session_id, summary, true)
+    }
+
+    /// Compress a session into an episode node, adding PART_OF edges from
+    /// the session's nodes to the episode only if `link_members` is set.
+    pub fn compress_session_with_links(
+        &self,
+        graph: &mut MemoryGraph,
+        session_id: u32,
+        summary: &str,
+        link_members: bool,
     ) -> AmemResult<u64> {
         // Find all nodes in this session
         let session_node_ids: Vec<u64> = graph.session_index().get_session(session_id).to_vec();
@@ -148,9 +163,11 @@ impl WriteEngine {
         let episode_id = graph.add_node(event)?;
 
         // Create PART_OF edges from each session node to the episode
-        for &node_id in &session_node_ids {
-            let edge = Edge::new(node_id, episode_id, EdgeType::PartOf, 1.0);
-            graph.add_edge(edge)?;
+        if link_members {
+            for &node_id in &session_node_ids {
+                let edge = Edge::new(node_id, episode_id, EdgeType::PartOf, 1.0);
+                graph.add_edge(edge)?;
+            }
         }
 
         // Ensure adjacency is rebuilt
@@ -6,7 +6,7 @@ use std::path::Path;
 
 use crate::format::{AmemReader, AmemWriter};
 use crate::graph::MemoryGraph;
-use crate::types::{CognitiveEventBuilder, Edge, EdgeType, EventType};
+use crate::types::{AmemError, CognitiveEventBuilder, Edge, EdgeType, EventType};
 
 const AMEM_OK: i32 = 0;
 const AMEM_ERR_IO: i32 = -1;
@@ -284,7 +284,8 @@ pub unsafe extern "C" fn amem_graph_get_event_type(
     .unwrap_or(-1)
 }
 
-/// Add an edge. Returns AMEM_OK or error code.
+/// Add an edge. Returns AMEM_OK or error code. Adding an edge that already
+/// exists changes nothing and returns AMEM_OK.
 ///
 /// # Safety
 ///
@@ -310,7 +311,7 @@ pub unsafe extern "C" fn amem_graph_add_edge(
         };
         let edge = Edge::new(source_id, target_id, et, weight);
         match graph.add_edge(edge) {
-            Ok(()) => AMEM_OK,
+            Ok(()) | Err(AmemError::DuplicateEdge { .. }) => AMEM_OK,
             Err(_) => AMEM_ERR_INVALID,
         }
     })
@@ -13,3 +13,15 @@ pub fn decompress_content(data: &[u8]) -> AmemResult<String> {
         .map_err(|e| AmemError::Compression(e.to_string()))?;
     String::from_utf8(bytes).map_err(|e| AmemError::Compression(e.to_string()))
 }
+
+/// Decompress content, replacing invalid UTF-8 (such as a character cut in
+/// half by an older writer) with U+FFFD. The flag is true if anything was
+/// replaced.
+pub fn decompress_content_lossy(data: &[u8]) -> AmemResult<(String, bool)> {
+    let bytes = lz4_flex::decompress_size_prepended(data)
+        .map_err(|e| AmemError::Compression(e.to_string()))?;
+    match String::from_utf8(bytes) {
+        Ok(content) => Ok((content, false)),
+        Err(e) => Ok((String::from_utf8_lossy(e.as_bytes()).into_owned(), true)),
+    }
+}
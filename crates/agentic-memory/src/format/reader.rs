@@ -9,7 +9,7 @@ use crate::types::error::{AmemError, AmemResult};
 use crate::types::header::FileHeader;
 use crate::types::{CognitiveEvent, Edge, EdgeType, EventType};
 
-use super::compression::decompress_content;
+use super::compression::{decompress_content, decompress_content_lossy};
 
 /// Reader for .amem binary files.
 pub struct AmemReader;
@@ -24,6 +24,17 @@ impl AmemReader {
 
     /// Read from any reader into a MemoryGraph.
     pub fn read_from(reader: &mut impl Read) -> AmemResult<MemoryGraph> {
+        Self::read_with(reader, false).map(|(graph, _)| graph)
+    }
+
+    /// Read an .amem file, replacing invalid UTF-8 in node content instead
+    /// of failing. Also returns how many nodes had their content replaced.
+    pub fn read_from_file_lossy(path: &Path) -> AmemResult<(MemoryGraph, usize)> {
+        let data = std::fs::read(path)?;
+        Self::read_with(&mut std::io::Cursor::new(data), true)
+    }
+
+    fn read_with(reader: &mut impl Read, lossy: bool) -> AmemResult<(MemoryGraph, usize)> {
         // Read all data into a buffer
         let mut data = Vec::new();
         reader.read_to_end(&mut data)?;
@@ -70,6 +81,7 @@ impl AmemReader {
 
         // Read content block
         let content_block_start = header.content_block_offset as usize;
+        let mut lossy_count = 0;
         for (i, node) in nodes.iter_mut().enumerate() {
             let (content_offset, content_length) = node_content_info[i];
             if content_length > 0 {
@@ -78,7 +90,13 @@ impl AmemReader {
                 if end > data.len() {
                     return Err(AmemError::Truncated);
                 }
-                node.content = decompress_content(&data[start..end])?;
+                if lossy {
+                    let (content, replaced) = decompress_content_lossy(&data[start..end])?;
+                    node.content = content;
+                    lossy_count += usize::from(replaced);
+                } else {
+                    node.content = decompress_content(&data[start..end])?;
+                }
             }
         }
 
@@ -143,7 +161,7 @@ impl AmemReader {
             }
         }
 
-        Ok(graph)
+        Ok((graph, lossy_count))
     }
 }
 
@@ -0,0 +1,246 @@
+//! Referential integrity checks over a loaded graph.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::MemoryGraph;
+use crate::types::{EdgeType, EventType};
+
+/// A single integrity problem found in a graph.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntegrityIssue {
+    /// An edge whose source node does not exist.
+    MissingSource {
+        source_id: u64,
+        target_id: u64,
+        edge_type: EdgeType,
+    },
+    /// An edge whose target node does not exist.
+    MissingTarget {
+        source_id: u64,
+        target_id: u64,
+        edge_type: EdgeType,
+    },
+    /// Several edges with the same source, target, and type.
+    DuplicateEdge {
+        source_id: u64,
+        target_id: u64,
+        edge_type: EdgeType,
+        count: usize,
+    },
+    /// A cycle of `Supersedes` edges; ids are listed in chain order.
+    SupersedesCycle { node_ids: Vec<u64> },
+    /// An episode node that no `PartOf` edge points at.
+    EmptyEpisode { node_id: u64 },
+    /// A node whose feature vector does not match the graph dimension.
+    DimensionMismatch {
+        node_id: u64,
+        expected: usize,
+        got: usize,
+    },
+}
+
+impl std::fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingSource {
+                source_id,
+                target_id,
+                edge_type,
+            } => write!(
+                f,
+                "edge {source_id} -> {target_id} ({}): source node missing",
+                edge_type.name()
+            ),
+            Self::MissingTarget {
+                source_id,
+                target_id,
+                edge_type,
+            } => write!(
+                f,
+                "edge {source_id} -> {target_id} ({}): target node missing",
+                edge_type.name()
+            ),
+            Self::DuplicateEdge {
+                source_id,
+                target_id,
+                edge_type,
+                count,
+            } => write!(
+                f,
+                "edge {source_id} -> {target_id} ({}): stored {count} times",
+                edge_type.name()
+            ),
+            Self::SupersedesCycle { node_ids } => {
+                let chain: Vec<String> = node_ids.iter().map(|id| id.to_string()).collect();
+                write!(f, "supersedes cycle: {}", chain.join(" -> "))
+            }
+            Self::EmptyEpisode { node_id } => {
+                write!(f, "episode node {node_id} has no part_of members")
+            }
+            Self::DimensionMismatch {
+                node_id,
+                expected,
+                got,
+            } => write!(
+                f,
+                "node {node_id}: feature vector has dimension {got}, expected {expected}"
+            ),
+        }
+    }
+}
+
+/// Result of [`check_integrity`].
+#[derive(Debug, Clone, Default)]
+pub struct IntegrityReport {
+    /// Every problem found, in a stable order.
+    pub issues: Vec<IntegrityIssue>,
+    /// Nodes with no incoming or outgoing edges. Reported, not an error.
+    pub orphan_count: usize,
+}
+
+impl IntegrityReport {
+    /// True when no problems were found.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Walk the graph and report dangling edges, duplicate edges, supersedes
+/// cycles, empty episodes, wrong-dimension vectors, and the number of orphan
+/// nodes.
+pub fn check_integrity(graph: &MemoryGraph) -> IntegrityReport {
+    let mut report = IntegrityReport::default();
+    let node_ids: HashSet<u64> = graph.nodes().iter().map(|n| n.id).collect();
+
+    let mut connected: HashSet<u64> = HashSet::new();
+    let mut episodes_with_members: HashSet<u64> = HashSet::new();
+    let mut supersedes: HashMap<u64, Vec<u64>> = HashMap::new();
+    let mut edge_counts: HashMap<(u64, u64, EdgeType), usize> = HashMap::new();
+
+    for edge in graph.edges() {
+        *edge_counts
+            .entry((edge.source_id, edge.target_id, edge.edge_type))
+            .or_default() += 1;
+        if !node_ids.contains(&edge.source_id) {
+            report.issues.push(IntegrityIssue::MissingSource {
+                source_id: edge.source_id,
+                target_id: edge.target_id,
+                edge_type: edge.edge_type,
+            });
+        }
+        if !node_ids.contains(&edge.target_id) {
+            report.issues.push(IntegrityIssue::MissingTarget {
+                source_id: edge.source_id,
+                target_id: edge.target_id,
+                edge_type: edge.edge_type,
+            });
+        }
+        connected.insert(edge.source_id);
+        connected.insert(edge.target_id);
+        match edge.edge_type {
+            EdgeType::PartOf => {
+                episodes_with_members.insert(edge.target_id);
+            }
+            EdgeType::Supersedes => {
+                supersedes
+                    .entry(edge.source_id)
+                    .or_default()
+                    .push(edge.target_id);
+            }
+            _ => {}
+        }
+    }
+
+    let mut duplicates: Vec<((u64, u64, EdgeType), usize)> = edge_counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .collect();
+    duplicates.sort_by_key(|((source, target, edge_type), _)| (*source, *target, *edge_type as u8));
+    for ((source_id, target_id, edge_type), count) in duplicates {
+        report.issues.push(IntegrityIssue::DuplicateEdge {
+            source_id,
+            target_id,
+            edge_type,
+            count,
+        });
+    }
+
+    for cycle in supersedes_cycles(&supersedes) {
+        report
+            .issues
+            .push(IntegrityIssue::SupersedesCycle { node_ids: cycle });
+    }
+
+    for node in graph.nodes() {
+        if node.event_type == EventType::Episode && !episodes_with_members.contains(&node.id) {
+            report
+                .issues
+                .push(IntegrityIssue::EmptyEpisode { node_id: node.id });
+        }
+        if node.feature_vec.len() != graph.dimension() {
+            report.issues.push(IntegrityIssue::DimensionMismatch {
+                node_id: node.id,
+                expected: graph.dimension(),
+                got: node.feature_vec.len(),
+            });
+        }
+        if !connected.contains(&node.id) {
+            report.orphan_count += 1;
+        }
+    }
+
+    report
+}
+
+/// Find each distinct cycle in the supersedes graph with an iterative
+/// three-colour DFS. Starts are visited in ascending id order so output is
+/// deterministic.
+fn supersedes_cycles(supersedes: &HashMap<u64, Vec<u64>>) -> Vec<Vec<u64>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Colour {
+        Grey,
+        Black,
+    }
+
+    let mut starts: Vec<u64> = supersedes.keys().copied().collect();
+    starts.sort_unstable();
+
+    let mut colour: HashMap<u64, Colour> = HashMap::new();
+    let mut cycles = Vec::new();
+
+    for start in starts {
+        if colour.contains_key(&start) {
+            continue;
+        }
+        // Stack of (node, index of next child to visit); `path` mirrors it.
+        let mut stack: Vec<(u64, usize)> = vec![(start, 0)];
+        let mut path: Vec<u64> = vec![start];
+        colour.insert(start, Colour::Grey);
+
+        while let Some(frame) = stack.last_mut() {
+            let node = frame.0;
+            let children = supersedes.get(&node).map(Vec::as_slice).unwrap_or(&[]);
+            if let Some(&child) = children.get(frame.1) {
+                frame.1 += 1;
+                match colour.get(&child) {
+                    None => {
+                        colour.insert(child, Colour::Grey);
+                        stack.push((child, 0));
+                        path.push(child);
+                    }
+                    Some(Colour::Grey) => {
+                        let pos = path.iter().position(|&id| id == child).unwrap_or(0);
+                        cycles.push(path[pos..].to_vec());
+                    }
+                    Some(Colour::Black) => {}
+                }
+            } else {
+                colour.insert(node, Colour::Black);
+                stack.pop();
+                path.pop();
+            }
+        }
+    }
+
+    cycles
+}
@@ -1,9 +1,12 @@
 //! Core graph structure — nodes + edges with adjacency indexes.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use crate::engine::tokenizer::Tokenizer;
 use crate::index::{ClusterMap, DocLengths, SessionIndex, TemporalIndex, TermIndex, TypeIndex};
-use crate::types::{AmemError, AmemResult, CognitiveEvent, Edge, EdgeType, MAX_EDGES_PER_NODE};
+use crate::types::{
+    AmemError, AmemResult, CognitiveEvent, Edge, EdgeType, EventType, MAX_EDGES_PER_NODE,
+};
 
 /// The core in-memory graph structure holding cognitive events and their relationships.
 pub struct MemoryGraph {
@@ -201,6 +204,7 @@ impl MemoryGraph {
         self.type_index.add_node(&event);
         self.temporal_index.add_node(&event);
         self.session_index.add_node(&event);
+        self.index_text(&event);
 
         self.nodes.push(event);
 
@@ -224,6 +228,19 @@ impl MemoryGraph {
             return Err(AmemError::InvalidEdgeTarget(edge.target_id));
         }
 
+        // Validate: no exact duplicate of an existing edge
+        if self
+            .edges_from(edge.source_id)
+            .iter()
+            .any(|e| e.target_id == edge.target_id && e.edge_type == edge.edge_type)
+        {
+            return Err(AmemError::DuplicateEdge {
+                source_id: edge.source_id,
+                target_id: edge.target_id,
+                edge_type: edge.edge_type,
+            });
+        }
+
         // Check max edges per node
         let current_count = self
             .adjacency
@@ -254,6 +271,12 @@ impl MemoryGraph {
         self.type_index.remove_node(id, removed.event_type);
         self.temporal_index.remove_node(id, removed.created_at);
         self.session_index.remove_node(id, removed.session_id);
+        if let Some(term_index) = self.term_index.as_mut() {
+            term_index.remove_node(id);
+        }
+        if let Some(doc_lengths) = self.doc_lengths.as_mut() {
+            doc_lengths.remove_node(id);
+        }
 
         // Remove all edges involving this node
         self.edges
@@ -265,6 +288,80 @@ impl MemoryGraph {
         Ok(removed)
     }
 
+    /// Re-insert a previously removed node under its original ID.
+    ///
+    /// Edges are not restored; add them back with [`Self::add_edge`].
+    pub fn restore_node(&mut self, event: CognitiveEvent) -> AmemResult<()> {
+        event.validate(self.dimension)?;
+        if event.feature_vec.len() != self.dimension {
+            return Err(AmemError::DimensionMismatch {
+                expected: self.dimension,
+                got: event.feature_vec.len(),
+            });
+        }
+        if self.get_node(event.id).is_some() {
+            return Err(AmemError::DuplicateNode(event.id));
+        }
+
+        self.type_index.add_node(&event);
+        self.temporal_index.add_node(&event);
+        self.session_index.add_node(&event);
+        self.index_text(&event);
+
+        self.next_id = self.next_id.max(event.id + 1);
+        let pos = self.nodes.partition_point(|n| n.id < event.id);
+        self.nodes.insert(pos, event);
+
+        Ok(())
+    }
+
+    /// Change a node's event type, keeping the type index consistent.
+    ///
+    /// Returns the node's previous event type.
+    pub fn retype_node(&mut self, id: u64, new_type: EventType) -> AmemResult<EventType> {
+        let node = self.get_node_mut(id).ok_or(AmemError::NodeNotFound(id))?;
+        let old_type = node.event_type;
+        if old_type == new_type {
+            return Ok(old_type);
+        }
+        node.event_type = new_type;
+        let updated = node.clone();
+
+        self.type_index.remove_node(id, old_type);
+        self.type_index.add_node(&updated);
+
+        Ok(old_type)
+    }
+
+    /// Set the weight of an existing edge, clamped to `[0.0, 1.0]`.
+    ///
+    /// Returns the edge's previous weight.
+    pub fn set_edge_weight(
+        &mut self,
+        source_id: u64,
+        target_id: u64,
+        edge_type: EdgeType,
+        weight: f32,
+    ) -> AmemResult<f32> {
+        if !weight.is_finite() {
+            return Err(AmemError::InvalidWeight(weight));
+        }
+        let edge = self
+            .edges
+            .iter_mut()
+            .find(|e| {
+                e.source_id == source_id && e.target_id == target_id && e.edge_type == edge_type
+            })
+            .ok_or(AmemError::EdgeNotFound {
+                source_id,
+                target_id,
+                edge_type,
+            })?;
+        let previous = edge.weight;
+        edge.weight = weight.clamp(0.0, 1.0);
+        Ok(previous)
+    }
+
     /// Remove a specific edge.
     pub fn remove_edge(
         &mut self,
@@ -283,6 +380,46 @@ impl MemoryGraph {
         Ok(())
     }
 
+    /// Collapse edges that share source, target, and type into one, keeping
+    /// the highest weight. Returns the number of edges removed.
+    ///
+    /// `add_edge` rejects duplicates, but files written by older versions or
+    /// assembled with `from_parts` can still contain them.
+    pub fn dedup_edges(&mut self) -> usize {
+        let before = self.edges.len();
+        let mut kept: Vec<Edge> = Vec::with_capacity(before);
+        let mut index: HashMap<(u64, u64, EdgeType), usize> = HashMap::new();
+        for edge in self.edges.drain(..) {
+            match index.get(&(edge.source_id, edge.target_id, edge.edge_type)) {
+                Some(&i) => kept[i].weight = kept[i].weight.max(edge.weight),
+                None => {
+                    index.insert((edge.source_id, edge.target_id, edge.edge_type), kept.len());
+                    kept.push(edge);
+                }
+            }
+        }
+        self.edges = kept;
+        let removed = before - self.edges.len();
+        if removed > 0 {
+            self.rebuild_adjacency();
+        }
+        removed
+    }
+
+    /// Drop edges whose source or target node does not exist. Returns the
+    /// number of edges removed.
+    pub fn remove_dangling_edges(&mut self) -> usize {
+        let node_ids: HashSet<u64> = self.nodes.iter().map(|n| n.id).collect();
+        let before = self.edges.len();
+        self.edges
+            .retain(|e| node_ids.contains(&e.source_id) && node_ids.contains(&e.target_id));
+        let removed = before - self.edges.len();
+        if removed > 0 {
+            self.rebuild_adjacency();
+        }
+        removed
+    }
+
     /// Rebuild adjacency indexes from the current edge list.
     fn rebuild_adjacency(&mut self) {
         self.adjacency.clear();
@@ -366,4 +503,29 @@ impl MemoryGraph {
     pub fn set_doc_lengths(&mut self, lengths: DocLengths) {
         self.doc_lengths = Some(lengths);
     }
+
+    /// Build the term index and doc lengths from every node's content.
+    ///
+    /// Once built, both are kept current by node adds, removals and restores.
+    pub fn build_text_index(&mut self) {
+        let tokenizer = Tokenizer::new();
+        self.term_index = Some(TermIndex::build(self, &tokenizer));
+        self.doc_lengths = Some(DocLengths::build(self, &tokenizer));
+    }
+
+    /// Drop the term index and doc lengths, falling back to full scans.
+    pub fn clear_text_index(&mut self) {
+        self.term_index = None;
+        self.doc_lengths = None;
+    }
+
+    /// Add a node's content to the text indexes, if they are built.
+    fn index_text(&mut self, event: &CognitiveEvent) {
+        if let Some(term_index) = self.term_index.as_mut() {
+            term_index.add_node(event);
+        }
+        if let Some(doc_lengths) = self.doc_lengths.as_mut() {
+            doc_lengths.add_node(event);
+        }
+    }
 }
@@ -1,9 +1,13 @@
 //! In-memory graph operations — the core data structure.
 
 pub mod builder;
+pub mod integrity;
 pub mod memory_graph;
+pub mod repair;
 pub mod traversal;
 
 pub use builder::GraphBuilder;
+pub use integrity::{check_integrity, IntegrityIssue, IntegrityReport};
 pub use memory_graph::MemoryGraph;
-pub use traversal::{bfs_traverse, TraversalDirection};
+pub use repair::{repair_graph, RepairReport};
+pub use traversal::{bfs_traverse, bfs_traverse_multi, TraversalDirection};
@@ -0,0 +1,85 @@
+//! Fixups that bring a graph read from an older file up to what the current
+//! version expects.
+
+use crate::engine::decay::calculate_decay;
+use crate::graph::MemoryGraph;
+use crate::types::MAX_CONTENT_SIZE;
+
+/// What [`repair_graph`] changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RepairReport {
+    /// Duplicate edges collapsed into one.
+    pub duplicate_edges_removed: usize,
+    /// Edges dropped because their source or target node was missing.
+    pub dangling_edges_removed: usize,
+    /// Nodes whose decay score was recomputed to a different value.
+    pub decay_scores_updated: usize,
+    /// Nodes given a zero vector of the graph dimension because theirs was
+    /// missing, the wrong length, or held NaN or infinite values.
+    pub vectors_filled: usize,
+    /// Nodes whose invalid UTF-8 content the lossy read replaced, as passed
+    /// in by the caller.
+    pub contents_replaced: usize,
+    /// Nodes whose content was cut back to the size limit at a character
+    /// boundary.
+    pub contents_truncated: usize,
+    /// Whether the stored text index was dropped because content changed.
+    pub text_index_dropped: bool,
+}
+
+impl RepairReport {
+    /// True when the graph needed no changes.
+    pub fn is_clean(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Deduplicate and drop dangling edges, recompute decay scores as of `now`
+/// (Unix epoch microseconds), zero-fill unusable feature vectors, and cut
+/// oversized content at a character boundary.
+///
+/// `contents_replaced` is the count returned by
+/// [`AmemReader::read_from_file_lossy`](crate::format::AmemReader::read_from_file_lossy);
+/// content it rewrote also invalidates the stored text index.
+pub fn repair_graph(graph: &mut MemoryGraph, now: u64, contents_replaced: usize) -> RepairReport {
+    let mut report = RepairReport {
+        duplicate_edges_removed: graph.dedup_edges(),
+        dangling_edges_removed: graph.remove_dangling_edges(),
+        contents_replaced,
+        ..Default::default()
+    };
+
+    let dimension = graph.dimension();
+    let node_ids: Vec<u64> = graph.nodes().iter().map(|n| n.id).collect();
+    for id in node_ids {
+        let Some(node) = graph.get_node_mut(id) else {
+            continue;
+        };
+        if node.feature_vec.len() != dimension || node.feature_vec.iter().any(|x| !x.is_finite()) {
+            node.feature_vec = vec![0.0; dimension];
+            report.vectors_filled += 1;
+        }
+        if node.content.len() > MAX_CONTENT_SIZE {
+            let mut end = MAX_CONTENT_SIZE;
+            while !node.content.is_char_boundary(end) {
+                end -= 1;
+            }
+            node.content.truncate(end);
+            report.contents_truncated += 1;
+        }
+        let decay = calculate_decay(node, now);
+        if (decay - node.decay_score).abs() > f32::EPSILON {
+            node.decay_score = decay;
+            report.decay_scores_updated += 1;
+        }
+    }
+
+    // A stored index over the old content would no longer match it.
+    let content_changed = report.contents_replaced > 0 || report.contents_truncated > 0;
+    if content_changed && graph.term_index.is_some() {
+        graph.clear_text_index();
+        report.text_index_dropped = true;
+    }
+
+    report
+}
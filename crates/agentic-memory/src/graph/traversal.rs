@@ -18,6 +18,10 @@ pub enum TraversalDirection {
 }
 
 /// BFS traversal from a starting node, following specific edge types.
+///
+/// Each node is visited at most once. Edges that lead back to an ancestor of
+/// the current node on the BFS tree (i.e. close a cycle) are returned as
+/// back-edges rather than followed.
 #[allow(clippy::type_complexity)]
 pub fn bfs_traverse(
     graph: &MemoryGraph,
@@ -27,9 +31,46 @@ pub fn bfs_traverse(
     max_depth: u32,
     max_results: usize,
     min_confidence: f32,
-) -> AmemResult<(Vec<u64>, Vec<Edge>, HashMap<u64, u32>)> {
-    if graph.get_node(start_id).is_none() {
-        return Err(AmemError::NodeNotFound(start_id));
+) -> AmemResult<(Vec<u64>, Vec<Edge>, HashMap<u64, u32>, Vec<Edge>)> {
+    let (visited, edges_traversed, depths, back_edges, _) = bfs_traverse_multi(
+        graph,
+        &[start_id],
+        edge_types,
+        direction,
+        max_depth,
+        max_results,
+        min_confidence,
+    )?;
+    Ok((visited, edges_traversed, depths, back_edges))
+}
+
+/// BFS traversal seeded from several start nodes at once.
+///
+/// All sources share one frontier and one visited set, so every reachable
+/// node appears once, at its minimum depth from any source. The last element
+/// maps each visited node to the source it was reached from; a node equally
+/// close to two sources is credited to whichever frontier got there first.
+/// Duplicate start IDs are ignored.
+#[allow(clippy::type_complexity)]
+pub fn bfs_traverse_multi(
+    graph: &MemoryGraph,
+    start_ids: &[u64],
+    edge_types: &[EdgeType],
+    direction: TraversalDirection,
+    max_depth: u32,
+    max_results: usize,
+    min_confidence: f32,
+) -> AmemResult<(
+    Vec<u64>,
+    Vec<Edge>,
+    HashMap<u64, u32>,
+    Vec<Edge>,
+    HashMap<u64, u64>,
+)> {
+    for &start_id in start_ids {
+        if graph.get_node(start_id).is_none() {
+            return Err(AmemError::NodeNotFound(start_id));
+        }
     }
 
     let edge_set: HashSet<EdgeType> = edge_types.iter().copied().collect();
@@ -37,12 +78,20 @@ pub fn bfs_traverse(
     let mut visited_order: Vec<u64> = Vec::new();
     let mut edges_traversed: Vec<Edge> = Vec::new();
     let mut depths: HashMap<u64, u32> = HashMap::new();
+    let mut sources: HashMap<u64, u64> = HashMap::new();
+    let mut parents: HashMap<u64, (u64, Edge)> = HashMap::new();
+    let mut back_edges: Vec<Edge> = Vec::new();
     let mut queue: VecDeque<(u64, u32)> = VecDeque::new();
 
-    visited.insert(start_id);
-    visited_order.push(start_id);
-    depths.insert(start_id, 0);
-    queue.push_back((start_id, 0));
+    for &start_id in start_ids {
+        if !visited.insert(start_id) {
+            continue;
+        }
+        visited_order.push(start_id);
+        depths.insert(start_id, 0);
+        sources.insert(start_id, start_id);
+        queue.push_back((start_id, 0));
+    }
 
     while let Some((current_id, depth)) = queue.pop_front() {
         if depth >= max_depth {
@@ -72,8 +121,18 @@ pub fn bfs_traverse(
             }
         }
 
+        let source = sources.get(&current_id).copied().unwrap_or(current_id);
         for (neighbor_id, edge) in neighbors {
             if visited.contains(&neighbor_id) {
+                let is_tree_edge = parents
+                    .get(&current_id)
+                    .is_some_and(|(_, tree_edge)| *tree_edge == edge);
+                if !is_tree_edge
+                    && is_ancestor(&parents, neighbor_id, current_id)
+                    && !back_edges.contains(&edge)
+                {
+                    back_edges.push(edge);
+                }
                 continue;
             }
             if visited_order.len() >= max_results {
@@ -92,10 +151,26 @@ pub fn bfs_traverse(
             visited.insert(neighbor_id);
             visited_order.push(neighbor_id);
             depths.insert(neighbor_id, depth + 1);
+            sources.insert(neighbor_id, source);
+            parents.insert(neighbor_id, (current_id, edge));
             edges_traversed.push(edge);
             queue.push_back((neighbor_id, depth + 1));
         }
     }
 
-    Ok((visited_order, edges_traversed, depths))
+    Ok((visited_order, edges_traversed, depths, back_edges, sources))
+}
+
+/// Whether `ancestor` lies on the BFS tree path from a start to `node` (inclusive).
+fn is_ancestor(parents: &HashMap<u64, (u64, Edge)>, ancestor: u64, node: u64) -> bool {
+    let mut current = node;
+    loop {
+        if current == ancestor {
+            return true;
+        }
+        match parents.get(&current) {
+            Some((parent, _)) => current = *parent,
+            None => return false,
+        }
+    }
 }
@@ -84,6 +84,7 @@ impl TermIndex {
     pub fn add_node(&mut self, event: &CognitiveEvent) {
         let tokenizer = Tokenizer::new();
         let freqs = tokenizer.term_frequencies(&event.content);
+        let doc_len: u32 = freqs.values().sum();
         for (term, freq) in freqs {
             let posting = self.postings.entry(term).or_default();
             let pos = posting
@@ -91,18 +92,27 @@ impl TermIndex {
                 .unwrap_or_else(|p| p);
             posting.insert(pos, (event.id, freq));
         }
+        let total = self.avg_doc_length as f64 * self.doc_count as f64 + doc_len as f64;
         self.doc_count += 1;
-        // avg_doc_length becomes approximate after incremental adds
+        self.avg_doc_length = (total / self.doc_count as f64) as f32;
     }
 
     /// Remove a node from the index.
     pub fn remove_node(&mut self, id: u64) {
-        for posting in self.postings.values_mut() {
+        let mut doc_len: u64 = 0;
+        self.postings.retain(|_, posting| {
             if let Ok(pos) = posting.binary_search_by_key(&id, |(nid, _)| *nid) {
-                posting.remove(pos);
+                doc_len += posting.remove(pos).1 as u64;
             }
-        }
+            !posting.is_empty()
+        });
+        let total = self.avg_doc_length as f64 * self.doc_count as f64 - doc_len as f64;
         self.doc_count = self.doc_count.saturating_sub(1);
+        self.avg_doc_length = if self.doc_count == 0 {
+            0.0
+        } else {
+            (total.max(0.0) / self.doc_count as f64) as f32
+        };
     }
 
     /// Clear the index.
@@ -27,18 +27,21 @@ pub mod v3;
 pub use engine::{
     CausalParams, CausalResult, DecayReport, IngestResult, MemoryQualityParams,
     MemoryQualityReport, PatternParams, PatternSort, QueryEngine, SimilarityMatchResult,
-    SimilarityParams, SubGraph, TemporalParams, TemporalResult, TimeRange, TraversalParams,
-    TraversalResult, WriteEngine,
+    SimilarityMetric, SimilarityParams, SubGraph, SupportContribution, SupportScore,
+    TemporalParams, TemporalResult, TimeRange, TraversalParams, TraversalResult, WriteEngine,
 };
 #[cfg(feature = "format")]
 pub use format::{AmemReader, AmemWriter, MmapReader, SimilarityMatch};
-pub use graph::{GraphBuilder, MemoryGraph, TraversalDirection};
+pub use graph::{
+    check_integrity, repair_graph, GraphBuilder, IntegrityIssue, IntegrityReport, MemoryGraph,
+    RepairReport, TraversalDirection,
+};
 pub use index::{
     cosine_similarity, ClusterMap, DocLengths, SessionIndex, TemporalIndex, TermIndex, TypeIndex,
 };
 pub use types::{
     now_micros, AmemError, AmemResult, CognitiveEvent, CognitiveEventBuilder, Edge, EdgeType,
-    EventType, FileHeader, DEFAULT_DIMENSION, MAX_CONTENT_SIZE, MAX_EDGES_PER_NODE,
+    EventType, FileHeader, DEFAULT_DIMENSION, FORMAT_VERSION, MAX_CONTENT_SIZE, MAX_EDGES_PER_NODE,
 };
 
 // New query expansion re-exports
@@ -52,6 +52,32 @@ impl EdgeType {
         }
     }
 
+    /// One-line description of what this edge type means.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::CausedBy => "This event happened because of the target event",
+            Self::Supports => "This event provides evidence for the target event",
+            Self::Contradicts => "This event conflicts with the target event",
+            Self::Supersedes => "This event replaces the target event (newer corrects older)",
+            Self::RelatedTo => "Semantic similarity without a causal or logical relationship",
+            Self::PartOf => "This event belongs to a larger episode or cluster",
+            Self::TemporalNext => "Chronological ordering within a session",
+        }
+    }
+
+    /// Every edge type, in discriminant order.
+    pub fn all() -> &'static [EdgeType] {
+        &[
+            Self::CausedBy,
+            Self::Supports,
+            Self::Contradicts,
+            Self::Supersedes,
+            Self::RelatedTo,
+            Self::PartOf,
+            Self::TemporalNext,
+        ]
+    }
+
     /// Parse an edge type from a string name.
     pub fn from_name(name: &str) -> Option<Self> {
         match name.to_lowercase().as_str() {
@@ -2,6 +2,8 @@
 
 use thiserror::Error;
 
+use super::EdgeType;
+
 /// All errors that can occur in the AgenticMemory library.
 #[derive(Error, Debug)]
 pub enum AmemError {
@@ -17,6 +19,10 @@ pub enum AmemError {
     #[error("Node ID {0} not found")]
     NodeNotFound(u64),
 
+    /// A node with this ID already exists.
+    #[error("Node ID {0} already exists")]
+    DuplicateNode(u64),
+
     /// Edge references an invalid node ID.
     #[error("Edge references invalid node ID: {0}")]
     InvalidEdgeTarget(u64),
@@ -25,6 +31,22 @@ pub enum AmemError {
     #[error("Self-edge not allowed on node {0}")]
     SelfEdge(u64),
 
+    /// An edge with the same source, target, and type already exists.
+    #[error("Edge {source_id} -> {target_id} ({edge_type}) already exists")]
+    DuplicateEdge {
+        source_id: u64,
+        target_id: u64,
+        edge_type: EdgeType,
+    },
+
+    /// No edge with this source, target, and type exists.
+    #[error("Edge {source_id} -> {target_id} ({edge_type}) not found")]
+    EdgeNotFound {
+        source_id: u64,
+        target_id: u64,
+        edge_type: EdgeType,
+    },
+
     /// Content exceeds maximum size.
     #[error("Content exceeds maximum size: {size} > {max}")]
     ContentTooLarge { size: usize, max: usize },
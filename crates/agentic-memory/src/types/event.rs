@@ -49,6 +49,30 @@ impl EventType {
         }
     }
 
+    /// One-line description of what this event type holds.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Fact => "Something learned about the world or the user",
+            Self::Decision => "A choice that was made and the reasoning behind it",
+            Self::Inference => "A conclusion drawn from multiple facts",
+            Self::Correction => "A previous belief that was corrected",
+            Self::Skill => "A learned pattern for how to accomplish something",
+            Self::Episode => "A compressed summary of an entire interaction session",
+        }
+    }
+
+    /// Every event type, in discriminant order.
+    pub fn all() -> &'static [EventType] {
+        &[
+            Self::Fact,
+            Self::Decision,
+            Self::Inference,
+            Self::Correction,
+            Self::Skill,
+            Self::Episode,
+        ]
+    }
+
     /// Parse an event type from a string name.
     pub fn from_name(name: &str) -> Option<Self> {
         match name.to_lowercase().as_str() {
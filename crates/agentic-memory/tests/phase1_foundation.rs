@@ -1,12 +1,12 @@
 //! Phase 1 tests: Data structures + file format.
 
 use agentic_memory::format::{AmemReader, AmemWriter};
-use agentic_memory::graph::MemoryGraph;
+use agentic_memory::graph::{check_integrity, repair_graph, IntegrityIssue, MemoryGraph};
 use agentic_memory::types::edge::{Edge, EdgeType};
 use agentic_memory::types::error::AmemError;
-use agentic_memory::types::event::{CognitiveEventBuilder, EventType};
+use agentic_memory::types::event::{CognitiveEvent, CognitiveEventBuilder, EventType};
 use agentic_memory::types::header::FileHeader;
-use agentic_memory::types::{AMEM_MAGIC, DEFAULT_DIMENSION, FORMAT_VERSION};
+use agentic_memory::types::{AMEM_MAGIC, DEFAULT_DIMENSION, FORMAT_VERSION, MAX_CONTENT_SIZE};
 
 use std::io::Cursor;
 use tempfile::NamedTempFile;
@@ -90,6 +90,40 @@ fn test_self_edge_rejected() {
     }
 }
 
+#[test]
+fn test_duplicate_edge_rejected() {
+    let mut graph = MemoryGraph::new(DEFAULT_DIMENSION);
+    let a = graph
+        .add_node(CognitiveEventBuilder::new(EventType::Fact, "a").build())
+        .unwrap();
+    let b = graph
+        .add_node(CognitiveEventBuilder::new(EventType::Fact, "b").build())
+        .unwrap();
+
+    graph
+        .add_edge(Edge::new(a, b, EdgeType::Supports, 0.5))
+        .unwrap();
+    match graph.add_edge(Edge::new(a, b, EdgeType::Supports, 0.9)) {
+        Err(AmemError::DuplicateEdge {
+            source_id,
+            target_id,
+            edge_type,
+        }) => assert_eq!(
+            (source_id, target_id, edge_type),
+            (a, b, EdgeType::Supports)
+        ),
+        other => panic!("Expected DuplicateEdge error, got {:?}", other),
+    }
+    assert_eq!(graph.edge_count(), 1);
+    assert_eq!(graph.edges_to(b).len(), 1);
+
+    // A different type between the same pair is a distinct edge.
+    graph
+        .add_edge(Edge::new(a, b, EdgeType::RelatedTo, 0.5))
+        .unwrap();
+    assert_eq!(graph.edge_count(), 2);
+}
+
 #[test]
 fn test_weight_validation() {
     // Edge weight is clamped, not rejected
@@ -308,6 +342,25 @@ fn test_remove_node() {
     assert_eq!(graph.edge_count(), 0); // All edges involving id1 removed
 }
 
+#[test]
+fn test_retype_node_updates_type_index() {
+    let mut graph = MemoryGraph::new(DEFAULT_DIMENSION);
+    let id = graph
+        .add_node(CognitiveEventBuilder::new(EventType::Inference, "pick sqlite").build())
+        .unwrap();
+
+    let old = graph.retype_node(id, EventType::Decision).unwrap();
+
+    assert_eq!(old, EventType::Inference);
+    assert_eq!(graph.get_node(id).unwrap().event_type, EventType::Decision);
+    assert_eq!(graph.type_index().count(EventType::Inference), 0);
+    assert_eq!(graph.type_index().get(EventType::Decision), &[id]);
+    assert!(matches!(
+        graph.retype_node(999, EventType::Fact),
+        Err(AmemError::NodeNotFound(999))
+    ));
+}
+
 #[test]
 fn test_graph_from_parts() {
     let events = vec![
@@ -513,3 +566,195 @@ fn test_file_extension() {
     writer.write_to_file(&graph, tmp.path()).unwrap();
     assert!(tmp.path().extension().unwrap() == "amem");
 }
+
+// ==================== Integrity Check Tests ====================
+
+fn raw_event(id: u64, event_type: EventType, dimension: usize) -> CognitiveEvent {
+    let mut e = CognitiveEventBuilder::new(event_type, format!("node {id}")).build();
+    e.id = id;
+    e.feature_vec = vec![0.0; dimension];
+    e
+}
+
+#[test]
+fn test_integrity_clean_graph() {
+    let events = vec![
+        raw_event(0, EventType::Fact, DEFAULT_DIMENSION),
+        raw_event(1, EventType::Episode, DEFAULT_DIMENSION),
+        raw_event(2, EventType::Fact, DEFAULT_DIMENSION),
+    ];
+    let edges = vec![Edge::new(0, 1, EdgeType::PartOf, 1.0)];
+    let graph = MemoryGraph::from_parts(events, edges, DEFAULT_DIMENSION).unwrap();
+
+    let report = check_integrity(&graph);
+    assert!(report.is_ok(), "{:?}", report.issues);
+    assert_eq!(report.orphan_count, 1);
+}
+
+#[test]
+fn test_integrity_reports_each_problem() {
+    let mut short = raw_event(3, EventType::Fact, DEFAULT_DIMENSION);
+    short.feature_vec = vec![0.0; 4];
+    let events = vec![
+        raw_event(0, EventType::Fact, DEFAULT_DIMENSION),
+        raw_event(1, EventType::Correction, DEFAULT_DIMENSION),
+        raw_event(2, EventType::Episode, DEFAULT_DIMENSION),
+        short,
+    ];
+    let edges = vec![
+        Edge::new(0, 1, EdgeType::Supersedes, 1.0),
+        Edge::new(1, 0, EdgeType::Supersedes, 1.0),
+        Edge::new(0, 99, EdgeType::RelatedTo, 1.0),
+        Edge::new(77, 1, EdgeType::Supports, 1.0),
+    ];
+    let graph = MemoryGraph::from_parts(events, edges, DEFAULT_DIMENSION).unwrap();
+
+    let report = check_integrity(&graph);
+    assert!(report.issues.contains(&IntegrityIssue::MissingTarget {
+        source_id: 0,
+        target_id: 99,
+        edge_type: EdgeType::RelatedTo,
+    }));
+    assert!(report.issues.contains(&IntegrityIssue::MissingSource {
+        source_id: 77,
+        target_id: 1,
+        edge_type: EdgeType::Supports,
+    }));
+    assert!(report.issues.contains(&IntegrityIssue::SupersedesCycle {
+        node_ids: vec![0, 1]
+    }));
+    assert!(report
+        .issues
+        .contains(&IntegrityIssue::EmptyEpisode { node_id: 2 }));
+    assert!(report.issues.contains(&IntegrityIssue::DimensionMismatch {
+        node_id: 3,
+        expected: DEFAULT_DIMENSION,
+        got: 4,
+    }));
+    assert_eq!(report.issues.len(), 5);
+    assert_eq!(report.orphan_count, 2);
+}
+
+#[test]
+fn test_integrity_reports_and_dedups_duplicate_edges() {
+    let events = vec![
+        raw_event(0, EventType::Fact, DEFAULT_DIMENSION),
+        raw_event(1, EventType::Fact, DEFAULT_DIMENSION),
+    ];
+    let edges = vec![
+        Edge::new(0, 1, EdgeType::Supports, 0.4),
+        Edge::new(0, 1, EdgeType::Supports, 0.9),
+        Edge::new(0, 1, EdgeType::Supports, 0.6),
+        Edge::new(0, 1, EdgeType::RelatedTo, 1.0),
+    ];
+    let mut graph = MemoryGraph::from_parts(events, edges, DEFAULT_DIMENSION).unwrap();
+
+    let report = check_integrity(&graph);
+    assert_eq!(
+        report.issues,
+        vec![IntegrityIssue::DuplicateEdge {
+            source_id: 0,
+            target_id: 1,
+            edge_type: EdgeType::Supports,
+            count: 3,
+        }]
+    );
+
+    assert_eq!(graph.dedup_edges(), 2);
+    assert_eq!(graph.edge_count(), 2);
+    let supports = graph
+        .edges_from(0)
+        .iter()
+        .find(|e| e.edge_type == EdgeType::Supports)
+        .unwrap();
+    assert!((supports.weight - 0.9).abs() < f32::EPSILON);
+    assert!(check_integrity(&graph).is_ok());
+    assert_eq!(graph.dedup_edges(), 0);
+}
+
+// ==================== Migration Repair Tests ====================
+
+#[test]
+fn test_repair_graph_fixes_old_file_problems() {
+    let mut nan = raw_event(2, EventType::Fact, DEFAULT_DIMENSION);
+    nan.feature_vec[0] = f32::NAN;
+    let mut short = raw_event(3, EventType::Fact, DEFAULT_DIMENSION);
+    short.feature_vec = vec![1.0; 4];
+    // One byte over the limit, with the limit falling inside a two-byte character.
+    let mut long = raw_event(4, EventType::Fact, DEFAULT_DIMENSION);
+    long.content = format!("{}é", "a".repeat(MAX_CONTENT_SIZE - 1));
+    let events = vec![
+        raw_event(0, EventType::Fact, DEFAULT_DIMENSION),
+        raw_event(1, EventType::Decision, DEFAULT_DIMENSION),
+        nan,
+        short,
+        long,
+    ];
+    let edges = vec![
+        Edge::new(0, 1, EdgeType::Supports, 0.4),
+        Edge::new(0, 1, EdgeType::Supports, 0.9),
+        Edge::new(0, 99, EdgeType::RelatedTo, 1.0),
+    ];
+    let mut graph = MemoryGraph::from_parts(events, edges, DEFAULT_DIMENSION).unwrap();
+
+    // A year on, every decay score has moved.
+    let now = agentic_memory::now_micros() + 365 * 86_400_000_000;
+    let report = repair_graph(&mut graph, now, 0);
+    assert_eq!(report.duplicate_edges_removed, 1);
+    assert_eq!(report.dangling_edges_removed, 1);
+    assert_eq!(report.vectors_filled, 2);
+    assert_eq!(report.contents_truncated, 1);
+    assert_eq!(report.decay_scores_updated, 5);
+    assert!(!report.text_index_dropped);
+
+    assert!(check_integrity(&graph).is_ok());
+    assert_eq!(
+        graph.get_node(2).unwrap().feature_vec,
+        vec![0.0; DEFAULT_DIMENSION]
+    );
+    assert_eq!(
+        graph.get_node(3).unwrap().feature_vec.len(),
+        DEFAULT_DIMENSION
+    );
+    assert_eq!(
+        graph.get_node(4).unwrap().content.len(),
+        MAX_CONTENT_SIZE - 1
+    );
+    assert!(repair_graph(&mut graph, now, 0).is_clean());
+}
+
+#[test]
+fn test_read_lossy_replaces_invalid_utf8() {
+    let mut graph = MemoryGraph::new(DEFAULT_DIMENSION);
+    graph
+        .add_node(CognitiveEventBuilder::new(EventType::Fact, "café").build())
+        .unwrap();
+    let tmp = NamedTempFile::new().unwrap();
+    AmemWriter::new(DEFAULT_DIMENSION)
+        .write_to_file(&graph, tmp.path())
+        .unwrap();
+
+    // Cut the "é" in half, as an older writer truncating by bytes could.
+    let mut data = std::fs::read(tmp.path()).unwrap();
+    let header = FileHeader::read_from(&mut Cursor::new(&data[..64])).unwrap();
+    let start = header.content_block_offset as usize;
+    let at = start
+        + data[start..]
+            .windows(2)
+            .position(|w| w == [0xC3, 0xA9])
+            .unwrap();
+    data[at + 1] = b'!';
+    std::fs::write(tmp.path(), &data).unwrap();
+
+    assert!(AmemReader::read_from_file(tmp.path()).is_err());
+    let (mut graph, replaced) = AmemReader::read_from_file_lossy(tmp.path()).unwrap();
+    assert_eq!(replaced, 1);
+    assert_eq!(graph.get_node(0).unwrap().content, "caf\u{FFFD}!");
+
+    // The stored text index was built over the old bytes.
+    assert!(graph.term_index().is_some());
+    let report = repair_graph(&mut graph, agentic_memory::now_micros(), replaced);
+    assert_eq!(report.contents_replaced, 1);
+    assert!(report.text_index_dropped);
+    assert!(graph.term_index().is_none());
+}
@@ -1,8 +1,8 @@
 //! Phase 2 tests: Write Engine + Query Engine.
 
 use agentic_memory::engine::query::{
-    CausalParams, PatternParams, PatternSort, QueryEngine, SimilarityParams, TemporalParams,
-    TimeRange, TraversalParams,
+    CausalParams, PatternParams, PatternSort, QueryEngine, SimilarityMetric, SimilarityParams,
+    TemporalParams, TimeRange, TraversalParams,
 };
 use agentic_memory::engine::write::WriteEngine;
 use agentic_memory::graph::traversal::TraversalDirection;
@@ -466,6 +466,51 @@ fn test_traverse_max_depth() {
     assert!(!result.visited.contains(&ids[4]));
 }
 
+#[test]
+fn test_traverse_multi_keeps_shortest_depth() {
+    let mut graph = MemoryGraph::new(DEFAULT_DIMENSION);
+
+    // Chain 0 -> 1 -> 2 -> 3 -> 4; sources at both ends of the middle.
+    let mut ids = Vec::new();
+    for i in 0..5 {
+        let event = CognitiveEventBuilder::new(EventType::Fact, format!("node_{}", i))
+            .session_id(1)
+            .confidence(1.0)
+            .feature_vec(zero_vec())
+            .build();
+        ids.push(graph.add_node(event).unwrap());
+    }
+    for i in 0..4 {
+        graph
+            .add_edge(Edge::new(ids[i], ids[i + 1], EdgeType::CausedBy, 1.0))
+            .unwrap();
+    }
+
+    let qe = QueryEngine::new();
+    let result = qe
+        .traverse_multi(
+            &graph,
+            &[ids[0], ids[3], ids[0]],
+            TraversalParams {
+                start_id: ids[0],
+                edge_types: vec![EdgeType::CausedBy],
+                direction: TraversalDirection::Forward,
+                max_depth: 10,
+                max_results: 100,
+                min_confidence: 0.0,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(result.visited.len(), 5);
+    assert_eq!(result.depths[&ids[3]], 0);
+    assert_eq!(result.depths[&ids[4]], 1);
+    assert_eq!(result.sources[&ids[4]], ids[3]);
+    assert_eq!(result.depths[&ids[2]], 2);
+    assert_eq!(result.sources[&ids[2]], ids[0]);
+    assert_eq!(result.edges_traversed.len(), 3);
+}
+
 #[test]
 fn test_traverse_respects_edge_type_filter() {
     let mut graph = MemoryGraph::new(DEFAULT_DIMENSION);
@@ -576,6 +621,44 @@ fn test_traverse_handles_cycles() {
     assert!(result.visited.contains(&id_a));
     assert!(result.visited.contains(&id_b));
     assert!(result.visited.contains(&id_c));
+    assert_eq!(result.edges_traversed.len(), 2);
+    assert_eq!(result.back_edges.len(), 1);
+    assert_eq!(result.back_edges[0].source_id, id_c);
+    assert_eq!(result.back_edges[0].target_id, id_a);
+}
+
+#[test]
+fn test_traverse_both_directions_no_false_cycle() {
+    let mut graph = MemoryGraph::new(DEFAULT_DIMENSION);
+    let a = CognitiveEventBuilder::new(EventType::Fact, "A")
+        .feature_vec(zero_vec())
+        .build();
+    let b = CognitiveEventBuilder::new(EventType::Fact, "B")
+        .feature_vec(zero_vec())
+        .build();
+    let id_a = graph.add_node(a).unwrap();
+    let id_b = graph.add_node(b).unwrap();
+    graph
+        .add_edge(Edge::new(id_a, id_b, EdgeType::CausedBy, 1.0))
+        .unwrap();
+
+    let result = QueryEngine::new()
+        .traverse(
+            &graph,
+            TraversalParams {
+                start_id: id_a,
+                edge_types: vec![EdgeType::CausedBy],
+                direction: TraversalDirection::Both,
+                max_depth: 10,
+                max_results: 10,
+                min_confidence: 0.0,
+            },
+        )
+        .unwrap();
+
+    // Walking the tree edge back to its parent is not a cycle.
+    assert_eq!(result.visited.len(), 2);
+    assert!(result.back_edges.is_empty());
 }
 
 #[test]
@@ -667,6 +750,7 @@ fn test_pattern_by_type() {
                 created_after: None,
                 created_before: None,
                 min_decay_score: None,
+                max_decay_score: None,
                 max_results: 100,
                 sort_by: PatternSort::MostRecent,
             },
@@ -707,6 +791,7 @@ fn test_pattern_by_session() {
                 created_after: None,
                 created_before: None,
                 min_decay_score: None,
+                max_decay_score: None,
                 max_results: 100,
                 sort_by: PatternSort::MostRecent,
             },
@@ -745,6 +830,7 @@ fn test_pattern_by_time_range() {
                 created_after: Some(2_000_000),
                 created_before: Some(4_000_000),
                 min_decay_score: None,
+                max_decay_score: None,
                 max_results: 100,
                 sort_by: PatternSort::MostRecent,
             },
@@ -785,6 +871,7 @@ fn test_pattern_by_confidence_range() {
                 created_after: None,
                 created_before: None,
                 min_decay_score: None,
+                max_decay_score: None,
                 max_results: 100,
                 sort_by: PatternSort::MostRecent,
             },
@@ -824,6 +911,7 @@ fn test_pattern_sort_recent() {
                 created_after: None,
                 created_before: None,
                 min_decay_score: None,
+                max_decay_score: None,
                 max_results: 100,
                 sort_by: PatternSort::MostRecent,
             },
@@ -839,6 +927,52 @@ fn test_pattern_sort_recent() {
     }
 }
 
+#[test]
+fn test_pattern_sort_ties_are_stable() {
+    let mut graph = MemoryGraph::new(DEFAULT_DIMENSION);
+
+    // A batch ingest: every node shares the same timestamp.
+    for i in 0..8 {
+        let event = CognitiveEventBuilder::new(EventType::Fact, format!("batch_{}", i))
+            .session_id(1)
+            .created_at(5_000_000)
+            .feature_vec(zero_vec())
+            .build();
+        graph.add_node(event).unwrap();
+    }
+
+    let qe = QueryEngine::new();
+    let run = || -> Vec<u64> {
+        qe.pattern(
+            &graph,
+            PatternParams {
+                event_types: vec![EventType::Fact],
+                min_confidence: None,
+                max_confidence: None,
+                session_ids: vec![],
+                created_after: None,
+                created_before: None,
+                min_decay_score: None,
+                max_decay_score: None,
+                max_results: 100,
+                sort_by: PatternSort::MostRecent,
+            },
+        )
+        .unwrap()
+        .iter()
+        .map(|n| n.id)
+        .collect()
+    };
+
+    let first = run();
+    let mut expected = first.clone();
+    expected.sort_unstable();
+    assert_eq!(first, expected, "Ties should resolve by ascending node id");
+    for _ in 0..5 {
+        assert_eq!(run(), first);
+    }
+}
+
 #[test]
 fn test_pattern_sort_confidence() {
     let mut graph = MemoryGraph::new(DEFAULT_DIMENSION);
@@ -864,6 +998,7 @@ fn test_pattern_sort_confidence() {
                 created_after: None,
                 created_before: None,
                 min_decay_score: None,
+                max_decay_score: None,
                 max_results: 100,
                 sort_by: PatternSort::HighestConfidence,
             },
@@ -905,6 +1040,7 @@ fn test_pattern_max_results() {
                 created_after: None,
                 created_before: None,
                 min_decay_score: None,
+                max_decay_score: None,
                 max_results: 10,
                 sort_by: PatternSort::MostRecent,
             },
@@ -1175,6 +1311,56 @@ fn test_causal_counts_decisions_and_inferences() {
     assert_eq!(result.dependents.len(), 6);
 }
 
+// ==================== Query Engine: Support Score Tests ====================
+
+#[test]
+fn test_support_score_noisy_or() {
+    let mut graph = MemoryGraph::new(DEFAULT_DIMENSION);
+    let mut add = |content: &str, confidence: f32| {
+        let event = CognitiveEventBuilder::new(EventType::Fact, content)
+            .confidence(confidence)
+            .feature_vec(zero_vec())
+            .build();
+        graph.add_node(event).unwrap()
+    };
+    let claim = add("claim", 0.7);
+    let strong = add("strong evidence", 0.8);
+    let weak = add("weak evidence", 0.5);
+    let counter = add("counter evidence", 0.9);
+    let related = add("related note", 1.0);
+
+    graph
+        .add_edge(Edge::new(strong, claim, EdgeType::Supports, 1.0))
+        .unwrap();
+    graph
+        .add_edge(Edge::new(weak, claim, EdgeType::Supports, 0.5))
+        .unwrap();
+    graph
+        .add_edge(Edge::new(counter, claim, EdgeType::Contradicts, 0.5))
+        .unwrap();
+    graph
+        .add_edge(Edge::new(related, claim, EdgeType::RelatedTo, 1.0))
+        .unwrap();
+
+    let score = QueryEngine::new().support_score(&graph, claim).unwrap();
+    // 1 - (1 - 0.8)(1 - 0.25)
+    assert!((score.support - 0.85).abs() < 1e-5);
+    assert!((score.opposition - 0.45).abs() < 1e-5);
+    assert!((score.net - 0.40).abs() < 1e-5);
+    assert_eq!(score.supporters.len(), 2);
+    assert_eq!(score.supporters[0].node_id, strong);
+    assert_eq!(score.opposers.len(), 1);
+
+    let lonely = QueryEngine::new().support_score(&graph, related).unwrap();
+    assert_eq!(lonely.support, 0.0);
+    assert_eq!(lonely.net, 0.0);
+
+    assert!(matches!(
+        QueryEngine::new().support_score(&graph, 999),
+        Err(AmemError::NodeNotFound(999))
+    ));
+}
+
 // ==================== Query Engine: Similarity Tests ====================
 
 #[test]
@@ -1206,6 +1392,7 @@ fn test_similarity_basic() {
                 min_similarity: 0.0,
                 event_types: vec![],
                 skip_zero_vectors: false,
+                metric: SimilarityMetric::Cosine,
             },
         )
         .unwrap();
@@ -1247,6 +1434,7 @@ fn test_similarity_respects_threshold() {
                 min_similarity: 0.9, // High threshold
                 event_types: vec![],
                 skip_zero_vectors: false,
+                metric: SimilarityMetric::Cosine,
             },
         )
         .unwrap();
@@ -1284,6 +1472,7 @@ fn test_similarity_skips_zero_vectors() {
                 min_similarity: 0.0,
                 event_types: vec![],
                 skip_zero_vectors: true,
+                metric: SimilarityMetric::Cosine,
             },
         )
         .unwrap();
@@ -1297,6 +1486,95 @@ fn test_similarity_skips_zero_vectors() {
     );
 }
 
+#[test]
+fn test_similarity_cancellable_stops_scan() {
+    use std::cell::Cell;
+
+    let mut graph = MemoryGraph::new(DEFAULT_DIMENSION);
+    for i in 0..4 {
+        let event = CognitiveEventBuilder::new(EventType::Fact, format!("N{i}"))
+            .session_id(1)
+            .feature_vec(basis_vec(0, 1.0))
+            .build();
+        graph.add_node(event).unwrap();
+    }
+    let params = || SimilarityParams {
+        query_vec: basis_vec(0, 1.0),
+        top_k: 10,
+        min_similarity: 0.0,
+        event_types: vec![],
+        skip_zero_vectors: false,
+        metric: SimilarityMetric::Cosine,
+    };
+
+    let qe = QueryEngine::new();
+    let (all, cancelled) = qe
+        .similarity_cancellable(&graph, params(), &|| false)
+        .unwrap();
+    assert_eq!(all.len(), 4);
+    assert!(!cancelled);
+
+    // Cancel after two candidates have been scanned.
+    let polls = Cell::new(0);
+    let (partial, cancelled) = qe
+        .similarity_cancellable(&graph, params(), &|| {
+            polls.set(polls.get() + 1);
+            polls.get() > 2
+        })
+        .unwrap();
+    assert!(cancelled);
+    assert_eq!(partial.len(), 2);
+}
+
+#[test]
+fn test_similarity_metrics_rank_unnormalized_vectors() {
+    let mut graph = MemoryGraph::new(DEFAULT_DIMENSION);
+    let unit = CognitiveEventBuilder::new(EventType::Fact, "unit")
+        .session_id(1)
+        .feature_vec(basis_vec(0, 1.0))
+        .build();
+    let long = CognitiveEventBuilder::new(EventType::Fact, "long")
+        .session_id(1)
+        .feature_vec(basis_vec(0, 3.0))
+        .build();
+    let id_unit = graph.add_node(unit).unwrap();
+    let id_long = graph.add_node(long).unwrap();
+
+    let qe = QueryEngine::new();
+    let ranked = |metric: SimilarityMetric| {
+        qe.similarity(
+            &graph,
+            SimilarityParams {
+                query_vec: basis_vec(0, 1.0),
+                top_k: 10,
+                min_similarity: f32::NEG_INFINITY,
+                event_types: vec![],
+                skip_zero_vectors: true,
+                metric,
+            },
+        )
+        .unwrap()
+    };
+
+    // Same direction, so cosine cannot tell them apart.
+    let cosine = ranked(SimilarityMetric::Cosine);
+    assert!(cosine.iter().all(|r| (r.similarity - 1.0).abs() < 1e-6));
+
+    let dot = ranked(SimilarityMetric::Dot);
+    assert_eq!(dot[0].node_id, id_long);
+    assert!((dot[0].similarity - 3.0).abs() < 1e-6);
+
+    let euclidean = ranked(SimilarityMetric::Euclidean);
+    assert_eq!(euclidean[0].node_id, id_unit);
+    assert!(euclidean[0].similarity.abs() < 1e-6);
+    assert!((euclidean[1].similarity + 2.0).abs() < 1e-6);
+
+    for metric in SimilarityMetric::all() {
+        assert_eq!(SimilarityMetric::from_name(metric.name()), Some(*metric));
+    }
+    assert_eq!(SimilarityMetric::from_name("manhattan"), None);
+}
+
 // ==================== Query Engine: Context Tests ====================
 
 #[test]
@@ -1358,6 +1636,50 @@ fn test_context_subgraph() {
     );
 }
 
+#[test]
+fn test_context_edge_type_filter() {
+    let mut graph = MemoryGraph::new(DEFAULT_DIMENSION);
+
+    let ids: Vec<u64> = ["center", "cause", "noise", "cause_of_cause"]
+        .iter()
+        .map(|content| {
+            let event = CognitiveEventBuilder::new(EventType::Fact, *content)
+                .session_id(1)
+                .feature_vec(zero_vec())
+                .build();
+            graph.add_node(event).unwrap()
+        })
+        .collect();
+
+    graph
+        .add_edge(Edge::new(ids[0], ids[1], EdgeType::CausedBy, 1.0))
+        .unwrap();
+    graph
+        .add_edge(Edge::new(ids[0], ids[2], EdgeType::RelatedTo, 1.0))
+        .unwrap();
+    graph
+        .add_edge(Edge::new(ids[1], ids[3], EdgeType::CausedBy, 1.0))
+        .unwrap();
+    graph
+        .add_edge(Edge::new(ids[2], ids[1], EdgeType::RelatedTo, 1.0))
+        .unwrap();
+
+    let qe = QueryEngine::new();
+    let subgraph = qe
+        .context_with_edge_types(&graph, ids[0], 2, &[EdgeType::CausedBy])
+        .unwrap();
+
+    let node_ids: Vec<u64> = subgraph.nodes.iter().map(|n| n.id).collect();
+    assert!(node_ids.contains(&ids[1]));
+    assert!(node_ids.contains(&ids[3]));
+    assert!(!node_ids.contains(&ids[2]));
+    assert_eq!(subgraph.edges.len(), 2);
+    assert!(subgraph
+        .edges
+        .iter()
+        .all(|e| e.edge_type == EdgeType::CausedBy));
+}
+
 // ==================== Query Engine: Resolve Tests ====================
 
 #[test]
@@ -1428,3 +1750,39 @@ fn test_resolve_chain() {
     let resolved_b = qe.resolve(&graph, id_b).unwrap();
     assert_eq!(resolved_b.id, id_c);
 }
+
+#[test]
+fn test_history_chain() {
+    let mut graph = MemoryGraph::new(DEFAULT_DIMENSION);
+    let engine = WriteEngine::new(DEFAULT_DIMENSION);
+
+    let a = CognitiveEventBuilder::new(EventType::Fact, "version 1")
+        .session_id(1)
+        .feature_vec(zero_vec())
+        .build();
+    let ingest_result = engine.ingest(&mut graph, vec![a], vec![]).unwrap();
+    let id_a = ingest_result.new_node_ids[0];
+    let id_b = engine.correct(&mut graph, id_a, "version 2", 2).unwrap();
+    let id_c = engine.correct(&mut graph, id_b, "version 3", 3).unwrap();
+
+    let qe = QueryEngine::new();
+    let ids = |versions: Vec<&agentic_memory::types::CognitiveEvent>| {
+        versions.iter().map(|n| n.id).collect::<Vec<_>>()
+    };
+
+    // Without earlier versions the chain starts at the given node
+    assert_eq!(
+        ids(qe.history(&graph, id_b, false).unwrap()),
+        vec![id_b, id_c]
+    );
+    // Including earlier versions yields the whole chain, oldest first
+    assert_eq!(
+        ids(qe.history(&graph, id_b, true).unwrap()),
+        vec![id_a, id_b, id_c]
+    );
+    assert_eq!(ids(qe.history(&graph, id_c, false).unwrap()), vec![id_c]);
+    assert!(matches!(
+        qe.history(&graph, 999, false),
+        Err(AmemError::NodeNotFound(999))
+    ));
+}
@@ -12,7 +12,8 @@ use agentic_memory::types::{
     CognitiveEvent, CognitiveEventBuilder, Edge, EdgeType, EventType, DEFAULT_DIMENSION,
 };
 use agentic_memory::{
-    PatternParams, PatternSort, QueryEngine, SimilarityParams, TraversalDirection, TraversalParams,
+    PatternParams, PatternSort, QueryEngine, SimilarityMetric, SimilarityParams,
+    TraversalDirection, TraversalParams,
 };
 
 // ==================== Helpers ====================
@@ -831,6 +832,7 @@ fn test_large_graph_pattern_query_speed() {
         created_after: None,
         created_before: None,
         min_decay_score: None,
+        max_decay_score: None,
         max_results: 100,
         sort_by: PatternSort::HighestConfidence,
     };
@@ -874,6 +876,7 @@ fn test_large_graph_similarity_speed() {
         min_similarity: 0.0,
         event_types: vec![],
         skip_zero_vectors: true,
+        metric: SimilarityMetric::Cosine,
     };
 
     let engine = QueryEngine::new();
@@ -6,8 +6,8 @@ use std::process::{Command, Output};
 use tempfile::NamedTempFile;
 
 use agentic_memory::engine::{
-    CausalParams, PatternParams, PatternSort, QueryEngine, SimilarityParams, TraversalParams,
-    WriteEngine,
+    CausalParams, PatternParams, PatternSort, QueryEngine, SimilarityMetric, SimilarityParams,
+    TraversalParams, WriteEngine,
 };
 use agentic_memory::format::{AmemReader, AmemWriter};
 use agentic_memory::graph::{MemoryGraph, TraversalDirection};
@@ -187,6 +187,30 @@ fn test_cli_traverse() {
     );
 }
 
+#[test]
+fn test_cli_link_twice_is_idempotent() {
+    let tmp = NamedTempFile::new().unwrap();
+    let path = tmp.path().to_str().unwrap();
+
+    assert_success(&run_amem(&["create", path]));
+    assert_success(&run_amem(&["add", path, "fact", "a", "--session", "1"]));
+    assert_success(&run_amem(&["add", path, "fact", "b", "--session", "1"]));
+
+    let link = || run_amem(&["--format", "json", "link", path, "1", "0", "supports"]);
+    let first = link();
+    assert_success(&first);
+    let first: serde_json::Value = serde_json::from_str(&stdout_str(&first)).unwrap();
+    assert_eq!(first["already_linked"], false);
+
+    let second = link();
+    assert_success(&second);
+    let second: serde_json::Value = serde_json::from_str(&stdout_str(&second)).unwrap();
+    assert_eq!(second["already_linked"], true);
+
+    let graph = AmemReader::read_from_file(tmp.path()).unwrap();
+    assert_eq!(graph.edge_count(), 1);
+}
+
 #[test]
 fn test_cli_impact() {
     let tmp = NamedTempFile::new().unwrap();
@@ -567,6 +591,7 @@ fn test_full_lifecycle() {
                 min_similarity: -1.0, // Accept anything
                 event_types: vec![],
                 skip_zero_vectors: false,
+                metric: SimilarityMetric::Cosine,
             },
         )
         .unwrap();
@@ -724,6 +749,7 @@ fn test_portable_file() {
                 created_after: None,
                 created_before: None,
                 min_decay_score: None,
+                max_decay_score: None,
                 max_results: 100,
                 sort_by: PatternSort::MostRecent,
             },
@@ -809,6 +835,7 @@ fn test_empty_file_operations() {
                 created_after: None,
                 created_before: None,
                 min_decay_score: None,
+                max_decay_score: None,
                 max_results: 100,
                 sort_by: PatternSort::MostRecent,
             },
@@ -826,6 +853,7 @@ fn test_empty_file_operations() {
                 min_similarity: 0.0,
                 event_types: vec![],
                 skip_zero_vectors: false,
+                metric: SimilarityMetric::Cosine,
             },
         )
         .unwrap();
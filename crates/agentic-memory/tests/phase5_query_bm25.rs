@@ -818,6 +818,50 @@ fn test_bm25_slow_path_matches_fast_path() {
     }
 }
 
+#[test]
+fn test_graph_text_index_stays_current() {
+    let mut graph = build_topic_graph();
+    graph.build_text_index();
+
+    let event = CognitiveEventBuilder::new(EventType::Fact, "Rust compiler borrow checker errors")
+        .session_id(5)
+        .confidence(0.9)
+        .build();
+    let added = graph.add_node(event).unwrap();
+    graph.remove_node(3).unwrap();
+
+    let term_index = graph.term_index().unwrap();
+    let rebuilt = TermIndex::build(&graph, &Tokenizer::new());
+    assert_eq!(term_index.doc_count(), graph.node_count() as u64);
+    assert_eq!(term_index.term_count(), rebuilt.term_count());
+    assert!((term_index.avg_doc_length() - rebuilt.avg_doc_length()).abs() < 1e-4);
+
+    let engine = QueryEngine::new();
+    let params = || TextSearchParams {
+        query: "rust borrow".to_string(),
+        max_results: 100,
+        event_types: vec![],
+        session_ids: vec![],
+        min_score: 0.0,
+    };
+    let fast = engine
+        .text_search(&graph, graph.term_index(), graph.doc_lengths(), params())
+        .unwrap();
+    let slow = engine.text_search(&graph, None, None, params()).unwrap();
+
+    assert_eq!(fast[0].node_id, added);
+    assert!(fast.iter().all(|m| m.node_id != 3));
+    assert_eq!(fast.len(), slow.len());
+    for f in &fast {
+        let s = slow.iter().find(|s| s.node_id == f.node_id).unwrap();
+        assert!((f.score - s.score).abs() < 1e-4);
+    }
+
+    graph.clear_text_index();
+    assert!(graph.term_index().is_none());
+    assert!(graph.doc_lengths().is_none());
+}
+
 #[test]
 fn test_bm25_max_results_limit() {
     let mut graph = MemoryGraph::new(DEFAULT_DIMENSION);
@@ -97,6 +97,7 @@ fn test_old_file_readable_by_new_code() {
             created_after: None,
             created_before: None,
             min_decay_score: None,
+            max_decay_score: None,
             max_results: 100,
             sort_by: agentic_memory::PatternSort::MostRecent,
         },
@@ -113,6 +114,7 @@ fn test_old_file_readable_by_new_code() {
             min_similarity: -1.0,
             event_types: vec![],
             skip_zero_vectors: false,
+            metric: agentic_memory::SimilarityMetric::Cosine,
         },
     );
     assert!(
@@ -325,6 +327,7 @@ fn test_existing_query_engine_methods_unchanged() {
                 created_after: None,
                 created_before: None,
                 min_decay_score: None,
+                max_decay_score: None,
                 max_results: 100,
                 sort_by: agentic_memory::PatternSort::HighestConfidence,
             },
@@ -369,6 +372,7 @@ fn test_existing_query_engine_methods_unchanged() {
                 min_similarity: -1.0,
                 event_types: vec![],
                 skip_zero_vectors: false,
+                metric: agentic_memory::SimilarityMetric::Cosine,
             },
         )
         .unwrap();
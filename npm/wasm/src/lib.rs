@@ -200,6 +200,7 @@ impl WasmMemoryGraph {
             created_after: None,
             created_before: None,
             min_decay_score: None,
+            max_decay_score: None,
             max_results: limit,
             sort_by: PatternSort::MostRecent,
         };
@@ -238,6 +239,7 @@ impl WasmMemoryGraph {
             created_after: None,
             created_before: None,
             min_decay_score: None,
+            max_decay_score: None,
             max_results: limit,
             sort_by: PatternSort::MostRecent,
         };
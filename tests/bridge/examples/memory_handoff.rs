@@ -4,7 +4,7 @@
 //! who continues in the same session. Demonstrates seamless continuity.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use agentic_memory_mcp::types::{JsonRpcMessage, JsonRpcNotification, JsonRpcRequest, RequestId};
 use agentic_memory_mcp::{ProtocolHandler, SessionManager};
@@ -12,7 +12,7 @@ use serde_json::json;
 
 fn create_handler(path: &str) -> ProtocolHandler {
     let session = SessionManager::open(path).expect("Failed to open session");
-    ProtocolHandler::new(Arc::new(Mutex::new(session)))
+    ProtocolHandler::new(Arc::new(RwLock::new(session)))
 }
 
 async fn init(handler: &ProtocolHandler) {
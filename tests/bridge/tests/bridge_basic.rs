@@ -5,7 +5,7 @@
 //! persists correctly to .amem files.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use agentic_memory::{
     AmemReader, AmemWriter, CognitiveEventBuilder, EdgeType, EventType, MemoryGraph,
@@ -19,7 +19,7 @@ use tempfile::tempdir;
 
 fn create_handler(path_str: &str) -> ProtocolHandler {
     let session = SessionManager::open(path_str).expect("Failed to open session");
-    let session_arc = Arc::new(Mutex::new(session));
+    let session_arc = Arc::new(RwLock::new(session));
     ProtocolHandler::new(session_arc)
 }
 
@@ -4,7 +4,7 @@
 //! and that rapid sequential handoffs between agents maintain data integrity.
 
 use std::sync::Arc;
-use tokio::sync::{Barrier, Mutex};
+use tokio::sync::{Barrier, RwLock};
 
 use agentic_memory_mcp::types::{JsonRpcMessage, JsonRpcNotification, JsonRpcRequest, RequestId};
 use agentic_memory_mcp::{ProtocolHandler, SessionManager};
@@ -15,7 +15,7 @@ use tempfile::tempdir;
 
 fn create_handler(path_str: &str) -> ProtocolHandler {
     let session = SessionManager::open(path_str).expect("Failed to open session");
-    let session_arc = Arc::new(Mutex::new(session));
+    let session_arc = Arc::new(RwLock::new(session));
     ProtocolHandler::new(session_arc)
 }
 
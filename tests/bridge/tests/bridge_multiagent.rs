@@ -5,7 +5,7 @@
 //! across agents, and causal chains spanning agent boundaries.
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use agentic_memory_mcp::types::{JsonRpcMessage, JsonRpcNotification, JsonRpcRequest, RequestId};
 use agentic_memory_mcp::{ProtocolHandler, SessionManager};
@@ -16,7 +16,7 @@ use tempfile::tempdir;
 
 fn create_handler(path_str: &str) -> ProtocolHandler {
     let session = SessionManager::open(path_str).expect("Failed to open session");
-    let session_arc = Arc::new(Mutex::new(session));
+    let session_arc = Arc::new(RwLock::new(session));
     ProtocolHandler::new(session_arc)
 }
 